@@ -1,54 +1,52 @@
 use bytes::Bytes;
+use futures::TryStreamExt;
 use gitlab_client::client::http::HttpGitlabClient;
+use gitlab_client::client::retry::{ExponentialBackoff, RetryGitlabClient};
 use gitlab_client::common::project::{ProjectRef, ProjectSlug};
 use gitlab_client::compact_str::CompactString;
 use gitlab_client::context::{Context, GitlabUrl};
+use gitlab_client::pagination::paginate;
 use gitlab_client::query::get_project::GetProjectQuery;
+use gitlab_client::query::get_project_list::GetProjectListQuery;
 use gitlab_client::query::get_tree_record_list::GetTreeRecordListQuery;
-use gitlab_client::tower_service::Service;
 use gitlab_client::url::Url;
-use gitlab_client::{GitlabAuth, UserAgent};
+use gitlab_client::{GitlabAuth, ProjectClient, RepositoryClient, UserAgent};
 use http_body_util::Full;
 use hyper_tls::HttpsConnector;
 use hyper_util::client::legacy::Client;
+use std::pin::pin;
 
 #[tokio::main]
 async fn main() {
-  let authentication: Option<GitlabAuth> = if let Some(token) = std::env::var("GITLAB_PRIVATE_TOKEN").ok() {
-    Some(GitlabAuth::PrivateToken(token.parse().unwrap()))
-  } else {
-    None
-  };
+  let authentication: Option<GitlabAuth> = std::env::var("GITLAB_PRIVATE_TOKEN")
+    .ok()
+    .map(|token| GitlabAuth::PrivateToken(token.parse().unwrap()));
 
   let connector = HttpsConnector::new();
   let client: Client<HttpsConnector<_>, Full<Bytes>> =
     Client::builder(hyper_util::rt::TokioExecutor::new()).build(connector);
-  let mut client = HttpGitlabClient::new(client);
+  let mut client = RetryGitlabClient::new(
+    HttpGitlabClient::new(client),
+    ExponentialBackoff::new(),
+    tokio::time::sleep,
+  );
   let context = Context::new()
     .set_gitlab_url(GitlabUrl(Url::parse("https://gitlab.com/").unwrap()))
     .set_user_agent(UserAgent::from_static("gitlab_client_example/0.0.0"));
-  // let mut query = GetProjectListQuery::<_>::new().set_context(context);
-  // // query.auth = Some(GitlabAuth::PrivateToken("...".parse().unwrap()));
-  // // query.owned = Some(true);
-  // let res = client.get_project_list(&query).await.unwrap();
-  // for p in &res.items {
-  //   dbg!(&p.path_with_namespace);
-  // }
-  // if let Some(next) = res.next {
-  //   let context = Context::new().set_gitlab_url(GitlabUrl(Url::parse("https://gitlab.com/").unwrap()));
-  //   let query = GetProjectListPageQuery::<_>::new(next).set_context(context);
-  //   let res = client.call(&query).await.unwrap();
-  //   for s in &res.items {
-  //     dbg!(&s.path_with_namespace);
-  //   }
-  // }
+  {
+    let query = GetProjectListQuery::<_>::new().set_context(context.clone());
+    let mut projects = pin!(paginate(&mut client, &query, Some(50)));
+    while let Some(project) = projects.try_next().await.unwrap() {
+      eprintln!("listed project: {}", project.path_with_namespace);
+    }
+  }
   {
     let mut query = GetProjectQuery::<_>::new(ProjectRef::Slug(ProjectSlug::new(CompactString::new(
       "demurgos/eternaltwin",
     ))))
     .set_context(context.clone());
     query.auth = authentication.clone();
-    let res = client.call(&query).await.unwrap();
+    let res = client.get_project(&query).await.unwrap();
     eprintln!("successfully fetched project. create_at={:?}", res.created_at);
   }
   {
@@ -57,7 +55,7 @@ async fn main() {
     ))))
     .set_context(context);
     query.auth = authentication.clone();
-    let res = client.call(&query).await.unwrap();
+    let res = client.get_tree_record_list(&query).await.unwrap();
     eprintln!("successfully fetched project. created_at={:?}", res.items.len());
   }
 }
@@ -1,16 +1,39 @@
+use futures::TryStreamExt;
 use gitlab_client::client::reqwest::ReqwestGitlabClient;
-use gitlab_client::command::publish_package_file::PublishPackageFileRequest;
+use gitlab_client::common::project::{ProjectRef, ProjectSlug};
+use gitlab_client::compact_str::CompactString;
+use gitlab_client::context::{Context, GitlabUrl};
+use gitlab_client::pagination::paginate;
+use gitlab_client::query::get_project::GetProjectQuery;
 use gitlab_client::query::get_project_list::GetProjectListQuery;
-use gitlab_client::tower_service::Service;
-use gitlab_client::GitlabClient;
-use gitlab_client::{QueryBase};
+use gitlab_client::url::Url;
+use gitlab_client::{GitlabAuth, ProjectClient, UserAgent};
+use std::pin::pin;
 
 #[tokio::main]
 async fn main() {
-  let client = gitlab_client::reqwest::Client::new();
-  let mut client = ReqwestGitlabClient::new(client);
-  let base = QueryBase::new();
-  let query: GetProjectListQuery = GetProjectListQuery::new(base);
-  let res = client.get_project_list(query.as_view()).await;
-  dbg!(res);
+  let authentication: Option<GitlabAuth> = std::env::var("GITLAB_PRIVATE_TOKEN")
+    .ok()
+    .map(|token| GitlabAuth::PrivateToken(token.parse().unwrap()));
+
+  let mut client = ReqwestGitlabClient::new(gitlab_client::reqwest::Client::new());
+  let context = Context::new()
+    .set_gitlab_url(GitlabUrl(Url::parse("https://gitlab.com/").unwrap()))
+    .set_user_agent(UserAgent::from_static("gitlab_client_example/0.0.0"));
+  {
+    let query = GetProjectListQuery::<_>::new().set_context(context.clone());
+    let mut projects = pin!(paginate(&mut client, &query, Some(50)));
+    while let Some(project) = projects.try_next().await.unwrap() {
+      eprintln!("listed project: {}", project.path_with_namespace);
+    }
+  }
+  {
+    let mut query = GetProjectQuery::<_>::new(ProjectRef::Slug(ProjectSlug::new(CompactString::new(
+      "demurgos/eternaltwin",
+    ))))
+    .set_context(context);
+    query.auth = authentication;
+    let res = client.get_project(&query).await.unwrap();
+    eprintln!("successfully fetched project. created_at={:?}", res.created_at);
+  }
 }
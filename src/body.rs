@@ -0,0 +1,369 @@
+//! Streaming request and response bodies
+
+use crate::client::http::HttpGitlabClientError;
+use bytes::Bytes;
+use compact_str::CompactString;
+use core::fmt;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::{Stream, TryStreamExt};
+use http_body::{Body, Frame, SizeHint};
+use http_body_util::combinators::UnsyncBoxBody;
+use http_body_util::{BodyExt, Full, StreamBody};
+use sha2::{Digest, Sha256};
+use std::error::Error as StdError;
+
+pub type BoxError = Box<dyn StdError + Send + Sync>;
+
+/// Upload state reported to the progress callback of an [`UploadBody`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UploadProgress {
+  /// Number of bytes sent so far
+  pub sent: u64,
+  /// Total number of bytes, if known
+  pub total: Option<u64>,
+}
+
+/// Request body streaming its data, with an optional known length and progress callback
+///
+/// Use it as the request body of an [`HttpGitlabClient`](crate::client::http::HttpGitlabClient) to upload files
+/// without buffering them in memory. When the length is known, it is reported as an exact size hint so the request
+/// is sent with a `Content-Length` header; otherwise it is sent with chunked encoding.
+pub struct UploadBody {
+  inner: UnsyncBoxBody<Bytes, BoxError>,
+  len: Option<u64>,
+  sent: u64,
+  progress: Option<Box<dyn FnMut(UploadProgress) + Send>>,
+}
+
+impl UploadBody {
+  /// Create an upload body from any `http_body::Body`, using its size hint as the length
+  pub fn new<B>(body: B) -> Self
+  where
+    B: Body<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+  {
+    let len = body.size_hint().exact();
+    Self {
+      inner: body.map_err(Into::into).boxed_unsync(),
+      len,
+      sent: 0,
+      progress: None,
+    }
+  }
+
+  /// Create an upload body from a stream of chunks
+  ///
+  /// `len` is the total length of the stream, if known.
+  pub fn from_stream<S>(stream: S, len: Option<u64>) -> Self
+  where
+    S: Stream<Item = Bytes> + Send + 'static,
+  {
+    Self::from_try_stream(futures::StreamExt::map(stream, Ok::<_, core::convert::Infallible>), len)
+  }
+
+  /// Create an upload body from a fallible stream of chunks (e.g. a file read from disk)
+  ///
+  /// `len` is the total length of the stream, if known.
+  pub fn from_try_stream<S, E>(stream: S, len: Option<u64>) -> Self
+  where
+    S: Stream<Item = Result<Bytes, E>> + Send + 'static,
+    E: Into<BoxError> + 'static,
+  {
+    let body = StreamBody::new(stream.map_ok(Frame::data).map_err(Into::into));
+    Self {
+      inner: body.boxed_unsync(),
+      len,
+      sent: 0,
+      progress: None,
+    }
+  }
+
+  /// Call `progress` after each chunk is sent
+  pub fn with_progress<F>(mut self, progress: F) -> Self
+  where
+    F: FnMut(UploadProgress) + Send + 'static,
+  {
+    self.progress = Some(Box::new(progress));
+    self
+  }
+
+  /// Total length of the body, if known
+  pub fn len(&self) -> Option<u64> {
+    self.len
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == Some(0)
+  }
+}
+
+impl fmt::Debug for UploadBody {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("UploadBody")
+      .field("len", &self.len)
+      .field("sent", &self.sent)
+      .field("progress", &self.progress.is_some())
+      .finish_non_exhaustive()
+  }
+}
+
+impl From<Bytes> for UploadBody {
+  fn from(data: Bytes) -> Self {
+    Self::new(Full::new(data))
+  }
+}
+
+impl From<Full<Bytes>> for UploadBody {
+  fn from(body: Full<Bytes>) -> Self {
+    Self::new(body)
+  }
+}
+
+impl Body for UploadBody {
+  type Data = Bytes;
+  type Error = BoxError;
+
+  fn poll_frame(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+    let this = &mut *self;
+    let frame = Pin::new(&mut this.inner).poll_frame(cx);
+    if let Poll::Ready(Some(Ok(frame))) = &frame {
+      if let Some(data) = frame.data_ref() {
+        this.sent += data.len() as u64;
+        if let Some(progress) = this.progress.as_mut() {
+          progress(UploadProgress {
+            sent: this.sent,
+            total: this.len,
+          });
+        }
+      }
+    }
+    frame
+  }
+
+  fn is_end_stream(&self) -> bool {
+    self.inner.is_end_stream()
+  }
+
+  fn size_hint(&self) -> SizeHint {
+    match self.len {
+      Some(len) => SizeHint::with_exact(len.saturating_sub(self.sent)),
+      None => self.inner.size_hint(),
+    }
+  }
+}
+
+/// Response body yielding its chunks as they arrive
+///
+/// The stream fails with [`HttpGitlabClientError::Receive`] if it ends before the announced `Content-Length`, and with
+/// [`HttpGitlabClientError::ChecksumMismatch`] if an expected SHA-256 digest was provided and does not match the
+/// received content. Callers must not trust the data until the stream ended without error.
+pub struct DownloadStream {
+  inner: UnsyncBoxBody<Bytes, BoxError>,
+  len: Option<u64>,
+  received: u64,
+  sha256: Option<(Sha256, [u8; 32])>,
+  done: bool,
+}
+
+impl DownloadStream {
+  pub(crate) fn new<B>(body: B, len: Option<u64>, sha256: Option<[u8; 32]>) -> Self
+  where
+    B: Body<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+  {
+    Self {
+      inner: body.map_err(Into::into).boxed_unsync(),
+      len,
+      received: 0,
+      sha256: sha256.map(|expected| (Sha256::new(), expected)),
+      done: false,
+    }
+  }
+
+  /// Length announced by the server, if any
+  pub fn len(&self) -> Option<u64> {
+    self.len
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == Some(0)
+  }
+
+  /// Check the received content once the body ended
+  fn finish(&mut self) -> Result<(), HttpGitlabClientError> {
+    if let Some(len) = self.len {
+      if len != self.received {
+        return Err(HttpGitlabClientError::Receive(format!(
+          "truncated body: expected {len} bytes, received {}",
+          self.received
+        )));
+      }
+    }
+    if let Some((hasher, expected)) = self.sha256.take() {
+      let actual: [u8; 32] = hasher.finalize().into();
+      if actual != expected {
+        return Err(HttpGitlabClientError::ChecksumMismatch(
+          to_hex(&expected),
+          to_hex(&actual),
+        ));
+      }
+    }
+    Ok(())
+  }
+}
+
+impl fmt::Debug for DownloadStream {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("DownloadStream")
+      .field("len", &self.len)
+      .field("received", &self.received)
+      .field("sha256", &self.sha256.as_ref().map(|(_, expected)| to_hex(expected)))
+      .finish_non_exhaustive()
+  }
+}
+
+impl Stream for DownloadStream {
+  type Item = Result<Bytes, HttpGitlabClientError>;
+
+  fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+    let this = &mut *self;
+    while !this.done {
+      match Pin::new(&mut this.inner).poll_frame(cx) {
+        Poll::Pending => return Poll::Pending,
+        Poll::Ready(Some(Ok(frame))) => {
+          if let Ok(data) = frame.into_data() {
+            this.received += data.len() as u64;
+            if let Some((hasher, _)) = this.sha256.as_mut() {
+              hasher.update(&data);
+            }
+            return Poll::Ready(Some(Ok(data)));
+          }
+        }
+        Poll::Ready(Some(Err(e))) => {
+          this.done = true;
+          return Poll::Ready(Some(Err(HttpGitlabClientError::Receive(format!("{e:?}")))));
+        }
+        Poll::Ready(None) => {
+          this.done = true;
+          if let Err(e) = this.finish() {
+            return Poll::Ready(Some(Err(e)));
+          }
+        }
+      }
+    }
+    Poll::Ready(None)
+  }
+}
+
+/// Parse a hex-encoded SHA-256 digest
+pub(crate) fn parse_sha256(hex: &str) -> Option<[u8; 32]> {
+  let hex = hex.as_bytes();
+  if hex.len() != 64 {
+    return None;
+  }
+  let mut digest = [0u8; 32];
+  for (byte, pair) in digest.iter_mut().zip(hex.chunks_exact(2)) {
+    // Decode the nibbles by hand: `u8::from_str_radix` would accept a leading `+`
+    let high = char::from(pair[0]).to_digit(16)?;
+    let low = char::from(pair[1]).to_digit(16)?;
+    *byte = u8::try_from(high << 4 | low).expect("two nibbles fit in a byte");
+  }
+  Some(digest)
+}
+
+pub(crate) fn to_hex(digest: &[u8]) -> CompactString {
+  use core::fmt::Write;
+  let mut hex = CompactString::default();
+  for byte in digest {
+    write!(hex, "{byte:02x}").expect("writing to a string never fails");
+  }
+  hex
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use std::sync::{Arc, Mutex};
+
+  #[test]
+  fn stream_with_progress() {
+    let chunks = [Bytes::from_static(b"Hello, "), Bytes::from_static(b"World!")];
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let body = UploadBody::from_stream(futures::stream::iter(chunks), Some(13)).with_progress({
+      let reports = Arc::clone(&reports);
+      move |progress| reports.lock().unwrap().push(progress)
+    });
+    assert_eq!(body.size_hint().exact(), Some(13));
+
+    let actual = futures::executor::block_on(body.collect()).unwrap().to_bytes();
+    assert_eq!(actual, Bytes::from_static(b"Hello, World!"));
+    let expected = vec![
+      UploadProgress {
+        sent: 7,
+        total: Some(13),
+      },
+      UploadProgress {
+        sent: 13,
+        total: Some(13),
+      },
+    ];
+    assert_eq!(*reports.lock().unwrap(), expected);
+  }
+
+  #[test]
+  fn stream_with_unknown_length() {
+    let body = UploadBody::from_stream(futures::stream::iter([Bytes::from_static(b"data")]), None);
+    assert_eq!(body.len(), None);
+    assert_eq!(body.size_hint().exact(), None);
+  }
+
+  const HELLO_SHA256: &str = "dffd6021bb2bd5b0af676290809ec3a53191dd81c7f70a4b28688a362182986f";
+
+  fn download(len: Option<u64>, sha256: Option<&str>) -> Result<Bytes, HttpGitlabClientError> {
+    let chunks: [Result<Bytes, BoxError>; 2] = [Ok(Bytes::from_static(b"Hello, ")), Ok(Bytes::from_static(b"World!"))];
+    let body = StreamBody::new(futures::stream::iter(chunks).map_ok(Frame::data));
+    let stream = DownloadStream::new(body, len, sha256.map(|hex| parse_sha256(hex).unwrap()));
+    let chunks: Vec<Bytes> = futures::executor::block_on(stream.try_collect())?;
+    Ok(Bytes::from(chunks.concat()))
+  }
+
+  #[test]
+  fn download_with_checksum() {
+    let actual = download(Some(13), Some(HELLO_SHA256));
+    assert_eq!(actual, Ok(Bytes::from_static(b"Hello, World!")));
+  }
+
+  #[test]
+  fn download_with_checksum_mismatch() {
+    let expected = "0ca093111f402faa55be1cd71006270644b58619eb0c2408b97b7d24bb70dd09";
+    let actual = download(None, Some(expected));
+    assert_eq!(
+      actual,
+      Err(HttpGitlabClientError::ChecksumMismatch(
+        CompactString::new(expected),
+        CompactString::new(HELLO_SHA256)
+      ))
+    );
+  }
+
+  #[test]
+  fn parse_sha256_rejects_signs() {
+    assert_eq!(
+      parse_sha256(HELLO_SHA256).map(|digest| to_hex(&digest)).as_deref(),
+      Some(HELLO_SHA256)
+    );
+    let signed = format!("+f{}", &HELLO_SHA256[2..]);
+    assert_eq!(parse_sha256(&signed), None);
+    assert_eq!(parse_sha256(&HELLO_SHA256[..62]), None);
+  }
+
+  #[test]
+  fn download_truncated() {
+    let actual = download(Some(20), None);
+    assert!(matches!(actual, Err(HttpGitlabClientError::Receive(_))));
+  }
+}
@@ -0,0 +1,557 @@
+//! Record and replay HTTP interactions using JSONL cassettes.
+//!
+//! Each line of a cassette is a JSON [`Interaction`]: a request and the response it received. Use
+//! [`RecordingService`] to capture real GitLab interactions once, then [`ReplayService`] to serve them again
+//! deterministically (e.g. in CI).
+//!
+//! Secrets are redacted before an interaction is written: the [`SENSITIVE_HEADERS`], the [`SENSITIVE_PARAMS`] of the
+//! URL query string and the [`SENSITIVE_FIELDS`] of JSON bodies are replaced by [`REDACTED`].
+
+use base64::Engine;
+use bytes::Bytes;
+use compact_str::CompactString;
+use core::task::{Context, Poll};
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use http::{HeaderMap, HeaderValue, Method, Request, Response, StatusCode};
+use http_body::Body;
+use http_body_util::{BodyExt, Full};
+use std::error::Error as StdError;
+use std::io::{BufRead, Write};
+use std::sync::{Arc, Mutex};
+use tower_service::Service;
+
+/// Headers whose value is replaced by [`REDACTED`] in cassettes
+pub const SENSITIVE_HEADERS: [&str; 5] = ["authorization", "cookie", "job-token", "private-token", "set-cookie"];
+
+/// URL query parameters whose value is replaced by [`REDACTED`] in cassettes
+pub const SENSITIVE_PARAMS: [&str; 3] = ["access_token", "job_token", "private_token"];
+
+/// JSON body fields whose value is replaced by [`REDACTED`] in cassettes, at any depth
+pub const SENSITIVE_FIELDS: [&str; 5] = ["access_token", "job_token", "private_token", "runners_token", "token"];
+
+/// Placeholder for redacted values
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Request and response pair, stored as one line of a cassette
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Interaction {
+  pub request: RecordedRequest,
+  pub response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RecordedRequest {
+  #[serde(with = "crate::client::http::serde_method")]
+  pub method: Method,
+  pub url: CompactString,
+  pub headers: Vec<(CompactString, RecordedHeaderValue)>,
+  pub body: RecordedBody,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RecordedResponse {
+  pub status: u16,
+  pub headers: Vec<(CompactString, RecordedHeaderValue)>,
+  pub body: RecordedBody,
+}
+
+/// Value of a recorded header: text when it is visible ASCII, base64-encoded bytes otherwise
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum RecordedHeaderValue {
+  Text(CompactString),
+  Binary { base64: CompactString },
+}
+
+impl RecordedHeaderValue {
+  pub fn new(value: &HeaderValue) -> Self {
+    match value.to_str() {
+      Ok(text) => Self::Text(CompactString::new(text)),
+      Err(_) => Self::Binary {
+        base64: CompactString::new(base64::engine::general_purpose::STANDARD.encode(value.as_bytes())),
+      },
+    }
+  }
+
+  pub fn to_header_value(&self) -> Result<HeaderValue, ReplayError> {
+    let bytes = match self {
+      Self::Text(text) => text.as_bytes().to_vec(),
+      Self::Binary { base64 } => base64::engine::general_purpose::STANDARD
+        .decode(base64.as_bytes())
+        .map_err(|e| ReplayError::InvalidResponse(format!("{e:?}")))?,
+    };
+    HeaderValue::from_bytes(&bytes).map_err(|e| ReplayError::InvalidResponse(format!("{e:?}")))
+  }
+}
+
+/// Body of a recorded message: text when it is valid UTF-8, raw bytes otherwise
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum RecordedBody {
+  Text(String),
+  Binary(Vec<u8>),
+}
+
+impl RecordedBody {
+  pub fn new(body: Bytes) -> Self {
+    match String::from_utf8(body.to_vec()) {
+      Ok(text) => Self::Text(text),
+      Err(e) => Self::Binary(e.into_bytes()),
+    }
+  }
+
+  pub fn to_bytes(&self) -> Bytes {
+    match self {
+      Self::Text(text) => Bytes::copy_from_slice(text.as_bytes()),
+      Self::Binary(bytes) => Bytes::copy_from_slice(bytes),
+    }
+  }
+}
+
+fn record_headers(headers: &HeaderMap) -> Vec<(CompactString, RecordedHeaderValue)> {
+  headers
+    .iter()
+    .map(|(name, value)| {
+      let value = if SENSITIVE_HEADERS.contains(&name.as_str()) {
+        RecordedHeaderValue::Text(CompactString::new(REDACTED))
+      } else {
+        RecordedHeaderValue::new(value)
+      };
+      (CompactString::new(name.as_str()), value)
+    })
+    .collect()
+}
+
+/// Replace the value of the [`SENSITIVE_PARAMS`] in the query string of the URL
+fn redact_url(url: &str) -> CompactString {
+  let Some((base, query)) = url.split_once('?') else {
+    return CompactString::new(url);
+  };
+  let mut redacted = CompactString::new(base);
+  for (i, pair) in query.split('&').enumerate() {
+    redacted.push(if i == 0 { '?' } else { '&' });
+    match pair.split_once('=') {
+      Some((key, _)) if SENSITIVE_PARAMS.contains(&key) => {
+        redacted.push_str(key);
+        redacted.push('=');
+        redacted.push_str(REDACTED);
+      }
+      _ => redacted.push_str(pair),
+    }
+  }
+  redacted
+}
+
+/// Replace the value of the [`SENSITIVE_FIELDS`] of a JSON body. Other bodies are kept as-is.
+fn redact_body(body: Bytes) -> RecordedBody {
+  let body = RecordedBody::new(body);
+  let RecordedBody::Text(text) = &body else {
+    return body;
+  };
+  let Ok(mut value) = serde_json::from_str::<serde_json::Value>(text) else {
+    return body;
+  };
+  if redact_json(&mut value) {
+    RecordedBody::Text(value.to_string())
+  } else {
+    body
+  }
+}
+
+/// Returns `true` if a value was redacted
+fn redact_json(value: &mut serde_json::Value) -> bool {
+  match value {
+    serde_json::Value::Object(fields) => {
+      let mut redacted = false;
+      for (key, value) in fields.iter_mut() {
+        if SENSITIVE_FIELDS.contains(&key.as_str()) && value.is_string() {
+          *value = serde_json::Value::String(REDACTED.to_string());
+          redacted = true;
+        } else {
+          redacted |= redact_json(value);
+        }
+      }
+      redacted
+    }
+    serde_json::Value::Array(items) => items
+      .iter_mut()
+      .fold(false, |redacted, item| redact_json(item) | redacted),
+    _ => false,
+  }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CassetteError {
+  #[error("failed to read cassette: {0}")]
+  Io(#[from] std::io::Error),
+  #[error("invalid interaction on line {line}: {source}")]
+  Format { line: usize, source: serde_json::Error },
+}
+
+/// Read all the interactions of a JSONL cassette. Blank lines are ignored.
+pub fn read_cassette<R: BufRead>(reader: R) -> Result<Vec<Interaction>, CassetteError> {
+  let mut interactions = Vec::new();
+  for (i, line) in reader.lines().enumerate() {
+    let line = line?;
+    if line.trim().is_empty() {
+      continue;
+    }
+    let interaction = serde_json::from_str(&line).map_err(|source| CassetteError::Format { line: i + 1, source })?;
+    interactions.push(interaction);
+  }
+  Ok(interactions)
+}
+
+/// Transport wrapper appending each request/response pair to a JSONL cassette
+///
+/// Secrets are redacted (see the [module documentation](self)). Clones share the same writer.
+#[derive(Debug)]
+pub struct RecordingService<TyInner, TyWriter> {
+  inner: TyInner,
+  writer: Arc<Mutex<TyWriter>>,
+}
+
+impl<TyInner: Clone, TyWriter> Clone for RecordingService<TyInner, TyWriter> {
+  fn clone(&self) -> Self {
+    Self {
+      inner: self.inner.clone(),
+      writer: Arc::clone(&self.writer),
+    }
+  }
+}
+
+impl<TyInner, TyWriter> RecordingService<TyInner, TyWriter> {
+  pub fn new(inner: TyInner, writer: TyWriter) -> Self {
+    Self {
+      inner,
+      writer: Arc::new(Mutex::new(writer)),
+    }
+  }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RecordingError<TyInner: StdError + 'static> {
+  #[error(transparent)]
+  Inner(TyInner),
+  #[error("failed to receive response: {0}")]
+  Receive(String),
+  #[error("failed to write interaction: {0}")]
+  Write(#[from] std::io::Error),
+}
+
+impl<TyInner, TyWriter, TyBody> Service<Request<Full<Bytes>>> for RecordingService<TyInner, TyWriter>
+where
+  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>>,
+  TyInner::Error: StdError + 'static,
+  TyInner::Future: Send + 'static,
+  TyWriter: Write + Send + 'static,
+  TyBody: Body + Send,
+  TyBody::Data: Send,
+  TyBody::Error: StdError,
+{
+  type Response = Response<Full<Bytes>>;
+  type Error = RecordingError<TyInner::Error>;
+  type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self.inner.poll_ready(cx).map_err(RecordingError::Inner)
+  }
+
+  fn call(&mut self, req: Request<Full<Bytes>>) -> Self::Future {
+    let (parts, body) = req.into_parts();
+    let body: Bytes = match body.collect().now_or_never() {
+      Some(Ok(body)) => body.to_bytes(),
+      Some(Err(e)) => match e {},
+      None => unreachable!("`Full` body is always ready"),
+    };
+    let request = RecordedRequest {
+      method: parts.method.clone(),
+      url: redact_url(&parts.uri.to_string()),
+      headers: record_headers(&parts.headers),
+      body: redact_body(body.clone()),
+    };
+    let res = self.inner.call(Request::from_parts(parts, Full::new(body)));
+    let writer = Arc::clone(&self.writer);
+    Box::pin(async move {
+      let res = res.await.map_err(RecordingError::Inner)?;
+      let (parts, body) = res.into_parts();
+      let body = body
+        .collect()
+        .await
+        .map_err(|e| RecordingError::Receive(format!("{e:?}")))?
+        .to_bytes();
+      let interaction = Interaction {
+        request,
+        response: RecordedResponse {
+          status: parts.status.as_u16(),
+          headers: record_headers(&parts.headers),
+          body: redact_body(body.clone()),
+        },
+      };
+      let mut line = serde_json::to_vec(&interaction).expect("interaction serialization never fails");
+      line.push(b'\n');
+      {
+        let mut writer = writer.lock().expect("cassette writer is not poisoned");
+        writer.write_all(&line)?;
+        writer.flush()?;
+      }
+      Ok(Response::from_parts(parts, Full::new(body)))
+    })
+  }
+}
+
+/// Transport serving the responses of a cassette
+///
+/// Requests are matched on their method, URL and body, after redacting them the same way as [`RecordingService`].
+/// Matching interactions are served in the cassette order; once they are all used, the last one is served again.
+/// Clones share the same replay state.
+#[derive(Debug, Clone)]
+pub struct ReplayService {
+  state: Arc<Mutex<ReplayState>>,
+}
+
+#[derive(Debug)]
+struct ReplayState {
+  interactions: Vec<Interaction>,
+  used: Vec<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ReplayError {
+  #[error("no recorded interaction for {0} {1}")]
+  NoMatch(Method, CompactString),
+  #[error("invalid recorded response: {0}")]
+  InvalidResponse(String),
+}
+
+impl ReplayService {
+  pub fn new(interactions: Vec<Interaction>) -> Self {
+    let used = vec![false; interactions.len()];
+    Self {
+      state: Arc::new(Mutex::new(ReplayState { interactions, used })),
+    }
+  }
+
+  /// Load the interactions of a JSONL cassette
+  pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, CassetteError> {
+    read_cassette(reader).map(Self::new)
+  }
+
+  fn find(&self, method: &Method, url: &str, body: &RecordedBody) -> Option<RecordedResponse> {
+    let mut state = self.state.lock().expect("replay state is not poisoned");
+    let ReplayState { interactions, used } = &mut *state;
+    let mut last = None;
+    for (i, interaction) in interactions.iter().enumerate() {
+      let req = &interaction.request;
+      if req.method != *method || req.url != url || req.body != *body {
+        continue;
+      }
+      if !used[i] {
+        used[i] = true;
+        return Some(interaction.response.clone());
+      }
+      last = Some(i);
+    }
+    last.map(|i| interactions[i].response.clone())
+  }
+}
+
+impl Service<Request<Full<Bytes>>> for ReplayService {
+  type Response = Response<Full<Bytes>>;
+  type Error = ReplayError;
+  type Future = futures::future::Ready<Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, req: Request<Full<Bytes>>) -> Self::Future {
+    let (parts, body) = req.into_parts();
+    let body: Bytes = match body.collect().now_or_never() {
+      Some(Ok(body)) => body.to_bytes(),
+      Some(Err(e)) => match e {},
+      None => unreachable!("`Full` body is always ready"),
+    };
+    let url = redact_url(&parts.uri.to_string());
+    let res = match self.find(&parts.method, &url, &redact_body(body)) {
+      Some(res) => build_response(res),
+      None => Err(ReplayError::NoMatch(parts.method, url)),
+    };
+    futures::future::ready(res)
+  }
+}
+
+fn build_response(res: RecordedResponse) -> Result<Response<Full<Bytes>>, ReplayError> {
+  let status = StatusCode::from_u16(res.status).map_err(|e| ReplayError::InvalidResponse(format!("{e:?}")))?;
+  let mut builder = Response::builder().status(status);
+  for (name, value) in &res.headers {
+    builder = builder.header(name.as_str(), value.to_header_value()?);
+  }
+  builder
+    .body(Full::new(res.body.to_bytes()))
+    .map_err(|e| ReplayError::InvalidResponse(format!("{e:?}")))
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::client::http::{HttpGitlabClient, HttpGitlabClientError};
+  use crate::command::update_release::UpdateReleaseCommand;
+  use crate::common::project::{ProjectId, ProjectRef};
+  use crate::context::GitlabUrl;
+  use crate::{GitlabAuth, ReleaseClient, UserAgent};
+
+  /// Transport answering every request with the first release of the libxml2 fixture
+  #[derive(Clone)]
+  struct FixtureTransport;
+
+  impl Service<Request<Full<Bytes>>> for FixtureTransport {
+    type Response = Response<Full<Bytes>>;
+    type Error = std::convert::Infallible;
+    type Future = futures::future::Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+      Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _req: Request<Full<Bytes>>) -> Self::Future {
+      let releases: serde_json::Value = serde_json::from_str(include_str!(
+        "../../test-resources/get-project-release-list/libxml2/output.json"
+      ))
+      .unwrap();
+      let res = Response::builder()
+        .header("content-type", "application/json")
+        .body(Full::new(Bytes::from(releases[0].to_string())))
+        .unwrap();
+      futures::future::ready(Ok(res))
+    }
+  }
+
+  fn update_release(description: &str) -> UpdateReleaseCommand<crate::context::Context<GitlabUrl, UserAgent>> {
+    UpdateReleaseCommand {
+      context: crate::context::Context::new()
+        .set_gitlab_url(GitlabUrl(url::Url::parse("https://gitlab.example.com/").unwrap()))
+        .set_user_agent(UserAgent::from_static("gitlab_client_test/0.0.0")),
+      auth: Some(GitlabAuth::PrivateToken(CompactString::new("secret"))),
+      project: ProjectRef::Id(ProjectId::new(1)),
+      tag_name: CompactString::new("v2.7.4"),
+      name: None,
+      description: Some(CompactString::new(description)),
+      milestones: None,
+      released_at: None,
+    }
+  }
+
+  #[test]
+  fn record_then_replay() {
+    let recorder = RecordingService::new(FixtureTransport, Vec::<u8>::new());
+    let mut client = HttpGitlabClient::new(recorder.clone());
+    let recorded = futures::executor::block_on(client.update_release(&update_release("Hello"))).unwrap();
+
+    let cassette = String::from_utf8(recorder.writer.lock().unwrap().clone()).unwrap();
+    assert_eq!(cassette.lines().count(), 1);
+    assert!(!cassette.contains("secret"));
+    let interactions = read_cassette(cassette.as_bytes()).unwrap();
+    let request = &interactions[0].request;
+    assert_eq!(request.method, Method::PUT);
+    assert_eq!(
+      request.url,
+      "https://gitlab.example.com/api/v4/projects/1/releases/v2.7.4"
+    );
+    assert!(request.headers.contains(&(
+      CompactString::new("private-token"),
+      RecordedHeaderValue::Text(CompactString::new(REDACTED))
+    )));
+    assert_eq!(
+      request.body,
+      RecordedBody::Text(r#"{"description":"Hello"}"#.to_string())
+    );
+
+    let mut client = HttpGitlabClient::new(ReplayService::new(interactions));
+    let replayed = futures::executor::block_on(client.update_release(&update_release("Hello"))).unwrap();
+    assert_eq!(replayed, recorded);
+
+    let actual = futures::executor::block_on(client.update_release(&update_release("Bye"))).unwrap_err();
+    assert!(matches!(actual, HttpGitlabClientError::Send(_)));
+  }
+
+  /// Transport answering with a new access token, a session cookie and a non-UTF-8 header
+  #[derive(Clone)]
+  struct SecretTransport;
+
+  impl Service<Request<Full<Bytes>>> for SecretTransport {
+    type Response = Response<Full<Bytes>>;
+    type Error = std::convert::Infallible;
+    type Future = futures::future::Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+      Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _req: Request<Full<Bytes>>) -> Self::Future {
+      let res = Response::builder()
+        .header("content-type", "application/json")
+        .header("set-cookie", "session=secret")
+        .header("x-binary", HeaderValue::from_bytes(&[0xff, b'a']).unwrap())
+        .body(Full::new(Bytes::from_static(
+          br#"{"id":1,"token":"glpat-secret","scopes":["api"]}"#,
+        )))
+        .unwrap();
+      futures::future::ready(Ok(res))
+    }
+  }
+
+  fn create_token_request() -> Request<Full<Bytes>> {
+    Request::builder()
+      .method(Method::POST)
+      .uri("https://gitlab.example.com/api/v4/user/personal_access_tokens?private_token=secret&scopes=api")
+      .body(Full::new(Bytes::from_static(br#"{"name":"ci","job_token":"secret"}"#)))
+      .unwrap()
+  }
+
+  #[test]
+  fn redact_secrets() {
+    let mut recorder = RecordingService::new(SecretTransport, Vec::<u8>::new());
+    futures::executor::block_on(recorder.call(create_token_request())).unwrap();
+
+    let cassette = String::from_utf8(recorder.writer.lock().unwrap().clone()).unwrap();
+    assert!(!cassette.contains("secret"));
+    let interactions = read_cassette(cassette.as_bytes()).unwrap();
+    let Interaction { request, response } = &interactions[0];
+    assert_eq!(
+      request.url,
+      "https://gitlab.example.com/api/v4/user/personal_access_tokens?private_token=[REDACTED]&scopes=api"
+    );
+    assert_eq!(
+      request.body,
+      RecordedBody::Text(r#"{"name":"ci","job_token":"[REDACTED]"}"#.to_string())
+    );
+    assert_eq!(
+      response.body,
+      RecordedBody::Text(r#"{"id":1,"token":"[REDACTED]","scopes":["api"]}"#.to_string())
+    );
+    assert!(response.headers.contains(&(
+      CompactString::new("set-cookie"),
+      RecordedHeaderValue::Text(CompactString::new(REDACTED))
+    )));
+    assert!(response.headers.contains(&(
+      CompactString::new("x-binary"),
+      RecordedHeaderValue::Binary {
+        base64: CompactString::new("/2E=")
+      }
+    )));
+
+    let mut replay = ReplayService::new(interactions);
+    let res = futures::executor::block_on(replay.call(create_token_request())).unwrap();
+    assert_eq!(res.headers()["x-binary"].as_bytes(), [0xff, b'a']);
+  }
+
+  #[test]
+  fn read_binary_body() {
+    let raw = r#"{"request":{"method":"GET","url":"https://gitlab.example.com/file","headers":[],"body":""},"response":{"status":200,"headers":[],"body":[0,159,146,150]}}"#;
+    let actual = read_cassette(format!("{raw}\n\n").as_bytes()).unwrap();
+    assert_eq!(actual[0].response.body, RecordedBody::Binary(vec![0, 159, 146, 150]));
+    assert_eq!(actual[0].request.body, RecordedBody::Text(String::new()));
+  }
+}
@@ -0,0 +1,602 @@
+//! Merge request approvals and approval rules of the fake GitLab
+
+use super::branches::matches_wildcard;
+use super::{error, fake_user, not_found, single_page, FakeApprovals, FakeGitlabState, FakeProject, FAKE_USER_ID};
+use crate::client::http::HttpGitlabClientError;
+use crate::command::approve_merge_request::ApproveMergeRequestCommand;
+use crate::command::create_merge_request_approval_rule::CreateMergeRequestApprovalRuleCommand;
+use crate::command::create_project_approval_rule::CreateProjectApprovalRuleCommand;
+use crate::command::delete_merge_request_approval_rule::DeleteMergeRequestApprovalRuleCommand;
+use crate::command::delete_project_approval_rule::DeleteProjectApprovalRuleCommand;
+use crate::command::reset_merge_request_approvals::ResetMergeRequestApprovalsCommand;
+use crate::command::unapprove_merge_request::UnapproveMergeRequestCommand;
+use crate::command::update_merge_request_approval_rule::UpdateMergeRequestApprovalRuleCommand;
+use crate::command::update_project_approval_rule::UpdateProjectApprovalRuleCommand;
+use crate::common::approval::{
+  ApprovalRuleId, ApprovalRuleSource, ApprovalRuleType, MergeRequestApprovalRule, MergeRequestApprovalRuleState,
+  MergeRequestApprovalState, MergeRequestApprovals, MergeRequestApprover, ProjectApprovalRule,
+};
+use crate::common::group::{Group, GroupId};
+use crate::common::merge_request::MergeRequestIid;
+use crate::common::project::{ProjectId, ProjectRef};
+use crate::common::protected_branch::{ProtectedBranch, ProtectedBranchId};
+use crate::common::user::UserId;
+use crate::common::Page;
+use crate::context::{GetRef, GitlabUrl};
+use crate::endpoint::Endpoint;
+use crate::query::get_merge_request_approval_rule::GetMergeRequestApprovalRuleQuery;
+use crate::query::get_merge_request_approval_rule_list::GetMergeRequestApprovalRuleListQuery;
+use crate::query::get_merge_request_approval_rule_list_page::GetMergeRequestApprovalRuleListPageQuery;
+use crate::query::get_merge_request_approval_state::GetMergeRequestApprovalStateQuery;
+use crate::query::get_merge_request_approvals::GetMergeRequestApprovalsQuery;
+use crate::query::get_project_approval_rule::GetProjectApprovalRuleQuery;
+use crate::query::get_project_approval_rule_list::GetProjectApprovalRuleListQuery;
+use crate::query::get_project_approval_rule_list_page::GetProjectApprovalRuleListPageQuery;
+use compact_str::{format_compact, CompactString};
+use http::StatusCode;
+
+/// Build the embedded representation of a fake group
+fn fake_group(state: &FakeGitlabState, id: GroupId) -> Option<Group> {
+  let group = state.groups.get(&id)?;
+  let (parent_path, path) = match group.full_path.rsplit_once('/') {
+    Some((parent, path)) => (Some(parent), path),
+    None => (None, group.full_path.as_str()),
+  };
+  let parent_id = parent_path.and_then(|parent_path| {
+    state
+      .groups
+      .iter()
+      .find(|(_, g)| g.full_path == parent_path)
+      .map(|(id, _)| *id)
+  });
+  Some(Group {
+    id,
+    name: CompactString::new(path),
+    path: CompactString::new(path),
+    full_name: CompactString::new(group.full_path.split('/').collect::<Vec<_>>().join(" / ")),
+    full_path: group.full_path.clone(),
+    parent_id,
+    avatar_url: None,
+    web_url: format_compact!("https://gitlab.example.com/groups/{}", group.full_path),
+  })
+}
+
+/// Resolve the groups of an approval rule, GitLab ignores the groups that do not exist
+fn approval_rule_groups(state: &FakeGitlabState, ids: &[GroupId]) -> Vec<Group> {
+  ids.iter().filter_map(|id| fake_group(state, *id)).collect()
+}
+
+/// Resolve the protected branches of a project approval rule, GitLab ignores the branches that are not protected
+fn approval_rule_protected_branches(project: &FakeProject, ids: &[ProtectedBranchId]) -> Vec<ProtectedBranch> {
+  project
+    .protected_branches
+    .values()
+    .filter(|branch| ids.contains(&branch.id))
+    .cloned()
+    .collect()
+}
+
+/// Whether a project approval rule applies to the merge requests targeting a branch
+fn applies_to_branch(project: &FakeProject, rule: &ProjectApprovalRule, branch: &str) -> bool {
+  if rule.applies_to_all_protected_branches {
+    project
+      .protected_branches
+      .keys()
+      .any(|pattern| matches_wildcard(pattern, branch))
+  } else {
+    rule.protected_branches.is_empty()
+      || rule
+        .protected_branches
+        .iter()
+        .any(|protected| matches_wildcard(&protected.name, branch))
+  }
+}
+
+/// Copy the project approval rules applying to a new merge request
+pub(super) fn copy_approval_rules(state: &mut FakeGitlabState, id: ProjectId, iid: MergeRequestIid) {
+  let project = &state.projects[&id];
+  let target_branch = &project.merge_requests[&iid].target_branch;
+  let rules = Vec::from_iter(
+    project
+      .approval_rules
+      .values()
+      .filter(|rule| applies_to_branch(project, rule, target_branch))
+      .cloned(),
+  );
+  let rules = rules
+    .into_iter()
+    .map(|rule| {
+      let id = ApprovalRuleId::new(state.next_id());
+      let rule = MergeRequestApprovalRule {
+        id,
+        name: rule.name,
+        rule_type: rule.rule_type,
+        eligible_approvers: rule.eligible_approvers,
+        approvals_required: rule.approvals_required,
+        users: rule.users,
+        groups: rule.groups,
+        contains_hidden_groups: rule.contains_hidden_groups,
+        source_rule: Some(ApprovalRuleSource {
+          approvals_required: rule.approvals_required,
+        }),
+        overridden: false,
+      };
+      (id, rule)
+    })
+    .collect();
+  let project = state.projects.get_mut(&id).expect("project exists");
+  project.merge_request_approvals.insert(
+    iid,
+    FakeApprovals {
+      rules,
+      overwritten: false,
+      approved_by: Vec::new(),
+    },
+  );
+}
+
+/// Approvals of a merge request counting towards an approval rule
+fn rule_state(rule: &MergeRequestApprovalRule, approved_by: &[UserId]) -> MergeRequestApprovalRuleState {
+  let approved_by = Vec::from_iter(
+    approved_by
+      .iter()
+      .copied()
+      .filter(|user| {
+        rule.rule_type == ApprovalRuleType::AnyApprover
+          || rule.eligible_approvers.iter().any(|approver| approver.id == *user)
+      })
+      .map(fake_user),
+  );
+  MergeRequestApprovalRuleState {
+    approved: approved_by.len() as u64 >= rule.approvals_required,
+    rule: rule.clone(),
+    approved_by,
+  }
+}
+
+fn fake_approvals(approvals: &FakeApprovals) -> MergeRequestApprovals {
+  let (required, left) = approvals.rules.values().fold((0, 0), |(required, left), rule| {
+    let approved = rule_state(rule, &approvals.approved_by).approved_by.len() as u64;
+    (
+      required + rule.approvals_required,
+      left + rule.approvals_required.saturating_sub(approved),
+    )
+  });
+  let user_has_approved = approvals.approved_by.contains(&FAKE_USER_ID);
+  MergeRequestApprovals {
+    approved: left == 0,
+    approvals_required: Some(required),
+    approvals_left: Some(left),
+    approved_by: approvals
+      .approved_by
+      .iter()
+      .map(|user| MergeRequestApprover { user: fake_user(*user) })
+      .collect(),
+    user_has_approved,
+    user_can_approve: !user_has_approved,
+  }
+}
+
+/// Resolve the project and the approvals of a merge request
+fn merge_request_approvals<'s, Q: Endpoint>(
+  state: &'s mut FakeGitlabState,
+  req: &Q,
+  project: &ProjectRef,
+  iid: MergeRequestIid,
+) -> Result<&'s mut FakeApprovals, HttpGitlabClientError> {
+  let id = state.project_id(project).ok_or_else(|| not_found(req, "Project"))?;
+  let project = state.projects.get_mut(&id).expect("project exists");
+  if !project.merge_requests.contains_key(&iid) {
+    return Err(not_found(req, "Merge Request"));
+  }
+  Ok(project.merge_request_approvals.entry(iid).or_default())
+}
+
+fake_service!(
+  GetMergeRequestApprovalsQuery,
+  MergeRequestApprovals,
+  get_merge_request_approvals
+);
+
+fn get_merge_request_approvals<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetMergeRequestApprovalsQuery<Cx>,
+) -> Result<MergeRequestApprovals, HttpGitlabClientError> {
+  let approvals = merge_request_approvals(state, req, &req.project, req.merge_request_iid)?;
+  Ok(fake_approvals(approvals))
+}
+
+fake_service!(
+  GetMergeRequestApprovalStateQuery,
+  MergeRequestApprovalState,
+  get_merge_request_approval_state
+);
+
+fn get_merge_request_approval_state<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetMergeRequestApprovalStateQuery<Cx>,
+) -> Result<MergeRequestApprovalState, HttpGitlabClientError> {
+  let approvals = merge_request_approvals(state, req, &req.project, req.merge_request_iid)?;
+  Ok(MergeRequestApprovalState {
+    approval_rules_overwritten: approvals.overwritten,
+    rules: approvals
+      .rules
+      .values()
+      .map(|rule| rule_state(rule, &approvals.approved_by))
+      .collect(),
+  })
+}
+
+fake_service!(
+  GetMergeRequestApprovalRuleListQuery,
+  Page<MergeRequestApprovalRule>,
+  get_merge_request_approval_rule_list
+);
+
+fn get_merge_request_approval_rule_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetMergeRequestApprovalRuleListQuery<Cx>,
+) -> Result<Page<MergeRequestApprovalRule>, HttpGitlabClientError> {
+  let approvals = merge_request_approvals(state, req, &req.project, req.merge_request_iid)?;
+  Ok(single_page(approvals.rules.values().cloned().collect()))
+}
+
+fake_service!(
+  GetMergeRequestApprovalRuleListPageQuery,
+  Page<MergeRequestApprovalRule>,
+  get_merge_request_approval_rule_list_page
+);
+
+fn get_merge_request_approval_rule_list_page<Cx: GetRef<GitlabUrl>>(
+  _state: &mut FakeGitlabState,
+  req: &GetMergeRequestApprovalRuleListPageQuery<Cx>,
+) -> Result<Page<MergeRequestApprovalRule>, HttpGitlabClientError> {
+  Err(not_found(req, "Page"))
+}
+
+fake_service!(
+  GetMergeRequestApprovalRuleQuery,
+  MergeRequestApprovalRule,
+  get_merge_request_approval_rule
+);
+
+fn get_merge_request_approval_rule<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetMergeRequestApprovalRuleQuery<Cx>,
+) -> Result<MergeRequestApprovalRule, HttpGitlabClientError> {
+  let approvals = merge_request_approvals(state, req, &req.project, req.merge_request_iid)?;
+  approvals
+    .rules
+    .get(&req.approval_rule_id)
+    .cloned()
+    .ok_or_else(|| not_found(req, "Approval Rule"))
+}
+
+fake_service!(ApproveMergeRequestCommand, MergeRequestApprovals, approve_merge_request);
+
+fn approve_merge_request<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &ApproveMergeRequestCommand<Cx>,
+) -> Result<MergeRequestApprovals, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = &state.projects[&id];
+  let merge_request = project
+    .merge_requests
+    .get(&req.merge_request_iid)
+    .ok_or_else(|| not_found(req, "Merge Request"))?;
+  if let Some(sha) = req.sha.as_deref() {
+    if project
+      .branches
+      .get(&merge_request.source_branch)
+      .map(CompactString::as_str)
+      != Some(sha)
+    {
+      return Err(error(
+        req,
+        StatusCode::CONFLICT,
+        "SHA does not match HEAD of source branch",
+      ));
+    }
+  }
+  let approvals = merge_request_approvals(state, req, &req.project, req.merge_request_iid)?;
+  if approvals.approved_by.contains(&FAKE_USER_ID) {
+    return Err(error(req, StatusCode::UNAUTHORIZED, "401 Unauthorized"));
+  }
+  approvals.approved_by.push(FAKE_USER_ID);
+  Ok(fake_approvals(approvals))
+}
+
+fake_service!(
+  UnapproveMergeRequestCommand,
+  MergeRequestApprovals,
+  unapprove_merge_request
+);
+
+fn unapprove_merge_request<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &UnapproveMergeRequestCommand<Cx>,
+) -> Result<MergeRequestApprovals, HttpGitlabClientError> {
+  let approvals = merge_request_approvals(state, req, &req.project, req.merge_request_iid)?;
+  if !approvals.approved_by.contains(&FAKE_USER_ID) {
+    return Err(not_found(req, "Approval"));
+  }
+  approvals.approved_by.retain(|user| *user != FAKE_USER_ID);
+  Ok(fake_approvals(approvals))
+}
+
+fake_service!(ResetMergeRequestApprovalsCommand, (), reset_merge_request_approvals);
+
+fn reset_merge_request_approvals<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &ResetMergeRequestApprovalsCommand<Cx>,
+) -> Result<(), HttpGitlabClientError> {
+  let approvals = merge_request_approvals(state, req, &req.project, req.merge_request_iid)?;
+  approvals.approved_by.clear();
+  Ok(())
+}
+
+fake_service!(
+  CreateMergeRequestApprovalRuleCommand,
+  MergeRequestApprovalRule,
+  create_merge_request_approval_rule
+);
+
+fn create_merge_request_approval_rule<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &CreateMergeRequestApprovalRuleCommand<Cx>,
+) -> Result<MergeRequestApprovalRule, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let source = match req.approval_project_rule_id {
+    Some(rule_id) => Some(
+      state.projects[&id]
+        .approval_rules
+        .get(&rule_id)
+        .cloned()
+        .ok_or_else(|| not_found(req, "Approval Rule"))?,
+    ),
+    None => None,
+  };
+  let users = match &source {
+    Some(source) if req.user_ids.is_empty() => source.users.clone(),
+    _ => Vec::from_iter(req.user_ids.iter().copied().map(fake_user)),
+  };
+  let groups = match &source {
+    Some(source) if req.group_ids.is_empty() => source.groups.clone(),
+    _ => approval_rule_groups(state, &req.group_ids),
+  };
+  let rule = MergeRequestApprovalRule {
+    id: ApprovalRuleId::new(state.next_id()),
+    name: req.name.clone(),
+    rule_type: ApprovalRuleType::Regular,
+    eligible_approvers: users.clone(),
+    approvals_required: req.approvals_required,
+    users,
+    groups,
+    contains_hidden_groups: false,
+    source_rule: source.map(|source| ApprovalRuleSource {
+      approvals_required: source.approvals_required,
+    }),
+    overridden: false,
+  };
+  let approvals = merge_request_approvals(state, req, &req.project, req.merge_request_iid)?;
+  approvals.rules.insert(rule.id, rule.clone());
+  approvals.overwritten = true;
+  Ok(rule)
+}
+
+fake_service!(
+  UpdateMergeRequestApprovalRuleCommand,
+  MergeRequestApprovalRule,
+  update_merge_request_approval_rule
+);
+
+fn update_merge_request_approval_rule<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &UpdateMergeRequestApprovalRuleCommand<Cx>,
+) -> Result<MergeRequestApprovalRule, HttpGitlabClientError> {
+  let groups = req.group_ids.as_ref().map(|ids| approval_rule_groups(state, ids));
+  let approvals = merge_request_approvals(state, req, &req.project, req.merge_request_iid)?;
+  let rule = approvals
+    .rules
+    .get_mut(&req.approval_rule_id)
+    .ok_or_else(|| not_found(req, "Approval Rule"))?;
+  if let Some(name) = &req.name {
+    rule.name = name.clone();
+  }
+  if let Some(approvals_required) = req.approvals_required {
+    rule.approvals_required = approvals_required;
+  }
+  if let Some(user_ids) = &req.user_ids {
+    rule.users = Vec::from_iter(user_ids.iter().copied().map(fake_user));
+    rule.eligible_approvers = rule.users.clone();
+  }
+  if let Some(groups) = groups {
+    rule.groups = groups;
+  }
+  rule.overridden = rule.source_rule.is_some();
+  let rule = rule.clone();
+  approvals.overwritten = true;
+  Ok(rule)
+}
+
+fake_service!(
+  DeleteMergeRequestApprovalRuleCommand,
+  (),
+  delete_merge_request_approval_rule
+);
+
+fn delete_merge_request_approval_rule<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &DeleteMergeRequestApprovalRuleCommand<Cx>,
+) -> Result<(), HttpGitlabClientError> {
+  let approvals = merge_request_approvals(state, req, &req.project, req.merge_request_iid)?;
+  approvals
+    .rules
+    .remove(&req.approval_rule_id)
+    .ok_or_else(|| not_found(req, "Approval Rule"))?;
+  approvals.overwritten = true;
+  Ok(())
+}
+
+fake_service!(
+  GetProjectApprovalRuleListQuery,
+  Page<ProjectApprovalRule>,
+  get_project_approval_rule_list
+);
+
+fn get_project_approval_rule_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetProjectApprovalRuleListQuery<Cx>,
+) -> Result<Page<ProjectApprovalRule>, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  Ok(single_page(
+    state.projects[&id].approval_rules.values().cloned().collect(),
+  ))
+}
+
+fake_service!(
+  GetProjectApprovalRuleListPageQuery,
+  Page<ProjectApprovalRule>,
+  get_project_approval_rule_list_page
+);
+
+fn get_project_approval_rule_list_page<Cx: GetRef<GitlabUrl>>(
+  _state: &mut FakeGitlabState,
+  req: &GetProjectApprovalRuleListPageQuery<Cx>,
+) -> Result<Page<ProjectApprovalRule>, HttpGitlabClientError> {
+  Err(not_found(req, "Page"))
+}
+
+fake_service!(
+  GetProjectApprovalRuleQuery,
+  ProjectApprovalRule,
+  get_project_approval_rule
+);
+
+fn get_project_approval_rule<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetProjectApprovalRuleQuery<Cx>,
+) -> Result<ProjectApprovalRule, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  state.projects[&id]
+    .approval_rules
+    .get(&req.approval_rule_id)
+    .cloned()
+    .ok_or_else(|| not_found(req, "Approval Rule"))
+}
+
+fake_service!(
+  CreateProjectApprovalRuleCommand,
+  ProjectApprovalRule,
+  create_project_approval_rule
+);
+
+fn create_project_approval_rule<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &CreateProjectApprovalRuleCommand<Cx>,
+) -> Result<ProjectApprovalRule, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let rule_type = req.rule_type.unwrap_or(ApprovalRuleType::Regular);
+  if !matches!(rule_type, ApprovalRuleType::Regular | ApprovalRuleType::AnyApprover) {
+    return Err(error(
+      req,
+      StatusCode::BAD_REQUEST,
+      "rule_type does not have a valid value",
+    ));
+  }
+  if state.projects[&id]
+    .approval_rules
+    .values()
+    .any(|rule| rule.name == req.name)
+  {
+    return Err(error(req, StatusCode::BAD_REQUEST, "name has already been taken"));
+  }
+  let users = Vec::from_iter(req.user_ids.iter().copied().map(fake_user));
+  let rule = ProjectApprovalRule {
+    id: ApprovalRuleId::new(state.next_id()),
+    name: req.name.clone(),
+    rule_type,
+    eligible_approvers: users.clone(),
+    approvals_required: req.approvals_required,
+    users,
+    groups: approval_rule_groups(state, &req.group_ids),
+    contains_hidden_groups: false,
+    protected_branches: approval_rule_protected_branches(&state.projects[&id], &req.protected_branch_ids),
+    applies_to_all_protected_branches: req.applies_to_all_protected_branches.unwrap_or(false),
+  };
+  let project = state.projects.get_mut(&id).expect("project exists");
+  project.approval_rules.insert(rule.id, rule.clone());
+  Ok(rule)
+}
+
+fake_service!(
+  UpdateProjectApprovalRuleCommand,
+  ProjectApprovalRule,
+  update_project_approval_rule
+);
+
+fn update_project_approval_rule<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &UpdateProjectApprovalRuleCommand<Cx>,
+) -> Result<ProjectApprovalRule, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let groups = req.group_ids.as_ref().map(|ids| approval_rule_groups(state, ids));
+  let project = state.projects.get_mut(&id).expect("project exists");
+  let protected_branches = req
+    .protected_branch_ids
+    .as_ref()
+    .map(|ids| approval_rule_protected_branches(project, ids));
+  let rule = project
+    .approval_rules
+    .get_mut(&req.approval_rule_id)
+    .ok_or_else(|| not_found(req, "Approval Rule"))?;
+  if let Some(name) = &req.name {
+    rule.name = name.clone();
+  }
+  if let Some(approvals_required) = req.approvals_required {
+    rule.approvals_required = approvals_required;
+  }
+  if let Some(user_ids) = &req.user_ids {
+    rule.users = Vec::from_iter(user_ids.iter().copied().map(fake_user));
+    rule.eligible_approvers = rule.users.clone();
+  }
+  if let Some(groups) = groups {
+    rule.groups = groups;
+  }
+  if let Some(protected_branches) = protected_branches {
+    rule.protected_branches = protected_branches;
+  }
+  if let Some(applies_to_all_protected_branches) = req.applies_to_all_protected_branches {
+    rule.applies_to_all_protected_branches = applies_to_all_protected_branches;
+  }
+  Ok(rule.clone())
+}
+
+fake_service!(DeleteProjectApprovalRuleCommand, (), delete_project_approval_rule);
+
+fn delete_project_approval_rule<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &DeleteProjectApprovalRuleCommand<Cx>,
+) -> Result<(), HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = state.projects.get_mut(&id).expect("project exists");
+  project
+    .approval_rules
+    .remove(&req.approval_rule_id)
+    .ok_or_else(|| not_found(req, "Approval Rule"))?;
+  Ok(())
+}
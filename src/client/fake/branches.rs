@@ -0,0 +1,421 @@
+//! Branches and protected branches of the fake GitLab
+
+use super::commits::{history, repository_ref};
+use super::{error, not_found, single_page, FakeGitlabState, FakeProject};
+use crate::client::http::HttpGitlabClientError;
+use crate::command::create_branch::CreateBranchCommand;
+use crate::command::delete_branch::DeleteBranchCommand;
+use crate::command::delete_merged_branches::DeleteMergedBranchesCommand;
+use crate::command::protect_branch::ProtectBranchCommand;
+use crate::command::unprotect_branch::UnprotectBranchCommand;
+use crate::command::update_protected_branch::UpdateProtectedBranchCommand;
+use crate::common::branch::Branch;
+use crate::common::protected_access::{ProtectedAccess, ProtectedAccessChange, ProtectedAccessId, ProtectedAccessRule};
+use crate::common::protected_branch::{ProtectedBranch, ProtectedBranchId};
+use crate::common::{AccessLevel, Page};
+use crate::context::{GetRef, GitlabUrl};
+use crate::query::get_branch::GetBranchQuery;
+use crate::query::get_branch_list::GetBranchListQuery;
+use crate::query::get_branch_list_page::GetBranchListPageQuery;
+use crate::query::get_protected_branch::GetProtectedBranchQuery;
+use crate::query::get_protected_branch_list::GetProtectedBranchListQuery;
+use crate::query::get_protected_branch_list_page::GetProtectedBranchListPageQuery;
+use compact_str::{format_compact, CompactString};
+use http::StatusCode;
+
+/// Match a name against a GitLab search term: `^term` and `term$` match the start and end of the name
+pub(super) fn matches_search(search: &str, name: &str) -> bool {
+  match (search.strip_prefix('^'), search.strip_suffix('$')) {
+    (Some(prefix), _) => name.starts_with(prefix),
+    (None, Some(suffix)) => name.ends_with(suffix),
+    (None, None) => name.contains(search),
+  }
+}
+
+/// Match a branch name against a protected branch name or wildcard, where `*` matches any characters
+pub(super) fn matches_wildcard(pattern: &str, name: &str) -> bool {
+  let parts = Vec::from_iter(pattern.split('*'));
+  let Some(mut rest) = name.strip_prefix(parts[0]) else {
+    return false;
+  };
+  match parts[1..].split_last() {
+    None => rest.is_empty(),
+    Some((last, middle)) => {
+      for part in middle {
+        match rest.find(part) {
+          Some(start) => rest = &rest[start + part.len()..],
+          None => return false,
+        }
+      }
+      rest.ends_with(last)
+    }
+  }
+}
+
+fn fake_branch(project: &FakeProject, name: &str, head: &str) -> Branch {
+  let head = &project.commits[head];
+  let default = project.project.default_branch.as_deref() == Some(name);
+  let merged = !default
+    && project
+      .project
+      .default_branch
+      .as_deref()
+      .and_then(|default| repository_ref(project, default))
+      .is_some_and(|default| history(project, default).any(|c| c.commit.id == head.commit.id));
+  let protection = project
+    .protected_branches
+    .values()
+    .find(|protection| matches_wildcard(&protection.name, name));
+  let developers_can = |levels: &[ProtectedAccess]| {
+    levels
+      .iter()
+      .any(|access| access.access_level == Some(AccessLevel::Developer))
+  };
+  Branch {
+    name: CompactString::new(name),
+    commit: head.commit.clone(),
+    merged,
+    protected: protection.is_some(),
+    default,
+    developers_can_push: protection.map_or(true, |p| developers_can(&p.push_access_levels)),
+    developers_can_merge: protection.map_or(true, |p| developers_can(&p.merge_access_levels)),
+    can_push: true,
+    web_url: Some(format!(
+      "{}/-/tree/{name}",
+      project.project.web_url.as_str().trim_end_matches('/')
+    )),
+  }
+}
+
+fn protected_access(id: ProtectedAccessId, rule: ProtectedAccessRule) -> ProtectedAccess {
+  let mut access = ProtectedAccess {
+    id,
+    access_level: None,
+    access_level_description: CompactString::default(),
+    user_id: None,
+    group_id: None,
+    deploy_key_id: None,
+  };
+  match rule {
+    ProtectedAccessRule::AccessLevel(level) => {
+      access.access_level = Some(level);
+      access.access_level_description = CompactString::new(match level {
+        AccessLevel::NoAccess => "No one",
+        AccessLevel::Developer => "Developers + Maintainers",
+        AccessLevel::Maintainer => "Maintainers",
+        AccessLevel::Owner => "Owners",
+        level => level.to_lower(),
+      });
+    }
+    ProtectedAccessRule::User(user) => {
+      access.user_id = Some(user);
+      access.access_level_description = format_compact!("User {}", user.into_u64());
+    }
+    ProtectedAccessRule::Group(group) => {
+      access.group_id = Some(group);
+      access.access_level_description = format_compact!("Group {}", group.into_u64());
+    }
+  }
+  access
+}
+
+/// Build an access list from its role (default: `Maintainer`) and additional rules
+pub(super) fn protected_access_list(
+  state: &mut FakeGitlabState,
+  level: Option<AccessLevel>,
+  rules: &[ProtectedAccessRule],
+) -> Vec<ProtectedAccess> {
+  let level = ProtectedAccessRule::AccessLevel(level.unwrap_or(AccessLevel::Maintainer));
+  core::iter::once(level)
+    .chain(rules.iter().copied())
+    .map(|rule| protected_access(ProtectedAccessId::new(state.next_id()), rule))
+    .collect()
+}
+
+fn update_access_list(state: &mut FakeGitlabState, list: &mut Vec<ProtectedAccess>, changes: &[ProtectedAccessChange]) {
+  for change in changes {
+    match *change {
+      ProtectedAccessChange::Add(rule) => list.push(protected_access(ProtectedAccessId::new(state.next_id()), rule)),
+      ProtectedAccessChange::Remove(id) => list.retain(|access| access.id != id),
+    }
+  }
+}
+
+fake_service!(GetBranchListQuery, Page<Branch>, get_branch_list);
+
+/// The fake only supports regexes matching a literal, optionally anchored with `^` and `$`
+fn get_branch_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetBranchListQuery<Cx>,
+) -> Result<Page<Branch>, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = &state.projects[&id];
+  let regex = req.regex.as_deref().map(|regex| {
+    let (start, literal) = regex
+      .strip_prefix('^')
+      .map_or((false, regex), |literal| (true, literal));
+    let (end, literal) = literal
+      .strip_suffix('$')
+      .map_or((false, literal), |literal| (true, literal));
+    (start, literal, end)
+  });
+  Ok(single_page(
+    project
+      .branches
+      .iter()
+      .filter(|(name, _)| {
+        req
+          .search
+          .as_deref()
+          .map_or(true, |search| matches_search(search, name))
+      })
+      .filter(|(name, _)| {
+        regex.map_or(true, |(start, literal, end)| match (start, end) {
+          (true, true) => name.as_str() == literal,
+          (true, false) => name.starts_with(literal),
+          (false, true) => name.ends_with(literal),
+          (false, false) => name.contains(literal),
+        })
+      })
+      .map(|(name, head)| fake_branch(project, name, head))
+      .collect(),
+  ))
+}
+
+fake_service!(GetBranchListPageQuery, Page<Branch>, get_branch_list_page);
+
+fn get_branch_list_page<Cx: GetRef<GitlabUrl>>(
+  _state: &mut FakeGitlabState,
+  req: &GetBranchListPageQuery<Cx>,
+) -> Result<Page<Branch>, HttpGitlabClientError> {
+  Err(not_found(req, "Page"))
+}
+
+fake_service!(GetBranchQuery, Branch, get_branch);
+
+fn get_branch<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetBranchQuery<Cx>,
+) -> Result<Branch, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = &state.projects[&id];
+  let head = project
+    .branches
+    .get(&req.branch)
+    .ok_or_else(|| not_found(req, "Branch"))?;
+  Ok(fake_branch(project, &req.branch, head))
+}
+
+fake_service!(CreateBranchCommand, Branch, create_branch);
+
+fn create_branch<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &CreateBranchCommand<Cx>,
+) -> Result<Branch, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = state.projects.get_mut(&id).expect("project exists");
+  if project.branches.contains_key(&req.branch) {
+    return Err(error(req, StatusCode::BAD_REQUEST, "Branch already exists"));
+  }
+  let head = repository_ref(project, &req.r#ref)
+    .map(|commit| CompactString::new(&commit.commit.id))
+    .ok_or_else(|| {
+      error(
+        req,
+        StatusCode::BAD_REQUEST,
+        &format!("Invalid reference name: {}", req.r#ref),
+      )
+    })?;
+  project.branches.insert(req.branch.clone(), head.clone());
+  Ok(fake_branch(project, &req.branch, &head))
+}
+
+fake_service!(DeleteBranchCommand, (), delete_branch);
+
+fn delete_branch<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &DeleteBranchCommand<Cx>,
+) -> Result<(), HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = state.projects.get_mut(&id).expect("project exists");
+  if !project.branches.contains_key(&req.branch) {
+    return Err(not_found(req, "Branch"));
+  }
+  if project.project.default_branch.as_deref() == Some(req.branch.as_str()) {
+    return Err(error(
+      req,
+      StatusCode::BAD_REQUEST,
+      "The default branch of a project cannot be deleted.",
+    ));
+  }
+  project.branches.remove(&req.branch);
+  Ok(())
+}
+
+fake_service!(DeleteMergedBranchesCommand, (), delete_merged_branches);
+
+fn delete_merged_branches<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &DeleteMergedBranchesCommand<Cx>,
+) -> Result<(), HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = state.projects.get_mut(&id).expect("project exists");
+  let merged = Vec::from_iter(
+    project
+      .branches
+      .iter()
+      .map(|(name, head)| fake_branch(project, name, head))
+      .filter(|branch| branch.merged && !branch.protected)
+      .map(|branch| branch.name),
+  );
+  for name in merged {
+    project.branches.remove(&name);
+  }
+  Ok(())
+}
+
+fake_service!(
+  GetProtectedBranchListQuery,
+  Page<ProtectedBranch>,
+  get_protected_branch_list
+);
+
+fn get_protected_branch_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetProtectedBranchListQuery<Cx>,
+) -> Result<Page<ProtectedBranch>, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  Ok(single_page(
+    state.projects[&id]
+      .protected_branches
+      .values()
+      .filter(|protection| {
+        req
+          .search
+          .as_deref()
+          .map_or(true, |search| protection.name.contains(search))
+      })
+      .cloned()
+      .collect(),
+  ))
+}
+
+fake_service!(
+  GetProtectedBranchListPageQuery,
+  Page<ProtectedBranch>,
+  get_protected_branch_list_page
+);
+
+fn get_protected_branch_list_page<Cx: GetRef<GitlabUrl>>(
+  _state: &mut FakeGitlabState,
+  req: &GetProtectedBranchListPageQuery<Cx>,
+) -> Result<Page<ProtectedBranch>, HttpGitlabClientError> {
+  Err(not_found(req, "Page"))
+}
+
+fake_service!(GetProtectedBranchQuery, ProtectedBranch, get_protected_branch);
+
+fn get_protected_branch<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetProtectedBranchQuery<Cx>,
+) -> Result<ProtectedBranch, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  state.projects[&id]
+    .protected_branches
+    .get(&req.name)
+    .cloned()
+    .ok_or_else(|| not_found(req, "Protected Branch"))
+}
+
+fake_service!(ProtectBranchCommand, ProtectedBranch, protect_branch);
+
+fn protect_branch<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &ProtectBranchCommand<Cx>,
+) -> Result<ProtectedBranch, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  if state.projects[&id].protected_branches.contains_key(&req.name) {
+    return Err(error(
+      req,
+      StatusCode::CONFLICT,
+      &format!("Protected branch '{}' already exists", req.name),
+    ));
+  }
+  let protection = ProtectedBranch {
+    id: ProtectedBranchId::new(state.next_id()),
+    name: req.name.clone(),
+    push_access_levels: protected_access_list(state, req.push_access_level, &req.allowed_to_push),
+    merge_access_levels: protected_access_list(state, req.merge_access_level, &req.allowed_to_merge),
+    unprotect_access_levels: protected_access_list(state, req.unprotect_access_level, &req.allowed_to_unprotect),
+    allow_force_push: req.allow_force_push.unwrap_or(false),
+    code_owner_approval_required: Some(req.code_owner_approval_required.unwrap_or(false)),
+  };
+  let project = state.projects.get_mut(&id).expect("project exists");
+  project.protected_branches.insert(req.name.clone(), protection.clone());
+  Ok(protection)
+}
+
+fake_service!(UpdateProtectedBranchCommand, ProtectedBranch, update_protected_branch);
+
+fn update_protected_branch<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &UpdateProtectedBranchCommand<Cx>,
+) -> Result<ProtectedBranch, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let mut protection = state.projects[&id]
+    .protected_branches
+    .get(&req.name)
+    .cloned()
+    .ok_or_else(|| not_found(req, "Protected Branch"))?;
+  update_access_list(state, &mut protection.push_access_levels, &req.allowed_to_push);
+  update_access_list(state, &mut protection.merge_access_levels, &req.allowed_to_merge);
+  update_access_list(
+    state,
+    &mut protection.unprotect_access_levels,
+    &req.allowed_to_unprotect,
+  );
+  if let Some(allow_force_push) = req.allow_force_push {
+    protection.allow_force_push = allow_force_push;
+  }
+  if let Some(required) = req.code_owner_approval_required {
+    protection.code_owner_approval_required = Some(required);
+  }
+  let project = state.projects.get_mut(&id).expect("project exists");
+  project.protected_branches.insert(req.name.clone(), protection.clone());
+  Ok(protection)
+}
+
+fake_service!(UnprotectBranchCommand, (), unprotect_branch);
+
+fn unprotect_branch<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &UnprotectBranchCommand<Cx>,
+) -> Result<(), HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = state.projects.get_mut(&id).expect("project exists");
+  project
+    .protected_branches
+    .remove(&req.name)
+    .map(|_| ())
+    .ok_or_else(|| not_found(req, "Protected Branch"))
+}
@@ -0,0 +1,686 @@
+//! Commits, cherry-picks and reverts of the fake GitLab
+
+use super::{
+  error, error_with_code, not_found, single_page, FakeCommit, FakeGitlabState, FakeProject, FakeRepositoryFile,
+  FakeWorkTree,
+};
+use crate::client::http::HttpGitlabClientError;
+use crate::command::cherry_pick_commit::CherryPickCommitCommand;
+use crate::command::create_commit::CreateCommitCommand;
+use crate::command::revert_commit::RevertCommitCommand;
+use crate::common::commit::{
+  Commit, CommitAction, CommitApplyFailure, CommitComment, CommitContent, CommitRef, CommitRefType, CommitStats,
+};
+use crate::common::diff::Diff;
+use crate::common::project::ProjectId;
+use crate::common::Page;
+use crate::context::{GetRef, GitlabUrl};
+use crate::endpoint::Endpoint;
+use crate::query::get_commit::GetCommitQuery;
+use crate::query::get_commit_comment_list::GetCommitCommentListQuery;
+use crate::query::get_commit_comment_list_page::GetCommitCommentListPageQuery;
+use crate::query::get_commit_diff::GetCommitDiffQuery;
+use crate::query::get_commit_list::GetCommitListQuery;
+use crate::query::get_commit_list_page::GetCommitListPageQuery;
+use crate::query::get_commit_ref_list::GetCommitRefListQuery;
+use crate::query::get_commit_ref_list_page::GetCommitRefListPageQuery;
+use crate::query::get_diff_list_page::GetDiffListPageQuery;
+use bytes::Bytes;
+use chrono::Utc;
+use compact_str::CompactString;
+use http::StatusCode;
+use std::collections::BTreeMap;
+
+fake_service!(CreateCommitCommand, Commit, create_commit);
+
+fn create_commit<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &CreateCommitCommand<Cx>,
+) -> Result<Commit, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let commit_id = state.next_object_id();
+  let blob_ids: Vec<CompactString> = req.actions.iter().map(|_| state.next_object_id()).collect();
+  let project = state.projects.get_mut(&id).expect("project exists");
+  // A forced commit replaces the branch, so it is built from its start point even if the branch exists
+  let branch = (req.force != Some(true)).then_some(req.branch.as_str());
+  let mut tree = work_tree(
+    req,
+    project,
+    branch,
+    req.start_branch.as_deref(),
+    req.start_sha.as_deref(),
+  )?;
+  let old_files = tree.files.clone();
+  for (action, blob_id) in req.actions.iter().zip(blob_ids) {
+    let new_file = |content: &CommitContent, execute_filemode: Option<bool>| FakeRepositoryFile {
+      content: Bytes::copy_from_slice(content.as_bytes()),
+      blob_id: blob_id.clone(),
+      last_commit_id: commit_id.clone(),
+      execute_filemode: execute_filemode.unwrap_or(false),
+    };
+    match action {
+      CommitAction::Create {
+        file_path,
+        content,
+        execute_filemode,
+      } => {
+        if tree.files.contains_key(file_path) {
+          return Err(error(
+            req,
+            StatusCode::BAD_REQUEST,
+            "A file with this name already exists",
+          ));
+        }
+        tree
+          .files
+          .insert(file_path.clone(), new_file(content, *execute_filemode));
+      }
+      CommitAction::Delete {
+        file_path,
+        last_commit_id,
+      } => {
+        changed_file(req, &mut tree, file_path, last_commit_id.as_deref())?;
+        tree.files.remove(file_path);
+      }
+      CommitAction::Move {
+        file_path,
+        previous_path,
+        content,
+        last_commit_id,
+      } => {
+        let previous = changed_file(req, &mut tree, previous_path, last_commit_id.as_deref())?.clone();
+        if tree.files.contains_key(file_path) {
+          return Err(error(
+            req,
+            StatusCode::BAD_REQUEST,
+            "A file with this name already exists",
+          ));
+        }
+        tree.files.remove(previous_path);
+        let file = match content {
+          Some(content) => new_file(content, Some(previous.execute_filemode)),
+          None => FakeRepositoryFile {
+            last_commit_id: commit_id.clone(),
+            ..previous
+          },
+        };
+        tree.files.insert(file_path.clone(), file);
+      }
+      CommitAction::Update {
+        file_path,
+        content,
+        last_commit_id,
+        execute_filemode,
+      } => {
+        let file = changed_file(req, &mut tree, file_path, last_commit_id.as_deref())?;
+        let execute_filemode = execute_filemode.unwrap_or(file.execute_filemode);
+        *file = new_file(content, Some(execute_filemode));
+      }
+      CommitAction::Chmod {
+        file_path,
+        execute_filemode,
+      } => {
+        let file = changed_file(req, &mut tree, file_path, None)?;
+        file.execute_filemode = *execute_filemode;
+        file.last_commit_id = commit_id.clone();
+      }
+    }
+  }
+  let stats = diff_stats(&old_files, &tree.files);
+  let mut commit = push_commit(
+    project,
+    &req.branch,
+    tree,
+    commit_id,
+    &req.commit_message,
+    req.author_name.as_deref(),
+    req.author_email.as_deref(),
+  );
+  if req.stats != Some(false) {
+    commit.stats = Some(stats);
+  }
+  Ok(commit)
+}
+
+/// Resolve a branch name, a tag name or a commit id (full or short)
+pub(super) fn repository_ref<'p>(project: &'p FakeProject, r#ref: &str) -> Option<&'p FakeCommit> {
+  let id = match (project.branches.get(r#ref), project.tags.get(r#ref)) {
+    (Some(head), _) => head.as_str(),
+    (None, Some(tag)) => tag.commit_id.as_str(),
+    (None, None) => r#ref,
+  };
+  project
+    .commits
+    .get(id)
+    .or_else(|| project.commits.values().find(|c| c.commit.short_id == id))
+}
+
+/// Get the files a commit is built from: the head of `branch` if it exists, else `start_branch` or `start_sha`
+///
+/// The first commit of an empty repository has no parent.
+pub(super) fn work_tree<Q: Endpoint>(
+  req: &Q,
+  project: &FakeProject,
+  branch: Option<&str>,
+  start_branch: Option<&str>,
+  start_sha: Option<&str>,
+) -> Result<FakeWorkTree, HttpGitlabClientError> {
+  let head = branch.and_then(|branch| project.branches.get(branch));
+  let start = match (head, start_branch, start_sha) {
+    (Some(head), _, _) => head.as_str(),
+    (None, Some(start_branch), _) => project.branches.get(start_branch).ok_or_else(|| {
+      error(
+        req,
+        StatusCode::BAD_REQUEST,
+        &format!("Invalid start branch: {start_branch}"),
+      )
+    })?,
+    (None, None, Some(start_sha)) => match project.commits.get(start_sha) {
+      Some(commit) => commit.commit.id.as_str(),
+      None => {
+        return Err(error(
+          req,
+          StatusCode::BAD_REQUEST,
+          &format!("Invalid start sha: {start_sha}"),
+        ))
+      }
+    },
+    (None, None, None) if project.branches.is_empty() => return Ok(FakeWorkTree::default()),
+    (None, None, None) => {
+      return Err(error(
+        req,
+        StatusCode::BAD_REQUEST,
+        "You can only create or edit files when you are on a branch",
+      ))
+    }
+  };
+  let commit = &project.commits[start];
+  Ok(FakeWorkTree {
+    parent_id: Some(CompactString::new(&commit.commit.id)),
+    files: commit.files.clone(),
+  })
+}
+
+/// Get a file changed by a commit, checking it was not changed since `last_commit_id`
+pub(super) fn changed_file<'t, Q: Endpoint>(
+  req: &Q,
+  tree: &'t mut FakeWorkTree,
+  file_path: &str,
+  last_commit_id: Option<&str>,
+) -> Result<&'t mut FakeRepositoryFile, HttpGitlabClientError> {
+  let file = tree
+    .files
+    .get_mut(file_path)
+    .ok_or_else(|| error(req, StatusCode::BAD_REQUEST, "A file with this name doesn't exist"))?;
+  match last_commit_id {
+    Some(last_commit_id) if last_commit_id != file.last_commit_id => Err(error(
+      req,
+      StatusCode::BAD_REQUEST,
+      "You are attempting to update a file that has changed since you started editing it.",
+    )),
+    _ => Ok(file),
+  }
+}
+
+/// Record a commit of `tree` and move `branch` to it
+pub(super) fn push_commit(
+  project: &mut FakeProject,
+  branch: &str,
+  tree: FakeWorkTree,
+  commit_id: CompactString,
+  message: &str,
+  author_name: Option<&str>,
+  author_email: Option<&str>,
+) -> Commit {
+  let now = Utc::now();
+  let author_name = author_name.unwrap_or("Fake User").to_string();
+  let author_email = author_email.unwrap_or("fake@example.com").to_string();
+  let commit = Commit {
+    id: commit_id.to_string(),
+    short_id: commit_id[..8].to_string(),
+    title: message.lines().next().unwrap_or_default().to_string(),
+    created_at: now,
+    parent_ids: tree.parent_id.iter().map(|id| id.to_string()).collect(),
+    message: message.to_string(),
+    author_name: author_name.clone(),
+    author_email: author_email.clone(),
+    authored_date: now,
+    committer_name: author_name,
+    committer_email: author_email,
+    committed_date: now,
+    trailers: Some(BTreeMap::new()),
+    web_url: Some(format!(
+      "{}/-/commit/{commit_id}",
+      project.project.web_url.as_str().trim_end_matches('/')
+    )),
+    stats: None,
+  };
+  project.commits.insert(
+    commit_id.clone(),
+    FakeCommit {
+      commit: commit.clone(),
+      files: tree.files,
+    },
+  );
+  project.branches.insert(CompactString::new(branch), commit_id);
+  commit
+}
+
+/// Count the lines added and removed between two snapshots of the repository files
+fn diff_stats(
+  old: &BTreeMap<CompactString, FakeRepositoryFile>,
+  new: &BTreeMap<CompactString, FakeRepositoryFile>,
+) -> CommitStats {
+  let lines = |file: Option<&FakeRepositoryFile>| {
+    file.map_or(0, |f| {
+      let newlines = f.content.iter().filter(|b| **b == b'\n').count();
+      (newlines + usize::from(!f.content.is_empty() && !f.content.ends_with(b"\n"))) as u64
+    })
+  };
+  let mut stats = CommitStats {
+    additions: 0,
+    deletions: 0,
+    total: 0,
+  };
+  for path in old.keys().chain(new.keys().filter(|path| !old.contains_key(*path))) {
+    let (old, new) = (old.get(path), new.get(path));
+    if old.map(|f| &f.content) != new.map(|f| &f.content) {
+      stats.additions += lines(new);
+      stats.deletions += lines(old);
+    }
+  }
+  stats.total = stats.additions + stats.deletions;
+  stats
+}
+
+/// Walk the first-parent history of a commit, newest first
+pub(super) fn history<'p>(project: &'p FakeProject, head: &'p FakeCommit) -> impl Iterator<Item = &'p FakeCommit> {
+  core::iter::successors(Some(head), move |commit| {
+    commit
+      .commit
+      .parent_ids
+      .first()
+      .and_then(|parent| project.commits.get(parent.as_str()))
+  })
+}
+
+/// Get the repository files of the first parent of a commit, empty for the first commit
+pub(super) fn parent_files(project: &FakeProject, commit: &FakeCommit) -> BTreeMap<CompactString, FakeRepositoryFile> {
+  match commit.commit.parent_ids.first() {
+    Some(parent) => project.commits[parent.as_str()].files.clone(),
+    None => BTreeMap::new(),
+  }
+}
+
+fn same_file(left: Option<&FakeRepositoryFile>, right: Option<&FakeRepositoryFile>) -> bool {
+  left.map(|f| (&f.content, f.execute_filemode)) == right.map(|f| (&f.content, f.execute_filemode))
+}
+
+/// Paths of the files whose content or mode differ between two snapshots of the repository files
+pub(super) fn changed_paths<'f>(
+  old: &'f BTreeMap<CompactString, FakeRepositoryFile>,
+  new: &'f BTreeMap<CompactString, FakeRepositoryFile>,
+) -> Vec<&'f CompactString> {
+  let mut paths = Vec::from_iter(old.keys().chain(new.keys().filter(|path| !old.contains_key(*path))));
+  paths.retain(|path| !same_file(old.get(*path), new.get(*path)));
+  paths.sort();
+  paths
+}
+
+/// Build the diff of each file changed between two snapshots of the repository files
+///
+/// Each changed file is a single hunk replacing all its old lines with its new lines.
+pub(super) fn diffs(
+  old: &BTreeMap<CompactString, FakeRepositoryFile>,
+  new: &BTreeMap<CompactString, FakeRepositoryFile>,
+  unidiff: bool,
+) -> Vec<Diff> {
+  let mode = |file: Option<&FakeRepositoryFile>| match file {
+    None => "0",
+    Some(file) if file.execute_filemode => "100755",
+    Some(_) => "100644",
+  };
+  let lines = |file: Option<&FakeRepositoryFile>| {
+    file.map_or_else(Vec::new, |f| {
+      Vec::from_iter(String::from_utf8_lossy(&f.content).lines().map(str::to_string))
+    })
+  };
+  let range = |len: usize| {
+    if len == 0 {
+      "0,0".to_string()
+    } else {
+      format!("1,{len}")
+    }
+  };
+  changed_paths(old, new)
+    .into_iter()
+    .map(|path| {
+      let (old_file, new_file) = (old.get(path), new.get(path));
+      let mut diff = String::new();
+      if unidiff {
+        let old_name = old_file.map_or_else(|| "/dev/null".to_string(), |_| format!("a/{path}"));
+        let new_name = new_file.map_or_else(|| "/dev/null".to_string(), |_| format!("b/{path}"));
+        diff.push_str(&format!("--- {old_name}\n+++ {new_name}\n"));
+      }
+      if old_file.map(|f| &f.content) != new_file.map(|f| &f.content) {
+        let (old_lines, new_lines) = (lines(old_file), lines(new_file));
+        diff.push_str(&format!(
+          "@@ -{} +{} @@\n",
+          range(old_lines.len()),
+          range(new_lines.len())
+        ));
+        for line in old_lines {
+          diff.push_str(&format!("-{line}\n"));
+        }
+        for line in new_lines {
+          diff.push_str(&format!("+{line}\n"));
+        }
+      }
+      Diff {
+        old_path: path.clone(),
+        new_path: path.clone(),
+        a_mode: CompactString::new(mode(old_file)),
+        b_mode: CompactString::new(mode(new_file)),
+        new_file: old_file.is_none(),
+        renamed_file: false,
+        deleted_file: new_file.is_none(),
+        diff,
+        generated_file: Some(false),
+        too_large: None,
+        collapsed: Some(false),
+      }
+    })
+    .collect()
+}
+
+fake_service!(GetCommitListQuery, Page<Commit>, get_commit_list);
+
+fn get_commit_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetCommitListQuery<Cx>,
+) -> Result<Page<Commit>, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = &state.projects[&id];
+  let mut commits = if req.all == Some(true) {
+    let mut commits = Vec::from_iter(project.commits.values());
+    commits.sort_by_key(|c| core::cmp::Reverse(c.commit.committed_date));
+    commits
+  } else {
+    let head = match req.ref_name.as_deref() {
+      Some(ref_name) => Some(repository_ref(project, ref_name).ok_or_else(|| not_found(req, "Commit"))?),
+      // An empty repository has no default branch yet
+      None => repository_ref(project, project.project.default_branch.as_deref().unwrap_or("main")),
+    };
+    head.map_or_else(Vec::new, |head| history(project, head).collect())
+  };
+  commits.retain(|c| {
+    let commit = &c.commit;
+    req.since.map_or(true, |since| commit.committed_date >= since)
+      && req.until.map_or(true, |until| commit.committed_date <= until)
+      && req.author.as_deref().map_or(true, |author| {
+        commit.author_name.contains(author) || commit.author_email.contains(author)
+      })
+      && req.path.as_deref().map_or(true, |path| {
+        let parent = parent_files(project, c);
+        changed_paths(&parent, &c.files)
+          .iter()
+          .any(|changed| changed.as_str() == path || changed.starts_with(&format!("{path}/")))
+      })
+  });
+  Ok(single_page(
+    commits
+      .into_iter()
+      .map(|c| {
+        let mut commit = c.commit.clone();
+        if req.with_stats == Some(true) {
+          commit.stats = Some(diff_stats(&parent_files(project, c), &c.files));
+        }
+        commit
+      })
+      .collect(),
+  ))
+}
+
+fake_service!(GetCommitListPageQuery, Page<Commit>, get_commit_list_page);
+
+fn get_commit_list_page<Cx: GetRef<GitlabUrl>>(
+  _state: &mut FakeGitlabState,
+  req: &GetCommitListPageQuery<Cx>,
+) -> Result<Page<Commit>, HttpGitlabClientError> {
+  Err(not_found(req, "Page"))
+}
+
+fake_service!(GetCommitQuery, Commit, get_commit);
+
+fn get_commit<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetCommitQuery<Cx>,
+) -> Result<Commit, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = &state.projects[&id];
+  let c = repository_ref(project, req.sha.as_str()).ok_or_else(|| not_found(req, "Commit"))?;
+  let mut commit = c.commit.clone();
+  if req.stats != Some(false) {
+    commit.stats = Some(diff_stats(&parent_files(project, c), &c.files));
+  }
+  Ok(commit)
+}
+
+fake_service!(GetCommitDiffQuery, Page<Diff>, get_commit_diff);
+
+fn get_commit_diff<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetCommitDiffQuery<Cx>,
+) -> Result<Page<Diff>, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = &state.projects[&id];
+  let commit = repository_ref(project, req.sha.as_str()).ok_or_else(|| not_found(req, "Commit"))?;
+  Ok(single_page(diffs(
+    &parent_files(project, commit),
+    &commit.files,
+    req.unidiff == Some(true),
+  )))
+}
+
+fake_service!(GetDiffListPageQuery, Page<Diff>, get_diff_list_page);
+
+fn get_diff_list_page<Cx: GetRef<GitlabUrl>>(
+  _state: &mut FakeGitlabState,
+  req: &GetDiffListPageQuery<Cx>,
+) -> Result<Page<Diff>, HttpGitlabClientError> {
+  Err(not_found(req, "Page"))
+}
+
+fake_service!(GetCommitRefListQuery, Page<CommitRef>, get_commit_ref_list);
+
+fn get_commit_ref_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetCommitRefListQuery<Cx>,
+) -> Result<Page<CommitRef>, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = &state.projects[&id];
+  let commit = repository_ref(project, req.sha.as_str()).ok_or_else(|| not_found(req, "Commit"))?;
+  let contains = |head: &str| history(project, &project.commits[head]).any(|c| c.commit.id == commit.commit.id);
+  let branches = project
+    .branches
+    .iter()
+    .filter(|(_, head)| contains(head))
+    .map(|(name, _)| CommitRef {
+      r#type: CommitRefType::Branch,
+      name: name.clone(),
+    });
+  let tags = project
+    .tags
+    .iter()
+    .filter(|(_, tag)| contains(&tag.commit_id))
+    .map(|(name, _)| CommitRef {
+      r#type: CommitRefType::Tag,
+      name: name.clone(),
+    });
+  Ok(single_page(match req.r#type {
+    Some(CommitRefType::Branch) => branches.collect(),
+    Some(CommitRefType::Tag) => tags.collect(),
+    None => branches.chain(tags).collect(),
+  }))
+}
+
+fake_service!(GetCommitRefListPageQuery, Page<CommitRef>, get_commit_ref_list_page);
+
+fn get_commit_ref_list_page<Cx: GetRef<GitlabUrl>>(
+  _state: &mut FakeGitlabState,
+  req: &GetCommitRefListPageQuery<Cx>,
+) -> Result<Page<CommitRef>, HttpGitlabClientError> {
+  Err(not_found(req, "Page"))
+}
+
+fake_service!(GetCommitCommentListQuery, Page<CommitComment>, get_commit_comment_list);
+
+/// Comments are not stored by the fake: every commit has an empty comment list
+fn get_commit_comment_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetCommitCommentListQuery<Cx>,
+) -> Result<Page<CommitComment>, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  repository_ref(&state.projects[&id], req.sha.as_str()).ok_or_else(|| not_found(req, "Commit"))?;
+  Ok(single_page(Vec::new()))
+}
+
+fake_service!(
+  GetCommitCommentListPageQuery,
+  Page<CommitComment>,
+  get_commit_comment_list_page
+);
+
+fn get_commit_comment_list_page<Cx: GetRef<GitlabUrl>>(
+  _state: &mut FakeGitlabState,
+  req: &GetCommitCommentListPageQuery<Cx>,
+) -> Result<Page<CommitComment>, HttpGitlabClientError> {
+  Err(not_found(req, "Page"))
+}
+
+fake_service!(CherryPickCommitCommand, Commit, cherry_pick_commit);
+
+fn cherry_pick_commit<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &CherryPickCommitCommand<Cx>,
+) -> Result<Commit, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = &state.projects[&id];
+  let commit = repository_ref(project, req.sha.as_str()).ok_or_else(|| not_found(req, "Commit"))?;
+  let message = match req.message.as_deref() {
+    Some(message) => message.to_string(),
+    None => format!(
+      "{}\n\n(cherry picked from commit {})",
+      commit.commit.message.trim_end(),
+      commit.commit.id
+    ),
+  };
+  let (before, after) = (parent_files(project, commit), commit.files.clone());
+  apply_commit(req, state, id, &req.branch, &before, &after, &message)
+}
+
+fake_service!(RevertCommitCommand, Commit, revert_commit);
+
+fn revert_commit<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &RevertCommitCommand<Cx>,
+) -> Result<Commit, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = &state.projects[&id];
+  let commit = repository_ref(project, req.sha.as_str()).ok_or_else(|| not_found(req, "Commit"))?;
+  let message = format!(
+    "Revert \"{}\"\n\nThis reverts commit {}",
+    commit.commit.title, commit.commit.id
+  );
+  let (before, after) = (commit.files.clone(), parent_files(project, commit));
+  apply_commit(req, state, id, &req.branch, &before, &after, &message)
+}
+
+/// Commit on `branch` the changes turning `before` into `after`, for a cherry-pick or a revert
+///
+/// Files already matching `after` are skipped; the commit fails with a typed `error_code` if another file does not
+/// match `before` (`conflict`) or if every change is already applied (`empty`).
+fn apply_commit<Q: Endpoint>(
+  req: &Q,
+  state: &mut FakeGitlabState,
+  id: ProjectId,
+  branch: &str,
+  before: &BTreeMap<CompactString, FakeRepositoryFile>,
+  after: &BTreeMap<CompactString, FakeRepositoryFile>,
+  message: &str,
+) -> Result<Commit, HttpGitlabClientError> {
+  let failure = |failure: CommitApplyFailure| {
+    error_with_code(
+      req,
+      StatusCode::BAD_REQUEST,
+      "Sorry, we cannot apply this commit automatically. It may already have been applied, or a more recent commit may \
+       have updated some of its content.",
+      Some(failure.as_str()),
+    )
+  };
+  let commit_id = state.next_object_id();
+  let project = state.projects.get_mut(&id).expect("project exists");
+  if !project.branches.contains_key(branch) {
+    return Err(not_found(req, "Branch"));
+  }
+  let mut tree = work_tree(req, project, Some(branch), None, None)?;
+  let old_files = tree.files.clone();
+  if !merge_files(&mut tree.files, before, after, &commit_id) {
+    return Err(failure(CommitApplyFailure::Conflict));
+  }
+  if changed_paths(&old_files, &tree.files).is_empty() {
+    return Err(failure(CommitApplyFailure::Empty));
+  }
+  Ok(push_commit(project, branch, tree, commit_id, message, None, None))
+}
+
+/// Apply to `files` the changes turning `before` into `after`, as part of the commit `commit_id`
+///
+/// Files already matching `after` are skipped. Returns `false` if another changed file does not match `before`.
+pub(super) fn merge_files(
+  files: &mut BTreeMap<CompactString, FakeRepositoryFile>,
+  before: &BTreeMap<CompactString, FakeRepositoryFile>,
+  after: &BTreeMap<CompactString, FakeRepositoryFile>,
+  commit_id: &CompactString,
+) -> bool {
+  for path in changed_paths(before, after) {
+    let current = files.get(path);
+    if same_file(current, after.get(path)) {
+      continue;
+    }
+    if !same_file(current, before.get(path)) {
+      return false;
+    }
+    match after.get(path) {
+      Some(file) => {
+        files.insert(
+          path.clone(),
+          FakeRepositoryFile {
+            last_commit_id: commit_id.clone(),
+            ..file.clone()
+          },
+        );
+      }
+      None => {
+        files.remove(path);
+      }
+    }
+  }
+  true
+}
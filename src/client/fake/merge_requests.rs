@@ -0,0 +1,885 @@
+//! Merge requests and their diff versions of the fake GitLab
+
+use super::approvals::copy_approval_rules;
+use super::commits::{changed_paths, diffs, history, merge_files, parent_files, push_commit};
+use super::{
+  error, fake_user, not_found, single_page, FakeCommit, FakeGitlabState, FakeProject, FakeRepositoryFile, FakeWorkTree,
+  FAKE_USER_ID,
+};
+use crate::client::http::HttpGitlabClientError;
+use crate::command::accept_merge_request::AcceptMergeRequestCommand;
+use crate::command::create_merge_request::CreateMergeRequestCommand;
+use crate::command::rebase_merge_request::RebaseMergeRequestCommand;
+use crate::command::update_merge_request::UpdateMergeRequestCommand;
+use crate::common::commit::Commit;
+use crate::common::diff::Diff;
+use crate::common::merge_request::{
+  MergeFailure, MergeRequest, MergeRequestChanges, MergeRequestDiffVersion, MergeRequestDiffVersionId, MergeRequestId,
+  MergeRequestIid, MergeRequestOrder, MergeRequestRebase, MergeRequestReferences, MergeRequestScope, MergeRequestState,
+  MergeRequestStateEvent,
+};
+use crate::common::project::{ProjectId, ProjectRef};
+use crate::common::user::{User, UserId};
+use crate::common::{KeysetPagination, Page, SortOrder};
+use crate::context::{GetRef, GitlabUrl};
+use crate::endpoint::Endpoint;
+use crate::query::get_group_merge_request_list::GetGroupMergeRequestListQuery;
+use crate::query::get_merge_request::GetMergeRequestQuery;
+use crate::query::get_merge_request_changes::GetMergeRequestChangesQuery;
+use crate::query::get_merge_request_commit_list::GetMergeRequestCommitListQuery;
+use crate::query::get_merge_request_diff_list::GetMergeRequestDiffListQuery;
+use crate::query::get_merge_request_diff_version::GetMergeRequestDiffVersionQuery;
+use crate::query::get_merge_request_diff_version_list::GetMergeRequestDiffVersionListQuery;
+use crate::query::get_merge_request_diff_version_list_page::GetMergeRequestDiffVersionListPageQuery;
+use crate::query::get_merge_request_list::GetMergeRequestListQuery;
+use crate::query::get_merge_request_list_page::GetMergeRequestListPageQuery;
+use crate::query::get_project_merge_request_list::GetProjectMergeRequestListQuery;
+use chrono::{DateTime, Utc};
+use compact_str::{format_compact, CompactString};
+use http::StatusCode;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+/// Ids of a commit and all its ancestors
+fn ancestors<'p>(project: &'p FakeProject, head: &'p FakeCommit) -> BTreeSet<&'p str> {
+  let mut ids = BTreeSet::new();
+  let mut pending = vec![head.commit.id.as_str()];
+  while let Some(id) = pending.pop() {
+    if ids.insert(id) {
+      pending.extend(project.commits[id].commit.parent_ids.iter().map(String::as_str));
+    }
+  }
+  ids
+}
+
+/// Closest common ancestor of two commits, walking the history of `right` breadth-first
+fn merge_base<'p>(project: &'p FakeProject, left: &'p FakeCommit, right: &'p FakeCommit) -> Option<&'p FakeCommit> {
+  let left = ancestors(project, left);
+  let mut visited = BTreeSet::new();
+  let mut pending = VecDeque::from([right]);
+  while let Some(commit) = pending.pop_front() {
+    if left.contains(commit.commit.id.as_str()) {
+      return Some(commit);
+    }
+    if visited.insert(commit.commit.id.as_str()) {
+      pending.extend(commit.commit.parent_ids.iter().map(|id| &project.commits[id.as_str()]));
+    }
+  }
+  None
+}
+
+/// Files of `target` with the changes of `source` since their merge base, `None` if they conflict
+fn merged_files(
+  project: &FakeProject,
+  source: &FakeCommit,
+  target: &FakeCommit,
+  commit_id: &CompactString,
+) -> Option<BTreeMap<CompactString, FakeRepositoryFile>> {
+  let base = merge_base(project, source, target)
+    .map(|base| base.files.clone())
+    .unwrap_or_default();
+  let mut files = target.files.clone();
+  merge_files(&mut files, &base, &source.files, commit_id).then_some(files)
+}
+
+/// Whether a merge request title marks it as a draft
+fn is_draft(title: &str) -> bool {
+  let title = title.to_ascii_lowercase();
+  ["draft:", "[draft]", "(draft)"]
+    .iter()
+    .any(|prefix| title.starts_with(prefix))
+}
+
+/// Refresh the fields of an open merge request depending on the state of its branches
+fn fake_merge_request(project: &FakeProject, merge_request: &MergeRequest) -> MergeRequest {
+  let mut merge_request = merge_request.clone();
+  if merge_request.state != MergeRequestState::Opened {
+    merge_request.detailed_merge_status = CompactString::new("not_open");
+    return merge_request;
+  }
+  let source = project.branches.get(&merge_request.source_branch);
+  let target = project.branches.get(&merge_request.target_branch);
+  merge_request.sha = source.cloned();
+  merge_request.has_conflicts = match (source, target) {
+    (Some(source), Some(target)) => merged_files(
+      project,
+      &project.commits[source],
+      &project.commits[target],
+      &CompactString::default(),
+    )
+    .is_none(),
+    _ => false,
+  };
+  let status = if merge_request.draft {
+    "draft_status"
+  } else if source.is_none() || target.is_none() {
+    "broken_status"
+  } else if merge_request.has_conflicts {
+    "conflict"
+  } else {
+    "mergeable"
+  };
+  merge_request.detailed_merge_status = CompactString::new(status);
+  merge_request
+}
+
+/// Filters shared by the project, group and global merge request lists
+struct MergeRequestFilter<'a> {
+  pagination: Option<KeysetPagination<MergeRequestOrder>>,
+  state: Option<MergeRequestState>,
+  scope: Option<MergeRequestScope>,
+  labels: Vec<&'a str>,
+  author_id: Option<UserId>,
+  author_username: Option<&'a str>,
+  reviewer_id: Option<UserId>,
+  reviewer_username: Option<&'a str>,
+  milestone: Option<&'a str>,
+  draft: Option<bool>,
+  target_branch: Option<&'a str>,
+  updated_after: Option<DateTime<Utc>>,
+  updated_before: Option<DateTime<Utc>>,
+}
+
+macro_rules! merge_request_filter {
+  ($req:expr) => {
+    MergeRequestFilter {
+      pagination: $req.pagination,
+      state: $req.state,
+      scope: $req.scope,
+      labels: Vec::from_iter($req.labels.iter().map(CompactString::as_str)),
+      author_id: $req.author_id,
+      author_username: $req.author_username.as_deref(),
+      reviewer_id: $req.reviewer_id,
+      reviewer_username: $req.reviewer_username.as_deref(),
+      milestone: $req.milestone.as_deref(),
+      draft: $req.draft,
+      target_branch: $req.target_branch.as_deref(),
+      updated_after: $req.updated_after,
+      updated_before: $req.updated_before,
+    }
+  };
+}
+
+impl MergeRequestFilter<'_> {
+  fn matches(&self, merge_request: &MergeRequest) -> bool {
+    let labels = match self.labels.as_slice() {
+      ["None"] => merge_request.labels.is_empty(),
+      ["Any"] => !merge_request.labels.is_empty(),
+      labels => labels
+        .iter()
+        .all(|label| merge_request.labels.iter().any(|l| l == label)),
+    };
+    let milestone = match (self.milestone, merge_request.milestone.as_ref()) {
+      (None, _) => true,
+      (Some("None"), milestone) => milestone.is_none(),
+      (Some("Any"), milestone) => milestone.is_some(),
+      (Some(title), milestone) => milestone.is_some_and(|m| m.title == title),
+    };
+    let scope = match self.scope.unwrap_or(MergeRequestScope::All) {
+      MergeRequestScope::CreatedByMe => merge_request.author.id == FAKE_USER_ID,
+      MergeRequestScope::AssignedToMe => merge_request.assignees.iter().any(|user| user.id == FAKE_USER_ID),
+      MergeRequestScope::All => true,
+    };
+    let reviewed_by = |matches: &dyn Fn(&User) -> bool| merge_request.reviewers.iter().any(matches);
+    labels
+      && milestone
+      && scope
+      && self.state.map_or(true, |state| merge_request.state == state)
+      && self.author_id.map_or(true, |id| merge_request.author.id == id)
+      && self
+        .author_username
+        .map_or(true, |username| merge_request.author.username == username)
+      && self.reviewer_id.map_or(true, |id| reviewed_by(&|user| user.id == id))
+      && self
+        .reviewer_username
+        .map_or(true, |username| reviewed_by(&|user| user.username == username))
+      && self.draft.map_or(true, |draft| merge_request.draft == draft)
+      && self
+        .target_branch
+        .map_or(true, |branch| merge_request.target_branch == branch)
+      && self.updated_after.map_or(true, |date| merge_request.updated_at > date)
+      && self.updated_before.map_or(true, |date| merge_request.updated_at < date)
+  }
+
+  fn apply<'p>(&self, projects: impl Iterator<Item = &'p FakeProject>) -> Page<MergeRequest> {
+    let mut merge_requests = Vec::from_iter(projects.flat_map(|project| {
+      project
+        .merge_requests
+        .values()
+        .map(|merge_request| fake_merge_request(project, merge_request))
+        .filter(|merge_request| self.matches(merge_request))
+    }));
+    let (order_by, sort) = match self.pagination {
+      Some(pagination) => (pagination.order_by, pagination.sort),
+      None => (MergeRequestOrder::CreatedAt, SortOrder::Desc),
+    };
+    match order_by {
+      MergeRequestOrder::CreatedAt => merge_requests.sort_by_key(|mr| (mr.created_at, mr.id)),
+      MergeRequestOrder::UpdatedAt => merge_requests.sort_by_key(|mr| (mr.updated_at, mr.id)),
+      MergeRequestOrder::MergedAt => merge_requests.sort_by_key(|mr| (mr.merged_at, mr.id)),
+      MergeRequestOrder::Title => merge_requests.sort_by(|left, right| left.title.cmp(&right.title)),
+    }
+    if sort == SortOrder::Desc {
+      merge_requests.reverse();
+    }
+    single_page(merge_requests)
+  }
+}
+
+fake_service!(
+  GetProjectMergeRequestListQuery,
+  Page<MergeRequest>,
+  get_project_merge_request_list
+);
+
+fn get_project_merge_request_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetProjectMergeRequestListQuery<Cx>,
+) -> Result<Page<MergeRequest>, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  Ok(merge_request_filter!(req).apply(core::iter::once(&state.projects[&id])))
+}
+
+fake_service!(
+  GetGroupMergeRequestListQuery,
+  Page<MergeRequest>,
+  get_group_merge_request_list
+);
+
+/// List the merge requests of the projects whose namespace is the group or one of its subgroups
+fn get_group_merge_request_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetGroupMergeRequestListQuery<Cx>,
+) -> Result<Page<MergeRequest>, HttpGitlabClientError> {
+  let group = state.groups.get(&req.group).ok_or_else(|| not_found(req, "Group"))?;
+  let prefix = format!("{}/", group.full_path);
+  let projects = state
+    .projects
+    .values()
+    .filter(|project| project.project.path_with_namespace.starts_with(&prefix));
+  Ok(merge_request_filter!(req).apply(projects))
+}
+
+fake_service!(GetMergeRequestListQuery, Page<MergeRequest>, get_merge_request_list);
+
+/// The fake has a single user: it creates every merge request
+fn get_merge_request_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetMergeRequestListQuery<Cx>,
+) -> Result<Page<MergeRequest>, HttpGitlabClientError> {
+  let filter = MergeRequestFilter {
+    scope: Some(req.scope.unwrap_or(MergeRequestScope::CreatedByMe)),
+    ..merge_request_filter!(req)
+  };
+  Ok(filter.apply(state.projects.values()))
+}
+
+fake_service!(
+  GetMergeRequestListPageQuery,
+  Page<MergeRequest>,
+  get_merge_request_list_page
+);
+
+fn get_merge_request_list_page<Cx: GetRef<GitlabUrl>>(
+  _state: &mut FakeGitlabState,
+  req: &GetMergeRequestListPageQuery<Cx>,
+) -> Result<Page<MergeRequest>, HttpGitlabClientError> {
+  Err(not_found(req, "Page"))
+}
+
+fake_service!(GetMergeRequestQuery, MergeRequest, get_merge_request);
+
+fn get_merge_request<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetMergeRequestQuery<Cx>,
+) -> Result<MergeRequest, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = &state.projects[&id];
+  let merge_request = project
+    .merge_requests
+    .get(&req.merge_request_iid)
+    .ok_or_else(|| not_found(req, "Merge Request"))?;
+  let mut merge_request = fake_merge_request(project, merge_request);
+  if req.include_rebase_in_progress == Some(true) {
+    merge_request.rebase_in_progress = Some(false);
+  }
+  Ok(merge_request)
+}
+
+fake_service!(CreateMergeRequestCommand, MergeRequest, create_merge_request);
+
+fn create_merge_request<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &CreateMergeRequestCommand<Cx>,
+) -> Result<MergeRequest, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let merge_request_id = MergeRequestId::new(state.next_id());
+  let project = state.projects.get_mut(&id).expect("project exists");
+  if req.source_branch == req.target_branch {
+    return Err(error(
+      req,
+      StatusCode::BAD_REQUEST,
+      "You can't use same project/branch for source and target",
+    ));
+  }
+  for branch in [&req.source_branch, &req.target_branch] {
+    if !project.branches.contains_key(branch) {
+      return Err(error(
+        req,
+        StatusCode::BAD_REQUEST,
+        &format!("Branch {branch} does not exist"),
+      ));
+    }
+  }
+  if let Some(existing) = project.merge_requests.values().find(|mr| {
+    mr.state == MergeRequestState::Opened
+      && mr.source_branch == req.source_branch
+      && mr.target_branch == req.target_branch
+  }) {
+    return Err(error(
+      req,
+      StatusCode::CONFLICT,
+      &format!(
+        "Another open merge request already exists for this source branch: !{}",
+        existing.iid.into_u64()
+      ),
+    ));
+  }
+  let iid = project
+    .merge_requests
+    .keys()
+    .next_back()
+    .map_or(1, |iid| iid.into_u64() + 1);
+  let mut labels = req.labels.clone();
+  labels.sort();
+  labels.dedup();
+  let now = Utc::now();
+  let merge_request = MergeRequest {
+    id: merge_request_id,
+    iid: MergeRequestIid::new(iid),
+    project_id: id,
+    title: req.title.clone(),
+    description: req.description.clone(),
+    state: MergeRequestState::Opened,
+    created_at: now,
+    updated_at: now,
+    merged_at: None,
+    merge_user: None,
+    closed_at: None,
+    closed_by: None,
+    source_project_id: id,
+    source_branch: req.source_branch.clone(),
+    target_project_id: id,
+    target_branch: req.target_branch.clone(),
+    author: fake_user(FAKE_USER_ID),
+    assignees: Vec::from_iter(req.assignee_ids.iter().copied().map(fake_user)),
+    reviewers: Vec::from_iter(req.reviewer_ids.iter().copied().map(fake_user)),
+    labels,
+    draft: is_draft(&req.title),
+    milestone: req.milestone_id.and_then(|id| project.milestones.get(&id).cloned()),
+    merge_when_pipeline_succeeds: false,
+    detailed_merge_status: CompactString::new("checking"),
+    has_conflicts: false,
+    sha: None,
+    merge_commit_sha: None,
+    squash_commit_sha: None,
+    squash: req.squash.unwrap_or(false),
+    force_remove_source_branch: req.remove_source_branch,
+    should_remove_source_branch: None,
+    discussion_locked: None,
+    user_notes_count: 0,
+    references: MergeRequestReferences {
+      short: format_compact!("!{iid}"),
+      relative: format_compact!("!{iid}"),
+      full: format_compact!("{}!{iid}", project.project.path_with_namespace),
+    },
+    web_url: format_compact!(
+      "{}/-/merge_requests/{iid}",
+      project.project.web_url.as_str().trim_end_matches('/')
+    ),
+    merge_error: None,
+    rebase_in_progress: None,
+  };
+  project.merge_requests.insert(merge_request.iid, merge_request.clone());
+  let merge_request = fake_merge_request(project, &merge_request);
+  diff_versions(state, id, merge_request.iid);
+  copy_approval_rules(state, id, merge_request.iid);
+  Ok(merge_request)
+}
+
+fake_service!(UpdateMergeRequestCommand, MergeRequest, update_merge_request);
+
+fn update_merge_request<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &UpdateMergeRequestCommand<Cx>,
+) -> Result<MergeRequest, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = state.projects.get_mut(&id).expect("project exists");
+  if let Some(target_branch) = req.target_branch.as_ref() {
+    if !project.branches.contains_key(target_branch) {
+      return Err(error(
+        req,
+        StatusCode::BAD_REQUEST,
+        &format!("Branch {target_branch} does not exist"),
+      ));
+    }
+  }
+  let milestone = req.milestone_id.map(|id| project.milestones.get(&id).cloned());
+  let merge_request = project
+    .merge_requests
+    .get_mut(&req.merge_request_iid)
+    .ok_or_else(|| not_found(req, "Merge Request"))?;
+  if let Some(title) = req.title.as_ref() {
+    merge_request.title = title.clone();
+    merge_request.draft = is_draft(title);
+  }
+  if let Some(description) = req.description.as_ref() {
+    merge_request.description = Some(description.clone());
+  }
+  if let Some(target_branch) = req.target_branch.as_ref() {
+    merge_request.target_branch = target_branch.clone();
+  }
+  if let Some(assignee_ids) = req.assignee_ids.as_ref() {
+    merge_request.assignees = Vec::from_iter(assignee_ids.iter().copied().map(fake_user));
+  }
+  if let Some(reviewer_ids) = req.reviewer_ids.as_ref() {
+    merge_request.reviewers = Vec::from_iter(reviewer_ids.iter().copied().map(fake_user));
+  }
+  if let Some(labels) = req.labels.as_ref() {
+    merge_request.labels = labels.clone();
+  }
+  merge_request.labels.extend(req.add_labels.iter().cloned());
+  merge_request.labels.retain(|label| !req.remove_labels.contains(label));
+  merge_request.labels.sort();
+  merge_request.labels.dedup();
+  if let Some(milestone) = milestone {
+    merge_request.milestone = milestone;
+  }
+  let now = Utc::now();
+  match (req.state_event, merge_request.state) {
+    (Some(MergeRequestStateEvent::Close), MergeRequestState::Opened) => {
+      merge_request.state = MergeRequestState::Closed;
+      merge_request.closed_at = Some(now);
+      merge_request.closed_by = Some(fake_user(FAKE_USER_ID));
+    }
+    (Some(MergeRequestStateEvent::Reopen), MergeRequestState::Closed) => {
+      merge_request.state = MergeRequestState::Opened;
+      merge_request.closed_at = None;
+      merge_request.closed_by = None;
+    }
+    _ => {}
+  }
+  if let Some(remove_source_branch) = req.remove_source_branch {
+    merge_request.force_remove_source_branch = Some(remove_source_branch);
+  }
+  if let Some(squash) = req.squash {
+    merge_request.squash = squash;
+  }
+  if let Some(discussion_locked) = req.discussion_locked {
+    merge_request.discussion_locked = Some(discussion_locked);
+  }
+  merge_request.updated_at = now;
+  let merge_request = merge_request.clone();
+  Ok(fake_merge_request(project, &merge_request))
+}
+
+fake_service!(AcceptMergeRequestCommand, MergeRequest, accept_merge_request);
+
+/// Merge with a merge commit, or with a single squash commit on the target branch when squashing
+fn accept_merge_request<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &AcceptMergeRequestCommand<Cx>,
+) -> Result<MergeRequest, HttpGitlabClientError> {
+  let failure = |failure: MergeFailure, message: &str| {
+    let status = StatusCode::from_u16(failure.status()).expect("merge failure status is valid");
+    error(req, status, message).classify_merge_failure()
+  };
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let commit_id = state.next_object_id();
+  let project = state.projects.get_mut(&id).expect("project exists");
+  let merge_request = project
+    .merge_requests
+    .get(&req.merge_request_iid)
+    .ok_or_else(|| not_found(req, "Merge Request"))?;
+  let merge_request = fake_merge_request(project, merge_request);
+  let not_mergeable = || failure(MergeFailure::NotMergeable, "405 Method Not Allowed");
+  if merge_request.state != MergeRequestState::Opened || merge_request.draft {
+    return Err(not_mergeable());
+  }
+  let (Some(source), Some(target)) = (
+    project.branches.get(&merge_request.source_branch),
+    project.branches.get(&merge_request.target_branch),
+  ) else {
+    return Err(not_mergeable());
+  };
+  let (source, target) = (&project.commits[source], &project.commits[target]);
+  if let Some(sha) = req.sha.as_deref() {
+    if sha != source.commit.id {
+      return Err(failure(
+        MergeFailure::ShaMismatch,
+        &format!("SHA does not match HEAD of source branch: {}", source.commit.id),
+      ));
+    }
+  }
+  let files = merged_files(project, source, target, &commit_id)
+    .ok_or_else(|| failure(MergeFailure::Conflict, "Branch cannot be merged"))?;
+  let tree = FakeWorkTree {
+    parent_id: Some(CompactString::new(&target.commit.id)),
+    files,
+  };
+  let source_id = source.commit.id.clone();
+  let squash = req.squash.unwrap_or(merge_request.squash);
+  let (merge_commit_sha, squash_commit_sha) = if squash {
+    let message = match req.squash_commit_message.as_deref() {
+      Some(message) => message.to_string(),
+      None => merge_request.title.to_string(),
+    };
+    push_commit(
+      project,
+      &merge_request.target_branch,
+      tree,
+      commit_id.clone(),
+      &message,
+      None,
+      None,
+    );
+    (None, Some(commit_id))
+  } else {
+    let message = match req.merge_commit_message.as_deref() {
+      Some(message) => message.to_string(),
+      None => format!(
+        "Merge branch '{}' into '{}'\n\n{}\n\nSee merge request {}",
+        merge_request.source_branch, merge_request.target_branch, merge_request.title, merge_request.references.full
+      ),
+    };
+    push_commit(
+      project,
+      &merge_request.target_branch,
+      tree,
+      commit_id.clone(),
+      &message,
+      None,
+      None,
+    );
+    let merge_commit = project.commits.get_mut(&commit_id).expect("merge commit exists");
+    merge_commit.commit.parent_ids.push(source_id.clone());
+    (Some(commit_id), None)
+  };
+  let remove_source_branch = req
+    .should_remove_source_branch
+    .or(merge_request.force_remove_source_branch)
+    .unwrap_or(false);
+  if remove_source_branch {
+    project.branches.remove(&merge_request.source_branch);
+  }
+  let now = Utc::now();
+  let merged = MergeRequest {
+    state: MergeRequestState::Merged,
+    updated_at: now,
+    merged_at: Some(now),
+    merge_user: Some(fake_user(FAKE_USER_ID)),
+    sha: Some(CompactString::new(&source_id)),
+    merge_commit_sha,
+    squash_commit_sha,
+    squash,
+    should_remove_source_branch: req.should_remove_source_branch,
+    detailed_merge_status: CompactString::new("not_open"),
+    ..merge_request
+  };
+  project.merge_requests.insert(merged.iid, merged.clone());
+  Ok(merged)
+}
+
+fake_service!(RebaseMergeRequestCommand, MergeRequestRebase, rebase_merge_request);
+
+/// Rebase immediately, reporting conflicts through `merge_error`: the response never has a rebase in progress
+fn rebase_merge_request<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &RebaseMergeRequestCommand<Cx>,
+) -> Result<MergeRequestRebase, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = &state.projects[&id];
+  let merge_request = project
+    .merge_requests
+    .get(&req.merge_request_iid)
+    .ok_or_else(|| not_found(req, "Merge Request"))?;
+  let (Some(source), Some(target)) = (
+    project.branches.get(&merge_request.source_branch),
+    project.branches.get(&merge_request.target_branch),
+  ) else {
+    return Err(
+      error(
+        req,
+        StatusCode::FORBIDDEN,
+        "403 Forbidden - Cannot push to source branch",
+      )
+      .classify_rebase_failure(),
+    );
+  };
+  let (source, target) = (&project.commits[source], &project.commits[target]);
+  let base = merge_base(project, source, target).map(|base| base.commit.id.as_str());
+  let mut replayed = Vec::from_iter(
+    history(project, source)
+      .take_while(|commit| Some(commit.commit.id.as_str()) != base)
+      .map(|commit| {
+        (
+          parent_files(project, commit),
+          commit.files.clone(),
+          commit.commit.clone(),
+        )
+      }),
+  );
+  replayed.reverse();
+  let (source_branch, mut head) = (merge_request.source_branch.clone(), target.commit.id.clone());
+  let mut files = target.files.clone();
+  let mut commits = Vec::new();
+  for (before, after, commit) in replayed {
+    let commit_id = state.next_object_id();
+    if !merge_files(&mut files, &before, &after, &commit_id) {
+      let project = state.projects.get_mut(&id).expect("project exists");
+      let merge_request = project
+        .merge_requests
+        .get_mut(&req.merge_request_iid)
+        .expect("merge request exists");
+      merge_request.merge_error = Some(CompactString::new(
+        "Rebase failed: Rebase locally, resolve all conflicts, then push the branch.",
+      ));
+      return Ok(MergeRequestRebase {
+        rebase_in_progress: false,
+      });
+    }
+    commits.push((
+      FakeWorkTree {
+        parent_id: Some(CompactString::new(&head)),
+        files: files.clone(),
+      },
+      commit_id.clone(),
+      commit,
+    ));
+    head = commit_id.to_string();
+  }
+  let project = state.projects.get_mut(&id).expect("project exists");
+  for (tree, commit_id, commit) in commits {
+    push_commit(
+      project,
+      &source_branch,
+      tree,
+      commit_id,
+      &commit.message,
+      Some(&commit.author_name),
+      Some(&commit.author_email),
+    );
+  }
+  if let Some(merge_request) = project.merge_requests.get_mut(&req.merge_request_iid) {
+    merge_request.merge_error = None;
+  }
+  Ok(MergeRequestRebase {
+    rebase_in_progress: false,
+  })
+}
+
+/// Diff versions of a merge request, oldest first
+///
+/// GitLab records a new version each time one of the branches of an open merge request is pushed to: the fake
+/// records it lazily, when the versions are read.
+pub(super) fn diff_versions(
+  state: &mut FakeGitlabState,
+  id: ProjectId,
+  iid: MergeRequestIid,
+) -> &[MergeRequestDiffVersion] {
+  let project = &state.projects[&id];
+  let merge_request = &project.merge_requests[&iid];
+  let last = project
+    .merge_request_versions
+    .get(&iid)
+    .and_then(|versions| versions.last());
+  let heads = match (
+    merge_request.state,
+    project.branches.get(&merge_request.source_branch),
+    project.branches.get(&merge_request.target_branch),
+  ) {
+    (MergeRequestState::Opened, Some(head), Some(start)) => Some((head, start)),
+    _ => None,
+  };
+  let moved = heads.filter(|(head, start)| {
+    last.map_or(true, |last| {
+      last.head_commit_sha != **head || last.start_commit_sha != **start
+    })
+  });
+  if let Some((head, start)) = moved {
+    let (head, start) = (&project.commits[head], &project.commits[start]);
+    let base = merge_base(project, head, start);
+    let changed = changed_paths(&base.map(|b| b.files.clone()).unwrap_or_default(), &head.files).len();
+    let mut version = MergeRequestDiffVersion {
+      id: MergeRequestDiffVersionId::new(0),
+      head_commit_sha: CompactString::new(&head.commit.id),
+      base_commit_sha: CompactString::new(&base.unwrap_or(start).commit.id),
+      start_commit_sha: CompactString::new(&start.commit.id),
+      created_at: Utc::now(),
+      merge_request_id: merge_request.id,
+      state: CompactString::new(if changed == 0 { "empty" } else { "collected" }),
+      real_size: format_compact!("{changed}"),
+      patch_id_sha: None,
+      commits: None,
+      diffs: None,
+    };
+    // Allocate the id once the project is no longer borrowed
+    version.id = MergeRequestDiffVersionId::new(state.next_id());
+    let project = state.projects.get_mut(&id).expect("project exists");
+    project.merge_request_versions.entry(iid).or_default().push(version);
+  }
+  state.projects[&id]
+    .merge_request_versions
+    .get(&iid)
+    .map_or(&[], Vec::as_slice)
+}
+
+/// Commits of a diff version, newest first: the commits of its head missing from its start
+fn version_commits(project: &FakeProject, version: &MergeRequestDiffVersion) -> Vec<Commit> {
+  let head = &project.commits[version.head_commit_sha.as_str()];
+  let start = ancestors(project, &project.commits[version.start_commit_sha.as_str()]);
+  let mut commits = Vec::from_iter(
+    ancestors(project, head)
+      .into_iter()
+      .filter(|id| !start.contains(id))
+      .map(|id| project.commits[id].commit.clone()),
+  );
+  commits.sort_by_key(|commit| core::cmp::Reverse(commit.committed_date));
+  commits
+}
+
+/// Diffs of a diff version, from the merge base of its branches to its head
+fn version_diffs(project: &FakeProject, version: &MergeRequestDiffVersion, unidiff: bool) -> Vec<Diff> {
+  let head = &project.commits[version.head_commit_sha.as_str()];
+  let start = &project.commits[version.start_commit_sha.as_str()];
+  let base = merge_base(project, head, start)
+    .map(|base| base.files.clone())
+    .unwrap_or_default();
+  diffs(&base, &head.files, unidiff)
+}
+
+/// Resolve the project and the latest diff version of a merge request
+fn latest_diff_version<Q: Endpoint>(
+  state: &mut FakeGitlabState,
+  req: &Q,
+  project: &ProjectRef,
+  iid: MergeRequestIid,
+) -> Result<(ProjectId, Option<MergeRequestDiffVersion>), HttpGitlabClientError> {
+  let id = state.project_id(project).ok_or_else(|| not_found(req, "Project"))?;
+  if !state.projects[&id].merge_requests.contains_key(&iid) {
+    return Err(not_found(req, "Merge Request"));
+  }
+  Ok((id, diff_versions(state, id, iid).last().cloned()))
+}
+
+fake_service!(
+  GetMergeRequestCommitListQuery,
+  Page<Commit>,
+  get_merge_request_commit_list
+);
+
+fn get_merge_request_commit_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetMergeRequestCommitListQuery<Cx>,
+) -> Result<Page<Commit>, HttpGitlabClientError> {
+  let (id, version) = latest_diff_version(state, req, &req.project, req.merge_request_iid)?;
+  let project = &state.projects[&id];
+  Ok(single_page(
+    version.map_or_else(Vec::new, |version| version_commits(project, &version)),
+  ))
+}
+
+fake_service!(GetMergeRequestDiffListQuery, Page<Diff>, get_merge_request_diff_list);
+
+fn get_merge_request_diff_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetMergeRequestDiffListQuery<Cx>,
+) -> Result<Page<Diff>, HttpGitlabClientError> {
+  let (id, version) = latest_diff_version(state, req, &req.project, req.merge_request_iid)?;
+  let project = &state.projects[&id];
+  Ok(single_page(version.map_or_else(Vec::new, |version| {
+    version_diffs(project, &version, req.unidiff == Some(true))
+  })))
+}
+
+fake_service!(
+  GetMergeRequestChangesQuery,
+  MergeRequestChanges,
+  get_merge_request_changes
+);
+
+fn get_merge_request_changes<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetMergeRequestChangesQuery<Cx>,
+) -> Result<MergeRequestChanges, HttpGitlabClientError> {
+  let (id, version) = latest_diff_version(state, req, &req.project, req.merge_request_iid)?;
+  let project = &state.projects[&id];
+  Ok(MergeRequestChanges {
+    merge_request: fake_merge_request(project, &project.merge_requests[&req.merge_request_iid]),
+    changes: version.map_or_else(Vec::new, |version| {
+      version_diffs(project, &version, req.unidiff == Some(true))
+    }),
+    overflow: false,
+  })
+}
+
+fake_service!(
+  GetMergeRequestDiffVersionListQuery,
+  Page<MergeRequestDiffVersion>,
+  get_merge_request_diff_version_list
+);
+
+fn get_merge_request_diff_version_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetMergeRequestDiffVersionListQuery<Cx>,
+) -> Result<Page<MergeRequestDiffVersion>, HttpGitlabClientError> {
+  let (id, _) = latest_diff_version(state, req, &req.project, req.merge_request_iid)?;
+  let versions = diff_versions(state, id, req.merge_request_iid);
+  Ok(single_page(versions.iter().rev().cloned().collect()))
+}
+
+fake_service!(
+  GetMergeRequestDiffVersionListPageQuery,
+  Page<MergeRequestDiffVersion>,
+  get_merge_request_diff_version_list_page
+);
+
+fn get_merge_request_diff_version_list_page<Cx: GetRef<GitlabUrl>>(
+  _state: &mut FakeGitlabState,
+  req: &GetMergeRequestDiffVersionListPageQuery<Cx>,
+) -> Result<Page<MergeRequestDiffVersion>, HttpGitlabClientError> {
+  Err(not_found(req, "Page"))
+}
+
+fake_service!(
+  GetMergeRequestDiffVersionQuery,
+  MergeRequestDiffVersion,
+  get_merge_request_diff_version
+);
+
+fn get_merge_request_diff_version<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetMergeRequestDiffVersionQuery<Cx>,
+) -> Result<MergeRequestDiffVersion, HttpGitlabClientError> {
+  let (id, _) = latest_diff_version(state, req, &req.project, req.merge_request_iid)?;
+  let mut version = diff_versions(state, id, req.merge_request_iid)
+    .iter()
+    .find(|version| version.id == req.version_id)
+    .cloned()
+    .ok_or_else(|| not_found(req, "Merge Request Diff"))?;
+  let project = &state.projects[&id];
+  version.commits = Some(version_commits(project, &version));
+  version.diffs = Some(version_diffs(project, &version, req.unidiff == Some(true)));
+  Ok(version)
+}
@@ -0,0 +1,349 @@
+//! Project and group milestones of the fake GitLab
+
+use super::{error, not_found, single_page, FakeGitlabState};
+use crate::client::http::HttpGitlabClientError;
+use crate::command::create_group_milestone::CreateGroupMilestoneCommand;
+use crate::command::create_project_milestone::CreateProjectMilestoneCommand;
+use crate::command::delete_group_milestone::DeleteGroupMilestoneCommand;
+use crate::command::delete_project_milestone::DeleteProjectMilestoneCommand;
+use crate::command::update_group_milestone::UpdateGroupMilestoneCommand;
+use crate::command::update_project_milestone::UpdateProjectMilestoneCommand;
+use crate::common::milestone::{Milestone, MilestoneId, MilestoneIid, MilestoneState, MilestoneStateEvent};
+use crate::common::Page;
+use crate::context::{GetRef, GitlabUrl};
+use crate::endpoint::Endpoint;
+use crate::query::get_group_milestone::GetGroupMilestoneQuery;
+use crate::query::get_group_milestone_list::GetGroupMilestoneListQuery;
+use crate::query::get_milestone_list_page::GetMilestoneListPageQuery;
+use crate::query::get_project_milestone::GetProjectMilestoneQuery;
+use crate::query::get_project_milestone_list::GetProjectMilestoneListQuery;
+use chrono::{DateTime, NaiveDate, Utc};
+use compact_str::{format_compact, CompactString};
+use http::StatusCode;
+use std::collections::BTreeMap;
+
+/// Filters shared by the project and group milestone lists
+struct MilestoneFilter<'a> {
+  iids: &'a [MilestoneIid],
+  state: Option<MilestoneState>,
+  title: Option<&'a str>,
+  search: Option<&'a str>,
+  updated_before: Option<DateTime<Utc>>,
+  updated_after: Option<DateTime<Utc>>,
+}
+
+impl MilestoneFilter<'_> {
+  fn matches(&self, milestone: &Milestone) -> bool {
+    (self.iids.is_empty() || self.iids.contains(&milestone.iid))
+      && self.state.map_or(true, |state| milestone.state == state)
+      && self.title.map_or(true, |title| milestone.title == title)
+      && self.search.map_or(true, |search| {
+        milestone.title.contains(search)
+          || milestone
+            .description
+            .as_ref()
+            .is_some_and(|description| description.contains(search))
+      })
+      && self.updated_before.map_or(true, |date| milestone.updated_at < date)
+      && self.updated_after.map_or(true, |date| milestone.updated_at > date)
+  }
+
+  fn apply(&self, milestones: &BTreeMap<MilestoneId, Milestone>) -> Page<Milestone> {
+    single_page(milestones.values().filter(|m| self.matches(m)).cloned().collect())
+  }
+}
+
+/// Milestone fields provided when creating or updating a milestone
+struct MilestoneChanges<'a> {
+  title: Option<&'a str>,
+  description: Option<&'a str>,
+  due_date: Option<NaiveDate>,
+  start_date: Option<NaiveDate>,
+  state_event: Option<MilestoneStateEvent>,
+}
+
+impl MilestoneChanges<'_> {
+  fn apply(&self, milestone: &mut Milestone) {
+    if let Some(title) = self.title {
+      milestone.title = CompactString::new(title);
+    }
+    if let Some(description) = self.description {
+      milestone.description = Some(CompactString::new(description));
+    }
+    if let Some(due_date) = self.due_date {
+      milestone.due_date = Some(due_date);
+      milestone.expired = Some(due_date < Utc::now().date_naive());
+    }
+    if let Some(start_date) = self.start_date {
+      milestone.start_date = Some(start_date);
+    }
+    match self.state_event {
+      Some(MilestoneStateEvent::Activate) => milestone.state = MilestoneState::Active,
+      Some(MilestoneStateEvent::Close) => milestone.state = MilestoneState::Closed,
+      None => {}
+    }
+    milestone.updated_at = Utc::now();
+  }
+}
+
+/// Insert a new milestone in `milestones`, failing if the title is already taken
+fn insert_milestone<Q: Endpoint>(
+  req: &Q,
+  milestones: &mut BTreeMap<MilestoneId, Milestone>,
+  mut milestone: Milestone,
+  changes: MilestoneChanges<'_>,
+) -> Result<Milestone, HttpGitlabClientError> {
+  changes.apply(&mut milestone);
+  if milestones.values().any(|m| m.title == milestone.title) {
+    return Err(error(req, StatusCode::BAD_REQUEST, "Title has already been taken"));
+  }
+  let iid = milestones.values().map(|m| m.iid.into_u64()).max().unwrap_or(0) + 1;
+  milestone.iid = MilestoneIid::new(iid);
+  milestone.web_url = format_compact!("{}/-/milestones/{iid}", milestone.web_url);
+  milestones.insert(milestone.id, milestone.clone());
+  Ok(milestone)
+}
+
+/// Update a milestone of `milestones`, failing if it does not exist or if the new title is already taken
+fn update_milestone<Q: Endpoint>(
+  req: &Q,
+  milestones: &mut BTreeMap<MilestoneId, Milestone>,
+  id: MilestoneId,
+  changes: MilestoneChanges<'_>,
+) -> Result<Milestone, HttpGitlabClientError> {
+  if let Some(title) = changes.title {
+    if milestones.values().any(|m| m.id != id && m.title == title) {
+      return Err(error(req, StatusCode::BAD_REQUEST, "Title has already been taken"));
+    }
+  }
+  let milestone = milestones.get_mut(&id).ok_or_else(|| not_found(req, "Milestone"))?;
+  changes.apply(milestone);
+  Ok(milestone.clone())
+}
+
+fn new_milestone(id: MilestoneId, title: &str, web_url: CompactString) -> Milestone {
+  let now = Utc::now();
+  Milestone {
+    id,
+    iid: MilestoneIid::new(0),
+    project_id: None,
+    group_id: None,
+    title: CompactString::new(title),
+    description: None,
+    state: MilestoneState::Active,
+    created_at: now,
+    updated_at: now,
+    due_date: None,
+    start_date: None,
+    expired: Some(false),
+    web_url,
+    issue_stats: None,
+  }
+}
+
+fake_service!(
+  GetProjectMilestoneListQuery,
+  Page<Milestone>,
+  get_project_milestone_list
+);
+
+fn get_project_milestone_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetProjectMilestoneListQuery<Cx>,
+) -> Result<Page<Milestone>, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let filter = MilestoneFilter {
+    iids: &req.iids,
+    state: req.state,
+    title: req.title.as_deref(),
+    search: req.search.as_deref(),
+    updated_before: req.updated_before,
+    updated_after: req.updated_after,
+  };
+  Ok(filter.apply(&state.projects[&id].milestones))
+}
+
+fake_service!(GetProjectMilestoneQuery, Milestone, get_project_milestone);
+
+fn get_project_milestone<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetProjectMilestoneQuery<Cx>,
+) -> Result<Milestone, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  state.projects[&id]
+    .milestones
+    .get(&req.milestone_id)
+    .cloned()
+    .ok_or_else(|| not_found(req, "Milestone"))
+}
+
+fake_service!(CreateProjectMilestoneCommand, Milestone, create_project_milestone);
+
+fn create_project_milestone<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &CreateProjectMilestoneCommand<Cx>,
+) -> Result<Milestone, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let milestone_id = MilestoneId::new(state.next_id());
+  let project = state.projects.get_mut(&id).expect("project exists");
+  let web_url = CompactString::new(project.project.web_url.as_str().trim_end_matches('/'));
+  let milestone = Milestone {
+    project_id: Some(id),
+    ..new_milestone(milestone_id, &req.title, web_url)
+  };
+  let changes = MilestoneChanges {
+    title: None,
+    description: req.description.as_deref(),
+    due_date: req.due_date,
+    start_date: req.start_date,
+    state_event: None,
+  };
+  insert_milestone(req, &mut project.milestones, milestone, changes)
+}
+
+fake_service!(UpdateProjectMilestoneCommand, Milestone, update_project_milestone);
+
+fn update_project_milestone<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &UpdateProjectMilestoneCommand<Cx>,
+) -> Result<Milestone, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = state.projects.get_mut(&id).expect("project exists");
+  let changes = MilestoneChanges {
+    title: req.title.as_deref(),
+    description: req.description.as_deref(),
+    due_date: req.due_date,
+    start_date: req.start_date,
+    state_event: req.state_event,
+  };
+  update_milestone(req, &mut project.milestones, req.milestone_id, changes)
+}
+
+fake_service!(DeleteProjectMilestoneCommand, (), delete_project_milestone);
+
+fn delete_project_milestone<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &DeleteProjectMilestoneCommand<Cx>,
+) -> Result<(), HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = state.projects.get_mut(&id).expect("project exists");
+  project
+    .milestones
+    .remove(&req.milestone_id)
+    .map(drop)
+    .ok_or_else(|| not_found(req, "Milestone"))
+}
+
+fake_service!(GetGroupMilestoneListQuery, Page<Milestone>, get_group_milestone_list);
+
+fn get_group_milestone_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetGroupMilestoneListQuery<Cx>,
+) -> Result<Page<Milestone>, HttpGitlabClientError> {
+  let group = state.groups.get(&req.group).ok_or_else(|| not_found(req, "Group"))?;
+  let filter = MilestoneFilter {
+    iids: &req.iids,
+    state: req.state,
+    title: req.title.as_deref(),
+    search: req.search.as_deref(),
+    updated_before: req.updated_before,
+    updated_after: req.updated_after,
+  };
+  Ok(filter.apply(&group.milestones))
+}
+
+fake_service!(GetGroupMilestoneQuery, Milestone, get_group_milestone);
+
+fn get_group_milestone<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetGroupMilestoneQuery<Cx>,
+) -> Result<Milestone, HttpGitlabClientError> {
+  let group = state.groups.get(&req.group).ok_or_else(|| not_found(req, "Group"))?;
+  group
+    .milestones
+    .get(&req.milestone_id)
+    .cloned()
+    .ok_or_else(|| not_found(req, "Milestone"))
+}
+
+fake_service!(CreateGroupMilestoneCommand, Milestone, create_group_milestone);
+
+fn create_group_milestone<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &CreateGroupMilestoneCommand<Cx>,
+) -> Result<Milestone, HttpGitlabClientError> {
+  if !state.groups.contains_key(&req.group) {
+    return Err(not_found(req, "Group"));
+  }
+  let milestone_id = MilestoneId::new(state.next_id());
+  let group = state.groups.get_mut(&req.group).expect("group exists");
+  let web_url = format_compact!("https://gitlab.example.com/groups/{}", group.full_path);
+  let milestone = Milestone {
+    group_id: Some(req.group),
+    ..new_milestone(milestone_id, &req.title, web_url)
+  };
+  let changes = MilestoneChanges {
+    title: None,
+    description: req.description.as_deref(),
+    due_date: req.due_date,
+    start_date: req.start_date,
+    state_event: None,
+  };
+  insert_milestone(req, &mut group.milestones, milestone, changes)
+}
+
+fake_service!(UpdateGroupMilestoneCommand, Milestone, update_group_milestone);
+
+fn update_group_milestone<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &UpdateGroupMilestoneCommand<Cx>,
+) -> Result<Milestone, HttpGitlabClientError> {
+  let group = state
+    .groups
+    .get_mut(&req.group)
+    .ok_or_else(|| not_found(req, "Group"))?;
+  let changes = MilestoneChanges {
+    title: req.title.as_deref(),
+    description: req.description.as_deref(),
+    due_date: req.due_date,
+    start_date: req.start_date,
+    state_event: req.state_event,
+  };
+  update_milestone(req, &mut group.milestones, req.milestone_id, changes)
+}
+
+fake_service!(DeleteGroupMilestoneCommand, (), delete_group_milestone);
+
+fn delete_group_milestone<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &DeleteGroupMilestoneCommand<Cx>,
+) -> Result<(), HttpGitlabClientError> {
+  let group = state
+    .groups
+    .get_mut(&req.group)
+    .ok_or_else(|| not_found(req, "Group"))?;
+  group
+    .milestones
+    .remove(&req.milestone_id)
+    .map(drop)
+    .ok_or_else(|| not_found(req, "Milestone"))
+}
+
+fake_service!(GetMilestoneListPageQuery, Page<Milestone>, get_milestone_list_page);
+
+fn get_milestone_list_page<Cx: GetRef<GitlabUrl>>(
+  _state: &mut FakeGitlabState,
+  req: &GetMilestoneListPageQuery<Cx>,
+) -> Result<Page<Milestone>, HttpGitlabClientError> {
+  Err(not_found(req, "Page"))
+}
@@ -0,0 +1,407 @@
+//! In-memory fake GitLab, to test code using the GitLab client without any network access.
+
+use crate::body::to_hex;
+use crate::client::http::{HttpGitlabClientError, RateLimit, ResponseError};
+use crate::common::approval::{ApprovalRuleId, MergeRequestApprovalRule, ProjectApprovalRule};
+use crate::common::commit::Commit;
+use crate::common::error::{GitlabErrorBody, GitlabErrorMessage};
+use crate::common::group::GroupId;
+use crate::common::merge_request::{MergeRequest, MergeRequestDiffVersion, MergeRequestIid};
+use crate::common::milestone::{Milestone, MilestoneId};
+use crate::common::note::Discussion;
+use crate::common::package::GenericPackageFile;
+use crate::common::project::{Project, ProjectId, ProjectRef};
+use crate::common::protected_branch::ProtectedBranch;
+use crate::common::protected_tag::ProtectedTag;
+use crate::common::release::Release;
+use crate::common::tree::TreeRecord;
+use crate::common::user::{User, UserId};
+use crate::common::Page;
+use crate::endpoint::{endpoint_url, Endpoint};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use compact_str::{format_compact, CompactString};
+use http::StatusCode;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// In-memory fake GitLab server
+///
+/// The fake implements the query and command `Service` traits directly, with the same error type as
+/// [`HttpGitlabClient`](crate::client::http::HttpGitlabClient). It stores groups, projects, milestones, releases,
+/// release links, generic package files, repository trees and
+/// repository branches, tags, protected branches and tags, commits and merge requests, and the discussions of merge
+/// requests and commits. Auth is not checked.
+///
+/// Clones share the same state.
+#[derive(Debug, Clone, Default)]
+pub struct FakeGitlabClient {
+  state: Arc<Mutex<FakeGitlabState>>,
+}
+
+#[derive(Debug, Default)]
+struct FakeGitlabState {
+  last_id: u64,
+  projects: BTreeMap<ProjectId, FakeProject>,
+  groups: BTreeMap<GroupId, FakeGroup>,
+}
+
+impl FakeGitlabState {
+  fn next_id(&mut self) -> u64 {
+    self.last_id += 1;
+    self.last_id
+  }
+
+  /// Generate a Git object id (commit or blob), with distinct short ids
+  fn next_object_id(&mut self) -> CompactString {
+    let id = self.next_id();
+    CompactString::new(&to_hex(&Sha256::digest(id.to_be_bytes()))[..40])
+  }
+
+  fn insert_project(&mut self, project: Project) {
+    self.last_id = self.last_id.max(project.id.into_u64());
+    self.projects.insert(
+      project.id,
+      FakeProject {
+        project,
+        releases: BTreeMap::new(),
+        packages: BTreeMap::new(),
+        milestones: BTreeMap::new(),
+        tree: Vec::new(),
+        branches: BTreeMap::new(),
+        commits: BTreeMap::new(),
+        protected_branches: BTreeMap::new(),
+        tags: BTreeMap::new(),
+        protected_tags: BTreeMap::new(),
+        merge_requests: BTreeMap::new(),
+        merge_request_versions: BTreeMap::new(),
+        approval_rules: BTreeMap::new(),
+        merge_request_approvals: BTreeMap::new(),
+        discussions: BTreeMap::new(),
+      },
+    );
+  }
+
+  fn project_id<Str: AsRef<str>>(&self, project: &ProjectRef<Str>) -> Option<ProjectId> {
+    match project {
+      ProjectRef::Id(id) => self.projects.contains_key(id).then_some(*id),
+      ProjectRef::Slug(slug) => self
+        .projects
+        .values()
+        .find(|p| p.project.path_with_namespace == slug.as_view().as_str())
+        .map(|p| p.project.id),
+    }
+  }
+}
+
+#[derive(Debug)]
+struct FakeProject {
+  project: Project,
+  /// Releases, by tag name
+  releases: BTreeMap<CompactString, Release>,
+  /// Generic package files, by package name, version and file name
+  packages: BTreeMap<(CompactString, CompactString, CompactString), FakePackageFile>,
+  milestones: BTreeMap<MilestoneId, Milestone>,
+  tree: Vec<TreeRecord>,
+  /// Head commit id of the repository branches, by branch name
+  branches: BTreeMap<CompactString, CompactString>,
+  /// Repository commits, by id
+  commits: BTreeMap<CompactString, FakeCommit>,
+  /// Protected branches, by name or wildcard
+  protected_branches: BTreeMap<CompactString, ProtectedBranch>,
+  /// Repository tags, by name
+  tags: BTreeMap<CompactString, FakeTag>,
+  /// Protected tags, by name or wildcard
+  protected_tags: BTreeMap<CompactString, ProtectedTag>,
+  merge_requests: BTreeMap<MergeRequestIid, MergeRequest>,
+  /// Diff versions of the merge requests, oldest first
+  merge_request_versions: BTreeMap<MergeRequestIid, Vec<MergeRequestDiffVersion>>,
+  approval_rules: BTreeMap<ApprovalRuleId, ProjectApprovalRule>,
+  merge_request_approvals: BTreeMap<MergeRequestIid, FakeApprovals>,
+  discussions: BTreeMap<FakeNoteable, Vec<Discussion>>,
+}
+
+/// Resource of a project holding discussions
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum FakeNoteable {
+  MergeRequest(MergeRequestIid),
+  /// Commit, by id
+  Commit(CompactString),
+}
+
+/// Approval rules and approvals of a merge request
+#[derive(Debug, Default)]
+struct FakeApprovals {
+  /// Copied from the project rules applying to the merge request when it is created
+  rules: BTreeMap<ApprovalRuleId, MergeRequestApprovalRule>,
+  /// The rules were edited since the merge request was created
+  overwritten: bool,
+  /// Users who approved the merge request, in approval order
+  approved_by: Vec<UserId>,
+}
+
+#[derive(Debug)]
+struct FakeGroup {
+  full_path: CompactString,
+  milestones: BTreeMap<MilestoneId, Milestone>,
+}
+
+#[derive(Debug)]
+struct FakeCommit {
+  commit: Commit,
+  /// Snapshot of the repository files at this commit, by path
+  files: BTreeMap<CompactString, FakeRepositoryFile>,
+}
+
+/// Files a new commit is built from
+#[derive(Debug, Default)]
+struct FakeWorkTree {
+  parent_id: Option<CompactString>,
+  files: BTreeMap<CompactString, FakeRepositoryFile>,
+}
+
+#[derive(Debug)]
+struct FakeTag {
+  /// Id of the tagged commit
+  commit_id: CompactString,
+  /// Id of the tag object for an annotated tag, or of the tagged commit for a lightweight tag
+  target: CompactString,
+  message: Option<String>,
+  created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+struct FakeRepositoryFile {
+  content: Bytes,
+  blob_id: CompactString,
+  last_commit_id: CompactString,
+  execute_filemode: bool,
+}
+
+#[derive(Debug)]
+struct FakePackageFile {
+  file: GenericPackageFile,
+  data: Bytes,
+}
+
+impl FakeGitlabClient {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  fn state(&self) -> MutexGuard<'_, FakeGitlabState> {
+    self.state.lock().expect("fake GitLab state is not poisoned")
+  }
+
+  /// Create a project with the provided path (e.g. `group/project`), returning its id.
+  pub fn create_project(&self, path_with_namespace: &str) -> ProjectId {
+    let mut state = self.state();
+    let id = ProjectId::new(state.next_id());
+    let (namespace, path) = path_with_namespace
+      .rsplit_once('/')
+      .unwrap_or(("", path_with_namespace));
+    let now = Utc::now();
+    let project: Project = serde_json::from_value(serde_json::json!({
+      "id": id.into_u64(),
+      "description": null,
+      "name": path,
+      "name_with_namespace": path_with_namespace.replace('/', " / "),
+      "path": path,
+      "path_with_namespace": path_with_namespace,
+      "created_at": now,
+      "default_branch": "main",
+      "tag_list": [],
+      "topics": [],
+      "ssh_url_to_repo": format!("git@gitlab.example.com:{path_with_namespace}.git"),
+      "http_url_to_repo": format!("https://gitlab.example.com/{path_with_namespace}.git"),
+      "web_url": format!("https://gitlab.example.com/{path_with_namespace}"),
+      "readme_url": null,
+      "forks_count": 0,
+      "avatar_url": null,
+      "star_count": 0,
+      "last_activity_at": now,
+      "namespace": {
+        "id": 1,
+        "name": namespace,
+        "path": namespace,
+        "kind": "group",
+        "full_path": namespace,
+        "parent_id": null,
+        "web_url": format!("https://gitlab.example.com/groups/{namespace}"),
+        "avatar_url": null,
+      },
+    }))
+    .expect("fake project is valid");
+    state.insert_project(project);
+    id
+  }
+
+  /// Create a group with the provided full path (e.g. `parent/group`), returning its id.
+  pub fn create_group(&self, full_path: &str) -> GroupId {
+    let mut state = self.state();
+    let id = GroupId::new(state.next_id());
+    state.groups.insert(
+      id,
+      FakeGroup {
+        full_path: CompactString::new(full_path),
+        milestones: BTreeMap::new(),
+      },
+    );
+    id
+  }
+
+  /// Insert or replace a project
+  pub fn insert_project(&self, project: Project) {
+    self.state().insert_project(project);
+  }
+
+  /// Add a file or directory to the repository tree of a project
+  ///
+  /// Panics if the project does not exist.
+  pub fn insert_tree_record(&self, project: ProjectId, record: TreeRecord) {
+    let mut state = self.state();
+    let project = state.projects.get_mut(&project).expect("project exists");
+    project.tree.retain(|r| r.path != record.path);
+    project.tree.push(record);
+  }
+
+  /// Releases of a project, by tag name
+  pub fn releases(&self, project: ProjectId) -> Vec<Release> {
+    let state = self.state();
+    state
+      .projects
+      .get(&project)
+      .map(|p| p.releases.values().cloned().collect())
+      .unwrap_or_default()
+  }
+
+  /// Content of a generic package file
+  pub fn package_file(&self, project: ProjectId, package_name: &str, version: &str, filename: &str) -> Option<Bytes> {
+    let state = self.state();
+    let project = state.projects.get(&project)?;
+    project
+      .packages
+      .get(&(
+        CompactString::new(package_name),
+        CompactString::new(version),
+        CompactString::new(filename),
+      ))
+      .map(|f| f.data.clone())
+  }
+
+  /// Content of a repository file, at the head of a branch
+  pub fn repository_file(&self, project: ProjectId, branch: &str, file_path: &str) -> Option<Bytes> {
+    let state = self.state();
+    let project = state.projects.get(&project)?;
+    let head = project.branches.get(branch)?;
+    let file = project.commits[head].files.get(file_path)?;
+    Some(file.content.clone())
+  }
+}
+
+/// Build the error GitLab would return for `query`.
+fn error<Q: Endpoint>(query: &Q, status: StatusCode, message: &str) -> HttpGitlabClientError {
+  error_with_code(query, status, message, None)
+}
+
+/// Build the error GitLab would return for `query`, with a machine-readable `error_code`.
+fn error_with_code<Q: Endpoint>(
+  query: &Q,
+  status: StatusCode,
+  message: &str,
+  code: Option<&str>,
+) -> HttpGitlabClientError {
+  let error = GitlabErrorBody {
+    message: Some(GitlabErrorMessage::Text(CompactString::new(message))),
+    error: None,
+    error_description: None,
+    error_code: code.map(CompactString::new),
+  };
+  let body = serde_json::to_vec(&error).expect("error body serialization never fails");
+  let res = Box::new(ResponseError {
+    method: Q::METHOD,
+    uri: endpoint_url(query)
+      .ok()
+      .and_then(|url| url.as_str().parse().ok())
+      .unwrap_or_default(),
+    status,
+    request_id: None,
+    error: Some(error),
+    body: Bytes::from(body),
+  });
+  HttpGitlabClientError::from_response_error(
+    res,
+    RateLimit {
+      retry_after: None,
+      remaining: None,
+      reset: None,
+    },
+  )
+}
+
+fn not_found<Q: Endpoint>(query: &Q, what: &str) -> HttpGitlabClientError {
+  error(query, StatusCode::NOT_FOUND, &format!("404 {what} Not Found"))
+}
+
+fn single_page<T>(items: Vec<T>) -> Page<T> {
+  Page {
+    first: None,
+    next: None,
+    last: None,
+    items,
+  }
+}
+
+/// Implement `Service` for a query or command, answering it with `$handler`
+macro_rules! fake_service {
+  ($query:ident, $response:ty, $handler:ident) => {
+    impl<'req, Cx> ::tower_service::Service<&'req $query<Cx>> for $crate::client::fake::FakeGitlabClient
+    where
+      Cx: $crate::context::GetRef<$crate::context::GitlabUrl>,
+    {
+      type Response = $response;
+      type Error = $crate::client::http::HttpGitlabClientError;
+      type Future = ::futures::future::Ready<Result<Self::Response, Self::Error>>;
+
+      fn poll_ready(&mut self, _cx: &mut ::core::task::Context<'_>) -> ::core::task::Poll<Result<(), Self::Error>> {
+        ::core::task::Poll::Ready(Ok(()))
+      }
+
+      fn call(&mut self, req: &'req $query<Cx>) -> Self::Future {
+        ::futures::future::ready($handler(&mut self.state(), req))
+      }
+    }
+  };
+}
+
+mod approvals;
+mod branches;
+mod commits;
+mod merge_requests;
+mod milestones;
+mod notes;
+mod packages;
+mod projects;
+mod releases;
+mod repository;
+mod tags;
+
+/// Id of the current user of the fake, author of the merge requests
+pub const FAKE_USER_ID: UserId = UserId::new(1);
+
+/// Build a user, the fake does not store users
+fn fake_user(id: UserId) -> User {
+  let username = format_compact!("user{}", id.into_u64());
+  User {
+    id,
+    name: format_compact!("User {}", id.into_u64()),
+    state: CompactString::new("active"),
+    locked: false,
+    avatar_url: None,
+    web_url: format!("https://gitlab.example.com/{username}")
+      .parse()
+      .expect("fake user url is valid"),
+    username,
+  }
+}
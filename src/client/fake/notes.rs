@@ -0,0 +1,344 @@
+//! Notes and discussions of the fake GitLab
+
+use super::commits::repository_ref;
+use super::merge_requests::diff_versions;
+use super::{error, fake_user, not_found, single_page, FakeGitlabState, FakeNoteable, FAKE_USER_ID};
+use crate::client::http::HttpGitlabClientError;
+use crate::command::create_discussion::CreateDiscussionCommand;
+use crate::command::create_note::CreateNoteCommand;
+use crate::command::delete_note::DeleteNoteCommand;
+use crate::command::resolve_discussion::ResolveDiscussionCommand;
+use crate::command::update_note::UpdateNoteCommand;
+use crate::common::note::{Discussion, Note, NoteId, NoteLocation, NoteOrder, NoteType, NoteableRef, NoteableType};
+use crate::common::project::ProjectId;
+use crate::common::{Page, SortOrder};
+use crate::context::{GetRef, GitlabUrl};
+use crate::endpoint::Endpoint;
+use crate::query::get_discussion::GetDiscussionQuery;
+use crate::query::get_discussion_list::GetDiscussionListQuery;
+use crate::query::get_discussion_list_page::GetDiscussionListPageQuery;
+use crate::query::get_note::GetNoteQuery;
+use crate::query::get_note_list::GetNoteListQuery;
+use crate::query::get_note_list_page::GetNoteListPageQuery;
+use chrono::Utc;
+use compact_str::CompactString;
+use http::StatusCode;
+
+/// Locate the discussions of a noteable, the fake only stores the discussions of merge requests and commits
+fn discussions<'s, Q: Endpoint>(
+  state: &'s mut FakeGitlabState,
+  req: &Q,
+  noteable: &NoteableRef,
+) -> Result<(ProjectId, FakeNoteable, &'s mut Vec<Discussion>), HttpGitlabClientError> {
+  let (project, key) = match noteable {
+    NoteableRef::MergeRequest {
+      project,
+      merge_request_iid,
+    } => (project, FakeNoteable::MergeRequest(*merge_request_iid)),
+    NoteableRef::Commit { project, sha } => (project, FakeNoteable::Commit(sha.clone())),
+    NoteableRef::Issue { .. } => return Err(not_found(req, "Issue")),
+    NoteableRef::Snippet { .. } => return Err(not_found(req, "Snippet")),
+    NoteableRef::Epic { .. } => return Err(not_found(req, "Epic")),
+  };
+  let id = state.project_id(project).ok_or_else(|| not_found(req, "Project"))?;
+  let project = state.projects.get_mut(&id).expect("project exists");
+  let key = match key {
+    FakeNoteable::MergeRequest(iid) if project.merge_requests.contains_key(&iid) => FakeNoteable::MergeRequest(iid),
+    FakeNoteable::MergeRequest(_) => return Err(not_found(req, "Merge Request")),
+    FakeNoteable::Commit(sha) => {
+      let commit = repository_ref(project, sha.as_str()).ok_or_else(|| not_found(req, "Commit"))?;
+      FakeNoteable::Commit(CompactString::new(&commit.commit.id))
+    }
+  };
+  Ok((id, key.clone(), project.discussions.entry(key).or_default()))
+}
+
+/// Split a note location into its noteable and optional discussion thread
+fn note_location(location: &NoteLocation) -> (NoteableRef, Option<&str>) {
+  match location {
+    NoteLocation::Parent(parent) => (parent.clone().into(), None),
+    NoteLocation::Discussion {
+      noteable,
+      discussion_id,
+    } => (noteable.clone(), Some(discussion_id.as_str())),
+  }
+}
+
+/// Build a note of the current user
+fn fake_note(state: &mut FakeGitlabState, project_id: ProjectId, noteable: &FakeNoteable, body: &str) -> Note {
+  let id = NoteId::new(state.next_id());
+  let project = &state.projects[&project_id];
+  let (noteable_type, noteable_id, noteable_iid, commit_id) = match noteable {
+    FakeNoteable::MergeRequest(iid) => (
+      NoteableType::MergeRequest,
+      Some(project.merge_requests[iid].id.into_u64()),
+      Some(iid.into_u64()),
+      None,
+    ),
+    FakeNoteable::Commit(sha) => (NoteableType::Commit, None, None, Some(sha.clone())),
+  };
+  let now = Utc::now();
+  Note {
+    id,
+    note_type: None,
+    body: body.to_string(),
+    author: fake_user(FAKE_USER_ID),
+    created_at: now,
+    updated_at: now,
+    system: false,
+    noteable_id,
+    noteable_type,
+    noteable_iid,
+    project_id: Some(project_id),
+    commit_id,
+    position: None,
+    resolvable: false,
+    resolved: None,
+    resolved_by: None,
+    resolved_at: None,
+    internal: false,
+  }
+}
+
+/// Mark a note of a merge request thread as resolvable
+fn make_resolvable(note: &mut Note) {
+  if note.noteable_type == NoteableType::MergeRequest {
+    note.resolvable = true;
+    note.resolved = Some(false);
+  }
+}
+
+/// Find a note, in the thread `discussion_id` if provided
+fn find_note<'d, Q: Endpoint>(
+  req: &Q,
+  discussions: &'d mut [Discussion],
+  discussion_id: Option<&str>,
+  note_id: NoteId,
+) -> Result<(usize, usize, &'d mut Note), HttpGitlabClientError> {
+  if let Some(id) = discussion_id {
+    find_discussion(req, discussions, id)?;
+  }
+  let (d, n) = discussions
+    .iter()
+    .enumerate()
+    .filter(|(_, discussion)| !discussion_id.is_some_and(|id| discussion.id != id))
+    .find_map(|(d, discussion)| Some((d, discussion.notes.iter().position(|note| note.id == note_id)?)))
+    .ok_or_else(|| not_found(req, "Note"))?;
+  Ok((d, n, &mut discussions[d].notes[n]))
+}
+
+fn find_discussion<'d, Q: Endpoint>(
+  req: &Q,
+  discussions: &'d mut [Discussion],
+  discussion_id: &str,
+) -> Result<&'d mut Discussion, HttpGitlabClientError> {
+  discussions
+    .iter_mut()
+    .find(|d| d.id == discussion_id)
+    .ok_or_else(|| not_found(req, "Discussion"))
+}
+
+fake_service!(GetNoteListQuery, Page<Note>, get_note_list);
+
+fn get_note_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetNoteListQuery<Cx>,
+) -> Result<Page<Note>, HttpGitlabClientError> {
+  let (_, _, discussions) = discussions(state, req, &req.noteable.clone().into())?;
+  let mut notes = Vec::from_iter(discussions.iter().flat_map(|d| d.notes.iter().cloned()));
+  match req.order_by.unwrap_or(NoteOrder::CreatedAt) {
+    NoteOrder::CreatedAt => notes.sort_by_key(|note| (note.created_at, note.id)),
+    NoteOrder::UpdatedAt => notes.sort_by_key(|note| (note.updated_at, note.id)),
+  }
+  if req.sort.unwrap_or(SortOrder::Desc) == SortOrder::Desc {
+    notes.reverse();
+  }
+  Ok(single_page(notes))
+}
+
+fake_service!(GetNoteListPageQuery, Page<Note>, get_note_list_page);
+
+fn get_note_list_page<Cx: GetRef<GitlabUrl>>(
+  _state: &mut FakeGitlabState,
+  req: &GetNoteListPageQuery<Cx>,
+) -> Result<Page<Note>, HttpGitlabClientError> {
+  Err(not_found(req, "Page"))
+}
+
+fake_service!(GetNoteQuery, Note, get_note);
+
+fn get_note<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetNoteQuery<Cx>,
+) -> Result<Note, HttpGitlabClientError> {
+  let (_, _, discussions) = discussions(state, req, &req.noteable.clone().into())?;
+  let (_, _, note) = find_note(req, discussions, None, req.note_id)?;
+  Ok(note.clone())
+}
+
+fake_service!(CreateNoteCommand, Note, create_note);
+
+fn create_note<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &CreateNoteCommand<Cx>,
+) -> Result<Note, HttpGitlabClientError> {
+  let (noteable, discussion_id) = note_location(&req.location);
+  let (project_id, key, _) = discussions(state, req, &noteable)?;
+  let mut note = fake_note(state, project_id, &key, &req.body);
+  note.internal = req.internal.unwrap_or(false);
+  let new_discussion_id = state.next_object_id();
+  let discussions = state
+    .projects
+    .get_mut(&project_id)
+    .expect("project exists")
+    .discussions
+    .get_mut(&key)
+    .expect("discussions exist");
+  match discussion_id {
+    Some(discussion_id) => {
+      // Replying to a standalone comment turns it into a thread
+      let discussion = find_discussion(req, discussions, discussion_id)?;
+      discussion.individual_note = false;
+      for note in discussion.notes.iter_mut().chain([&mut note]) {
+        note.note_type.get_or_insert(NoteType::DiscussionNote);
+        if !note.resolvable {
+          make_resolvable(note);
+        }
+      }
+      discussion.notes.push(note.clone());
+    }
+    None => discussions.push(Discussion {
+      id: new_discussion_id,
+      individual_note: true,
+      notes: vec![note.clone()],
+    }),
+  }
+  Ok(note)
+}
+
+fake_service!(UpdateNoteCommand, Note, update_note);
+
+fn update_note<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &UpdateNoteCommand<Cx>,
+) -> Result<Note, HttpGitlabClientError> {
+  let (noteable, discussion_id) = note_location(&req.location);
+  let (_, _, discussions) = discussions(state, req, &noteable)?;
+  let (_, _, note) = find_note(req, discussions, discussion_id, req.note_id)?;
+  note.body = req.body.to_string();
+  note.updated_at = Utc::now();
+  Ok(note.clone())
+}
+
+fake_service!(DeleteNoteCommand, (), delete_note);
+
+fn delete_note<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &DeleteNoteCommand<Cx>,
+) -> Result<(), HttpGitlabClientError> {
+  let (noteable, discussion_id) = note_location(&req.location);
+  let (_, _, discussions) = discussions(state, req, &noteable)?;
+  let (d, n, _) = find_note(req, discussions, discussion_id, req.note_id)?;
+  discussions[d].notes.remove(n);
+  if discussions[d].notes.is_empty() {
+    discussions.remove(d);
+  }
+  Ok(())
+}
+
+fake_service!(GetDiscussionListQuery, Page<Discussion>, get_discussion_list);
+
+fn get_discussion_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetDiscussionListQuery<Cx>,
+) -> Result<Page<Discussion>, HttpGitlabClientError> {
+  let (_, _, discussions) = discussions(state, req, &req.noteable)?;
+  Ok(single_page(discussions.clone()))
+}
+
+fake_service!(GetDiscussionListPageQuery, Page<Discussion>, get_discussion_list_page);
+
+fn get_discussion_list_page<Cx: GetRef<GitlabUrl>>(
+  _state: &mut FakeGitlabState,
+  req: &GetDiscussionListPageQuery<Cx>,
+) -> Result<Page<Discussion>, HttpGitlabClientError> {
+  Err(not_found(req, "Page"))
+}
+
+fake_service!(GetDiscussionQuery, Discussion, get_discussion);
+
+fn get_discussion<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetDiscussionQuery<Cx>,
+) -> Result<Discussion, HttpGitlabClientError> {
+  let (_, _, discussions) = discussions(state, req, &req.noteable)?;
+  find_discussion(req, discussions, &req.discussion_id).cloned()
+}
+
+fake_service!(CreateDiscussionCommand, Discussion, create_discussion);
+
+fn create_discussion<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &CreateDiscussionCommand<Cx>,
+) -> Result<Discussion, HttpGitlabClientError> {
+  let (project_id, key, _) = discussions(state, req, &req.noteable)?;
+  let mut note = fake_note(state, project_id, &key, &req.body);
+  note.note_type = Some(NoteType::DiscussionNote);
+  make_resolvable(&mut note);
+  if let Some(position) = &req.position {
+    // The fake does not check the lines, only that the position targets the latest diff
+    let head = match &key {
+      FakeNoteable::MergeRequest(iid) => diff_versions(state, project_id, *iid)
+        .last()
+        .map(|version| version.head_commit_sha.clone()),
+      FakeNoteable::Commit(sha) => Some(sha.clone()),
+    };
+    if head.as_ref() != Some(&position.head_sha) {
+      return Err(error(
+        req,
+        StatusCode::BAD_REQUEST,
+        "400 Bad request - Note {:line_code=>[\"can't be blank\", \"must be a valid line code\"]}",
+      ));
+    }
+    note.note_type = Some(NoteType::DiffNote);
+    note.commit_id = Some(position.head_sha.clone());
+    note.position = Some(position.clone());
+  }
+  let discussion = Discussion {
+    id: state.next_object_id(),
+    individual_note: false,
+    notes: vec![note],
+  };
+  state
+    .projects
+    .get_mut(&project_id)
+    .expect("project exists")
+    .discussions
+    .get_mut(&key)
+    .expect("discussions exist")
+    .push(discussion.clone());
+  Ok(discussion)
+}
+
+fake_service!(ResolveDiscussionCommand, Discussion, resolve_discussion);
+
+fn resolve_discussion<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &ResolveDiscussionCommand<Cx>,
+) -> Result<Discussion, HttpGitlabClientError> {
+  if !matches!(req.noteable, NoteableRef::MergeRequest { .. }) {
+    return Err(error(req, StatusCode::NOT_FOUND, "404 Not Found"));
+  }
+  let (_, _, discussions) = discussions(state, req, &req.noteable)?;
+  let discussion = find_discussion(req, discussions, &req.discussion_id)?;
+  if !discussion.notes.iter().all(|note| note.resolvable) {
+    return Err(error(req, StatusCode::FORBIDDEN, "403 Forbidden"));
+  }
+  let now = Utc::now();
+  for note in &mut discussion.notes {
+    note.resolved = Some(req.resolved);
+    note.resolved_by = req.resolved.then(|| fake_user(FAKE_USER_ID));
+    note.resolved_at = req.resolved.then_some(now);
+  }
+  Ok(discussion.clone())
+}
@@ -0,0 +1,241 @@
+//! Generic packages of the fake GitLab
+
+use super::{not_found, single_page, FakeGitlabClient, FakeGitlabState, FakePackageFile};
+use crate::body::{to_hex, UploadBody};
+use crate::client::http::HttpGitlabClientError;
+use crate::command::publish_package_file::PublishPackageFileCommand;
+use crate::common::package::{
+  GenericPackageFile, GitlabFile, Package, PackageFile, PackageId, PackageLinks, PackageType,
+};
+use crate::common::Page;
+use crate::context::{GetRef, GitlabUrl};
+use crate::query::get_package_file::GetPackageFileQuery;
+use crate::query::get_package_file_list::GetPackageFileListQuery;
+use crate::query::get_package_file_list_page::GetPackageFileListPageQuery;
+use crate::query::get_project_package_list::GetProjectPackageListQuery;
+use crate::query::get_project_package_list_page::GetProjectPackageListPageQuery;
+use crate::PackageStatus;
+use bytes::Bytes;
+use chrono::Utc;
+use compact_str::{format_compact, CompactString};
+use core::task::{Context, Poll};
+use futures::future::BoxFuture;
+use http_body_util::BodyExt;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use tower_service::Service;
+
+fake_service!(GetPackageFileQuery, Bytes, get_package_file);
+
+fn get_package_file<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetPackageFileQuery<Cx>,
+) -> Result<Bytes, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  state.projects[&id]
+    .packages
+    .get(&(
+      req.package_name.clone(),
+      req.package_version.clone(),
+      req.filename.clone(),
+    ))
+    .map(|f| f.data.clone())
+    .ok_or_else(|| not_found(req, "Package"))
+}
+
+fake_service!(GetPackageFileListQuery, Page<PackageFile>, get_package_file_list);
+
+fn get_package_file_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetPackageFileListQuery<Cx>,
+) -> Result<Page<PackageFile>, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let items: Vec<PackageFile> = state.projects[&id]
+    .packages
+    .values()
+    .map(|f| &f.file)
+    .filter(|file| file.package_id == req.package_id.into_u64())
+    .map(|file| PackageFile {
+      id: file.id,
+      package_id: PackageId::new(file.package_id),
+      created_at: file.created_at,
+      file_name: file.file_name.clone(),
+      size: file.size,
+      file_md5: file.file_md5.clone(),
+      file_sha1: file.file_sha1.clone(),
+      file_sha256: file.file_sha256.clone(),
+      pipelines: Vec::new(),
+    })
+    .collect();
+  if items.is_empty() {
+    return Err(not_found(req, "Package"));
+  }
+  Ok(single_page(items))
+}
+
+fake_service!(
+  GetPackageFileListPageQuery,
+  Page<PackageFile>,
+  get_package_file_list_page
+);
+
+fn get_package_file_list_page<Cx: GetRef<GitlabUrl>>(
+  _state: &mut FakeGitlabState,
+  req: &GetPackageFileListPageQuery<Cx>,
+) -> Result<Page<PackageFile>, HttpGitlabClientError> {
+  Err(not_found(req, "Page"))
+}
+
+fake_service!(GetProjectPackageListQuery, Page<Package>, get_project_package_list);
+
+fn get_project_package_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetProjectPackageListQuery<Cx>,
+) -> Result<Page<Package>, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = &state.projects[&id];
+  let mut packages: BTreeMap<(&str, &str), Package> = BTreeMap::new();
+  for ((name, version, _), file) in &project.packages {
+    let file = &file.file;
+    let status = match file.status.as_str() {
+      "hidden" => PackageStatus::Hidden,
+      _ => PackageStatus::Default,
+    };
+    packages
+      .entry((name.as_str(), version.as_str()))
+      .or_insert_with(|| Package {
+        id: PackageId::new(file.package_id),
+        name: name.clone(),
+        version: version.clone(),
+        package_type: PackageType::Generic,
+        status,
+        _links: PackageLinks {
+          web_path: format_compact!(
+            "/{}/-/packages/{}",
+            project.project.path_with_namespace,
+            file.package_id
+          ),
+          delete_api_path: None,
+        },
+        created_at: file.created_at,
+        last_downloaded_at: None,
+        pipelines: Vec::new(),
+        tags: Vec::new(),
+      });
+  }
+  let items = packages
+    .into_values()
+    .filter(|p| req.package_type.map_or(true, |t| t == p.package_type))
+    .filter(|p| req.package_name.as_ref().map_or(true, |n| p.name.contains(n.as_str())))
+    .filter(|p| req.status.map_or(true, |s| s == p.status))
+    .collect();
+  Ok(single_page(items))
+}
+
+fake_service!(
+  GetProjectPackageListPageQuery,
+  Page<Package>,
+  get_project_package_list_page
+);
+
+fn get_project_package_list_page<Cx: GetRef<GitlabUrl>>(
+  _state: &mut FakeGitlabState,
+  req: &GetProjectPackageListPageQuery<Cx>,
+) -> Result<Page<Package>, HttpGitlabClientError> {
+  Err(not_found(req, "Page"))
+}
+
+fake_service!(PublishPackageFileCommand, GenericPackageFile, publish_package_file);
+
+fn publish_package_file<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &PublishPackageFileCommand<Cx>,
+) -> Result<GenericPackageFile, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let file_id = state.next_id();
+  let key = (
+    req.package_name.clone(),
+    req.package_version.clone(),
+    req.filename.clone(),
+  );
+  let project = state.projects.get_mut(&id).expect("project exists");
+  let package_id = project
+    .packages
+    .iter()
+    .find(|((name, version, _), _)| *name == key.0 && *version == key.1)
+    .map(|(_, f)| f.file.package_id)
+    .unwrap_or(file_id);
+  let now = Utc::now();
+  let file = GenericPackageFile {
+    id: file_id,
+    package_id,
+    created_at: now,
+    updated_at: now,
+    size: req.data.len() as u64,
+    file_store: 1,
+    file_md5: None,
+    file_sha1: None,
+    file_name: req.filename.clone(),
+    file: GitlabFile {
+      url: format_compact!(
+        "{}/-/package_files/{}/download",
+        project.project.web_url.as_str().trim_end_matches('/'),
+        file_id
+      ),
+    },
+    file_sha256: Some(to_hex(&Sha256::digest(&req.data))),
+    verification_retry_at: None,
+    verified_at: None,
+    verification_failure: None,
+    verification_retry_count: None,
+    verification_checksum: None,
+    verification_state: 0,
+    verification_started_at: None,
+    status: CompactString::new(req.status.as_str()),
+    new_file_path: None,
+  };
+  project.packages.insert(
+    key,
+    FakePackageFile {
+      file: file.clone(),
+      data: Bytes::from(req.data.clone()),
+    },
+  );
+  Ok(file)
+}
+
+/// Publish a package file streamed from an [`UploadBody`], collected in memory
+impl<Cx> Service<PublishPackageFileCommand<Cx, CompactString, UploadBody>> for FakeGitlabClient
+where
+  Cx: GetRef<GitlabUrl> + Send + 'static,
+{
+  type Response = GenericPackageFile;
+  type Error = HttpGitlabClientError;
+  type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, req: PublishPackageFileCommand<Cx, CompactString, UploadBody>) -> Self::Future {
+    let client = self.clone();
+    let (req, data) = req.replace_data(Vec::<u8>::new());
+    Box::pin(async move {
+      let data = data
+        .collect()
+        .await
+        .map_err(|e| HttpGitlabClientError::Send(format!("{e:?}")))?
+        .to_bytes();
+      let (req, _) = req.replace_data(data.to_vec());
+      publish_package_file(&mut client.state(), &req)
+    })
+  }
+}
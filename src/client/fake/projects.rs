@@ -0,0 +1,42 @@
+//! Projects of the fake GitLab
+
+use super::{not_found, single_page, FakeGitlabState};
+use crate::client::http::HttpGitlabClientError;
+use crate::common::project::Project;
+use crate::common::Page;
+use crate::context::{GetRef, GitlabUrl};
+use crate::query::get_project::GetProjectQuery;
+use crate::query::get_project_list::GetProjectListQuery;
+use crate::query::get_project_list_page::GetProjectListPageQuery;
+
+fake_service!(GetProjectListQuery, Page<Project>, get_project_list);
+
+fn get_project_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  _req: &GetProjectListQuery<Cx>,
+) -> Result<Page<Project>, HttpGitlabClientError> {
+  Ok(single_page(
+    state.projects.values().map(|p| p.project.clone()).collect(),
+  ))
+}
+
+fake_service!(GetProjectListPageQuery, Page<Project>, get_project_list_page);
+
+fn get_project_list_page<Cx: GetRef<GitlabUrl>>(
+  _state: &mut FakeGitlabState,
+  req: &GetProjectListPageQuery<Cx>,
+) -> Result<Page<Project>, HttpGitlabClientError> {
+  Err(not_found(req, "Page"))
+}
+
+fake_service!(GetProjectQuery, Project, get_project);
+
+fn get_project<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetProjectQuery<Cx>,
+) -> Result<Project, HttpGitlabClientError> {
+  state
+    .project_id(&req.id)
+    .map(|id| state.projects[&id].project.clone())
+    .ok_or_else(|| not_found(req, "Project"))
+}
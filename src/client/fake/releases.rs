@@ -0,0 +1,411 @@
+//! Releases and release links of the fake GitLab
+
+use super::{error, not_found, single_page, FakeGitlabState, FakeProject};
+use crate::client::http::HttpGitlabClientError;
+use crate::command::create_release::CreateReleaseCommand;
+use crate::command::create_release_link::CreateReleaseLinkCommand;
+use crate::command::delete_release::DeleteReleaseCommand;
+use crate::command::delete_release_link::DeleteReleaseLinkCommand;
+use crate::command::update_release::UpdateReleaseCommand;
+use crate::command::update_release_link::UpdateReleaseLinkCommand;
+use crate::common::commit::Commit;
+use crate::common::milestone::Milestone;
+use crate::common::release::{
+  Author, AuthorId, Release, ReleaseAssets, ReleaseLink, ReleaseLinkId, ReleaseLinks, ReleaseSource,
+};
+use crate::common::Page;
+use crate::context::{GetRef, GitlabUrl};
+use crate::endpoint::Endpoint;
+use crate::query::get_project_release::GetProjectReleaseQuery;
+use crate::query::get_project_release_list::GetProjectReleaseListQuery;
+use crate::query::get_project_release_list_page::GetProjectReleaseListPageQuery;
+use crate::query::get_release_link::GetReleaseLinkQuery;
+use crate::query::get_release_link_list::GetReleaseLinkListQuery;
+use chrono::Utc;
+use http::StatusCode;
+
+fake_service!(GetProjectReleaseQuery, Release, get_project_release);
+
+fn get_project_release<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetProjectReleaseQuery<Cx>,
+) -> Result<Release, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  state.projects[&id]
+    .releases
+    .get(&req.tag_name)
+    .cloned()
+    .ok_or_else(|| not_found(req, "Release"))
+}
+
+fake_service!(GetProjectReleaseListQuery, Page<Release>, get_project_release_list);
+
+fn get_project_release_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetProjectReleaseListQuery<Cx>,
+) -> Result<Page<Release>, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let mut items: Vec<Release> = state.projects[&id].releases.values().cloned().collect();
+  items.sort_by_key(|r| core::cmp::Reverse(r.released_at));
+  Ok(single_page(items))
+}
+
+fake_service!(
+  GetProjectReleaseListPageQuery,
+  Page<Release>,
+  get_project_release_list_page
+);
+
+fn get_project_release_list_page<Cx: GetRef<GitlabUrl>>(
+  _state: &mut FakeGitlabState,
+  req: &GetProjectReleaseListPageQuery<Cx>,
+) -> Result<Page<Release>, HttpGitlabClientError> {
+  Err(not_found(req, "Page"))
+}
+
+fake_service!(CreateReleaseCommand, Release, create_release);
+
+fn create_release<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &CreateReleaseCommand<Cx>,
+) -> Result<Release, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let mut link_ids = Vec::with_capacity(req.assets.links.len());
+  for _ in &req.assets.links {
+    link_ids.push(ReleaseLinkId::new(state.next_id()));
+  }
+  let project = state.projects.get_mut(&id).expect("project exists");
+  if project.releases.contains_key(&req.tag_name) {
+    return Err(error(req, StatusCode::CONFLICT, "Release already exists"));
+  }
+  let milestones = release_milestones(req, project, &req.milestones)?;
+  let web_url = project.project.web_url.as_str().trim_end_matches('/').to_string();
+  let tag_name = req.tag_name.as_str();
+  let now = Utc::now();
+  let links = req
+    .assets
+    .links
+    .iter()
+    .zip(link_ids)
+    .map(|(link, id)| ReleaseLink {
+      id,
+      name: link.name.to_string(),
+      url: link.url.to_string(),
+      direct_asset_url: direct_asset_url(&web_url, tag_name, link.direct_asset_path.as_deref(), &link.url),
+      direct_asset_path: link.direct_asset_path.as_ref().map(|p| p.to_string()),
+      link_type: link.link_type,
+    })
+    .collect::<Vec<_>>();
+  let sources: Vec<ReleaseSource> = ["zip", "tar.gz", "tar.bz2", "tar"]
+    .into_iter()
+    .map(|format| ReleaseSource {
+      format: format.to_string(),
+      url: format!(
+        "{web_url}/-/archive/{tag_name}/{}-{tag_name}.{format}",
+        project.project.path
+      ),
+    })
+    .collect();
+  let release = Release {
+    name: req.name.as_deref().unwrap_or(tag_name).to_string(),
+    tag_name: tag_name.to_string(),
+    description: req.description.as_ref().map(|d| d.to_string()),
+    created_at: now,
+    released_at: req.released_at.unwrap_or(now),
+    upcoming_release: req.released_at.is_some_and(|date| date > now),
+    author: Author {
+      id: AuthorId::new(1),
+      name: "Fake User".to_string(),
+      username: "fake".to_string(),
+      state: "active".to_string(),
+      avatar_url: String::new(),
+      web_url: "https://gitlab.example.com/fake".to_string(),
+    },
+    commit: Commit {
+      id: "0000000000000000000000000000000000000000".to_string(),
+      short_id: "00000000".to_string(),
+      title: String::new(),
+      created_at: now,
+      parent_ids: Vec::new(),
+      message: String::new(),
+      author_name: "Fake User".to_string(),
+      author_email: "fake@example.com".to_string(),
+      authored_date: now,
+      committer_name: "Fake User".to_string(),
+      committer_email: "fake@example.com".to_string(),
+      committed_date: now,
+      trailers: None,
+      web_url: None,
+      stats: None,
+    },
+    milestones,
+    commit_path: format!(
+      "/{}/-/commit/0000000000000000000000000000000000000000",
+      project.project.path_with_namespace
+    ),
+    tag_path: format!("/{}/-/tags/{tag_name}", project.project.path_with_namespace),
+    assets: ReleaseAssets {
+      count: (sources.len() + links.len()) as u64,
+      sources,
+      links,
+    },
+    evidences: Vec::new(),
+    _links: release_links(&web_url, tag_name),
+  };
+  project.releases.insert(req.tag_name.clone(), release.clone());
+  Ok(release)
+}
+
+fake_service!(CreateReleaseLinkCommand, ReleaseLink, create_release_link);
+
+fn create_release_link<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &CreateReleaseLinkCommand<Cx>,
+) -> Result<ReleaseLink, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let link_id = ReleaseLinkId::new(state.next_id());
+  let project = state.projects.get_mut(&id).expect("project exists");
+  let web_url = project.project.web_url.as_str().trim_end_matches('/').to_string();
+  let release = match project.releases.get_mut(&req.tag_name) {
+    Some(release) => release,
+    None => return Err(not_found(req, "Release")),
+  };
+  if release.assets.links.iter().any(|l| l.name == req.name.as_str()) {
+    return Err(error(
+      req,
+      StatusCode::BAD_REQUEST,
+      "Validation failed: Name has already been taken",
+    ));
+  }
+  let link = ReleaseLink {
+    id: link_id,
+    name: req.name.to_string(),
+    url: req.url.to_string(),
+    direct_asset_url: direct_asset_url(&web_url, &req.tag_name, req.direct_asset_path.as_deref(), &req.url),
+    direct_asset_path: req.direct_asset_path.as_ref().map(|p| p.to_string()),
+    link_type: req.link_type,
+  };
+  release.assets.links.push(link.clone());
+  release.assets.count += 1;
+  Ok(link)
+}
+
+fake_service!(UpdateReleaseCommand, Release, update_release);
+
+fn update_release<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &UpdateReleaseCommand<Cx>,
+) -> Result<Release, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = state.projects.get_mut(&id).expect("project exists");
+  let milestones = match req.milestones.as_ref() {
+    Some(titles) => Some(release_milestones(req, project, titles)?),
+    None => None,
+  };
+  let release = match project.releases.get_mut(&req.tag_name) {
+    Some(release) => release,
+    None => return Err(not_found(req, "Release")),
+  };
+  if let Some(name) = req.name.as_ref() {
+    release.name = name.to_string();
+  }
+  if let Some(milestones) = milestones {
+    release.milestones = milestones;
+  }
+  if let Some(description) = req.description.as_ref() {
+    release.description = Some(description.to_string());
+  }
+  if let Some(released_at) = req.released_at {
+    release.released_at = released_at;
+  }
+  Ok(release.clone())
+}
+
+fake_service!(DeleteReleaseCommand, Release, delete_release);
+
+fn delete_release<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &DeleteReleaseCommand<Cx>,
+) -> Result<Release, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = state.projects.get_mut(&id).expect("project exists");
+  project
+    .releases
+    .remove(&req.tag_name)
+    .ok_or_else(|| not_found(req, "Release"))
+}
+
+fake_service!(GetReleaseLinkListQuery, Page<ReleaseLink>, get_release_link_list);
+
+fn get_release_link_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetReleaseLinkListQuery<Cx>,
+) -> Result<Page<ReleaseLink>, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = state.projects.get(&id).expect("project exists");
+  let release = project
+    .releases
+    .get(&req.tag_name)
+    .ok_or_else(|| not_found(req, "Release"))?;
+  Ok(single_page(release.assets.links.clone()))
+}
+
+fake_service!(GetReleaseLinkQuery, ReleaseLink, get_release_link);
+
+fn get_release_link<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetReleaseLinkQuery<Cx>,
+) -> Result<ReleaseLink, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = state.projects.get(&id).expect("project exists");
+  let release = project
+    .releases
+    .get(&req.tag_name)
+    .ok_or_else(|| not_found(req, "Release"))?;
+  release
+    .assets
+    .links
+    .iter()
+    .find(|l| l.id == req.link_id)
+    .cloned()
+    .ok_or_else(|| not_found(req, "Link"))
+}
+
+fake_service!(UpdateReleaseLinkCommand, ReleaseLink, update_release_link);
+
+fn update_release_link<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &UpdateReleaseLinkCommand<Cx>,
+) -> Result<ReleaseLink, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = state.projects.get_mut(&id).expect("project exists");
+  let web_url = project.project.web_url.as_str().trim_end_matches('/').to_string();
+  let release = project
+    .releases
+    .get_mut(&req.tag_name)
+    .ok_or_else(|| not_found(req, "Release"))?;
+  if let Some(name) = req.name.as_ref() {
+    if release
+      .assets
+      .links
+      .iter()
+      .any(|l| l.id != req.link_id && l.name == name.as_str())
+    {
+      return Err(error(
+        req,
+        StatusCode::BAD_REQUEST,
+        "Validation failed: Name has already been taken",
+      ));
+    }
+  }
+  let link = release
+    .assets
+    .links
+    .iter_mut()
+    .find(|l| l.id == req.link_id)
+    .ok_or_else(|| not_found(req, "Link"))?;
+  if let Some(name) = req.name.as_ref() {
+    link.name = name.to_string();
+  }
+  if let Some(url) = req.url.as_ref() {
+    link.url = url.to_string();
+  }
+  if let Some(path) = req.direct_asset_path.as_ref() {
+    link.direct_asset_path = Some(path.to_string());
+  }
+  if let Some(link_type) = req.link_type {
+    link.link_type = link_type;
+  }
+  link.direct_asset_url = direct_asset_url(&web_url, &req.tag_name, link.direct_asset_path.as_deref(), &link.url);
+  Ok(link.clone())
+}
+
+fake_service!(DeleteReleaseLinkCommand, ReleaseLink, delete_release_link);
+
+fn delete_release_link<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &DeleteReleaseLinkCommand<Cx>,
+) -> Result<ReleaseLink, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = state.projects.get_mut(&id).expect("project exists");
+  let release = project
+    .releases
+    .get_mut(&req.tag_name)
+    .ok_or_else(|| not_found(req, "Release"))?;
+  let index = release
+    .assets
+    .links
+    .iter()
+    .position(|l| l.id == req.link_id)
+    .ok_or_else(|| not_found(req, "Link"))?;
+  release.assets.count -= 1;
+  Ok(release.assets.links.remove(index))
+}
+
+/// Resolve the project milestones with the provided titles, for the milestones of a release
+fn release_milestones<Q: Endpoint, Str: AsRef<str>>(
+  req: &Q,
+  project: &FakeProject,
+  titles: &[Str],
+) -> Result<Option<Vec<Milestone>>, HttpGitlabClientError> {
+  let mut milestones = Vec::with_capacity(titles.len());
+  let mut missing = Vec::new();
+  for title in titles {
+    let title = title.as_ref();
+    match project.milestones.values().find(|m| m.title == title) {
+      Some(milestone) => milestones.push(milestone.clone()),
+      None => missing.push(title),
+    }
+  }
+  if !missing.is_empty() {
+    return Err(error(
+      req,
+      StatusCode::BAD_REQUEST,
+      &format!("Milestone(s) not found: {}", missing.join(", ")),
+    ));
+  }
+  Ok(if milestones.is_empty() { None } else { Some(milestones) })
+}
+
+fn direct_asset_url(web_url: &str, tag_name: &str, direct_asset_path: Option<&str>, url: &str) -> String {
+  match direct_asset_path {
+    Some(path) => format!(
+      "{web_url}/-/releases/{tag_name}/downloads/{}",
+      path.trim_start_matches('/')
+    ),
+    None => url.to_string(),
+  }
+}
+
+fn release_links(web_url: &str, tag_name: &str) -> ReleaseLinks {
+  let filter = |kind: &str, state: &str| format!("{web_url}/-/{kind}?release_tag={tag_name}&scope=all&state={state}");
+  ReleaseLinks {
+    closed_issues_url: filter("issues", "closed"),
+    closed_merge_requests_url: filter("merge_requests", "closed"),
+    edit_url: Some(format!("{web_url}/-/releases/{tag_name}/edit")),
+    merged_merge_requests_url: filter("merge_requests", "merged"),
+    opened_issues_url: filter("issues", "opened"),
+    opened_merge_requests_url: filter("merge_requests", "opened"),
+    this: format!("{web_url}/-/releases/{tag_name}"),
+  }
+}
@@ -0,0 +1,206 @@
+//! Repository trees and files of the fake GitLab
+
+use super::commits::{changed_file, push_commit, repository_ref, work_tree};
+use super::{error, not_found, single_page, FakeGitlabState, FakeRepositoryFile};
+use crate::body::to_hex;
+use crate::client::http::HttpGitlabClientError;
+use crate::command::create_repository_file::CreateRepositoryFileCommand;
+use crate::command::delete_repository_file::DeleteRepositoryFileCommand;
+use crate::command::update_repository_file::UpdateRepositoryFileCommand;
+use crate::common::repository_file::{RepositoryFile, RepositoryFileCommit};
+use crate::common::tree::TreeRecord;
+use crate::common::Page;
+use crate::context::{GetRef, GitlabUrl};
+use crate::query::get_raw_repository_file::GetRawRepositoryFileQuery;
+use crate::query::get_repository_file::GetRepositoryFileQuery;
+use crate::query::get_tree_record_list::GetTreeRecordListQuery;
+use bytes::Bytes;
+use compact_str::CompactString;
+use http::StatusCode;
+use sha2::{Digest, Sha256};
+
+fake_service!(GetTreeRecordListQuery, Page<TreeRecord>, get_tree_record_list);
+
+fn get_tree_record_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetTreeRecordListQuery<Cx>,
+) -> Result<Page<TreeRecord>, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let prefix = match req.path.as_deref() {
+    Some(path) if !path.is_empty() => format!("{}/", path.trim_end_matches('/')),
+    _ => String::new(),
+  };
+  let recursive = req.recursive.unwrap_or(false);
+  let items = state.projects[&id]
+    .tree
+    .iter()
+    .filter(|r| match r.path.strip_prefix(prefix.as_str()) {
+      Some(rest) => recursive || !rest.contains('/'),
+      None => false,
+    })
+    .cloned()
+    .collect();
+  Ok(single_page(items))
+}
+
+fake_service!(GetRepositoryFileQuery, RepositoryFile, get_repository_file);
+
+fn get_repository_file<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetRepositoryFileQuery<Cx>,
+) -> Result<RepositoryFile, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let commit = repository_ref(&state.projects[&id], req.r#ref.as_str()).ok_or_else(|| not_found(req, "Commit"))?;
+  let file = commit.files.get(&req.file_path).ok_or_else(|| not_found(req, "File"))?;
+  Ok(RepositoryFile {
+    file_name: CompactString::new(req.file_path.rsplit('/').next().unwrap_or_default()),
+    file_path: req.file_path.clone(),
+    size: file.content.len() as u64,
+    content: file.content.clone(),
+    content_sha256: to_hex(&Sha256::digest(&file.content)),
+    r#ref: req.r#ref.clone(),
+    blob_id: file.blob_id.clone(),
+    commit_id: CompactString::new(&commit.commit.id),
+    last_commit_id: file.last_commit_id.clone(),
+    execute_filemode: Some(file.execute_filemode),
+  })
+}
+
+fake_service!(GetRawRepositoryFileQuery, Bytes, get_raw_repository_file);
+
+fn get_raw_repository_file<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetRawRepositoryFileQuery<Cx>,
+) -> Result<Bytes, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = &state.projects[&id];
+  let r#ref = match req.r#ref.as_deref() {
+    Some(r#ref) => r#ref,
+    None => project.project.default_branch.as_deref().unwrap_or("main"),
+  };
+  repository_ref(project, r#ref)
+    .ok_or_else(|| not_found(req, "Commit"))?
+    .files
+    .get(&req.file_path)
+    .map(|f| f.content.clone())
+    .ok_or_else(|| not_found(req, "File"))
+}
+
+fake_service!(
+  CreateRepositoryFileCommand,
+  RepositoryFileCommit,
+  create_repository_file
+);
+
+fn create_repository_file<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &CreateRepositoryFileCommand<Cx>,
+) -> Result<RepositoryFileCommit, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let commit_id = state.next_object_id();
+  let blob_id = state.next_object_id();
+  let project = state.projects.get_mut(&id).expect("project exists");
+  let mut tree = work_tree(req, project, Some(&req.branch), req.start_branch.as_deref(), None)?;
+  if tree.files.contains_key(&req.file_path) {
+    return Err(error(
+      req,
+      StatusCode::BAD_REQUEST,
+      "A file with this name already exists",
+    ));
+  }
+  tree.files.insert(
+    req.file_path.clone(),
+    FakeRepositoryFile {
+      content: Bytes::from(req.content.clone()),
+      blob_id,
+      last_commit_id: commit_id.clone(),
+      execute_filemode: req.execute_filemode.unwrap_or(false),
+    },
+  );
+  push_commit(
+    project,
+    &req.branch,
+    tree,
+    commit_id,
+    &req.commit_message,
+    req.author_name.as_deref(),
+    req.author_email.as_deref(),
+  );
+  Ok(RepositoryFileCommit {
+    file_path: req.file_path.clone(),
+    branch: req.branch.clone(),
+  })
+}
+
+fake_service!(
+  UpdateRepositoryFileCommand,
+  RepositoryFileCommit,
+  update_repository_file
+);
+
+fn update_repository_file<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &UpdateRepositoryFileCommand<Cx>,
+) -> Result<RepositoryFileCommit, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let commit_id = state.next_object_id();
+  let blob_id = state.next_object_id();
+  let project = state.projects.get_mut(&id).expect("project exists");
+  let mut tree = work_tree(req, project, Some(&req.branch), req.start_branch.as_deref(), None)?;
+  let file = changed_file(req, &mut tree, &req.file_path, req.last_commit_id.as_deref())?;
+  file.content = Bytes::from(req.content.clone());
+  file.blob_id = blob_id;
+  file.last_commit_id = commit_id.clone();
+  if let Some(execute_filemode) = req.execute_filemode {
+    file.execute_filemode = execute_filemode;
+  }
+  push_commit(
+    project,
+    &req.branch,
+    tree,
+    commit_id,
+    &req.commit_message,
+    req.author_name.as_deref(),
+    req.author_email.as_deref(),
+  );
+  Ok(RepositoryFileCommit {
+    file_path: req.file_path.clone(),
+    branch: req.branch.clone(),
+  })
+}
+
+fake_service!(DeleteRepositoryFileCommand, (), delete_repository_file);
+
+fn delete_repository_file<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &DeleteRepositoryFileCommand<Cx>,
+) -> Result<(), HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let commit_id = state.next_object_id();
+  let project = state.projects.get_mut(&id).expect("project exists");
+  let mut tree = work_tree(req, project, Some(&req.branch), req.start_branch.as_deref(), None)?;
+  changed_file(req, &mut tree, &req.file_path, req.last_commit_id.as_deref())?;
+  tree.files.remove(&req.file_path);
+  push_commit(
+    project,
+    &req.branch,
+    tree,
+    commit_id,
+    &req.commit_message,
+    req.author_name.as_deref(),
+    req.author_email.as_deref(),
+  );
+  Ok(())
+}
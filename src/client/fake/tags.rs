@@ -0,0 +1,274 @@
+//! Tags and protected tags of the fake GitLab
+
+use super::branches::{matches_search, matches_wildcard, protected_access_list};
+use super::commits::repository_ref;
+use super::{error, not_found, single_page, FakeGitlabState, FakeProject, FakeTag};
+use crate::client::http::HttpGitlabClientError;
+use crate::command::create_tag::CreateTagCommand;
+use crate::command::delete_tag::DeleteTagCommand;
+use crate::command::protect_tag::ProtectTagCommand;
+use crate::command::unprotect_tag::UnprotectTagCommand;
+use crate::common::protected_tag::ProtectedTag;
+use crate::common::tag::{Tag, TagOrder, TagRelease, TagSignature};
+use crate::common::{Page, SortOrder};
+use crate::context::{GetRef, GitlabUrl};
+use crate::query::get_protected_tag::GetProtectedTagQuery;
+use crate::query::get_protected_tag_list::GetProtectedTagListQuery;
+use crate::query::get_protected_tag_list_page::GetProtectedTagListPageQuery;
+use crate::query::get_tag::GetTagQuery;
+use crate::query::get_tag_list::GetTagListQuery;
+use crate::query::get_tag_list_page::GetTagListPageQuery;
+use crate::query::get_tag_signature::GetTagSignatureQuery;
+use chrono::Utc;
+use compact_str::CompactString;
+use http::StatusCode;
+
+fn fake_tag(project: &FakeProject, name: &str, tag: &FakeTag) -> Tag {
+  Tag {
+    name: CompactString::new(name),
+    message: tag.message.clone(),
+    target: tag.target.clone(),
+    commit: project.commits[tag.commit_id.as_str()].commit.clone(),
+    release: project.releases.get(name).map(|release| TagRelease {
+      tag_name: CompactString::new(&release.tag_name),
+      description: release.description.clone(),
+    }),
+    protected: project
+      .protected_tags
+      .keys()
+      .any(|pattern| matches_wildcard(pattern, name)),
+    created_at: tag.created_at,
+  }
+}
+
+/// Compare tag names as semantic versions: numeric components are compared as numbers, and a leading `v` is ignored
+fn compare_versions(left: &str, right: &str) -> core::cmp::Ordering {
+  let parts = |name: &str| -> Vec<Result<u64, String>> {
+    name
+      .trim_start_matches('v')
+      .split(['.', '-', '+'])
+      .map(|part| part.parse::<u64>().map_err(|_| part.to_string()))
+      .collect()
+  };
+  parts(left).cmp(&parts(right))
+}
+
+fake_service!(GetTagListQuery, Page<Tag>, get_tag_list);
+
+fn get_tag_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetTagListQuery<Cx>,
+) -> Result<Page<Tag>, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = &state.projects[&id];
+  let mut tags = Vec::from_iter(
+    project
+      .tags
+      .iter()
+      .filter(|(name, _)| {
+        req
+          .search
+          .as_deref()
+          .map_or(true, |search| matches_search(search, name))
+      })
+      .map(|(name, tag)| fake_tag(project, name, tag)),
+  );
+  match req.order_by.unwrap_or(TagOrder::Updated) {
+    TagOrder::Name => tags.sort_by(|left, right| left.name.cmp(&right.name)),
+    TagOrder::Updated => tags.sort_by_key(|tag| tag.commit.committed_date),
+    TagOrder::Version => tags.sort_by(|left, right| compare_versions(&left.name, &right.name)),
+  }
+  if req.sort.unwrap_or(SortOrder::Desc) == SortOrder::Desc {
+    tags.reverse();
+  }
+  Ok(single_page(tags))
+}
+
+fake_service!(GetTagListPageQuery, Page<Tag>, get_tag_list_page);
+
+fn get_tag_list_page<Cx: GetRef<GitlabUrl>>(
+  _state: &mut FakeGitlabState,
+  req: &GetTagListPageQuery<Cx>,
+) -> Result<Page<Tag>, HttpGitlabClientError> {
+  Err(not_found(req, "Page"))
+}
+
+fake_service!(GetTagQuery, Tag, get_tag);
+
+fn get_tag<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetTagQuery<Cx>,
+) -> Result<Tag, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = &state.projects[&id];
+  let tag = project.tags.get(&req.tag_name).ok_or_else(|| not_found(req, "Tag"))?;
+  Ok(fake_tag(project, &req.tag_name, tag))
+}
+
+fake_service!(GetTagSignatureQuery, TagSignature, get_tag_signature);
+
+/// Tags created through the fake are never signed
+fn get_tag_signature<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetTagSignatureQuery<Cx>,
+) -> Result<TagSignature, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  if !state.projects[&id].tags.contains_key(&req.tag_name) {
+    return Err(not_found(req, "Tag"));
+  }
+  Err(not_found(req, "Signature"))
+}
+
+fake_service!(CreateTagCommand, Tag, create_tag);
+
+fn create_tag<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &CreateTagCommand<Cx>,
+) -> Result<Tag, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let target = state.next_object_id();
+  let project = state.projects.get_mut(&id).expect("project exists");
+  if project.tags.contains_key(&req.tag_name) {
+    return Err(error(
+      req,
+      StatusCode::BAD_REQUEST,
+      &format!("Tag {} already exists", req.tag_name),
+    ));
+  }
+  let commit_id = repository_ref(project, &req.r#ref)
+    .map(|commit| CompactString::new(&commit.commit.id))
+    .ok_or_else(|| {
+      error(
+        req,
+        StatusCode::BAD_REQUEST,
+        &format!("Target {} is invalid", req.r#ref),
+      )
+    })?;
+  let tag = match req.message.as_deref() {
+    Some(message) => FakeTag {
+      commit_id,
+      target,
+      message: Some(message.to_string()),
+      created_at: Some(Utc::now()),
+    },
+    None => FakeTag {
+      target: commit_id.clone(),
+      commit_id,
+      message: None,
+      created_at: None,
+    },
+  };
+  let created = fake_tag(project, &req.tag_name, &tag);
+  project.tags.insert(req.tag_name.clone(), tag);
+  Ok(created)
+}
+
+fake_service!(DeleteTagCommand, (), delete_tag);
+
+fn delete_tag<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &DeleteTagCommand<Cx>,
+) -> Result<(), HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = state.projects.get_mut(&id).expect("project exists");
+  project
+    .tags
+    .remove(&req.tag_name)
+    .map(|_| ())
+    .ok_or_else(|| not_found(req, "Tag"))
+}
+
+fake_service!(GetProtectedTagListQuery, Page<ProtectedTag>, get_protected_tag_list);
+
+fn get_protected_tag_list<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetProtectedTagListQuery<Cx>,
+) -> Result<Page<ProtectedTag>, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  Ok(single_page(
+    state.projects[&id].protected_tags.values().cloned().collect(),
+  ))
+}
+
+fake_service!(
+  GetProtectedTagListPageQuery,
+  Page<ProtectedTag>,
+  get_protected_tag_list_page
+);
+
+fn get_protected_tag_list_page<Cx: GetRef<GitlabUrl>>(
+  _state: &mut FakeGitlabState,
+  req: &GetProtectedTagListPageQuery<Cx>,
+) -> Result<Page<ProtectedTag>, HttpGitlabClientError> {
+  Err(not_found(req, "Page"))
+}
+
+fake_service!(GetProtectedTagQuery, ProtectedTag, get_protected_tag);
+
+fn get_protected_tag<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &GetProtectedTagQuery<Cx>,
+) -> Result<ProtectedTag, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  state.projects[&id]
+    .protected_tags
+    .get(&req.name)
+    .cloned()
+    .ok_or_else(|| not_found(req, "Protected Tag"))
+}
+
+fake_service!(ProtectTagCommand, ProtectedTag, protect_tag);
+
+fn protect_tag<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &ProtectTagCommand<Cx>,
+) -> Result<ProtectedTag, HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  if state.projects[&id].protected_tags.contains_key(&req.name) {
+    return Err(error(
+      req,
+      StatusCode::CONFLICT,
+      &format!("Protected tag '{}' already exists", req.name),
+    ));
+  }
+  let protection = ProtectedTag {
+    name: req.name.clone(),
+    create_access_levels: protected_access_list(state, req.create_access_level, &req.allowed_to_create),
+  };
+  let project = state.projects.get_mut(&id).expect("project exists");
+  project.protected_tags.insert(req.name.clone(), protection.clone());
+  Ok(protection)
+}
+
+fake_service!(UnprotectTagCommand, (), unprotect_tag);
+
+fn unprotect_tag<Cx: GetRef<GitlabUrl>>(
+  state: &mut FakeGitlabState,
+  req: &UnprotectTagCommand<Cx>,
+) -> Result<(), HttpGitlabClientError> {
+  let id = state
+    .project_id(&req.project)
+    .ok_or_else(|| not_found(req, "Project"))?;
+  let project = state.projects.get_mut(&id).expect("project exists");
+  project
+    .protected_tags
+    .remove(&req.name)
+    .map(|_| ())
+    .ok_or_else(|| not_found(req, "Protected Tag"))
+}
@@ -1,42 +1,68 @@
-use crate::command::create_release::CreateReleaseCommand;
-use crate::command::create_release_link::CreateReleaseLinkCommand;
+use crate::body::{parse_sha256, DownloadStream, UploadBody};
+use crate::client::retry::{RetryReason, RetryableError};
 use crate::command::publish_package_file::PublishPackageFileCommand;
+use crate::common::commit::CommitApplyFailure;
+use crate::common::error::GitlabErrorBody;
+use crate::common::merge_request::{MergeFailure, RebaseFailure};
 use crate::common::package::GenericPackageFile;
-use crate::common::release::{InputReleaseAssetsView, Release, ReleaseLink};
-use crate::common::tree::TreeRecord;
-use crate::common::Page;
 use crate::context::{GetRef, GitlabUrl};
-use crate::query::get_package_file::GetPackageFileQuery;
-use crate::query::get_project::GetProjectQuery;
-use crate::query::get_project_list::GetProjectListQuery;
-use crate::query::get_project_list_page::GetProjectListPageQuery;
-use crate::query::get_project_release::GetProjectReleaseQuery;
-use crate::query::get_project_release_list::GetProjectReleaseListQuery;
-use crate::query::get_project_release_list_page::GetProjectReleaseListPageQuery;
-use crate::query::get_tree_record_list::GetTreeRecordListQuery;
-use crate::url_util::UrlExt;
-use crate::{GitlabAuth, GitlabAuthView, InputPackageStatus, Project};
+use crate::endpoint::{decode_json, endpoint_url, DecodeError, Endpoint};
+use crate::query::get_package_file_stream::GetPackageFileStreamQuery;
+use crate::query::get_raw_repository_file_stream::GetRawRepositoryFileStreamQuery;
+use crate::GitlabAuthView;
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use compact_str::CompactString;
+use core::cmp::Ordering;
+use core::fmt;
+use core::marker::PhantomData;
 use core::task::{Context, Poll};
-use demurgos_headers::link::{Link, RelationType};
+use core::time::Duration;
 use demurgos_headers::UserAgent;
 use futures::future::BoxFuture;
-use http::header::CONTENT_TYPE;
-use http::{HeaderMap, Method, Request, Response, StatusCode};
+use http::header::{CONTENT_LENGTH, CONTENT_TYPE};
+use http::uri::Authority;
+use http::{HeaderMap, Method, Request, Response, StatusCode, Uri};
 use http_body::Body;
 use http_body_util::{BodyExt, Full};
+use std::collections::BTreeMap;
 use std::error::Error as StdError;
 use tower_service::Service;
 
-pub struct HttpGitlabClient<TyInner> {
+/// GitLab client backed by an HTTP service
+///
+/// `TyReqBody` is the request body type of the inner service. It defaults to `Full<Bytes>`; use
+/// [`UploadBody`] (see [`HttpGitlabClient::with_request_body`]) to also stream package files.
+pub struct HttpGitlabClient<TyInner, TyReqBody = Full<Bytes>> {
   inner: TyInner,
+  phantom: PhantomData<fn(TyReqBody)>,
 }
 
 impl<TyInner> HttpGitlabClient<TyInner> {
   pub fn new(inner: TyInner) -> Self {
-    Self { inner }
+    Self::with_request_body(inner)
+  }
+}
+
+impl<TyInner, TyReqBody> HttpGitlabClient<TyInner, TyReqBody> {
+  /// Create a client whose inner service takes requests with a `TyReqBody` body
+  pub fn with_request_body(inner: TyInner) -> Self {
+    Self {
+      inner,
+      phantom: PhantomData,
+    }
+  }
+}
+
+impl<TyInner: fmt::Debug, TyReqBody> fmt::Debug for HttpGitlabClient<TyInner, TyReqBody> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("HttpGitlabClient").field("inner", &self.inner).finish()
+  }
+}
+
+impl<TyInner: Clone, TyReqBody> Clone for HttpGitlabClient<TyInner, TyReqBody> {
+  fn clone(&self) -> Self {
+    Self::with_request_body(self.inner.clone())
   }
 }
 
@@ -45,398 +71,318 @@ impl<TyInner> HttpGitlabClient<TyInner> {
 pub enum HttpGitlabClientError {
   #[error("failed to poll ready status: {0}")]
   PollReady(String),
+  #[error("failed to build request: {0}")]
+  Request(String),
   #[error("failed to send request: {0}")]
   Send(String),
   #[error("failed to receive response: {0}")]
   Receive(String),
   #[error("failed to parse response: {0}")]
   ResponseFormat(String, Bytes),
-  #[error("operation is forbidden for provided auth")]
-  Forbidden,
-  #[error("resource already exists")]
-  Conflict,
-  #[error("resource not found")]
-  NotFound,
+  #[error("checksum mismatch: expected SHA-256 {0}, received {1}")]
+  ChecksumMismatch(CompactString, CompactString),
+  #[error("invalid request: {0}")]
+  Invalid(Box<ResponseError>),
+  #[error("authentication failed: {0}")]
+  Unauthorized(Box<ResponseError>),
+  #[error("operation is forbidden for provided auth: {0}")]
+  Forbidden(Box<ResponseError>),
+  #[error("resource not found: {0}")]
+  NotFound(Box<ResponseError>),
+  #[error("resource already exists: {0}")]
+  Conflict(Box<ResponseError>),
+  #[error("rate limit exceeded: {0}")]
+  RateLimited(Box<ResponseError>, RateLimit),
+  #[error("server error: {0}")]
+  Server(Box<ResponseError>),
+  #[error("unexpected response: {0}")]
+  Status(Box<ResponseError>),
+  #[error("merge request cannot be merged ({}): {1}", .0.as_str())]
+  MergeFailed(MergeFailure, Box<ResponseError>),
+  #[error("merge request cannot be rebased ({}): {1}", .0.as_str())]
+  RebaseFailed(RebaseFailure, Box<ResponseError>),
   #[error("unexpected error: {0}")]
   Other(String),
 }
 
-impl<'req, Cx, TyInner, TyBody> Service<&'req GetProjectListQuery<Cx>> for HttpGitlabClient<TyInner>
-where
-  Cx: GetRef<GitlabUrl> + GetRef<UserAgent>,
-  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
-  TyInner::Error: StdError,
-  TyInner::Future: Send,
-  TyBody: Body + Send,
-  TyBody::Data: Send,
-  TyBody::Error: StdError,
-{
-  type Response = Page<Project>;
-  type Error = HttpGitlabClientError;
-  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
-
-  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-    self
-      .inner
-      .poll_ready(cx)
-      .map_err(|e| HttpGitlabClientError::PollReady(format!("{e:?}")))
+impl HttpGitlabClientError {
+  /// Error response returned by GitLab, if any
+  pub fn response(&self) -> Option<&ResponseError> {
+    match self {
+      Self::Invalid(res)
+      | Self::Unauthorized(res)
+      | Self::Forbidden(res)
+      | Self::NotFound(res)
+      | Self::Conflict(res)
+      | Self::RateLimited(res, _)
+      | Self::Server(res)
+      | Self::Status(res)
+      | Self::MergeFailed(_, res)
+      | Self::RebaseFailed(_, res) => Some(res),
+      _ => None,
+    }
   }
 
-  fn call(&mut self, req: &'req GetProjectListQuery<Cx>) -> Self::Future {
-    let mut url = GetRef::<GitlabUrl>::get_ref(&req.context).url_join(["projects"]);
-
-    {
-      let mut query = url.query_pairs_mut();
-      if let Some(owned) = req.owned {
-        query.append_pair("owned", owned.as_str());
-      }
+  /// Reason why GitLab refused to merge a merge request, see [`AcceptMergeRequestCommand`]
+  ///
+  /// [`AcceptMergeRequestCommand`]: crate::command::accept_merge_request::AcceptMergeRequestCommand
+  pub fn merge_failure(&self) -> Option<MergeFailure> {
+    match self {
+      Self::MergeFailed(failure, _) => Some(*failure),
+      _ => None,
     }
+  }
 
-    let req = Request::builder()
-      .method(Method::GET)
-      .uri(url.as_str())
-      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
-      .gitlab_auth(req.auth.as_ref().map(GitlabAuth::as_view))
-      .body(Full::new(Bytes::new()))
-      .unwrap();
-    let res = self.inner.call(req);
-    Box::pin(async move {
-      let res: Response<TyBody> = res.await.map_err(|e| HttpGitlabClientError::Send(format!("{e:?}")))?;
-      let cursors = get_cursors(res.headers());
-      let body = res
-        .into_body()
-        .collect()
-        .await
-        .map_err(|e| HttpGitlabClientError::Receive(format!("{e:?}")))?;
-      let body: Bytes = body.to_bytes();
-
-      let body: Vec<Project> =
-        serde_json::from_slice(&body).map_err(|e| HttpGitlabClientError::ResponseFormat(format!("{e:?}"), body))?;
-      Ok(Page {
-        first: cursors.first,
-        next: cursors.next,
-        last: cursors.last,
-        items: body,
-      })
-    })
+  /// Report the error response to a merge request as [`Self::MergeFailed`], based on its status
+  pub(crate) fn classify_merge_failure(self) -> Self {
+    let failure = self
+      .status()
+      .and_then(|status| MergeFailure::from_status(status.as_u16()));
+    match (failure, self) {
+      (Some(failure), Self::Conflict(res) | Self::Status(res)) => Self::MergeFailed(failure, res),
+      (_, err) => err,
+    }
   }
-}
 
-impl<'req, Cx, TyInner, TyBody> Service<&'req GetProjectListPageQuery<Cx>> for HttpGitlabClient<TyInner>
-where
-  Cx: GetRef<UserAgent>,
-  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
-  TyInner::Error: StdError,
-  TyInner::Future: Send,
-  TyBody: Body + Send,
-  TyBody::Data: Send,
-  TyBody::Error: StdError,
-{
-  type Response = Page<Project>;
-  type Error = HttpGitlabClientError;
-  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
+  /// Reason why GitLab refused to rebase a merge request, see [`RebaseMergeRequestCommand`]
+  ///
+  /// [`RebaseMergeRequestCommand`]: crate::command::rebase_merge_request::RebaseMergeRequestCommand
+  pub fn rebase_failure(&self) -> Option<RebaseFailure> {
+    match self {
+      Self::RebaseFailed(failure, _) => Some(*failure),
+      _ => None,
+    }
+  }
 
-  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-    self
-      .inner
-      .poll_ready(cx)
-      .map_err(|e| HttpGitlabClientError::PollReady(format!("{e:?}")))
+  /// Report the error response to a rebase as [`Self::RebaseFailed`], based on its status
+  pub(crate) fn classify_rebase_failure(self) -> Self {
+    let failure = self
+      .status()
+      .and_then(|status| RebaseFailure::from_status(status.as_u16()));
+    match (failure, self) {
+      (Some(failure), Self::Forbidden(res) | Self::Conflict(res)) => Self::RebaseFailed(failure, res),
+      (_, err) => err,
+    }
   }
 
-  fn call(&mut self, req: &'req GetProjectListPageQuery<Cx>) -> Self::Future {
-    let url = &req.cursor;
+  /// Status code of the error response returned by GitLab, if any
+  pub fn status(&self) -> Option<StatusCode> {
+    self.response().map(|res| res.status)
+  }
 
-    let req = Request::builder()
-      .method(Method::GET)
-      .uri(url.as_str())
-      .gitlab_auth(req.auth.as_ref().map(GitlabAuth::as_view))
-      .body(Full::new(Bytes::new()))
-      .unwrap();
+  /// Build the error corresponding to a non-successful response.
+  async fn from_response<B>(info: RequestInfo, res: Response<B>) -> Self
+  where
+    B: Body,
+    B::Error: StdError,
+  {
+    let (parts, body) = res.into_parts();
+    let body: Bytes = match body.collect().await {
+      Ok(body) => body.to_bytes(),
+      Err(e) => return Self::Receive(format!("{e:?}")),
+    };
+    let rate_limit = RateLimit::from_headers(&parts.headers, Utc::now());
+    let res = Box::new(ResponseError {
+      method: info.method,
+      uri: info.uri,
+      status: parts.status,
+      request_id: parts
+        .headers
+        .get("x-request-id")
+        .and_then(|v| v.to_str().ok())
+        .map(CompactString::new),
+      error: serde_json::from_slice(&body).ok(),
+      body,
+    });
+    Self::from_response_error(res, rate_limit)
+  }
 
-    let res = self.inner.call(req);
-    Box::pin(async move {
-      let res: Response<TyBody> = res.await.map_err(|e| HttpGitlabClientError::Send(format!("{e:?}")))?;
-      let cursors = get_cursors(res.headers());
-      let body = res
-        .into_body()
-        .collect()
-        .await
-        .map_err(|e| HttpGitlabClientError::Receive(format!("{e:?}")))?;
-      let body: Bytes = body.to_bytes();
-
-      let body: Vec<Project> =
-        serde_json::from_slice(&body).map_err(|e| HttpGitlabClientError::ResponseFormat(format!("{e:?}"), body))?;
-      Ok(Page {
-        first: cursors.first,
-        next: cursors.next,
-        last: cursors.last,
-        items: body,
-      })
-    })
+  /// Build the error corresponding to the status of an error response.
+  pub(crate) fn from_response_error(res: Box<ResponseError>, rate_limit: RateLimit) -> Self {
+    match res.status {
+      StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => Self::Invalid(res),
+      StatusCode::UNAUTHORIZED => Self::Unauthorized(res),
+      StatusCode::FORBIDDEN => Self::Forbidden(res),
+      StatusCode::NOT_FOUND => Self::NotFound(res),
+      StatusCode::CONFLICT => Self::Conflict(res),
+      StatusCode::TOO_MANY_REQUESTS => Self::RateLimited(res, rate_limit),
+      status if status.is_server_error() => Self::Server(res),
+      _ => Self::Status(res),
+    }
   }
 }
 
-impl<'req, Cx, TyInner, TyBody> Service<&'req GetTreeRecordListQuery<Cx>> for HttpGitlabClient<TyInner>
-where
-  Cx: GetRef<GitlabUrl> + GetRef<UserAgent>,
-  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
-  TyInner::Error: StdError,
-  TyInner::Future: Send,
-  TyBody: Body + Send,
-  TyBody::Data: Send,
-  TyBody::Error: StdError,
-{
-  type Response = Page<TreeRecord>;
-  type Error = HttpGitlabClientError;
-  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
-
-  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-    self
-      .inner
-      .poll_ready(cx)
-      .map_err(|e| HttpGitlabClientError::PollReady(format!("{e:?}")))
+impl RetryableError for HttpGitlabClientError {
+  fn retry_reason(&self) -> Option<RetryReason> {
+    match self {
+      Self::Send(_) => Some(RetryReason::Connection),
+      Self::RateLimited(_, rate_limit) => Some(RetryReason::RateLimited {
+        retry_after: rate_limit.retry_delay(Utc::now()),
+      }),
+      Self::Server(_) => Some(RetryReason::Server),
+      _ => None,
+    }
   }
+}
 
-  fn call(&mut self, req: &'req GetTreeRecordListQuery<Cx>) -> Self::Future {
-    let mut url = req.project.with_str(|project| {
-      GetRef::<GitlabUrl>::get_ref(&req.context).url_join(["projects", project, "repository", "tree"])
-    });
-    {
-      let mut query = url.query_pairs_mut();
-      if let Some(recursive) = req.recursive {
-        query.append_pair("recursive", recursive.as_str());
-      }
-      if let Some(git_ref) = req.r#ref.as_deref() {
-        query.append_pair("ref", git_ref);
-      }
-    }
+/// Non-successful response returned by GitLab
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ResponseError {
+  /// Method of the failed request
+  #[cfg_attr(feature = "serde", serde(with = "serde_method"))]
+  pub method: Method,
+  /// URI of the failed request
+  #[cfg_attr(feature = "serde", serde(with = "serde_uri"))]
+  pub uri: Uri,
+  #[cfg_attr(feature = "serde", serde(with = "serde_status"))]
+  pub status: StatusCode,
+  /// Value of the `X-Request-Id` header, used by GitLab to correlate logs
+  pub request_id: Option<CompactString>,
+  /// Decoded GitLab error payload, if the body is a JSON error object
+  pub error: Option<GitlabErrorBody>,
+  /// Raw response body
+  pub body: Bytes,
+}
 
-    let req = Request::builder()
-      .method(Method::GET)
-      .uri(url.as_str())
-      .gitlab_auth(req.auth.as_ref().map(GitlabAuth::as_view))
-      .body(Full::new(Bytes::new()))
-      .unwrap();
+impl ResponseError {
+  /// Validation messages for each field, when the request was rejected by the model validation.
+  pub fn field_errors(&self) -> Option<&BTreeMap<CompactString, Vec<CompactString>>> {
+    self.error.as_ref().and_then(GitlabErrorBody::field_errors)
+  }
 
-    let res = self.inner.call(req);
-    Box::pin(async move {
-      let res: Response<TyBody> = res.await.map_err(|e| HttpGitlabClientError::Send(format!("{e:?}")))?;
-      let cursors = get_cursors(res.headers());
-      let body = res
-        .into_body()
-        .collect()
-        .await
-        .map_err(|e| HttpGitlabClientError::Receive(format!("{e:?}")))?;
-      let body: Bytes = body.to_bytes();
-
-      let body: Vec<TreeRecord> =
-        serde_json::from_slice(&body).map_err(|e| HttpGitlabClientError::ResponseFormat(format!("{e:?}"), body))?;
-      Ok(Page {
-        first: cursors.first,
-        next: cursors.next,
-        last: cursors.last,
-        items: body,
-      })
-    })
+  /// Reason why a cherry-pick or revert failed, when GitLab reported a known `error_code`.
+  pub fn commit_apply_failure(&self) -> Option<CommitApplyFailure> {
+    self.error.as_ref().and_then(GitlabErrorBody::commit_apply_failure)
   }
 }
 
-impl<'req, Cx, TyInner, TyBody> Service<&'req GetProjectQuery<Cx>> for HttpGitlabClient<TyInner>
-where
-  Cx: GetRef<GitlabUrl> + GetRef<UserAgent>,
-  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
-  TyInner::Error: StdError,
-  TyInner::Future: Send,
-  TyBody: Body + Send,
-  TyBody::Data: Send,
-  TyBody::Error: StdError,
-{
-  type Response = Project;
-  type Error = HttpGitlabClientError;
-  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
+impl PartialOrd for ResponseError {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
 
-  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+/// `Method` and `Uri` are not ordered: compare their string forms, ignoring the case of the scheme and authority like
+/// their `PartialEq` implementations.
+impl Ord for ResponseError {
+  fn cmp(&self, other: &Self) -> Ordering {
+    let lowercase = |s: Option<&str>| s.map(str::to_ascii_lowercase);
     self
-      .inner
-      .poll_ready(cx)
-      .map_err(|e| HttpGitlabClientError::PollReady(format!("{e:?}")))
+      .method
+      .as_str()
+      .cmp(other.method.as_str())
+      .then_with(|| lowercase(self.uri.scheme_str()).cmp(&lowercase(other.uri.scheme_str())))
+      .then_with(|| {
+        lowercase(self.uri.authority().map(Authority::as_str))
+          .cmp(&lowercase(other.uri.authority().map(Authority::as_str)))
+      })
+      .then_with(|| self.uri.path().cmp(other.uri.path()))
+      .then_with(|| self.uri.query().cmp(&other.uri.query()))
+      .then_with(|| self.status.cmp(&other.status))
+      .then_with(|| self.request_id.cmp(&other.request_id))
+      .then_with(|| self.error.cmp(&other.error))
+      .then_with(|| self.body.cmp(&other.body))
   }
+}
 
-  fn call(&mut self, req: &'req GetProjectQuery<Cx>) -> Self::Future {
-    let mut url = req
-      .id
-      .with_str(|id| GetRef::<GitlabUrl>::get_ref(&req.context).url_join(["projects", id]));
-
-    {
-      let mut query = url.query_pairs_mut();
-      if let Some(license) = req.license {
-        query.append_pair("license", license.as_str());
-      }
-      if let Some(statistics) = req.statistics {
-        query.append_pair("statistics", statistics.as_str());
-      }
-      if let Some(with_custom_attributes) = req.with_custom_attributes {
-        query.append_pair("with_custom_attributes", with_custom_attributes.as_str());
-      }
+impl core::fmt::Display for ResponseError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    write!(f, "{} {} returned {}", self.method, self.uri, self.status)?;
+    if let Some(error) = self.error.as_ref() {
+      write!(f, ": {error}")?;
     }
-
-    let req = Request::builder()
-      .method(Method::GET)
-      .uri(url.as_str())
-      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
-      .gitlab_auth(req.auth.as_ref().map(GitlabAuth::as_view))
-      .body(Full::new(Bytes::new()))
-      .unwrap();
-
-    let res = self.inner.call(req);
-    Box::pin(async move {
-      let res: Response<TyBody> = res.await.map_err(|e| HttpGitlabClientError::Send(format!("{e:?}")))?;
-      let body = res
-        .into_body()
-        .collect()
-        .await
-        .map_err(|e| HttpGitlabClientError::Receive(format!("{e:?}")))?;
-      let body: Bytes = body.to_bytes();
-
-      let body: Project =
-        serde_json::from_slice(&body).map_err(|e| HttpGitlabClientError::ResponseFormat(format!("{e:?}"), body))?;
-      Ok(body)
-    })
+    if let Some(request_id) = self.request_id.as_deref() {
+      write!(f, " (request id: {request_id})")?;
+    }
+    Ok(())
   }
 }
 
-impl<'req, Cx, TyInner, TyBody> Service<&'req GetPackageFileQuery<Cx>> for HttpGitlabClient<TyInner>
-where
-  Cx: GetRef<GitlabUrl> + GetRef<UserAgent>,
-  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
-  TyInner::Error: StdError,
-  TyInner::Future: Send,
-  TyBody: Body + Send,
-  TyBody::Data: Send,
-  TyBody::Error: StdError,
-{
-  type Response = Bytes;
-  type Error = HttpGitlabClientError;
-  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
-
-  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-    self
-      .inner
-      .poll_ready(cx)
-      .map_err(|e| HttpGitlabClientError::PollReady(format!("{e:?}")))
-  }
-
-  fn call(&mut self, req: &'req GetPackageFileQuery<Cx>) -> Self::Future {
-    let url = req.project.with_str(|project| {
-      GetRef::<GitlabUrl>::get_ref(&req.context).url_join([
-        "projects",
-        project,
-        "packages",
-        "generic",
-        &req.package_name,
-        &req.package_version,
-        &req.filename,
-      ])
-    });
-    let req = Request::builder()
-      .method(Method::GET)
-      .uri(url.as_str())
-      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
-      .gitlab_auth(req.auth.as_ref().map(GitlabAuth::as_view))
-      .body(Full::new(Bytes::new()))
-      .unwrap();
+/// Metadata of a sent request, used to report errors
+pub(crate) struct RequestInfo {
+  method: Method,
+  uri: Uri,
+}
 
-    let res = self.inner.call(req);
-    Box::pin(async move {
-      let res: Response<TyBody> = res.await.map_err(|e| HttpGitlabClientError::Send(format!("{e:?}")))?;
-      let body = res
-        .into_body()
-        .collect()
-        .await
-        .map_err(|e| HttpGitlabClientError::Receive(format!("{e:?}")))?;
-      let body: Bytes = body.to_bytes();
-      Ok(body)
-    })
+impl RequestInfo {
+  pub(crate) fn new<B>(req: &Request<B>) -> Self {
+    Self {
+      method: req.method().clone(),
+      uri: req.uri().clone(),
+    }
   }
 }
 
-impl<'req, Cx, TyInner, TyBody> Service<&'req GetProjectReleaseListQuery<Cx>> for HttpGitlabClient<TyInner>
+/// Fail with a typed error if the response does not have a successful status.
+pub(crate) async fn check_status<B>(info: RequestInfo, res: Response<B>) -> Result<Response<B>, HttpGitlabClientError>
 where
-  Cx: GetRef<GitlabUrl> + GetRef<UserAgent>,
-  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
-  TyInner::Error: StdError,
-  TyInner::Future: Send,
-  TyBody: Body + Send,
-  TyBody::Data: Send,
-  TyBody::Error: StdError,
+  B: Body,
+  B::Error: StdError,
 {
-  type Response = Page<Release>;
-  type Error = HttpGitlabClientError;
-  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
-
-  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-    self
-      .inner
-      .poll_ready(cx)
-      .map_err(|e| HttpGitlabClientError::PollReady(format!("{e:?}")))
+  if res.status().is_success() {
+    Ok(res)
+  } else {
+    Err(HttpGitlabClientError::from_response(info, res).await)
   }
+}
 
-  fn call(&mut self, req: &'req GetProjectReleaseListQuery<Cx>) -> Self::Future {
-    let mut url = req
-      .project
-      .with_str(|project| GetRef::<GitlabUrl>::get_ref(&req.context).url_join(["projects", project, "releases"]));
+/// Rate limit state reported by GitLab when rejecting a request
+///
+/// <https://docs.gitlab.com/ee/user/gitlab_com/index.html#gitlabcom-specific-rate-limits>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RateLimit {
+  /// Delay from the `Retry-After` header
+  pub retry_after: Option<Duration>,
+  /// Value of the `RateLimit-Remaining` header
+  pub remaining: Option<u64>,
+  /// Value of the `RateLimit-Reset` header
+  pub reset: Option<DateTime<Utc>>,
+}
 
-    {
-      let mut query = url.query_pairs_mut();
-      if let Some(pagination) = req.pagination {
-        query.append_pair("order_by", pagination.order_by.as_str());
-        query.append_pair("sort", pagination.sort.as_str());
-      }
+impl RateLimit {
+  pub fn from_headers(headers: &HeaderMap, now: DateTime<Utc>) -> Self {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok()).map(str::trim);
+    let retry_after = header("retry-after").and_then(|v| match v.parse::<u64>() {
+      Ok(secs) => Some(Duration::from_secs(secs)),
+      Err(_) => DateTime::parse_from_rfc2822(v)
+        .ok()
+        .map(|date| (date.with_timezone(&Utc) - now).to_std().unwrap_or(Duration::ZERO)),
+    });
+    let remaining = header("ratelimit-remaining").and_then(|v| v.parse::<u64>().ok());
+    let reset = header("ratelimit-reset")
+      .and_then(|v| v.parse::<i64>().ok())
+      .and_then(|v| DateTime::from_timestamp(v, 0));
+    Self {
+      retry_after,
+      remaining,
+      reset,
     }
+  }
 
-    let req = Request::builder()
-      .method(Method::GET)
-      .uri(url.as_str())
-      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
-      .gitlab_auth(req.auth.as_ref().map(GitlabAuth::as_view))
-      .body(Full::new(Bytes::new()))
-      .unwrap();
-    let res = self.inner.call(req);
-    Box::pin(async move {
-      let res: Response<TyBody> = res.await.map_err(|e| HttpGitlabClientError::Send(format!("{e:?}")))?;
-      let cursors = get_cursors(res.headers());
-      let body = res
-        .into_body()
-        .collect()
-        .await
-        .map_err(|e| HttpGitlabClientError::Receive(format!("{e:?}")))?;
-      let body: Bytes = body.to_bytes();
-
-      let body: Vec<Release> =
-        serde_json::from_slice(&body).map_err(|e| HttpGitlabClientError::ResponseFormat(format!("{e:?}"), body))?;
-      Ok(Page {
-        first: cursors.first,
-        next: cursors.next,
-        last: cursors.last,
-        items: body,
-      })
-    })
+  /// Delay to wait before sending a new request: `Retry-After` if present, otherwise the time until `RateLimit-Reset`.
+  pub fn retry_delay(&self, now: DateTime<Utc>) -> Option<Duration> {
+    self
+      .retry_after
+      .or_else(|| self.reset.map(|reset| (reset - now).to_std().unwrap_or(Duration::ZERO)))
   }
 }
 
-impl<'req, Cx, Str, TyInner, TyBody> Service<&'req GetProjectReleaseListPageQuery<Cx, Str>>
-  for HttpGitlabClient<TyInner>
+impl<'req, Q, TyInner, TyReqBody, TyBody> Service<&'req Q> for HttpGitlabClient<TyInner, TyReqBody>
 where
-  Cx: GetRef<UserAgent>,
-  Str: AsRef<str>,
-  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
+  Q: Endpoint,
+  Q::Context: GetRef<UserAgent>,
+  Q::Output: Send + 'static,
+  TyInner: Service<Request<TyReqBody>, Response = Response<TyBody>> + 'req,
+  TyReqBody: From<Bytes> + 'req,
   TyInner::Error: StdError,
   TyInner::Future: Send,
   TyBody: Body + Send,
   TyBody::Data: Send,
   TyBody::Error: StdError,
 {
-  type Response = Page<Release>;
+  type Response = Q::Output;
   type Error = HttpGitlabClientError;
   type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
 
@@ -447,53 +393,37 @@ where
       .map_err(|e| HttpGitlabClientError::PollReady(format!("{e:?}")))
   }
 
-  fn call(&mut self, req: &'req GetProjectReleaseListPageQuery<Cx, Str>) -> Self::Future {
-    let url: &str = req.cursor.as_ref();
-
-    let req = Request::builder()
-      .method(Method::GET)
-      .uri(url)
-      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
-      .gitlab_auth(req.auth.as_ref().map(GitlabAuth::as_view))
-      .body(Full::new(Bytes::new()))
-      .unwrap();
-
+  fn call(&mut self, req: &'req Q) -> Self::Future {
+    let req = match endpoint_request(req) {
+      Ok(req) => req.map(TyReqBody::from),
+      Err(e) => return Box::pin(futures::future::ready(Err(e))),
+    };
+    let info = RequestInfo::new(&req);
     let res = self.inner.call(req);
     Box::pin(async move {
       let res: Response<TyBody> = res.await.map_err(|e| HttpGitlabClientError::Send(format!("{e:?}")))?;
-      let cursors = get_cursors(res.headers());
-      let body = res
-        .into_body()
-        .collect()
-        .await
-        .map_err(|e| HttpGitlabClientError::Receive(format!("{e:?}")))?;
-      let body: Bytes = body.to_bytes();
-
-      let body: Vec<Release> =
-        serde_json::from_slice(&body).map_err(|e| HttpGitlabClientError::ResponseFormat(format!("{e:?}"), body))?;
-      Ok(Page {
-        first: cursors.first,
-        next: cursors.next,
-        last: cursors.last,
-        items: body,
-      })
+      read_response::<Q, _>(info, res).await
     })
   }
 }
 
-impl<'req, Cx, TyInner, TyBody> Service<&'req GetProjectReleaseQuery<Cx>> for HttpGitlabClient<TyInner>
+/// Publish a package file streamed from an [`UploadBody`], without buffering it
+impl<Cx, Str, TyInner, TyReqBody, TyBody> Service<PublishPackageFileCommand<Cx, Str, UploadBody>>
+  for HttpGitlabClient<TyInner, TyReqBody>
 where
   Cx: GetRef<GitlabUrl> + GetRef<UserAgent>,
-  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
+  Str: AsRef<str>,
+  TyInner: Service<Request<TyReqBody>, Response = Response<TyBody>>,
   TyInner::Error: StdError,
-  TyInner::Future: Send,
-  TyBody: Body + Send,
+  TyInner::Future: Send + 'static,
+  TyReqBody: From<UploadBody>,
+  TyBody: Body + Send + 'static,
   TyBody::Data: Send,
   TyBody::Error: StdError,
 {
-  type Response = Release;
+  type Response = GenericPackageFile;
   type Error = HttpGitlabClientError;
-  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
+  type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
 
   fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
     self
@@ -502,56 +432,35 @@ where
       .map_err(|e| HttpGitlabClientError::PollReady(format!("{e:?}")))
   }
 
-  fn call(&mut self, req: &'req GetProjectReleaseQuery<Cx>) -> Self::Future {
-    let url = req.project.with_str(|project| {
-      GetRef::<GitlabUrl>::get_ref(&req.context).url_join(["projects", project, "releases", &req.tag_name])
-    });
-    let req = Request::builder()
-      .method(Method::GET)
-      .uri(url.as_str())
-      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
-      .gitlab_auth(req.auth.as_ref().map(GitlabAuth::as_view))
-      .body(Full::new(Bytes::new()))
-      .unwrap();
-
+  fn call(&mut self, req: PublishPackageFileCommand<Cx, Str, UploadBody>) -> Self::Future {
+    let (req, data) = req.replace_data(Bytes::new());
+    let req = match endpoint_request(&req) {
+      Ok(req) => req.map(|_| TyReqBody::from(data)),
+      Err(e) => return Box::pin(futures::future::ready(Err(e))),
+    };
+    let info = RequestInfo::new(&req);
     let res = self.inner.call(req);
     Box::pin(async move {
       let res: Response<TyBody> = res.await.map_err(|e| HttpGitlabClientError::Send(format!("{e:?}")))?;
-      match res.status() {
-        StatusCode::OK => {
-          let body = res
-            .into_body()
-            .collect()
-            .await
-            .map_err(|e| HttpGitlabClientError::Receive(format!("{e:?}")))?;
-          let body: Bytes = body.to_bytes();
-
-          let body: Release =
-            serde_json::from_slice(&body).map_err(|e| HttpGitlabClientError::ResponseFormat(format!("{e:?}"), body))?;
-
-          Ok(body)
-        }
-        StatusCode::NOT_FOUND => Err(HttpGitlabClientError::NotFound),
-        code => Err(HttpGitlabClientError::Receive(format!(
-          "unexpected status code: {}",
-          code
-        ))),
-      }
+      read_response_with(info, res, decode_json::<GenericPackageFile>).await
     })
   }
 }
 
-impl<'req, Cx, TyInner, TyBody> Service<&'req PublishPackageFileCommand<Cx>> for HttpGitlabClient<TyInner>
+/// Download a package file as a stream, verifying its length and optional SHA-256 digest
+impl<'req, Cx, Str, TyInner, TyReqBody, TyBody> Service<&'req GetPackageFileStreamQuery<Cx, Str>>
+  for HttpGitlabClient<TyInner, TyReqBody>
 where
   Cx: GetRef<GitlabUrl> + GetRef<UserAgent>,
-  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
+  Str: AsRef<str>,
+  TyInner: Service<Request<TyReqBody>, Response = Response<TyBody>> + 'req,
   TyInner::Error: StdError,
   TyInner::Future: Send,
-  TyBody: Body + Send,
-  TyBody::Data: Send,
-  TyBody::Error: StdError,
+  TyReqBody: From<Bytes> + 'req,
+  TyBody: Body<Data = Bytes> + Send + 'static,
+  TyBody::Error: StdError + Send + Sync + 'static,
 {
-  type Response = GenericPackageFile;
+  type Response = DownloadStream;
   type Error = HttpGitlabClientError;
   type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
 
@@ -562,75 +471,37 @@ where
       .map_err(|e| HttpGitlabClientError::PollReady(format!("{e:?}")))
   }
 
-  fn call(&mut self, req: &'req PublishPackageFileCommand<Cx>) -> Self::Future {
-    let mut url = req.project.with_str(|project| {
-      GetRef::<GitlabUrl>::get_ref(&req.context).url_join([
-        "projects",
-        project,
-        "packages",
-        "generic",
-        &req.package_name,
-        &req.package_version,
-        &req.filename,
-      ])
-    });
-
+  fn call(&mut self, req: &'req GetPackageFileStreamQuery<Cx, Str>) -> Self::Future {
+    let sha256 = match req
+      .sha256
+      .as_ref()
+      .map(|hex| parse_sha256(hex.as_ref()).ok_or(hex.as_ref()))
     {
-      let mut q = url.query_pairs_mut();
-      q.append_pair(
-        "status",
-        match req.status {
-          InputPackageStatus::Default => "default",
-          InputPackageStatus::Hidden => "hidden",
-        },
-      );
-      q.append_pair("select", "package_file");
-    }
-
-    let req = Request::builder()
-      .method(Method::GET)
-      .uri(url.as_str())
-      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
-      .gitlab_auth(req.auth.as_ref().map(GitlabAuth::as_view))
-      .body(Full::new(Bytes::from(req.data.clone())))
-      .unwrap();
-
-    let res = self.inner.call(req);
-    Box::pin(async move {
-      let res: Response<TyBody> = res.await.map_err(|e| HttpGitlabClientError::Send(format!("{e:?}")))?;
-      match res.status() {
-        StatusCode::OK | StatusCode::CREATED => {
-          let body = res
-            .into_body()
-            .collect()
-            .await
-            .map_err(|e| HttpGitlabClientError::Receive(format!("{e:?}")))?;
-          let body: Bytes = body.to_bytes();
-          let body: GenericPackageFile =
-            serde_json::from_slice(&body).map_err(|e| HttpGitlabClientError::ResponseFormat(format!("{e:?}"), body))?;
-          Ok(body)
-        }
-        StatusCode::FORBIDDEN => Err(HttpGitlabClientError::Forbidden),
-        code => Err(HttpGitlabClientError::Receive(format!(
-          "unexpected status code: {}",
-          code
-        ))),
+      None => None,
+      Some(Ok(sha256)) => Some(sha256),
+      Some(Err(hex)) => {
+        let e = HttpGitlabClientError::Request(format!("invalid SHA-256 digest: {hex:?}"));
+        return Box::pin(futures::future::ready(Err(e)));
       }
-    })
+    };
+    download_stream(&mut self.inner, &req.as_file_query(), sha256)
   }
 }
 
-impl<'req, Cx, TyInner, TyBody> Service<&'req CreateReleaseCommand<Cx>> for HttpGitlabClient<TyInner>
+/// Download a raw repository file as a stream
+impl<'req, Cx, Str, TyInner, TyReqBody, TyBody> Service<&'req GetRawRepositoryFileStreamQuery<Cx, Str>>
+  for HttpGitlabClient<TyInner, TyReqBody>
 where
   Cx: GetRef<GitlabUrl> + GetRef<UserAgent>,
-  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
+  Str: AsRef<str>,
+  TyInner: Service<Request<TyReqBody>, Response = Response<TyBody>> + 'req,
   TyInner::Error: StdError,
   TyInner::Future: Send,
-  TyBody: Body + Send,
-  TyBody::Data: Send,
-  TyBody::Error: StdError,
+  TyReqBody: From<Bytes> + 'req,
+  TyBody: Body<Data = Bytes> + Send + 'static,
+  TyBody::Error: StdError + Send + Sync + 'static,
 {
-  type Response = Release;
+  type Response = DownloadStream;
   type Error = HttpGitlabClientError;
   type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
 
@@ -641,188 +512,94 @@ where
       .map_err(|e| HttpGitlabClientError::PollReady(format!("{e:?}")))
   }
 
-  fn call(&mut self, req: &'req CreateReleaseCommand<Cx>) -> Self::Future {
-    #[derive(Debug, serde::Serialize)]
-    struct Body<'r> {
-      name: Option<&'r str>,
-      tag_name: &'r str,
-      tag_message: Option<&'r str>,
-      description: Option<&'r str>,
-      r#ref: Option<&'r str>,
-      assets: InputReleaseAssetsView<'r>,
-      released_at: Option<DateTime<Utc>>,
-    }
-
-    let url = req
-      .project
-      .with_str(|project| GetRef::<GitlabUrl>::get_ref(&req.context).url_join(["projects", project, "releases"]));
-
-    let body = serde_json::to_vec(&Body {
-      name: req.name.as_deref(),
-      tag_name: req.tag_name.as_str(),
-      tag_message: req.tag_message.as_deref(),
-      description: req.description.as_deref(),
-      r#ref: req.r#ref.as_deref(),
-      assets: req.assets.as_view(),
-      released_at: req.released_at,
-    })
-    .unwrap();
-
-    let req = Request::builder()
-      .method(Method::GET)
-      .uri(url.as_str())
-      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
-      .gitlab_auth(req.auth.as_ref().map(GitlabAuth::as_view))
-      .header(CONTENT_TYPE, "application/json")
-      .body(Full::new(Bytes::from(body)))
-      .unwrap();
-
-    let res = self.inner.call(req);
-    Box::pin(async move {
-      let res: Response<TyBody> = res.await.map_err(|e| HttpGitlabClientError::Send(format!("{e:?}")))?;
-      match res.status() {
-        StatusCode::OK | StatusCode::CREATED => {
-          let body = res
-            .into_body()
-            .collect()
-            .await
-            .map_err(|e| HttpGitlabClientError::Receive(format!("{e:?}")))?;
-          let body: Bytes = body.to_bytes();
-          let body: Release =
-            serde_json::from_slice(&body).map_err(|e| HttpGitlabClientError::ResponseFormat(format!("{e:?}"), body))?;
-          Ok(body)
-        }
-        StatusCode::CONFLICT => Err(HttpGitlabClientError::Conflict),
-        StatusCode::FORBIDDEN => Err(HttpGitlabClientError::Forbidden),
-        code => Err(HttpGitlabClientError::Receive(format!(
-          "unexpected status code: {}",
-          code
-        ))),
-      }
-    })
+  fn call(&mut self, req: &'req GetRawRepositoryFileStreamQuery<Cx, Str>) -> Self::Future {
+    download_stream(&mut self.inner, &req.as_file_query(), None)
   }
 }
 
-impl<'req, Cx, TyInner, TyBody> Service<&'req CreateReleaseLinkCommand<Cx>> for HttpGitlabClient<TyInner>
+/// Send an endpoint request and stream the body of its response
+fn download_stream<'req, Q, TyInner, TyReqBody, TyBody>(
+  inner: &mut TyInner,
+  req: &Q,
+  sha256: Option<[u8; 32]>,
+) -> BoxFuture<'req, Result<DownloadStream, HttpGitlabClientError>>
 where
-  Cx: GetRef<GitlabUrl> + GetRef<UserAgent>,
-  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
+  Q: Endpoint,
+  Q::Context: GetRef<UserAgent>,
+  TyInner: Service<Request<TyReqBody>, Response = Response<TyBody>> + 'req,
   TyInner::Error: StdError,
-  TyInner::Future: Send,
-  TyBody: Body + Send,
-  TyBody::Data: Send,
-  TyBody::Error: StdError,
+  TyInner::Future: Send + 'req,
+  TyReqBody: From<Bytes>,
+  TyBody: Body<Data = Bytes> + Send + 'static,
+  TyBody::Error: StdError + Send + Sync + 'static,
 {
-  type Response = ReleaseLink;
-  type Error = HttpGitlabClientError;
-  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
-
-  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-    self
-      .inner
-      .poll_ready(cx)
-      .map_err(|e| HttpGitlabClientError::PollReady(format!("{e:?}")))
-  }
-
-  fn call(&mut self, req: &'req CreateReleaseLinkCommand<Cx>) -> Self::Future {
-    #[derive(Debug, serde::Serialize)]
-    struct Body<'r> {
-      name: &'r str,
-      url: &'r str,
-      direct_asset_path: Option<&'r str>,
-      link_type: &'r str,
-    }
-
-    let url = req.project.with_str(|project| {
-      GetRef::<GitlabUrl>::get_ref(&req.context).url_join([
-        "projects",
-        project,
-        "releases",
-        req.tag_name.as_str(),
-        "assets",
-        "links",
-      ])
-    });
-
-    let body = serde_json::to_vec(&Body {
-      name: req.name.as_str(),
-      url: req.url.as_str(),
-      direct_asset_path: req.direct_asset_path.as_deref(),
-      link_type: req.link_type.as_str(),
-    })
-    .unwrap();
-
-    let req = Request::builder()
-      .method(Method::GET)
-      .uri(url.as_str())
-      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
-      .gitlab_auth(req.auth.as_ref().map(GitlabAuth::as_view))
-      .header(CONTENT_TYPE, "application/json")
-      .body(Full::new(Bytes::from(body)))
-      .unwrap();
+  let req = match endpoint_request(req) {
+    Ok(req) => req.map(TyReqBody::from),
+    Err(e) => return Box::pin(futures::future::ready(Err(e))),
+  };
+  let info = RequestInfo::new(&req);
+  let res = inner.call(req);
+  Box::pin(async move {
+    let res: Response<TyBody> = res.await.map_err(|e| HttpGitlabClientError::Send(format!("{e:?}")))?;
+    let res = check_status(info, res).await?;
+    let len = res
+      .headers()
+      .get(CONTENT_LENGTH)
+      .and_then(|len| len.to_str().ok())
+      .and_then(|len| len.parse::<u64>().ok());
+    Ok(DownloadStream::new(res.into_body(), len, sha256))
+  })
+}
 
-    let res = self.inner.call(req);
-    Box::pin(async move {
-      let res: Response<TyBody> = res.await.map_err(|e| HttpGitlabClientError::Send(format!("{e:?}")))?;
-      match res.status() {
-        StatusCode::OK | StatusCode::CREATED => {
-          let body = res
-            .into_body()
-            .collect()
-            .await
-            .map_err(|e| HttpGitlabClientError::Receive(format!("{e:?}")))?;
-          let body: Bytes = body.to_bytes();
-          let body: ReleaseLink =
-            serde_json::from_slice(&body).map_err(|e| HttpGitlabClientError::ResponseFormat(format!("{e:?}"), body))?;
-          Ok(body)
-        }
-        StatusCode::FORBIDDEN => Err(HttpGitlabClientError::Forbidden),
-        code => Err(HttpGitlabClientError::Receive(format!(
-          "unexpected status code: {}",
-          code
-        ))),
-      }
-    })
+/// Build the HTTP request calling an endpoint
+pub(crate) fn endpoint_request<Q>(req: &Q) -> Result<Request<Bytes>, HttpGitlabClientError>
+where
+  Q: Endpoint,
+  Q::Context: GetRef<UserAgent>,
+{
+  let url = endpoint_url(req).map_err(|e| HttpGitlabClientError::Request(format!("{e:?}")))?;
+  let body = req.body();
+  let mut builder = Request::builder()
+    .method(Q::METHOD)
+    .uri(url.as_str())
+    .user_agent(GetRef::<UserAgent>::get_ref(req.context()))
+    .gitlab_auth(req.auth());
+  if let Some(content_type) = body.content_type() {
+    builder = builder.header(CONTENT_TYPE, content_type);
   }
+  builder
+    .body(body.into_bytes())
+    .map_err(|e| HttpGitlabClientError::Request(format!("{e:?}")))
 }
 
-struct Cursors<Str> {
-  first: Option<Str>,
-  next: Option<Str>,
-  last: Option<Str>,
+/// Check the status of an endpoint response, then decode it
+pub(crate) async fn read_response<Q, B>(info: RequestInfo, res: Response<B>) -> Result<Q::Output, HttpGitlabClientError>
+where
+  Q: Endpoint,
+  B: Body,
+  B::Error: StdError,
+{
+  read_response_with(info, res, Q::decode).await.map_err(Q::map_error)
 }
 
-fn get_cursors(headers: &HeaderMap) -> Cursors<CompactString> {
-  use demurgos_headers::HeaderMapExt;
-
-  let mut next: Option<CompactString> = None;
-  let mut first: Option<CompactString> = None;
-  let mut last: Option<CompactString> = None;
-
-  let links: Option<Link> = headers.typed_get::<Link>();
-
-  if let Some(links) = links {
-    for value in links.values() {
-      let rel = match value.rel() {
-        Some(rel) => rel,
-        None => continue,
-      };
-      for r in rel {
-        // todo: detect when there are multiple different links for the same rel type
-        if *r == RelationType::NEXT {
-          next = Some(CompactString::new(value.link()));
-        }
-        if *r == RelationType::FIRST {
-          first = Some(CompactString::new(value.link()));
-        }
-        if *r == RelationType::LAST {
-          last = Some(CompactString::new(value.link()));
-        }
-      }
-    }
-  }
-
-  Cursors { first, next, last }
+/// Check the status of a response, then decode it with `decode`
+pub(crate) async fn read_response_with<T, B>(
+  info: RequestInfo,
+  res: Response<B>,
+  decode: fn(Response<Bytes>) -> Result<T, DecodeError>,
+) -> Result<T, HttpGitlabClientError>
+where
+  B: Body,
+  B::Error: StdError,
+{
+  let res = check_status(info, res).await?;
+  let (parts, body) = res.into_parts();
+  let body = body
+    .collect()
+    .await
+    .map_err(|e| HttpGitlabClientError::Receive(format!("{e:?}")))?;
+  let res = Response::from_parts(parts, body.to_bytes());
+  decode(res).map_err(|e| HttpGitlabClientError::ResponseFormat(e.message, e.body))
 }
 
 trait RequestBuilderExt {
@@ -851,16 +628,271 @@ impl RequestBuilderExt for http::request::Builder {
   }
 }
 
-trait BoolExt {
-  fn as_str(&self) -> &'static str;
+pub(crate) mod serde_method {
+  use http::Method;
+  use serde::{Deserialize, Deserializer, Serializer};
+
+  pub(crate) fn serialize<S: Serializer>(value: &Method, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(value.as_str())
+  }
+
+  pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Method, D::Error> {
+    let value = std::borrow::Cow::<'de, str>::deserialize(deserializer)?;
+    Method::from_bytes(value.as_bytes()).map_err(serde::de::Error::custom)
+  }
+}
+
+mod serde_uri {
+  use http::Uri;
+  use serde::{Deserialize, Deserializer, Serializer};
+
+  pub(super) fn serialize<S: Serializer>(value: &Uri, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+  }
+
+  pub(super) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Uri, D::Error> {
+    let value = std::borrow::Cow::<'de, str>::deserialize(deserializer)?;
+    value.parse().map_err(serde::de::Error::custom)
+  }
 }
 
-impl BoolExt for bool {
-  fn as_str(&self) -> &'static str {
-    if *self {
-      "true"
-    } else {
-      "false"
+mod serde_status {
+  use http::StatusCode;
+  use serde::{Deserialize, Deserializer, Serializer};
+
+  pub(super) fn serialize<S: Serializer>(value: &StatusCode, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u16(value.as_u16())
+  }
+
+  pub(super) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<StatusCode, D::Error> {
+    let value = u16::deserialize(deserializer)?;
+    StatusCode::from_u16(value).map_err(serde::de::Error::custom)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::context::GitlabUrl;
+  use crate::query::get_project::GetProjectQuery;
+  use chrono::TimeZone;
+
+  /// Transport always returning the same response
+  struct FakeTransport {
+    status: StatusCode,
+    body: &'static str,
+  }
+
+  impl Service<Request<Full<Bytes>>> for FakeTransport {
+    type Response = Response<Full<Bytes>>;
+    type Error = std::convert::Infallible;
+    type Future = futures::future::Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+      Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _req: Request<Full<Bytes>>) -> Self::Future {
+      let res = Response::builder()
+        .status(self.status)
+        .header("X-Request-Id", "01JXQ5Z5Y8")
+        .body(Full::new(Bytes::from_static(self.body.as_bytes())))
+        .unwrap();
+      futures::future::ready(Ok(res))
     }
   }
+
+  /// Transport recording the last request, and always responding with `404 Not Found`
+  #[derive(Default)]
+  struct RecordingTransport {
+    last: Option<Request<Full<Bytes>>>,
+  }
+
+  impl Service<Request<Full<Bytes>>> for RecordingTransport {
+    type Response = Response<Full<Bytes>>;
+    type Error = std::convert::Infallible;
+    type Future = futures::future::Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+      Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<Full<Bytes>>) -> Self::Future {
+      self.last = Some(req);
+      let res = Response::builder()
+        .status(StatusCode::NOT_FOUND)
+        .body(Full::new(Bytes::from_static(br#"{"message":"404 Not Found"}"#)))
+        .unwrap();
+      futures::future::ready(Ok(res))
+    }
+  }
+
+  fn context() -> crate::context::Context<GitlabUrl, UserAgent> {
+    crate::context::Context::new()
+      .set_gitlab_url(GitlabUrl(url::Url::parse("https://gitlab.example.com/").unwrap()))
+      .set_user_agent(UserAgent::from_static("gitlab_client_test/0.0.0"))
+  }
+
+  #[test]
+  fn get_project_not_found() {
+    use crate::common::error::GitlabErrorMessage;
+    use crate::common::project::{ProjectRef, ProjectSlug};
+    use crate::ProjectClient;
+
+    let mut client = HttpGitlabClient::new(FakeTransport {
+      status: StatusCode::NOT_FOUND,
+      body: r#"{"message":"404 Project Not Found"}"#,
+    });
+    let query = GetProjectQuery::<_>::new(ProjectRef::Slug(ProjectSlug::new(CompactString::new("group/project"))))
+      .set_context(context());
+    let actual = futures::executor::block_on(client.get_project(&query)).unwrap_err();
+    let res = match &actual {
+      HttpGitlabClientError::NotFound(res) => res,
+      e => panic!("unexpected error: {e:?}"),
+    };
+    assert_eq!(res.method, Method::GET);
+    assert_eq!(res.uri.path(), "/api/v4/projects/group%2Fproject");
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+    assert_eq!(res.request_id.as_deref(), Some("01JXQ5Z5Y8"));
+    assert_eq!(
+      res.error.as_ref().and_then(|e| e.message.as_ref()),
+      Some(&GitlabErrorMessage::Text(CompactString::new("404 Project Not Found")))
+    );
+  }
+
+  #[test]
+  fn map_error_status() {
+    let cases = [
+      (StatusCode::CONFLICT, r#"{"message":"Release already exists"}"#),
+      (StatusCode::UNAUTHORIZED, r#"{"message":"401 Unauthorized"}"#),
+      (
+        StatusCode::UNPROCESSABLE_ENTITY,
+        r#"{"message":{"released_at":["is invalid"]}}"#,
+      ),
+    ];
+    for (status, body) in cases {
+      let info = RequestInfo {
+        method: Method::POST,
+        uri: Uri::from_static("https://gitlab.example.com/api/v4/projects/1/releases"),
+      };
+      let res = Response::builder()
+        .status(status)
+        .body(Full::new(Bytes::from_static(body.as_bytes())))
+        .unwrap();
+      let actual = futures::executor::block_on(check_status(info, res)).unwrap_err();
+      assert_eq!(actual.status(), Some(status));
+      match status {
+        StatusCode::CONFLICT => assert!(matches!(actual, HttpGitlabClientError::Conflict(_))),
+        StatusCode::UNAUTHORIZED => assert!(matches!(actual, HttpGitlabClientError::Unauthorized(_))),
+        _ => {
+          assert!(matches!(actual, HttpGitlabClientError::Invalid(_)));
+          let fields = actual.response().and_then(ResponseError::field_errors).unwrap();
+          assert_eq!(fields["released_at"], vec![CompactString::new("is invalid")]);
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn order_response_errors() {
+    let error = |method: Method, uri: &'static str, status: StatusCode| ResponseError {
+      method,
+      uri: Uri::from_static(uri),
+      status,
+      request_id: None,
+      error: None,
+      body: Bytes::new(),
+    };
+    let lower = error(
+      Method::GET,
+      "https://gitlab.example.com/api/v4/projects/1",
+      StatusCode::NOT_FOUND,
+    );
+    let upper = error(
+      Method::GET,
+      "https://GITLAB.example.com/api/v4/projects/1",
+      StatusCode::NOT_FOUND,
+    );
+    assert_eq!(lower, upper);
+    assert_eq!(lower.cmp(&upper), Ordering::Equal);
+
+    let errors = std::collections::BTreeSet::from([
+      HttpGitlabClientError::NotFound(Box::new(error(
+        Method::GET,
+        "https://gitlab.example.com/api/v4/projects/2",
+        StatusCode::NOT_FOUND,
+      ))),
+      HttpGitlabClientError::Other(String::from("boom")),
+      HttpGitlabClientError::NotFound(Box::new(lower)),
+      HttpGitlabClientError::NotFound(Box::new(upper)),
+    ]);
+    let actual = Vec::from_iter(errors.iter().map(|err| err.response().map(|res| res.uri.path())));
+    assert_eq!(actual, [Some("/api/v4/projects/1"), Some("/api/v4/projects/2"), None]);
+  }
+
+  #[test]
+  fn read_rate_limit_headers() {
+    let now = Utc.with_ymd_and_hms(2025, 6, 16, 12, 0, 0).unwrap();
+    let mut headers = HeaderMap::new();
+    headers.insert("RateLimit-Remaining", "0".parse().unwrap());
+    headers.insert(
+      "RateLimit-Reset",
+      now.timestamp().saturating_add(42).to_string().parse().unwrap(),
+    );
+    let actual = RateLimit::from_headers(&headers, now);
+    assert_eq!(
+      actual,
+      RateLimit {
+        retry_after: None,
+        remaining: Some(0),
+        reset: Some(Utc.with_ymd_and_hms(2025, 6, 16, 12, 0, 42).unwrap()),
+      }
+    );
+    assert_eq!(actual.retry_delay(now), Some(Duration::from_secs(42)));
+
+    headers.insert("Retry-After", "7".parse().unwrap());
+    let actual = RateLimit::from_headers(&headers, now);
+    assert_eq!(actual.retry_delay(now), Some(Duration::from_secs(7)));
+
+    headers.insert("Retry-After", "Mon, 16 Jun 2025 12:01:00 GMT".parse().unwrap());
+    let actual = RateLimit::from_headers(&headers, now);
+    assert_eq!(actual.retry_delay(now), Some(Duration::from_secs(60)));
+  }
+
+  #[test]
+  fn create_release_request() {
+    use crate::command::create_release::CreateReleaseCommand;
+    use crate::common::project::{ProjectId, ProjectRef};
+    use crate::common::release::InputReleaseAssets;
+    use crate::GitlabAuth;
+
+    let mut client = HttpGitlabClient::new(RecordingTransport::default());
+    let command = CreateReleaseCommand {
+      context: context(),
+      auth: Some(GitlabAuth::PrivateToken(CompactString::new("secret"))),
+      project: ProjectRef::Id(ProjectId::new(1)),
+      tag_name: CompactString::new("v1.0.0"),
+      name: Some(CompactString::new("Version 1.0.0")),
+      tag_message: None,
+      description: None,
+      r#ref: None,
+      milestones: Vec::new(),
+      assets: InputReleaseAssets { links: Vec::new() },
+      released_at: None,
+    };
+    let actual = futures::executor::block_on(crate::call(&mut client, &command));
+    assert!(matches!(actual, Err(HttpGitlabClientError::NotFound(_))));
+
+    let req = client.inner.last.take().unwrap();
+    assert_eq!(req.method(), Method::POST);
+    assert_eq!(req.uri(), "https://gitlab.example.com/api/v4/projects/1/releases");
+    assert_eq!(req.headers()[CONTENT_TYPE], "application/json");
+    assert_eq!(req.headers()["private-token"], "secret");
+    let body = futures::executor::block_on(req.into_body().collect())
+      .unwrap()
+      .to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["tag_name"], "v1.0.0");
+    assert_eq!(body["name"], "Version 1.0.0");
+  }
 }
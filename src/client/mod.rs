@@ -1,4 +1,9 @@
 #[cfg(feature = "http")]
+pub mod cassette;
+#[cfg(feature = "testing")]
+pub mod fake;
+#[cfg(feature = "http")]
 pub mod http;
 #[cfg(feature = "reqwest")]
 pub mod reqwest;
+pub mod retry;
@@ -1,12 +1,29 @@
+use crate::body::{parse_sha256, BoxError, DownloadStream, UploadBody};
+use crate::client::http::{
+  check_status, endpoint_request, read_response, read_response_with, HttpGitlabClientError, RequestInfo,
+};
+use crate::command::publish_package_file::PublishPackageFileCommand;
+use crate::common::package::GenericPackageFile;
 use crate::context::{GetRef, GitlabUrl};
-use crate::query::get_project_list::GetProjectListQueryView;
-use crate::url_util::UrlExt;
-use crate::Project;
-use core::task::{Context, Poll};
+use crate::endpoint::{decode_json, Endpoint};
+use crate::query::get_package_file_stream::GetPackageFileStreamQuery;
+use crate::query::get_raw_repository_file_stream::GetRawRepositoryFileStreamQuery;
+use bytes::Bytes;
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+use demurgos_headers::UserAgent;
 use futures::future::BoxFuture;
-use reqwest::{Method, Request, Response};
+use futures::{Stream, TryStreamExt};
+use http_body::{Body, Frame};
+use http_body_util::{Full, StreamBody};
+use std::sync::{Mutex, PoisonError};
 use tower_service::Service;
+use url::Url;
 
+/// GitLab client backed by a `reqwest` service (e.g. `reqwest::Client`)
+///
+/// The requests and error mapping are the same as [`HttpGitlabClient`](crate::client::http::HttpGitlabClient).
+#[derive(Debug, Clone)]
 pub struct ReqwestGitlabClient<TyInner> {
   inner: TyInner,
 }
@@ -17,30 +34,18 @@ impl<TyInner> ReqwestGitlabClient<TyInner> {
   }
 }
 
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, thiserror::Error)]
-pub enum ReqwestGitlabClientError {
-  #[error("failed to poll ready status: {0}")]
-  PollReady(String),
-  #[error("failed to send request: {0}")]
-  Send(String),
-  #[error("failed to receive response: {0}")]
-  Receive(String),
-  #[error("failed to parse response with body = {1}: {0}")]
-  ResponseFormat(String, String),
-  #[error("operation is forbidden for provided auth")]
-  Forbidden,
-  #[error("unexpected error: {0}")]
-  Other(String),
-}
+/// Error returned by [`ReqwestGitlabClient`], shared with the `http` backend
+pub type ReqwestGitlabClientError = HttpGitlabClientError;
 
-impl<'req, ExtraInput, TyInner> Service<GetProjectListQueryView<'req, ExtraInput>> for ReqwestGitlabClient<TyInner>
+impl<'req, Q, TyInner> Service<&'req Q> for ReqwestGitlabClient<TyInner>
 where
-  ExtraInput: GetRef<GitlabUrl>,
-  TyInner: Service<Request, Response = Response, Error = reqwest::Error> + 'req,
+  Q: Endpoint,
+  Q::Context: GetRef<UserAgent>,
+  Q::Output: Send + 'static,
+  TyInner: Service<reqwest::Request, Response = reqwest::Response, Error = reqwest::Error> + 'req,
   TyInner::Future: Send,
 {
-  type Response = Vec<Project>;
+  type Response = Q::Output;
   type Error = ReqwestGitlabClientError;
   type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
 
@@ -48,42 +53,227 @@ where
     self
       .inner
       .poll_ready(cx)
-      .map_err(|e| ReqwestGitlabClientError::PollReady(format!("{e:?}")))
+      .map_err(|e| HttpGitlabClientError::PollReady(format!("{e:?}")))
+  }
+
+  fn call(&mut self, req: &'req Q) -> Self::Future {
+    let (info, req) = match reqwest_endpoint_request(req) {
+      Ok(req) => req,
+      Err(e) => return Box::pin(futures::future::ready(Err(e))),
+    };
+    let res = self.inner.call(req);
+    Box::pin(async move {
+      let res: reqwest::Response = res.await.map_err(|e| HttpGitlabClientError::Send(format!("{e:?}")))?;
+      let res = from_reqwest_response(res).await?;
+      read_response::<Q, _>(info, res).await
+    })
+  }
+}
+
+/// Publish a package file streamed from an [`UploadBody`], without buffering it
+impl<Cx, Str, TyInner> Service<PublishPackageFileCommand<Cx, Str, UploadBody>> for ReqwestGitlabClient<TyInner>
+where
+  Cx: GetRef<GitlabUrl> + GetRef<UserAgent>,
+  Str: AsRef<str>,
+  TyInner: Service<reqwest::Request, Response = reqwest::Response, Error = reqwest::Error>,
+  TyInner::Future: Send + 'static,
+{
+  type Response = GenericPackageFile;
+  type Error = ReqwestGitlabClientError;
+  type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self
+      .inner
+      .poll_ready(cx)
+      .map_err(|e| HttpGitlabClientError::PollReady(format!("{e:?}")))
   }
 
-  fn call(&mut self, req: GetProjectListQueryView<'req, ExtraInput>) -> Self::Future {
-    let req = Request::new(Method::GET, req.context.get_ref().url_join(["projects"]));
+  fn call(&mut self, req: PublishPackageFileCommand<Cx, Str, UploadBody>) -> Self::Future {
+    let (req, data) = req.replace_data(Bytes::new());
+    let (info, mut req) = match reqwest_endpoint_request(&req) {
+      Ok(req) => req,
+      Err(e) => return Box::pin(futures::future::ready(Err(e))),
+    };
+    if let Some(len) = data.len() {
+      req.headers_mut().insert(reqwest::header::CONTENT_LENGTH, len.into());
+    }
+    *req.body_mut() = Some(reqwest::Body::wrap_stream(UploadStream(Mutex::new(data))));
     let res = self.inner.call(req);
     Box::pin(async move {
-      let res: Response = res
-        .await
-        .map_err(|e| ReqwestGitlabClientError::Send(format!("{e:?}")))?;
-      let body = res
-        .text()
-        .await
-        .map_err(|e| ReqwestGitlabClientError::Receive(format!("{e:?}")))?;
-
-      let body: Vec<Project> =
-        serde_json::from_str(&body).map_err(|e| ReqwestGitlabClientError::ResponseFormat(format!("{e:?}"), body))?;
-      Ok(body)
+      let res: reqwest::Response = res.await.map_err(|e| HttpGitlabClientError::Send(format!("{e:?}")))?;
+      let res = from_reqwest_response(res).await?;
+      read_response_with(info, res, decode_json::<GenericPackageFile>).await
     })
   }
 }
 
-// impl<TyInner> Service<PublishPackageFileRequest> for ReqwestGitlabClient<TyInner>
-//   where
-//     TyInner: Service<Request, Response=Response, Error=reqwest::Error>,
-//     TyInner::Future: Send,
-// {
-//   type Response = GenericPackageFile;
-//   type Error = ReqwestGitlabClientError;
-//   type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
-//
-//   fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-//     self.inner.poll_ready(cx).map_err(|e| ReqwestGitlabClientError::PollReady(format!("{e:?}")))
-//   }
-//
-//   fn call(&mut self, req: PublishPackageFileRequest) -> Self::Future {
-//     todo!()
-//   }
-// }
+/// Download a package file as a stream, verifying its length and optional SHA-256 digest
+impl<'req, Cx, Str, TyInner> Service<&'req GetPackageFileStreamQuery<Cx, Str>> for ReqwestGitlabClient<TyInner>
+where
+  Cx: GetRef<GitlabUrl> + GetRef<UserAgent>,
+  Str: AsRef<str>,
+  TyInner: Service<reqwest::Request, Response = reqwest::Response, Error = reqwest::Error> + 'req,
+  TyInner::Future: Send,
+{
+  type Response = DownloadStream;
+  type Error = ReqwestGitlabClientError;
+  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self
+      .inner
+      .poll_ready(cx)
+      .map_err(|e| HttpGitlabClientError::PollReady(format!("{e:?}")))
+  }
+
+  fn call(&mut self, req: &'req GetPackageFileStreamQuery<Cx, Str>) -> Self::Future {
+    let sha256 = match req
+      .sha256
+      .as_ref()
+      .map(|hex| parse_sha256(hex.as_ref()).ok_or(hex.as_ref()))
+    {
+      None => None,
+      Some(Ok(sha256)) => Some(sha256),
+      Some(Err(hex)) => {
+        let e = HttpGitlabClientError::Request(format!("invalid SHA-256 digest: {hex:?}"));
+        return Box::pin(futures::future::ready(Err(e)));
+      }
+    };
+    download_stream(&mut self.inner, &req.as_file_query(), sha256)
+  }
+}
+
+/// Download a raw repository file as a stream
+impl<'req, Cx, Str, TyInner> Service<&'req GetRawRepositoryFileStreamQuery<Cx, Str>> for ReqwestGitlabClient<TyInner>
+where
+  Cx: GetRef<GitlabUrl> + GetRef<UserAgent>,
+  Str: AsRef<str>,
+  TyInner: Service<reqwest::Request, Response = reqwest::Response, Error = reqwest::Error> + 'req,
+  TyInner::Future: Send,
+{
+  type Response = DownloadStream;
+  type Error = ReqwestGitlabClientError;
+  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self
+      .inner
+      .poll_ready(cx)
+      .map_err(|e| HttpGitlabClientError::PollReady(format!("{e:?}")))
+  }
+
+  fn call(&mut self, req: &'req GetRawRepositoryFileStreamQuery<Cx, Str>) -> Self::Future {
+    download_stream(&mut self.inner, &req.as_file_query(), None)
+  }
+}
+
+/// Send an endpoint request and stream the body of its response
+fn download_stream<'req, Q, TyInner>(
+  inner: &mut TyInner,
+  req: &Q,
+  sha256: Option<[u8; 32]>,
+) -> BoxFuture<'req, Result<DownloadStream, HttpGitlabClientError>>
+where
+  Q: Endpoint,
+  Q::Context: GetRef<UserAgent>,
+  TyInner: Service<reqwest::Request, Response = reqwest::Response, Error = reqwest::Error> + 'req,
+  TyInner::Future: Send + 'req,
+{
+  let (info, req) = match reqwest_endpoint_request(req) {
+    Ok(req) => req,
+    Err(e) => return Box::pin(futures::future::ready(Err(e))),
+  };
+  let res = inner.call(req);
+  Box::pin(async move {
+    let res: reqwest::Response = res.await.map_err(|e| HttpGitlabClientError::Send(format!("{e:?}")))?;
+    let len = res.content_length();
+    let res = check_status(info, from_reqwest_response_stream(res)?).await?;
+    Ok(DownloadStream::new(res.into_body(), len, sha256))
+  })
+}
+
+/// Stream of the chunks of an [`UploadBody`]
+///
+/// `reqwest::Body::wrap_stream` requires a `Sync` stream: the body is only accessed through `&mut`, so the mutex is
+/// never locked.
+struct UploadStream(Mutex<UploadBody>);
+
+impl Stream for UploadStream {
+  type Item = Result<Bytes, BoxError>;
+
+  fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+    let body = self.get_mut().0.get_mut().unwrap_or_else(PoisonError::into_inner);
+    loop {
+      match ready!(Pin::new(&mut *body).poll_frame(cx)) {
+        None => return Poll::Ready(None),
+        Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+        Some(Ok(frame)) => {
+          if let Ok(data) = frame.into_data() {
+            return Poll::Ready(Some(Ok(data)));
+          }
+        }
+      }
+    }
+  }
+}
+
+/// Build the `reqwest` request calling an endpoint
+fn reqwest_endpoint_request<Q>(req: &Q) -> Result<(RequestInfo, reqwest::Request), HttpGitlabClientError>
+where
+  Q: Endpoint,
+  Q::Context: GetRef<UserAgent>,
+{
+  let req = endpoint_request(req)?;
+  let info = RequestInfo::new(&req);
+  to_reqwest_request(req).map(|req| (info, req))
+}
+
+/// Convert a request to the `http` 0.2 types used by `reqwest`
+fn to_reqwest_request(req: http::Request<Bytes>) -> Result<reqwest::Request, HttpGitlabClientError> {
+  let (parts, body) = req.into_parts();
+  let invalid = |e: &dyn core::fmt::Debug| HttpGitlabClientError::Request(format!("{e:?}"));
+  let method = reqwest::Method::from_bytes(parts.method.as_str().as_bytes()).map_err(|e| invalid(&e))?;
+  let url = Url::parse(&parts.uri.to_string()).map_err(|e| invalid(&e))?;
+  let mut res = reqwest::Request::new(method, url);
+  for (name, value) in &parts.headers {
+    let name = reqwest::header::HeaderName::from_bytes(name.as_str().as_bytes()).map_err(|e| invalid(&e))?;
+    let value = reqwest::header::HeaderValue::from_bytes(value.as_bytes()).map_err(|e| invalid(&e))?;
+    res.headers_mut().append(name, value);
+  }
+  if !body.is_empty() {
+    *res.body_mut() = Some(reqwest::Body::from(body));
+  }
+  Ok(res)
+}
+
+/// Read a `reqwest` response into the `http` 1.x types
+async fn from_reqwest_response(res: reqwest::Response) -> Result<http::Response<Full<Bytes>>, HttpGitlabClientError> {
+  let builder = reqwest_response_builder(&res);
+  let body = res
+    .bytes()
+    .await
+    .map_err(|e| HttpGitlabClientError::Receive(format!("{e:?}")))?;
+  builder
+    .body(Full::new(body))
+    .map_err(|e| HttpGitlabClientError::Receive(format!("{e:?}")))
+}
+
+/// Convert a `reqwest` response into the `http` 1.x types, streaming its body
+fn from_reqwest_response_stream(
+  res: reqwest::Response,
+) -> Result<http::Response<impl Body<Data = Bytes, Error = reqwest::Error> + Send + 'static>, HttpGitlabClientError> {
+  let builder = reqwest_response_builder(&res);
+  builder
+    .body(StreamBody::new(res.bytes_stream().map_ok(Frame::data)))
+    .map_err(|e| HttpGitlabClientError::Receive(format!("{e:?}")))
+}
+
+/// Response builder with the status and headers of a `reqwest` response
+fn reqwest_response_builder(res: &reqwest::Response) -> http::response::Builder {
+  let mut builder = http::Response::builder().status(res.status().as_u16());
+  for (name, value) in res.headers() {
+    builder = builder.header(name.as_str(), value.as_bytes());
+  }
+  builder
+}
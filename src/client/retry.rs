@@ -0,0 +1,658 @@
+use crate::command::accept_merge_request::AcceptMergeRequestCommand;
+use crate::command::approve_merge_request::ApproveMergeRequestCommand;
+use crate::command::cherry_pick_commit::CherryPickCommitCommand;
+use crate::command::create_branch::CreateBranchCommand;
+use crate::command::create_commit::CreateCommitCommand;
+use crate::command::create_discussion::CreateDiscussionCommand;
+use crate::command::create_group_milestone::CreateGroupMilestoneCommand;
+use crate::command::create_merge_request::CreateMergeRequestCommand;
+use crate::command::create_merge_request_approval_rule::CreateMergeRequestApprovalRuleCommand;
+use crate::command::create_note::CreateNoteCommand;
+use crate::command::create_project_approval_rule::CreateProjectApprovalRuleCommand;
+use crate::command::create_project_milestone::CreateProjectMilestoneCommand;
+use crate::command::create_release::CreateReleaseCommand;
+use crate::command::create_release_link::CreateReleaseLinkCommand;
+use crate::command::create_repository_file::CreateRepositoryFileCommand;
+use crate::command::create_tag::CreateTagCommand;
+use crate::command::delete_branch::DeleteBranchCommand;
+use crate::command::delete_group_milestone::DeleteGroupMilestoneCommand;
+use crate::command::delete_merge_request_approval_rule::DeleteMergeRequestApprovalRuleCommand;
+use crate::command::delete_merged_branches::DeleteMergedBranchesCommand;
+use crate::command::delete_note::DeleteNoteCommand;
+use crate::command::delete_project_approval_rule::DeleteProjectApprovalRuleCommand;
+use crate::command::delete_project_milestone::DeleteProjectMilestoneCommand;
+use crate::command::delete_release::DeleteReleaseCommand;
+use crate::command::delete_release_link::DeleteReleaseLinkCommand;
+use crate::command::delete_repository_file::DeleteRepositoryFileCommand;
+use crate::command::delete_tag::DeleteTagCommand;
+use crate::command::protect_branch::ProtectBranchCommand;
+use crate::command::protect_tag::ProtectTagCommand;
+use crate::command::publish_package_file::PublishPackageFileCommand;
+use crate::command::rebase_merge_request::RebaseMergeRequestCommand;
+use crate::command::reset_merge_request_approvals::ResetMergeRequestApprovalsCommand;
+use crate::command::resolve_discussion::ResolveDiscussionCommand;
+use crate::command::revert_commit::RevertCommitCommand;
+use crate::command::unapprove_merge_request::UnapproveMergeRequestCommand;
+use crate::command::unprotect_branch::UnprotectBranchCommand;
+use crate::command::unprotect_tag::UnprotectTagCommand;
+use crate::command::update_group_milestone::UpdateGroupMilestoneCommand;
+use crate::command::update_merge_request::UpdateMergeRequestCommand;
+use crate::command::update_merge_request_approval_rule::UpdateMergeRequestApprovalRuleCommand;
+use crate::command::update_note::UpdateNoteCommand;
+use crate::command::update_project_approval_rule::UpdateProjectApprovalRuleCommand;
+use crate::command::update_project_milestone::UpdateProjectMilestoneCommand;
+use crate::command::update_protected_branch::UpdateProtectedBranchCommand;
+use crate::command::update_release::UpdateReleaseCommand;
+use crate::command::update_release_link::UpdateReleaseLinkCommand;
+use crate::command::update_repository_file::UpdateRepositoryFileCommand;
+use crate::query::get_branch::GetBranchQuery;
+use crate::query::get_branch_list::GetBranchListQuery;
+use crate::query::get_branch_list_page::GetBranchListPageQuery;
+use crate::query::get_commit::GetCommitQuery;
+use crate::query::get_commit_comment_list::GetCommitCommentListQuery;
+use crate::query::get_commit_comment_list_page::GetCommitCommentListPageQuery;
+use crate::query::get_commit_diff::GetCommitDiffQuery;
+use crate::query::get_commit_list::GetCommitListQuery;
+use crate::query::get_commit_list_page::GetCommitListPageQuery;
+use crate::query::get_commit_ref_list::GetCommitRefListQuery;
+use crate::query::get_commit_ref_list_page::GetCommitRefListPageQuery;
+use crate::query::get_diff_list_page::GetDiffListPageQuery;
+use crate::query::get_discussion::GetDiscussionQuery;
+use crate::query::get_discussion_list::GetDiscussionListQuery;
+use crate::query::get_discussion_list_page::GetDiscussionListPageQuery;
+use crate::query::get_group_merge_request_list::GetGroupMergeRequestListQuery;
+use crate::query::get_group_milestone::GetGroupMilestoneQuery;
+use crate::query::get_group_milestone_list::GetGroupMilestoneListQuery;
+use crate::query::get_merge_request::GetMergeRequestQuery;
+use crate::query::get_merge_request_approval_rule::GetMergeRequestApprovalRuleQuery;
+use crate::query::get_merge_request_approval_rule_list::GetMergeRequestApprovalRuleListQuery;
+use crate::query::get_merge_request_approval_rule_list_page::GetMergeRequestApprovalRuleListPageQuery;
+use crate::query::get_merge_request_approval_state::GetMergeRequestApprovalStateQuery;
+use crate::query::get_merge_request_approvals::GetMergeRequestApprovalsQuery;
+use crate::query::get_merge_request_changes::GetMergeRequestChangesQuery;
+use crate::query::get_merge_request_commit_list::GetMergeRequestCommitListQuery;
+use crate::query::get_merge_request_diff_list::GetMergeRequestDiffListQuery;
+use crate::query::get_merge_request_diff_version::GetMergeRequestDiffVersionQuery;
+use crate::query::get_merge_request_diff_version_list::GetMergeRequestDiffVersionListQuery;
+use crate::query::get_merge_request_diff_version_list_page::GetMergeRequestDiffVersionListPageQuery;
+use crate::query::get_merge_request_list::GetMergeRequestListQuery;
+use crate::query::get_merge_request_list_page::GetMergeRequestListPageQuery;
+use crate::query::get_milestone_list_page::GetMilestoneListPageQuery;
+use crate::query::get_note::GetNoteQuery;
+use crate::query::get_note_list::GetNoteListQuery;
+use crate::query::get_note_list_page::GetNoteListPageQuery;
+use crate::query::get_package_file::GetPackageFileQuery;
+use crate::query::get_package_file_list::GetPackageFileListQuery;
+use crate::query::get_package_file_list_page::GetPackageFileListPageQuery;
+use crate::query::get_project::GetProjectQuery;
+use crate::query::get_project_approval_rule::GetProjectApprovalRuleQuery;
+use crate::query::get_project_approval_rule_list::GetProjectApprovalRuleListQuery;
+use crate::query::get_project_approval_rule_list_page::GetProjectApprovalRuleListPageQuery;
+use crate::query::get_project_list::GetProjectListQuery;
+use crate::query::get_project_list_page::GetProjectListPageQuery;
+use crate::query::get_project_merge_request_list::GetProjectMergeRequestListQuery;
+use crate::query::get_project_milestone::GetProjectMilestoneQuery;
+use crate::query::get_project_milestone_list::GetProjectMilestoneListQuery;
+use crate::query::get_project_package_list::GetProjectPackageListQuery;
+use crate::query::get_project_package_list_page::GetProjectPackageListPageQuery;
+use crate::query::get_project_release::GetProjectReleaseQuery;
+use crate::query::get_project_release_list::GetProjectReleaseListQuery;
+use crate::query::get_project_release_list_page::GetProjectReleaseListPageQuery;
+use crate::query::get_protected_branch::GetProtectedBranchQuery;
+use crate::query::get_protected_branch_list::GetProtectedBranchListQuery;
+use crate::query::get_protected_branch_list_page::GetProtectedBranchListPageQuery;
+use crate::query::get_protected_tag::GetProtectedTagQuery;
+use crate::query::get_protected_tag_list::GetProtectedTagListQuery;
+use crate::query::get_protected_tag_list_page::GetProtectedTagListPageQuery;
+use crate::query::get_raw_repository_file::GetRawRepositoryFileQuery;
+use crate::query::get_release_link::GetReleaseLinkQuery;
+use crate::query::get_release_link_list::GetReleaseLinkListQuery;
+use crate::query::get_repository_file::GetRepositoryFileQuery;
+use crate::query::get_tag::GetTagQuery;
+use crate::query::get_tag_list::GetTagListQuery;
+use crate::query::get_tag_list_page::GetTagListPageQuery;
+use crate::query::get_tag_signature::GetTagSignatureQuery;
+use crate::query::get_tree_record_list::GetTreeRecordListQuery;
+use core::future::Future;
+use core::task::{Context, Poll};
+use core::time::Duration;
+use futures::future::BoxFuture;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use tower_service::Service;
+
+/// Client wrapper retrying failed requests according to a [`RetryPolicy`].
+///
+/// Each retry is sent to a clone of the inner service, after waiting for the delay returned by the policy.
+#[derive(Debug, Clone)]
+pub struct RetryGitlabClient<TyInner, TyPolicy, TySleep> {
+  inner: TyInner,
+  policy: TyPolicy,
+  sleep: TySleep,
+}
+
+impl<TyInner, TyPolicy, TySleep> RetryGitlabClient<TyInner, TyPolicy, TySleep> {
+  pub fn new(inner: TyInner, policy: TyPolicy, sleep: TySleep) -> Self {
+    Self { inner, policy, sleep }
+  }
+}
+
+/// Retry safety of an operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OperationKind {
+  /// Read-only operation
+  Query,
+  /// Write operation where sending the same request multiple times has the same effect as sending it once
+  IdempotentCommand,
+  /// Write operation that must not be replayed blindly
+  Command,
+}
+
+/// Classification of queries and commands, used by retry policies.
+pub trait RetryOperation {
+  const KIND: OperationKind;
+}
+
+/// Transient failure reported by a client
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RetryReason {
+  /// The request was rejected by the rate limiter (HTTP 429), it was not processed.
+  ///
+  /// `retry_after` is the delay requested by the server, if any.
+  RateLimited { retry_after: Option<Duration> },
+  /// The server failed to handle the request (HTTP 5xx).
+  Server,
+  /// The request could not be sent, or the connection failed.
+  Connection,
+}
+
+/// Client error that may be caused by a transient failure.
+pub trait RetryableError {
+  /// Returns the reason why the request may be retried, or `None` if the error is permanent.
+  fn retry_reason(&self) -> Option<RetryReason>;
+}
+
+/// Policy deciding if and when a failed request should be retried.
+pub trait RetryPolicy {
+  /// Returns the delay before the next attempt, or `None` to give up.
+  ///
+  /// `attempt` is the number of retries already performed for this request.
+  fn retry_delay(&self, attempt: u32, kind: OperationKind, reason: RetryReason) -> Option<Duration>;
+}
+
+/// Exponential backoff with jitter.
+///
+/// - Rate-limited requests are retried for all operations since the server did not process them. The delay
+///   requested by the server is used when available; the request is not retried if it exceeds `max_delay`.
+/// - Server and connection failures are only retried for queries and idempotent commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ExponentialBackoff {
+  /// Maximum number of retries for a single request
+  pub max_retries: u32,
+  /// Delay before the first retry, doubled on each attempt
+  pub base_delay: Duration,
+  /// Upper bound for the delay before a retry, including the delay requested by the server
+  pub max_delay: Duration,
+}
+
+impl ExponentialBackoff {
+  pub const fn new() -> Self {
+    Self {
+      max_retries: 3,
+      base_delay: Duration::from_millis(500),
+      max_delay: Duration::from_secs(30),
+    }
+  }
+
+  /// Backoff delay for the provided attempt, with "equal jitter": half of the delay is fixed, the other half is random.
+  pub fn backoff(&self, attempt: u32) -> Duration {
+    let factor = 1u32.checked_shl(attempt).unwrap_or(u32::MAX);
+    let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+    let half = delay / 2;
+    half + half.mul_f64(random_unit())
+  }
+}
+
+impl Default for ExponentialBackoff {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl RetryPolicy for ExponentialBackoff {
+  fn retry_delay(&self, attempt: u32, kind: OperationKind, reason: RetryReason) -> Option<Duration> {
+    if attempt >= self.max_retries {
+      return None;
+    }
+    match reason {
+      RetryReason::RateLimited { retry_after } => match retry_after {
+        Some(retry_after) if retry_after > self.max_delay => None,
+        Some(retry_after) => Some(retry_after),
+        None => Some(self.backoff(attempt)),
+      },
+      RetryReason::Server | RetryReason::Connection => match kind {
+        OperationKind::Query | OperationKind::IdempotentCommand => Some(self.backoff(attempt)),
+        OperationKind::Command => None,
+      },
+    }
+  }
+}
+
+/// Returns a random value in `[0, 1]`
+fn random_unit() -> f64 {
+  // `RandomState` is randomly seeded on each call, this is enough for jitter
+  let random = RandomState::new().build_hasher().finish();
+  (random as f64) / (u64::MAX as f64)
+}
+
+/// Timer used to wait between attempts.
+///
+/// This trait is implemented for functions such as `tokio::time::sleep`.
+pub trait Sleep {
+  type Future: Future<Output = ()>;
+
+  fn sleep(&self, duration: Duration) -> Self::Future;
+}
+
+impl<F, Fut> Sleep for F
+where
+  F: Fn(Duration) -> Fut,
+  Fut: Future<Output = ()>,
+{
+  type Future = Fut;
+
+  fn sleep(&self, duration: Duration) -> Self::Future {
+    (self)(duration)
+  }
+}
+
+impl<'req, Req, TyInner, TyResponse, TyError, TyFuture, TyPolicy, TySleep> Service<&'req Req>
+  for RetryGitlabClient<TyInner, TyPolicy, TySleep>
+where
+  Req: RetryOperation + Sync,
+  TyInner: Service<&'req Req, Response = TyResponse, Error = TyError, Future = TyFuture> + Clone + Send + 'req,
+  TyResponse: Send + 'req,
+  TyError: RetryableError + Send + 'req,
+  TyFuture: Future<Output = Result<TyResponse, TyError>> + Send + 'req,
+  TyPolicy: RetryPolicy + Clone + Send + 'req,
+  TySleep: Sleep + Clone + Send + 'req,
+  TySleep::Future: Send + 'req,
+{
+  type Response = TyResponse;
+  type Error = TyError;
+  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self.inner.poll_ready(cx)
+  }
+
+  fn call(&mut self, req: &'req Req) -> Self::Future {
+    let res = self.inner.call(req);
+    let mut inner = self.inner.clone();
+    let policy = self.policy.clone();
+    let sleep = self.sleep.clone();
+    Box::pin(async move {
+      let mut res = res.await;
+      let mut attempt: u32 = 0;
+      loop {
+        let err = match res {
+          Ok(res) => return Ok(res),
+          Err(e) => e,
+        };
+        let delay = err
+          .retry_reason()
+          .and_then(|reason| policy.retry_delay(attempt, Req::KIND, reason));
+        let delay = match delay {
+          Some(delay) => delay,
+          None => return Err(err),
+        };
+        sleep.sleep(delay).await;
+        attempt += 1;
+        res = match futures::future::poll_fn(|cx| inner.poll_ready(cx)).await {
+          Ok(()) => inner.call(req).await,
+          Err(e) => Err(e),
+        };
+      }
+    })
+  }
+}
+
+macro_rules! retry_operation {
+  ($kind:ident: $($ty:ident),* $(,)?) => {
+    $(
+      impl<Cx, Str> RetryOperation for $ty<Cx, Str> {
+        const KIND: OperationKind = OperationKind::$kind;
+      }
+    )*
+  };
+}
+
+retry_operation!(
+  Query: GetBranchQuery,
+  GetBranchListQuery,
+  GetBranchListPageQuery,
+  GetCommitQuery,
+  GetCommitCommentListQuery,
+  GetCommitCommentListPageQuery,
+  GetCommitDiffQuery,
+  GetCommitListQuery,
+  GetCommitListPageQuery,
+  GetCommitRefListQuery,
+  GetCommitRefListPageQuery,
+  GetDiffListPageQuery,
+  GetDiscussionQuery,
+  GetDiscussionListQuery,
+  GetDiscussionListPageQuery,
+  GetGroupMergeRequestListQuery,
+  GetGroupMilestoneQuery,
+  GetGroupMilestoneListQuery,
+  GetMergeRequestQuery,
+  GetMergeRequestApprovalRuleQuery,
+  GetMergeRequestApprovalRuleListQuery,
+  GetMergeRequestApprovalRuleListPageQuery,
+  GetMergeRequestApprovalStateQuery,
+  GetMergeRequestApprovalsQuery,
+  GetMergeRequestChangesQuery,
+  GetMergeRequestCommitListQuery,
+  GetMergeRequestDiffListQuery,
+  GetMergeRequestDiffVersionQuery,
+  GetMergeRequestDiffVersionListQuery,
+  GetMergeRequestDiffVersionListPageQuery,
+  GetMergeRequestListQuery,
+  GetMergeRequestListPageQuery,
+  GetMilestoneListPageQuery,
+  GetNoteQuery,
+  GetNoteListQuery,
+  GetNoteListPageQuery,
+  GetPackageFileQuery,
+  GetPackageFileListQuery,
+  GetPackageFileListPageQuery,
+  GetProjectQuery,
+  GetProjectApprovalRuleQuery,
+  GetProjectApprovalRuleListQuery,
+  GetProjectApprovalRuleListPageQuery,
+  GetProjectListQuery,
+  GetProjectListPageQuery,
+  GetProjectMergeRequestListQuery,
+  GetProjectPackageListQuery,
+  GetProjectMilestoneQuery,
+  GetProjectMilestoneListQuery,
+  GetProjectPackageListPageQuery,
+  GetProjectReleaseQuery,
+  GetProjectReleaseListQuery,
+  GetProjectReleaseListPageQuery,
+  GetProtectedBranchQuery,
+  GetProtectedBranchListQuery,
+  GetProtectedBranchListPageQuery,
+  GetProtectedTagQuery,
+  GetProtectedTagListQuery,
+  GetProtectedTagListPageQuery,
+  GetRawRepositoryFileQuery,
+  GetReleaseLinkQuery,
+  GetReleaseLinkListQuery,
+  GetRepositoryFileQuery,
+  GetTagQuery,
+  GetTagListQuery,
+  GetTagListPageQuery,
+  GetTagSignatureQuery,
+  GetTreeRecordListQuery,
+);
+retry_operation!(
+  IdempotentCommand: DeleteMergedBranchesCommand,
+  ResetMergeRequestApprovalsCommand,
+  ResolveDiscussionCommand,
+  UpdateGroupMilestoneCommand,
+  UpdateMergeRequestCommand,
+  UpdateMergeRequestApprovalRuleCommand,
+  UpdateNoteCommand,
+  UpdateProjectApprovalRuleCommand,
+  UpdateProjectMilestoneCommand,
+  UpdateReleaseCommand,
+  UpdateReleaseLinkCommand,
+);
+retry_operation!(
+  Command: AcceptMergeRequestCommand,
+  ApproveMergeRequestCommand,
+  CherryPickCommitCommand,
+  CreateBranchCommand,
+  CreateDiscussionCommand,
+  CreateGroupMilestoneCommand,
+  CreateMergeRequestCommand,
+  CreateMergeRequestApprovalRuleCommand,
+  CreateNoteCommand,
+  CreateProjectApprovalRuleCommand,
+  CreateProjectMilestoneCommand,
+  CreateReleaseLinkCommand,
+  CreateTagCommand,
+  DeleteBranchCommand,
+  DeleteGroupMilestoneCommand,
+  DeleteMergeRequestApprovalRuleCommand,
+  DeleteNoteCommand,
+  DeleteProjectApprovalRuleCommand,
+  DeleteProjectMilestoneCommand,
+  DeleteReleaseCommand,
+  DeleteReleaseLinkCommand,
+  DeleteRepositoryFileCommand,
+  DeleteTagCommand,
+  ProtectBranchCommand,
+  ProtectTagCommand,
+  RebaseMergeRequestCommand,
+  RevertCommitCommand,
+  UnapproveMergeRequestCommand,
+  UnprotectBranchCommand,
+  UnprotectTagCommand,
+);
+
+/// Entries added to the access lists are not deduplicated, so updates are not idempotent
+impl<Cx, Str> RetryOperation for UpdateProtectedBranchCommand<Cx, Str> {
+  const KIND: OperationKind = OperationKind::Command;
+}
+
+impl<Cx, Str, Assets> RetryOperation for CreateReleaseCommand<Cx, Str, Assets> {
+  const KIND: OperationKind = OperationKind::Command;
+}
+
+impl<Cx, Str, Bytes> RetryOperation for CreateCommitCommand<Cx, Str, Bytes> {
+  const KIND: OperationKind = OperationKind::Command;
+}
+
+impl<Cx, Str, Bytes> RetryOperation for CreateRepositoryFileCommand<Cx, Str, Bytes> {
+  const KIND: OperationKind = OperationKind::Command;
+}
+
+/// Unlike other updates, each call adds a commit to the branch so it is not idempotent
+impl<Cx, Str, Bytes> RetryOperation for UpdateRepositoryFileCommand<Cx, Str, Bytes> {
+  const KIND: OperationKind = OperationKind::Command;
+}
+
+impl<Cx, Str, Bytes, const SELECT: bool> RetryOperation for PublishPackageFileCommand<Cx, Str, Bytes, SELECT> {
+  const KIND: OperationKind = OperationKind::Command;
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use futures::future::{ready, Ready};
+  use std::collections::VecDeque;
+  use std::sync::{Arc, Mutex};
+
+  struct FakeQuery;
+
+  impl RetryOperation for FakeQuery {
+    const KIND: OperationKind = OperationKind::Query;
+  }
+
+  struct FakeCommand;
+
+  impl RetryOperation for FakeCommand {
+    const KIND: OperationKind = OperationKind::Command;
+  }
+
+  #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+  enum FakeError {
+    Transient(RetryReason),
+    Permanent,
+  }
+
+  impl RetryableError for FakeError {
+    fn retry_reason(&self) -> Option<RetryReason> {
+      match self {
+        Self::Transient(reason) => Some(*reason),
+        Self::Permanent => None,
+      }
+    }
+  }
+
+  /// Service returning scripted results, shared across clones
+  #[derive(Clone, Default)]
+  struct ScriptedService {
+    script: Arc<Mutex<VecDeque<Result<u32, FakeError>>>>,
+    calls: Arc<Mutex<u32>>,
+  }
+
+  impl ScriptedService {
+    fn new(script: impl IntoIterator<Item = Result<u32, FakeError>>) -> Self {
+      Self {
+        script: Arc::new(Mutex::new(script.into_iter().collect())),
+        calls: Arc::new(Mutex::new(0)),
+      }
+    }
+
+    fn calls(&self) -> u32 {
+      *self.calls.lock().unwrap()
+    }
+
+    fn next(&self) -> Ready<Result<u32, FakeError>> {
+      *self.calls.lock().unwrap() += 1;
+      ready(self.script.lock().unwrap().pop_front().expect("unexpected call"))
+    }
+  }
+
+  impl<'req> Service<&'req FakeQuery> for ScriptedService {
+    type Response = u32;
+    type Error = FakeError;
+    type Future = Ready<Result<u32, FakeError>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+      Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _req: &'req FakeQuery) -> Self::Future {
+      self.next()
+    }
+  }
+
+  impl<'req> Service<&'req FakeCommand> for ScriptedService {
+    type Response = u32;
+    type Error = FakeError;
+    type Future = Ready<Result<u32, FakeError>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+      Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _req: &'req FakeCommand) -> Self::Future {
+      self.next()
+    }
+  }
+
+  /// Timer recording the requested delays without waiting
+  #[derive(Clone, Default)]
+  struct FakeSleep {
+    delays: Arc<Mutex<Vec<Duration>>>,
+  }
+
+  impl Sleep for FakeSleep {
+    type Future = Ready<()>;
+
+    fn sleep(&self, duration: Duration) -> Self::Future {
+      self.delays.lock().unwrap().push(duration);
+      ready(())
+    }
+  }
+
+  fn client(
+    inner: ScriptedService,
+    sleep: FakeSleep,
+  ) -> RetryGitlabClient<ScriptedService, ExponentialBackoff, FakeSleep> {
+    RetryGitlabClient::new(inner, ExponentialBackoff::new(), sleep)
+  }
+
+  #[test]
+  fn retry_query_on_server_error() {
+    let inner = ScriptedService::new([
+      Err(FakeError::Transient(RetryReason::Server)),
+      Err(FakeError::Transient(RetryReason::Connection)),
+      Ok(42),
+    ]);
+    let sleep = FakeSleep::default();
+    let mut client = client(inner.clone(), sleep.clone());
+    let actual = futures::executor::block_on(crate::call(&mut client, &FakeQuery));
+    assert_eq!(actual, Ok(42));
+    assert_eq!(inner.calls(), 3);
+    let delays = sleep.delays.lock().unwrap().clone();
+    assert_eq!(delays.len(), 2);
+    assert!(Duration::from_millis(250) <= delays[0] && delays[0] <= Duration::from_millis(500));
+    assert!(Duration::from_millis(500) <= delays[1] && delays[1] <= Duration::from_millis(1000));
+  }
+
+  #[test]
+  fn honour_retry_after() {
+    let inner = ScriptedService::new([
+      Err(FakeError::Transient(RetryReason::RateLimited {
+        retry_after: Some(Duration::from_secs(7)),
+      })),
+      Ok(42),
+    ]);
+    let sleep = FakeSleep::default();
+    let mut client = client(inner.clone(), sleep.clone());
+    let actual = futures::executor::block_on(crate::call(&mut client, &FakeCommand));
+    assert_eq!(actual, Ok(42));
+    assert_eq!(*sleep.delays.lock().unwrap(), vec![Duration::from_secs(7)]);
+  }
+
+  #[test]
+  fn give_up_when_retry_after_exceeds_max_delay() {
+    let inner = ScriptedService::new([Err(FakeError::Transient(RetryReason::RateLimited {
+      retry_after: Some(Duration::from_secs(3600)),
+    }))]);
+    let sleep = FakeSleep::default();
+    let mut client = client(inner.clone(), sleep.clone());
+    let actual = futures::executor::block_on(crate::call(&mut client, &FakeQuery));
+    assert_eq!(
+      actual,
+      Err(FakeError::Transient(RetryReason::RateLimited {
+        retry_after: Some(Duration::from_secs(3600)),
+      }))
+    );
+    assert_eq!(inner.calls(), 1);
+    assert!(sleep.delays.lock().unwrap().is_empty());
+  }
+
+  #[test]
+  fn do_not_retry_command_on_server_error() {
+    let inner = ScriptedService::new([Err(FakeError::Transient(RetryReason::Server))]);
+    let mut client = client(inner.clone(), FakeSleep::default());
+    let actual = futures::executor::block_on(crate::call(&mut client, &FakeCommand));
+    assert_eq!(actual, Err(FakeError::Transient(RetryReason::Server)));
+    assert_eq!(inner.calls(), 1);
+  }
+
+  #[test]
+  fn do_not_retry_permanent_error() {
+    let inner = ScriptedService::new([Err(FakeError::Permanent)]);
+    let mut client = client(inner.clone(), FakeSleep::default());
+    let actual = futures::executor::block_on(crate::call(&mut client, &FakeQuery));
+    assert_eq!(actual, Err(FakeError::Permanent));
+    assert_eq!(inner.calls(), 1);
+  }
+
+  #[test]
+  fn give_up_after_max_retries() {
+    let inner = ScriptedService::new([Err(FakeError::Transient(RetryReason::Server)); 4]);
+    let mut client = client(inner.clone(), FakeSleep::default());
+    let actual = futures::executor::block_on(crate::call(&mut client, &FakeQuery));
+    assert_eq!(actual, Err(FakeError::Transient(RetryReason::Server)));
+    assert_eq!(inner.calls(), 4);
+  }
+}
@@ -0,0 +1,127 @@
+#[cfg(feature = "http")]
+use crate::client::http::HttpGitlabClientError;
+#[cfg(feature = "http")]
+use crate::common::merge_request::MergeRequest;
+use crate::common::merge_request::MergeRequestIid;
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Merge a merge request
+///
+/// GitLab reports why the merge was refused through the status of the response. These responses are reported as
+/// `HttpGitlabClientError::MergeFailed`, telling whether the merge request is not mergeable, conflicts with the target
+/// branch, or changed since `sha`.
+///
+/// <https://docs.gitlab.com/ee/api/merge_requests.html#merge-a-merge-request>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AcceptMergeRequestCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub merge_request_iid: MergeRequestIid,
+  /// Only merge if this is the head of the source branch
+  pub sha: Option<Str>,
+  /// Squash the commits when merging, overriding the setting of the merge request
+  pub squash: Option<bool>,
+  /// Delete the source branch after the merge
+  pub should_remove_source_branch: Option<bool>,
+  pub merge_commit_message: Option<Str>,
+  pub squash_commit_message: Option<Str>,
+  /// Merge once the pipeline succeeds instead of immediately
+  pub auto_merge: Option<bool>,
+}
+
+pub type AcceptMergeRequestCommandView<'req, Cx> = AcceptMergeRequestCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> AcceptMergeRequestCommand<Cx, Str> {
+  pub fn as_view(&self) -> AcceptMergeRequestCommandView<'_, Cx> {
+    AcceptMergeRequestCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      merge_request_iid: self.merge_request_iid,
+      sha: self.sha.as_ref().map(AsRef::as_ref),
+      squash: self.squash,
+      should_remove_source_branch: self.should_remove_source_branch,
+      merge_commit_message: self.merge_commit_message.as_ref().map(AsRef::as_ref),
+      squash_commit_message: self.squash_commit_message.as_ref().map(AsRef::as_ref),
+      auto_merge: self.auto_merge,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for AcceptMergeRequestCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = MergeRequest;
+
+  const METHOD: http::Method = http::Method::PUT;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.merge_request_iid.with_str(|merge_request_iid| {
+        self
+          .context
+          .get_ref()
+          .url_join(["projects", project, "merge_requests", merge_request_iid, "merge"])
+      })
+    }))
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      #[serde(skip_serializing_if = "Option::is_none")]
+      sha: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      squash: Option<bool>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      should_remove_source_branch: Option<bool>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      merge_commit_message: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      squash_commit_message: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      auto_merge: Option<bool>,
+    }
+
+    EndpointBody::json(&Body {
+      sha: self.sha.as_ref().map(AsRef::as_ref),
+      squash: self.squash,
+      should_remove_source_branch: self.should_remove_source_branch,
+      merge_commit_message: self.merge_commit_message.as_ref().map(AsRef::as_ref),
+      squash_commit_message: self.squash_commit_message.as_ref().map(AsRef::as_ref),
+      auto_merge: self.auto_merge,
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+
+  fn map_error(err: HttpGitlabClientError) -> HttpGitlabClientError {
+    err.classify_merge_failure()
+  }
+}
@@ -0,0 +1,92 @@
+#[cfg(feature = "http")]
+use crate::common::approval::MergeRequestApprovals;
+use crate::common::merge_request::MergeRequestIid;
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Approve a merge request as the current user
+///
+/// GitLab responds with `409 Conflict` if `sha` is not the head of the source branch, and with
+/// `401 Unauthorized` if the user already approved the merge request or is not allowed to approve it.
+///
+/// <https://docs.gitlab.com/ee/api/merge_request_approvals.html#approve-merge-request>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ApproveMergeRequestCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub merge_request_iid: MergeRequestIid,
+  /// Only approve if this is the head of the source branch
+  pub sha: Option<Str>,
+}
+
+pub type ApproveMergeRequestCommandView<'req, Cx> = ApproveMergeRequestCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> ApproveMergeRequestCommand<Cx, Str> {
+  pub fn as_view(&self) -> ApproveMergeRequestCommandView<'_, Cx> {
+    ApproveMergeRequestCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      merge_request_iid: self.merge_request_iid,
+      sha: self.sha.as_ref().map(AsRef::as_ref),
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for ApproveMergeRequestCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = MergeRequestApprovals;
+
+  const METHOD: http::Method = http::Method::POST;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.merge_request_iid.with_str(|merge_request_iid| {
+        self
+          .context
+          .get_ref()
+          .url_join(["projects", project, "merge_requests", merge_request_iid, "approve"])
+      })
+    }))
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      #[serde(skip_serializing_if = "Option::is_none")]
+      sha: Option<&'r str>,
+    }
+
+    EndpointBody::json(&Body {
+      sha: self.sha.as_ref().map(AsRef::as_ref),
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
@@ -0,0 +1,99 @@
+#[cfg(feature = "http")]
+use crate::common::commit::Commit;
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Cherry-pick a commit into a branch
+///
+/// If the changes cannot be applied, the request fails with a `400 Bad Request` error whose
+/// `ResponseError::commit_apply_failure` tells whether they conflict or are already applied.
+///
+/// <https://docs.gitlab.com/ee/api/commits.html#cherry-pick-a-commit>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CherryPickCommitCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  /// Commit hash, or branch or tag name
+  pub sha: Str,
+  /// Branch receiving the cherry-picked commit
+  pub branch: Str,
+  /// Message of the new commit (default: the message of the cherry-picked commit)
+  pub message: Option<Str>,
+}
+
+pub type CherryPickCommitCommandView<'req, Cx> = CherryPickCommitCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> CherryPickCommitCommand<Cx, Str> {
+  pub fn as_view(&self) -> CherryPickCommitCommandView<'_, Cx> {
+    CherryPickCommitCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      sha: self.sha.as_ref(),
+      branch: self.branch.as_ref(),
+      message: self.message.as_ref().map(AsRef::as_ref),
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for CherryPickCommitCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Commit;
+
+  const METHOD: http::Method = http::Method::POST;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.context.get_ref().url_join([
+        "projects",
+        project,
+        "repository",
+        "commits",
+        self.sha.as_ref(),
+        "cherry_pick",
+      ])
+    }))
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      branch: &'r str,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      message: Option<&'r str>,
+    }
+
+    EndpointBody::json(&Body {
+      branch: self.branch.as_ref(),
+      message: self.message.as_ref().map(AsRef::as_ref),
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
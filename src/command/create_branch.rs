@@ -0,0 +1,88 @@
+#[cfg(feature = "http")]
+use crate::common::branch::Branch;
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Create a repository branch
+///
+/// <https://docs.gitlab.com/ee/api/branches.html#create-repository-branch>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CreateBranchCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  /// Branch name
+  pub branch: Str,
+  /// Branch name or commit to create the branch from
+  pub r#ref: Str,
+}
+
+pub type CreateBranchCommandView<'req, Cx> = CreateBranchCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> CreateBranchCommand<Cx, Str> {
+  pub fn as_view(&self) -> CreateBranchCommandView<'_, Cx> {
+    CreateBranchCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      branch: self.branch.as_ref(),
+      r#ref: self.r#ref.as_ref(),
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for CreateBranchCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Branch;
+
+  const METHOD: http::Method = http::Method::POST;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self
+        .context
+        .get_ref()
+        .url_join(["projects", project, "repository", "branches"])
+    }))
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      branch: &'r str,
+      r#ref: &'r str,
+    }
+
+    EndpointBody::json(&Body {
+      branch: self.branch.as_ref(),
+      r#ref: self.r#ref.as_ref(),
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
@@ -0,0 +1,210 @@
+use crate::common::commit::CommitAction;
+#[cfg(feature = "http")]
+use crate::common::commit::{Commit, CommitActionView, CommitContent, CommitContentView};
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Create a commit applying several file actions at once
+///
+/// The commit is atomic: if any action fails, no change is committed.
+///
+/// <https://docs.gitlab.com/ee/api/commits.html#create-a-commit-with-multiple-files-and-actions>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CreateCommitCommand<Cx, Str = CompactString, Bytes = Vec<u8>> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  /// Branch receiving the commit
+  pub branch: Str,
+  pub commit_message: Str,
+  /// Create `branch` from this branch if it does not exist yet
+  pub start_branch: Option<Str>,
+  /// Create `branch` from this commit if it does not exist yet
+  pub start_sha: Option<Str>,
+  pub actions: Vec<CommitAction<Str, Bytes>>,
+  pub author_email: Option<Str>,
+  pub author_name: Option<Str>,
+  /// Include the commit stats in the response (GitLab defaults to `true`)
+  pub stats: Option<bool>,
+  /// Overwrite `branch` with a new commit based on `start_branch` or `start_sha`
+  pub force: Option<bool>,
+}
+
+pub type CreateCommitCommandView<'req, Cx> = CreateCommitCommand<&'req Cx, &'req str, &'req [u8]>;
+
+impl<Cx, Str: AsRef<str>, Bytes: AsRef<[u8]>> CreateCommitCommand<Cx, Str, Bytes> {
+  pub fn as_view(&self) -> CreateCommitCommandView<'_, Cx> {
+    CreateCommitCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      branch: self.branch.as_ref(),
+      commit_message: self.commit_message.as_ref(),
+      start_branch: self.start_branch.as_ref().map(AsRef::as_ref),
+      start_sha: self.start_sha.as_ref().map(AsRef::as_ref),
+      actions: Vec::from_iter(self.actions.iter().map(CommitAction::as_view)),
+      author_email: self.author_email.as_ref().map(AsRef::as_ref),
+      author_name: self.author_name.as_ref().map(AsRef::as_ref),
+      stats: self.stats,
+      force: self.force,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str, Bytes> Endpoint for CreateCommitCommand<Cx, Str, Bytes>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+  Bytes: AsRef<[u8]>,
+{
+  type Context = Cx;
+  type Output = Commit;
+
+  const METHOD: http::Method = http::Method::POST;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self
+        .context
+        .get_ref()
+        .url_join(["projects", project, "repository", "commits"])
+    }))
+  }
+
+  fn body(&self) -> EndpointBody {
+    use base64::Engine;
+    use std::borrow::Cow;
+
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      branch: &'r str,
+      commit_message: &'r str,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      start_branch: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      start_sha: Option<&'r str>,
+      actions: Vec<Action<'r>>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      author_email: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      author_name: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      stats: Option<bool>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      force: Option<bool>,
+    }
+
+    #[derive(Debug, serde::Serialize)]
+    struct Action<'r> {
+      action: &'static str,
+      file_path: &'r str,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      previous_path: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      content: Option<Cow<'r, str>>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      encoding: Option<&'static str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      last_commit_id: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      execute_filemode: Option<bool>,
+    }
+
+    impl<'r> Action<'r> {
+      fn new(action: &'r CommitActionView<'r>) -> Self {
+        let mut body = Self {
+          action: action.as_str(),
+          file_path: action.file_path(),
+          previous_path: None,
+          content: None,
+          encoding: None,
+          last_commit_id: None,
+          execute_filemode: None,
+        };
+        match *action {
+          CommitAction::Create {
+            content,
+            execute_filemode,
+            ..
+          } => {
+            body.set_content(content);
+            body.execute_filemode = execute_filemode;
+          }
+          CommitAction::Delete { last_commit_id, .. } => body.last_commit_id = last_commit_id,
+          CommitAction::Move {
+            previous_path,
+            content,
+            last_commit_id,
+            ..
+          } => {
+            body.previous_path = Some(previous_path);
+            if let Some(content) = content {
+              body.set_content(content);
+            }
+            body.last_commit_id = last_commit_id;
+          }
+          CommitAction::Update {
+            content,
+            last_commit_id,
+            execute_filemode,
+            ..
+          } => {
+            body.set_content(content);
+            body.last_commit_id = last_commit_id;
+            body.execute_filemode = execute_filemode;
+          }
+          CommitAction::Chmod { execute_filemode, .. } => body.execute_filemode = Some(execute_filemode),
+        }
+        body
+      }
+
+      fn set_content(&mut self, content: CommitContentView<'r>) {
+        let (content, encoding) = match content {
+          CommitContent::Text(text) => (Cow::Borrowed(text), "text"),
+          CommitContent::Base64(bytes) => (
+            Cow::Owned(base64::engine::general_purpose::STANDARD.encode(bytes)),
+            "base64",
+          ),
+        };
+        self.content = Some(content);
+        self.encoding = Some(encoding);
+      }
+    }
+
+    let view = self.as_view();
+    EndpointBody::json(&Body {
+      branch: self.branch.as_ref(),
+      commit_message: self.commit_message.as_ref(),
+      start_branch: self.start_branch.as_ref().map(AsRef::as_ref),
+      start_sha: self.start_sha.as_ref().map(AsRef::as_ref),
+      actions: Vec::from_iter(view.actions.iter().map(Action::new)),
+      author_email: self.author_email.as_ref().map(AsRef::as_ref),
+      author_name: self.author_name.as_ref().map(AsRef::as_ref),
+      stats: self.stats,
+      force: self.force,
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
@@ -0,0 +1,91 @@
+#[cfg(feature = "http")]
+use crate::common::note::Discussion;
+#[cfg(feature = "http")]
+use crate::common::note::PositionView;
+use crate::common::note::{NoteableRef, Position};
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Start a discussion thread on an issue, merge request, commit, snippet or epic
+///
+/// Set `position` to attach the thread to lines of the diff of a merge request or commit.
+///
+/// <https://docs.gitlab.com/ee/api/discussions.html#create-new-merge-request-thread>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CreateDiscussionCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub noteable: NoteableRef<Str>,
+  pub body: Str,
+  pub position: Option<Position<Str>>,
+}
+
+pub type CreateDiscussionCommandView<'req, Cx> = CreateDiscussionCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> CreateDiscussionCommand<Cx, Str> {
+  pub fn as_view(&self) -> CreateDiscussionCommandView<'_, Cx> {
+    CreateDiscussionCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      noteable: self.noteable.as_view(),
+      body: self.body.as_ref(),
+      position: self.position.as_ref().map(Position::as_view),
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for CreateDiscussionCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Discussion;
+
+  const METHOD: http::Method = http::Method::POST;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.noteable.with_path(|noteable| {
+      self
+        .context
+        .get_ref()
+        .url_join(noteable.iter().copied().chain(["discussions"]))
+    }))
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      body: &'r str,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      position: Option<PositionView<'r>>,
+    }
+
+    EndpointBody::json(&Body {
+      body: self.body.as_ref(),
+      position: self.position.as_ref().map(Position::as_view),
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
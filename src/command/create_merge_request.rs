@@ -0,0 +1,129 @@
+#[cfg(feature = "http")]
+use crate::common::merge_request::MergeRequest;
+use crate::common::milestone::MilestoneId;
+use crate::common::project::ProjectRef;
+use crate::common::user::UserId;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Create a merge request
+///
+/// <https://docs.gitlab.com/ee/api/merge_requests.html#create-mr>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CreateMergeRequestCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub source_branch: Str,
+  pub target_branch: Str,
+  /// Title, prefix it with `Draft:` to create a draft merge request
+  pub title: Str,
+  pub description: Option<Str>,
+  pub assignee_ids: Vec<UserId>,
+  pub reviewer_ids: Vec<UserId>,
+  pub labels: Vec<Str>,
+  pub milestone_id: Option<MilestoneId>,
+  /// Delete the source branch when merging
+  pub remove_source_branch: Option<bool>,
+  /// Squash the commits when merging
+  pub squash: Option<bool>,
+}
+
+pub type CreateMergeRequestCommandView<'req, Cx> = CreateMergeRequestCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> CreateMergeRequestCommand<Cx, Str> {
+  pub fn as_view(&self) -> CreateMergeRequestCommandView<'_, Cx> {
+    CreateMergeRequestCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      source_branch: self.source_branch.as_ref(),
+      target_branch: self.target_branch.as_ref(),
+      title: self.title.as_ref(),
+      description: self.description.as_ref().map(AsRef::as_ref),
+      assignee_ids: self.assignee_ids.clone(),
+      reviewer_ids: self.reviewer_ids.clone(),
+      labels: Vec::from_iter(self.labels.iter().map(AsRef::as_ref)),
+      milestone_id: self.milestone_id,
+      remove_source_branch: self.remove_source_branch,
+      squash: self.squash,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for CreateMergeRequestCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = MergeRequest;
+
+  const METHOD: http::Method = http::Method::POST;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(
+      self
+        .project
+        .with_str(|project| self.context.get_ref().url_join(["projects", project, "merge_requests"])),
+    )
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      source_branch: &'r str,
+      target_branch: &'r str,
+      title: &'r str,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      description: Option<&'r str>,
+      #[serde(skip_serializing_if = "Vec::is_empty")]
+      assignee_ids: Vec<u64>,
+      #[serde(skip_serializing_if = "Vec::is_empty")]
+      reviewer_ids: Vec<u64>,
+      #[serde(skip_serializing_if = "Vec::is_empty")]
+      labels: Vec<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      milestone_id: Option<u64>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      remove_source_branch: Option<bool>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      squash: Option<bool>,
+    }
+
+    EndpointBody::json(&Body {
+      source_branch: self.source_branch.as_ref(),
+      target_branch: self.target_branch.as_ref(),
+      title: self.title.as_ref(),
+      description: self.description.as_ref().map(AsRef::as_ref),
+      assignee_ids: Vec::from_iter(self.assignee_ids.iter().copied().map(UserId::into_u64)),
+      reviewer_ids: Vec::from_iter(self.reviewer_ids.iter().copied().map(UserId::into_u64)),
+      labels: Vec::from_iter(self.labels.iter().map(AsRef::as_ref)),
+      milestone_id: self.milestone_id.map(MilestoneId::into_u64),
+      remove_source_branch: self.remove_source_branch,
+      squash: self.squash,
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
@@ -0,0 +1,118 @@
+use crate::common::approval::ApprovalRuleId;
+#[cfg(feature = "http")]
+use crate::common::approval::MergeRequestApprovalRule;
+use crate::common::group::GroupId;
+use crate::common::merge_request::MergeRequestIid;
+use crate::common::project::ProjectRef;
+use crate::common::user::UserId;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Create an approval rule for a merge request
+///
+/// The approval rules of the merge request then no longer follow the rules of the project.
+///
+/// <https://docs.gitlab.com/ee/api/merge_request_approvals.html#create-merge-request-level-rule>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CreateMergeRequestApprovalRuleCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub merge_request_iid: MergeRequestIid,
+  pub name: Str,
+  pub approvals_required: u64,
+  /// Copy the eligible approvers of this project rule
+  pub approval_project_rule_id: Option<ApprovalRuleId>,
+  /// Users allowed to approve
+  pub user_ids: Vec<UserId>,
+  /// Groups whose members are allowed to approve
+  pub group_ids: Vec<GroupId>,
+}
+
+pub type CreateMergeRequestApprovalRuleCommandView<'req, Cx> =
+  CreateMergeRequestApprovalRuleCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> CreateMergeRequestApprovalRuleCommand<Cx, Str> {
+  pub fn as_view(&self) -> CreateMergeRequestApprovalRuleCommandView<'_, Cx> {
+    CreateMergeRequestApprovalRuleCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      merge_request_iid: self.merge_request_iid,
+      name: self.name.as_ref(),
+      approvals_required: self.approvals_required,
+      approval_project_rule_id: self.approval_project_rule_id,
+      user_ids: self.user_ids.clone(),
+      group_ids: self.group_ids.clone(),
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for CreateMergeRequestApprovalRuleCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = MergeRequestApprovalRule;
+
+  const METHOD: http::Method = http::Method::POST;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.merge_request_iid.with_str(|merge_request_iid| {
+        self.context.get_ref().url_join([
+          "projects",
+          project,
+          "merge_requests",
+          merge_request_iid,
+          "approval_rules",
+        ])
+      })
+    }))
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      name: &'r str,
+      approvals_required: u64,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      approval_project_rule_id: Option<u64>,
+      #[serde(skip_serializing_if = "Vec::is_empty")]
+      user_ids: Vec<u64>,
+      #[serde(skip_serializing_if = "Vec::is_empty")]
+      group_ids: Vec<u64>,
+    }
+
+    EndpointBody::json(&Body {
+      name: self.name.as_ref(),
+      approvals_required: self.approvals_required,
+      approval_project_rule_id: self.approval_project_rule_id.map(ApprovalRuleId::into_u64),
+      user_ids: Vec::from_iter(self.user_ids.iter().copied().map(UserId::into_u64)),
+      group_ids: Vec::from_iter(self.group_ids.iter().copied().map(GroupId::into_u64)),
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
@@ -0,0 +1,88 @@
+#[cfg(feature = "http")]
+use crate::common::note::Note;
+use crate::common::note::NoteLocation;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Comment on an issue, merge request, snippet or epic, or reply to a discussion thread of any noteable
+///
+/// <https://docs.gitlab.com/ee/api/notes.html#create-new-issue-note>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CreateNoteCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  /// Parent of the note, or thread to reply to, the notes of a commit are only available in their threads
+  pub location: NoteLocation<Str>,
+  pub body: Str,
+  /// Only show the note to members with at least the reporter role
+  pub internal: Option<bool>,
+}
+
+pub type CreateNoteCommandView<'req, Cx> = CreateNoteCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> CreateNoteCommand<Cx, Str> {
+  pub fn as_view(&self) -> CreateNoteCommandView<'_, Cx> {
+    CreateNoteCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      location: self.location.as_view(),
+      body: self.body.as_ref(),
+      internal: self.internal,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for CreateNoteCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Note;
+
+  const METHOD: http::Method = http::Method::POST;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(
+      self
+        .location
+        .with_path(|notes| self.context.get_ref().url_join(notes.iter().copied())),
+    )
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      body: &'r str,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      internal: Option<bool>,
+    }
+
+    EndpointBody::json(&Body {
+      body: self.body.as_ref(),
+      internal: self.internal,
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
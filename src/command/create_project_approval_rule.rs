@@ -0,0 +1,125 @@
+use crate::common::approval::ApprovalRuleType;
+#[cfg(feature = "http")]
+use crate::common::approval::ProjectApprovalRule;
+use crate::common::group::GroupId;
+use crate::common::project::ProjectRef;
+use crate::common::protected_branch::ProtectedBranchId;
+use crate::common::user::UserId;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Create an approval rule for a project
+///
+/// <https://docs.gitlab.com/ee/api/merge_request_approvals.html#create-project-level-rule>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CreateProjectApprovalRuleCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub name: Str,
+  pub approvals_required: u64,
+  /// Only `Regular` (default) and `AnyApprover` rules can be created
+  pub rule_type: Option<ApprovalRuleType>,
+  /// Users allowed to approve
+  pub user_ids: Vec<UserId>,
+  /// Groups whose members are allowed to approve
+  pub group_ids: Vec<GroupId>,
+  /// Only apply the rule to the merge requests targeting these protected branches
+  pub protected_branch_ids: Vec<ProtectedBranchId>,
+  /// Apply the rule to the merge requests targeting any protected branch
+  pub applies_to_all_protected_branches: Option<bool>,
+}
+
+pub type CreateProjectApprovalRuleCommandView<'req, Cx> = CreateProjectApprovalRuleCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> CreateProjectApprovalRuleCommand<Cx, Str> {
+  pub fn as_view(&self) -> CreateProjectApprovalRuleCommandView<'_, Cx> {
+    CreateProjectApprovalRuleCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      name: self.name.as_ref(),
+      approvals_required: self.approvals_required,
+      rule_type: self.rule_type,
+      user_ids: self.user_ids.clone(),
+      group_ids: self.group_ids.clone(),
+      protected_branch_ids: self.protected_branch_ids.clone(),
+      applies_to_all_protected_branches: self.applies_to_all_protected_branches,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for CreateProjectApprovalRuleCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = ProjectApprovalRule;
+
+  const METHOD: http::Method = http::Method::POST;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(
+      self
+        .project
+        .with_str(|project| self.context.get_ref().url_join(["projects", project, "approval_rules"])),
+    )
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      name: &'r str,
+      approvals_required: u64,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      rule_type: Option<&'r str>,
+      #[serde(skip_serializing_if = "Vec::is_empty")]
+      user_ids: Vec<u64>,
+      #[serde(skip_serializing_if = "Vec::is_empty")]
+      group_ids: Vec<u64>,
+      #[serde(skip_serializing_if = "Vec::is_empty")]
+      protected_branch_ids: Vec<u64>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      applies_to_all_protected_branches: Option<bool>,
+    }
+
+    EndpointBody::json(&Body {
+      name: self.name.as_ref(),
+      approvals_required: self.approvals_required,
+      rule_type: self.rule_type.map(ApprovalRuleType::as_str),
+      user_ids: Vec::from_iter(self.user_ids.iter().copied().map(UserId::into_u64)),
+      group_ids: Vec::from_iter(self.group_ids.iter().copied().map(GroupId::into_u64)),
+      protected_branch_ids: Vec::from_iter(
+        self
+          .protected_branch_ids
+          .iter()
+          .copied()
+          .map(ProtectedBranchId::into_u64),
+      ),
+      applies_to_all_protected_branches: self.applies_to_all_protected_branches,
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
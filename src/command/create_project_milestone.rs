@@ -0,0 +1,97 @@
+#[cfg(feature = "http")]
+use crate::common::milestone::Milestone;
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use chrono::NaiveDate;
+use compact_str::CompactString;
+
+/// Create a project milestone
+///
+/// <https://docs.gitlab.com/ee/api/milestones.html#create-new-milestone>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CreateProjectMilestoneCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub title: Str,
+  pub description: Option<Str>,
+  pub due_date: Option<NaiveDate>,
+  pub start_date: Option<NaiveDate>,
+}
+
+pub type CreateProjectMilestoneCommandView<'req, Cx> = CreateProjectMilestoneCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> CreateProjectMilestoneCommand<Cx, Str> {
+  pub fn as_view(&self) -> CreateProjectMilestoneCommandView<'_, Cx> {
+    CreateProjectMilestoneCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      title: self.title.as_ref(),
+      description: self.description.as_ref().map(AsRef::as_ref),
+      due_date: self.due_date,
+      start_date: self.start_date,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for CreateProjectMilestoneCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Milestone;
+
+  const METHOD: http::Method = http::Method::POST;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(
+      self
+        .project
+        .with_str(|project| self.context.get_ref().url_join(["projects", project, "milestones"])),
+    )
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      title: &'r str,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      description: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      due_date: Option<NaiveDate>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      start_date: Option<NaiveDate>,
+    }
+
+    EndpointBody::json(&Body {
+      title: self.title.as_ref(),
+      description: self.description.as_ref().map(AsRef::as_ref),
+      due_date: self.due_date,
+      start_date: self.start_date,
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
@@ -1,6 +1,16 @@
 use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::release::Release;
 use crate::common::release::{InputReleaseAssets, InputReleaseAssetsView, InputReleaseLink};
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
 use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
 use chrono::{DateTime, Utc};
 use compact_str::CompactString;
 
@@ -8,7 +18,7 @@ use compact_str::CompactString;
 ///
 /// <https://docs.gitlab.com/ee/api/releases/#create-a-release>
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct CreateReleaseCommand<Cx, Str = CompactString, Assets = InputReleaseAssets> {
   pub context: Cx,
   pub auth: Option<GitlabAuth<Str>>,
@@ -18,7 +28,8 @@ pub struct CreateReleaseCommand<Cx, Str = CompactString, Assets = InputReleaseAs
   pub tag_message: Option<Str>,
   pub description: Option<Str>,
   pub r#ref: Option<Str>,
-  // milestones: Vec<Str>,
+  /// Titles of the milestones to associate with the release
+  pub milestones: Vec<Str>,
   pub assets: Assets,
   pub released_at: Option<DateTime<Utc>>,
 }
@@ -40,9 +51,66 @@ where
       tag_message: self.tag_message.as_ref().map(|s| s.as_ref()),
       description: self.description.as_ref().map(|s| s.as_ref()),
       r#ref: self.r#ref.as_ref().map(|s| s.as_ref()),
-      // milestones: Vec<Str>,
+      milestones: Vec::from_iter(self.milestones.iter().map(|s| s.as_ref())),
       assets: self.assets.as_view(),
       released_at: self.released_at,
     }
   }
 }
+
+#[cfg(feature = "http")]
+impl<Cx> Endpoint for CreateReleaseCommand<Cx>
+where
+  Cx: GetRef<GitlabUrl>,
+{
+  type Context = Cx;
+  type Output = Release;
+
+  const METHOD: http::Method = http::Method::POST;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(
+      self
+        .project
+        .with_str(|project| self.context.get_ref().url_join(["projects", project, "releases"])),
+    )
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      name: Option<&'r str>,
+      tag_name: &'r str,
+      tag_message: Option<&'r str>,
+      description: Option<&'r str>,
+      r#ref: Option<&'r str>,
+      #[serde(skip_serializing_if = "Vec::is_empty")]
+      milestones: Vec<&'r str>,
+      assets: InputReleaseAssetsView<'r>,
+      released_at: Option<DateTime<Utc>>,
+    }
+
+    EndpointBody::json(&Body {
+      name: self.name.as_deref(),
+      tag_name: self.tag_name.as_str(),
+      tag_message: self.tag_message.as_deref(),
+      description: self.description.as_deref(),
+      r#ref: self.r#ref.as_deref(),
+      milestones: Vec::from_iter(self.milestones.iter().map(CompactString::as_str)),
+      assets: self.assets.as_view(),
+      released_at: self.released_at,
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
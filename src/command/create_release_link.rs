@@ -1,6 +1,16 @@
 use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::release::ReleaseLink;
 use crate::common::release::ReleaseLinkType;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
 use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
 use compact_str::CompactString;
 
 /// Create a project release link
@@ -35,3 +45,57 @@ impl<Cx, Str: AsRef<str>> CreateReleaseLinkCommand<Cx, Str> {
     }
   }
 }
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for CreateReleaseLinkCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = ReleaseLink;
+
+  const METHOD: http::Method = http::Method::POST;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.context.get_ref().url_join([
+        "projects",
+        project,
+        "releases",
+        self.tag_name.as_ref(),
+        "assets",
+        "links",
+      ])
+    }))
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      name: &'r str,
+      url: &'r str,
+      direct_asset_path: Option<&'r str>,
+      link_type: &'r str,
+    }
+
+    EndpointBody::json(&Body {
+      name: self.name.as_ref(),
+      url: self.url.as_ref(),
+      direct_asset_path: self.direct_asset_path.as_ref().map(|s| s.as_ref()),
+      link_type: self.link_type.as_str(),
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
@@ -0,0 +1,94 @@
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::tag::Tag;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Create a repository tag
+///
+/// <https://docs.gitlab.com/ee/api/tags.html#create-a-new-tag>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CreateTagCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  /// Tag name
+  pub tag_name: Str,
+  /// Branch name or commit to tag
+  pub r#ref: Str,
+  /// Message of an annotated tag, `None` to create a lightweight tag
+  pub message: Option<Str>,
+}
+
+pub type CreateTagCommandView<'req, Cx> = CreateTagCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> CreateTagCommand<Cx, Str> {
+  pub fn as_view(&self) -> CreateTagCommandView<'_, Cx> {
+    CreateTagCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      tag_name: self.tag_name.as_ref(),
+      r#ref: self.r#ref.as_ref(),
+      message: self.message.as_ref().map(AsRef::as_ref),
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for CreateTagCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Tag;
+
+  const METHOD: http::Method = http::Method::POST;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self
+        .context
+        .get_ref()
+        .url_join(["projects", project, "repository", "tags"])
+    }))
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      tag_name: &'r str,
+      r#ref: &'r str,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      message: Option<&'r str>,
+    }
+
+    EndpointBody::json(&Body {
+      tag_name: self.tag_name.as_ref(),
+      r#ref: self.r#ref.as_ref(),
+      message: self.message.as_ref().map(AsRef::as_ref),
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
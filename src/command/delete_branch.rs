@@ -0,0 +1,70 @@
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_empty, DecodeError, Endpoint};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Delete a repository branch
+///
+/// <https://docs.gitlab.com/ee/api/branches.html#delete-repository-branch>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DeleteBranchCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  /// Branch name
+  pub branch: Str,
+}
+
+pub type DeleteBranchCommandView<'req, Cx> = DeleteBranchCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> DeleteBranchCommand<Cx, Str> {
+  pub fn as_view(&self) -> DeleteBranchCommandView<'_, Cx> {
+    DeleteBranchCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      branch: self.branch.as_ref(),
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for DeleteBranchCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = ();
+
+  const METHOD: http::Method = http::Method::DELETE;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self
+        .context
+        .get_ref()
+        .url_join(["projects", project, "repository", "branches", self.branch.as_ref()])
+    }))
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_empty(res)
+  }
+}
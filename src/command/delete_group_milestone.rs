@@ -0,0 +1,72 @@
+use crate::common::group::GroupId;
+use crate::common::milestone::MilestoneId;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_empty, DecodeError, Endpoint};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Delete a group milestone
+///
+/// <https://docs.gitlab.com/ee/api/group_milestones.html#delete-group-milestone>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DeleteGroupMilestoneCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub group: GroupId,
+  pub milestone_id: MilestoneId,
+}
+
+pub type DeleteGroupMilestoneCommandView<'req, Cx> = DeleteGroupMilestoneCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> DeleteGroupMilestoneCommand<Cx, Str> {
+  pub fn as_view(&self) -> DeleteGroupMilestoneCommandView<'_, Cx> {
+    DeleteGroupMilestoneCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      group: self.group,
+      milestone_id: self.milestone_id,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for DeleteGroupMilestoneCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = ();
+
+  const METHOD: http::Method = http::Method::DELETE;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.group.with_str(|group| {
+      self.milestone_id.with_str(|milestone_id| {
+        self
+          .context
+          .get_ref()
+          .url_join(["groups", group, "milestones", milestone_id])
+      })
+    }))
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_empty(res)
+  }
+}
@@ -0,0 +1,69 @@
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_empty, DecodeError, Endpoint};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Delete the branches merged into the default branch
+///
+/// Protected branches are kept. GitLab deletes the branches in the background, after accepting the request.
+///
+/// <https://docs.gitlab.com/ee/api/branches.html#delete-merged-branches>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DeleteMergedBranchesCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+}
+
+pub type DeleteMergedBranchesCommandView<'req, Cx> = DeleteMergedBranchesCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> DeleteMergedBranchesCommand<Cx, Str> {
+  pub fn as_view(&self) -> DeleteMergedBranchesCommandView<'_, Cx> {
+    DeleteMergedBranchesCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for DeleteMergedBranchesCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = ();
+
+  const METHOD: http::Method = http::Method::DELETE;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self
+        .context
+        .get_ref()
+        .url_join(["projects", project, "repository", "merged_branches"])
+    }))
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_empty(res)
+  }
+}
@@ -0,0 +1,69 @@
+use crate::common::note::{NoteId, NoteLocation};
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_empty, DecodeError, Endpoint};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Delete a note
+///
+/// <https://docs.gitlab.com/ee/api/notes.html#delete-an-issue-note>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DeleteNoteCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  /// Parent or discussion thread of the note, the notes of a commit are only available in their threads
+  pub location: NoteLocation<Str>,
+  pub note_id: NoteId,
+}
+
+pub type DeleteNoteCommandView<'req, Cx> = DeleteNoteCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> DeleteNoteCommand<Cx, Str> {
+  pub fn as_view(&self) -> DeleteNoteCommandView<'_, Cx> {
+    DeleteNoteCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      location: self.location.as_view(),
+      note_id: self.note_id,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for DeleteNoteCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = ();
+
+  const METHOD: http::Method = http::Method::DELETE;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.location.with_path(|notes| {
+      self
+        .note_id
+        .with_str(|note_id| self.context.get_ref().url_join(notes.iter().copied().chain([note_id])))
+    }))
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_empty(res)
+  }
+}
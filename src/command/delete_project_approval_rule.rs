@@ -0,0 +1,72 @@
+use crate::common::approval::ApprovalRuleId;
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_empty, DecodeError, Endpoint};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Delete an approval rule of a project
+///
+/// <https://docs.gitlab.com/ee/api/merge_request_approvals.html#delete-project-level-rule>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DeleteProjectApprovalRuleCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub approval_rule_id: ApprovalRuleId,
+}
+
+pub type DeleteProjectApprovalRuleCommandView<'req, Cx> = DeleteProjectApprovalRuleCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> DeleteProjectApprovalRuleCommand<Cx, Str> {
+  pub fn as_view(&self) -> DeleteProjectApprovalRuleCommandView<'_, Cx> {
+    DeleteProjectApprovalRuleCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      approval_rule_id: self.approval_rule_id,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for DeleteProjectApprovalRuleCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = ();
+
+  const METHOD: http::Method = http::Method::DELETE;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.approval_rule_id.with_str(|approval_rule_id| {
+        self
+          .context
+          .get_ref()
+          .url_join(["projects", project, "approval_rules", approval_rule_id])
+      })
+    }))
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_empty(res)
+  }
+}
@@ -0,0 +1,72 @@
+use crate::common::milestone::MilestoneId;
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_empty, DecodeError, Endpoint};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Delete a project milestone
+///
+/// <https://docs.gitlab.com/ee/api/milestones.html#delete-project-milestone>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DeleteProjectMilestoneCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub milestone_id: MilestoneId,
+}
+
+pub type DeleteProjectMilestoneCommandView<'req, Cx> = DeleteProjectMilestoneCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> DeleteProjectMilestoneCommand<Cx, Str> {
+  pub fn as_view(&self) -> DeleteProjectMilestoneCommandView<'_, Cx> {
+    DeleteProjectMilestoneCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      milestone_id: self.milestone_id,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for DeleteProjectMilestoneCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = ();
+
+  const METHOD: http::Method = http::Method::DELETE;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.milestone_id.with_str(|milestone_id| {
+        self
+          .context
+          .get_ref()
+          .url_join(["projects", project, "milestones", milestone_id])
+      })
+    }))
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_empty(res)
+  }
+}
@@ -0,0 +1,81 @@
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::release::ReleaseLink;
+use crate::common::release::ReleaseLinkId;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Delete an asset link of a project release
+///
+/// <https://docs.gitlab.com/ee/api/releases/links.html#delete-a-release-link>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DeleteReleaseLinkCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub tag_name: Str,
+  pub link_id: ReleaseLinkId,
+}
+
+pub type DeleteReleaseLinkCommandView<'req, Cx> = DeleteReleaseLinkCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> DeleteReleaseLinkCommand<Cx, Str> {
+  pub fn as_view(&self) -> DeleteReleaseLinkCommandView<'_, Cx> {
+    DeleteReleaseLinkCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      tag_name: self.tag_name.as_ref(),
+      link_id: self.link_id,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for DeleteReleaseLinkCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = ReleaseLink;
+
+  const METHOD: http::Method = http::Method::DELETE;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.link_id.with_str(|link_id| {
+        self.context.get_ref().url_join([
+          "projects",
+          project,
+          "releases",
+          self.tag_name.as_ref(),
+          "assets",
+          "links",
+          link_id,
+        ])
+      })
+    }))
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
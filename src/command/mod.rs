@@ -1,4 +1,47 @@
+pub mod accept_merge_request;
+pub mod approve_merge_request;
+pub mod cherry_pick_commit;
+pub mod create_branch;
+pub mod create_commit;
+pub mod create_discussion;
+pub mod create_group_milestone;
+pub mod create_merge_request;
+pub mod create_merge_request_approval_rule;
+pub mod create_note;
+pub mod create_project_approval_rule;
+pub mod create_project_milestone;
 pub mod create_release;
 pub mod create_release_link;
+pub mod create_repository_file;
+pub mod create_tag;
+pub mod delete_branch;
+pub mod delete_group_milestone;
+pub mod delete_merge_request_approval_rule;
+pub mod delete_merged_branches;
+pub mod delete_note;
+pub mod delete_project_approval_rule;
+pub mod delete_project_milestone;
+pub mod delete_release;
+pub mod delete_release_link;
+pub mod delete_repository_file;
+pub mod delete_tag;
+pub mod protect_branch;
+pub mod protect_tag;
 pub mod publish_package_file;
+pub mod rebase_merge_request;
+pub mod reset_merge_request_approvals;
+pub mod resolve_discussion;
+pub mod revert_commit;
+pub mod unapprove_merge_request;
+pub mod unprotect_branch;
+pub mod unprotect_tag;
+pub mod update_group_milestone;
+pub mod update_merge_request;
+pub mod update_merge_request_approval_rule;
+pub mod update_note;
+pub mod update_project_approval_rule;
+pub mod update_project_milestone;
+pub mod update_protected_branch;
 pub mod update_release;
+pub mod update_release_link;
+pub mod update_repository_file;
@@ -0,0 +1,134 @@
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::protected_access::ProtectedAccessBody;
+use crate::common::protected_access::ProtectedAccessRule;
+#[cfg(feature = "http")]
+use crate::common::protected_branch::ProtectedBranch;
+use crate::common::AccessLevel;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Protect the branches matching a name or wildcard
+///
+/// <https://docs.gitlab.com/ee/api/protected_branches.html#protect-repository-branches>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProtectBranchCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  /// Branch name or wildcard
+  pub name: Str,
+  /// Role allowed to push (GitLab defaults to `Maintainer`)
+  pub push_access_level: Option<AccessLevel>,
+  /// Role allowed to merge (GitLab defaults to `Maintainer`)
+  pub merge_access_level: Option<AccessLevel>,
+  /// Role allowed to unprotect (GitLab defaults to `Maintainer`)
+  pub unprotect_access_level: Option<AccessLevel>,
+  pub allow_force_push: Option<bool>,
+  /// Additional roles, users or groups allowed to push (Premium)
+  pub allowed_to_push: Vec<ProtectedAccessRule>,
+  /// Additional roles, users or groups allowed to merge (Premium)
+  pub allowed_to_merge: Vec<ProtectedAccessRule>,
+  /// Additional roles, users or groups allowed to unprotect (Premium)
+  pub allowed_to_unprotect: Vec<ProtectedAccessRule>,
+  /// Require the approval of code owners to push (Premium)
+  pub code_owner_approval_required: Option<bool>,
+}
+
+pub type ProtectBranchCommandView<'req, Cx> = ProtectBranchCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> ProtectBranchCommand<Cx, Str> {
+  pub fn as_view(&self) -> ProtectBranchCommandView<'_, Cx> {
+    ProtectBranchCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      name: self.name.as_ref(),
+      push_access_level: self.push_access_level,
+      merge_access_level: self.merge_access_level,
+      unprotect_access_level: self.unprotect_access_level,
+      allow_force_push: self.allow_force_push,
+      allowed_to_push: self.allowed_to_push.clone(),
+      allowed_to_merge: self.allowed_to_merge.clone(),
+      allowed_to_unprotect: self.allowed_to_unprotect.clone(),
+      code_owner_approval_required: self.code_owner_approval_required,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for ProtectBranchCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = ProtectedBranch;
+
+  const METHOD: http::Method = http::Method::POST;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self
+        .context
+        .get_ref()
+        .url_join(["projects", project, "protected_branches"])
+    }))
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      name: &'r str,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      push_access_level: Option<u8>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      merge_access_level: Option<u8>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      unprotect_access_level: Option<u8>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      allow_force_push: Option<bool>,
+      #[serde(skip_serializing_if = "Vec::is_empty")]
+      allowed_to_push: Vec<ProtectedAccessBody>,
+      #[serde(skip_serializing_if = "Vec::is_empty")]
+      allowed_to_merge: Vec<ProtectedAccessBody>,
+      #[serde(skip_serializing_if = "Vec::is_empty")]
+      allowed_to_unprotect: Vec<ProtectedAccessBody>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      code_owner_approval_required: Option<bool>,
+    }
+
+    EndpointBody::json(&Body {
+      name: self.name.as_ref(),
+      push_access_level: self.push_access_level.as_ref().map(AccessLevel::to_u8),
+      merge_access_level: self.merge_access_level.as_ref().map(AccessLevel::to_u8),
+      unprotect_access_level: self.unprotect_access_level.as_ref().map(AccessLevel::to_u8),
+      allow_force_push: self.allow_force_push,
+      allowed_to_push: Vec::from_iter(self.allowed_to_push.iter().copied().map(ProtectedAccessBody::from)),
+      allowed_to_merge: Vec::from_iter(self.allowed_to_merge.iter().copied().map(ProtectedAccessBody::from)),
+      allowed_to_unprotect: Vec::from_iter(self.allowed_to_unprotect.iter().copied().map(ProtectedAccessBody::from)),
+      code_owner_approval_required: self.code_owner_approval_required,
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
@@ -0,0 +1,98 @@
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::protected_access::ProtectedAccessBody;
+use crate::common::protected_access::ProtectedAccessRule;
+#[cfg(feature = "http")]
+use crate::common::protected_tag::ProtectedTag;
+use crate::common::AccessLevel;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Protect the tags matching a name or wildcard
+///
+/// <https://docs.gitlab.com/ee/api/protected_tags.html#protect-repository-tags>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProtectTagCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  /// Tag name or wildcard
+  pub name: Str,
+  /// Role allowed to create the tags (GitLab defaults to `Maintainer`)
+  pub create_access_level: Option<AccessLevel>,
+  /// Additional roles, users or groups allowed to create the tags (Premium)
+  pub allowed_to_create: Vec<ProtectedAccessRule>,
+}
+
+pub type ProtectTagCommandView<'req, Cx> = ProtectTagCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> ProtectTagCommand<Cx, Str> {
+  pub fn as_view(&self) -> ProtectTagCommandView<'_, Cx> {
+    ProtectTagCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      name: self.name.as_ref(),
+      create_access_level: self.create_access_level,
+      allowed_to_create: self.allowed_to_create.clone(),
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for ProtectTagCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = ProtectedTag;
+
+  const METHOD: http::Method = http::Method::POST;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(
+      self
+        .project
+        .with_str(|project| self.context.get_ref().url_join(["projects", project, "protected_tags"])),
+    )
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      name: &'r str,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      create_access_level: Option<u8>,
+      #[serde(skip_serializing_if = "Vec::is_empty")]
+      allowed_to_create: Vec<ProtectedAccessBody>,
+    }
+
+    EndpointBody::json(&Body {
+      name: self.name.as_ref(),
+      create_access_level: self.create_access_level.as_ref().map(AccessLevel::to_u8),
+      allowed_to_create: Vec::from_iter(self.allowed_to_create.iter().copied().map(ProtectedAccessBody::from)),
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
@@ -1,4 +1,14 @@
+#[cfg(feature = "http")]
+use crate::common::package::GenericPackageFile;
 use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody, QueryPairs};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
 use crate::{GitlabAuth, InputPackageStatus};
 use compact_str::CompactString;
 
@@ -37,3 +47,70 @@ impl<Cx, Str: AsRef<str>, Bytes: AsRef<[u8]>, const SELECT: bool> PublishPackage
     }
   }
 }
+
+impl<Cx, Str, Bytes, const SELECT: bool> PublishPackageFileCommand<Cx, Str, Bytes, SELECT> {
+  /// Replace the file data, returning the updated command and the previous data
+  pub fn replace_data<NewBytes>(self, data: NewBytes) -> (PublishPackageFileCommand<Cx, Str, NewBytes, SELECT>, Bytes) {
+    (
+      PublishPackageFileCommand {
+        context: self.context,
+        auth: self.auth,
+        project: self.project,
+        package_name: self.package_name,
+        package_version: self.package_version,
+        filename: self.filename,
+        status: self.status,
+        data,
+      },
+      self.data,
+    )
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str, Bytes> Endpoint for PublishPackageFileCommand<Cx, Str, Bytes>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+  Bytes: AsRef<[u8]>,
+{
+  type Context = Cx;
+  type Output = GenericPackageFile;
+
+  const METHOD: http::Method = http::Method::PUT;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.context.get_ref().url_join([
+        "projects",
+        project,
+        "packages",
+        "generic",
+        self.package_name.as_ref(),
+        self.package_version.as_ref(),
+        self.filename.as_ref(),
+      ])
+    }))
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    query.append_pair("status", self.status.as_str());
+    query.append_pair("select", "package_file");
+  }
+
+  fn body(&self) -> EndpointBody {
+    EndpointBody::Raw(bytes::Bytes::copy_from_slice(self.data.as_ref()))
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
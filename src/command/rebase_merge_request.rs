@@ -0,0 +1,98 @@
+#[cfg(feature = "http")]
+use crate::client::http::HttpGitlabClientError;
+use crate::common::merge_request::MergeRequestIid;
+#[cfg(feature = "http")]
+use crate::common::merge_request::MergeRequestRebase;
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Rebase the source branch of a merge request on its target branch
+///
+/// GitLab runs the rebase in the background: poll the merge request until `rebase_in_progress` is cleared, then check
+/// `merge_error` to find out whether the rebase failed. GitLab refuses to start the rebase with `403 Forbidden` if the
+/// source branch cannot be pushed to, and with `409 Conflict` if another rebase is holding the lock. These responses
+/// are reported as `HttpGitlabClientError::RebaseFailed`.
+///
+/// <https://docs.gitlab.com/ee/api/merge_requests.html#rebase-a-merge-request>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RebaseMergeRequestCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub merge_request_iid: MergeRequestIid,
+  /// Do not run a pipeline for the rebased commits
+  pub skip_ci: Option<bool>,
+}
+
+pub type RebaseMergeRequestCommandView<'req, Cx> = RebaseMergeRequestCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> RebaseMergeRequestCommand<Cx, Str> {
+  pub fn as_view(&self) -> RebaseMergeRequestCommandView<'_, Cx> {
+    RebaseMergeRequestCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      merge_request_iid: self.merge_request_iid,
+      skip_ci: self.skip_ci,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for RebaseMergeRequestCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = MergeRequestRebase;
+
+  const METHOD: http::Method = http::Method::PUT;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.merge_request_iid.with_str(|merge_request_iid| {
+        self
+          .context
+          .get_ref()
+          .url_join(["projects", project, "merge_requests", merge_request_iid, "rebase"])
+      })
+    }))
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body {
+      #[serde(skip_serializing_if = "Option::is_none")]
+      skip_ci: Option<bool>,
+    }
+
+    EndpointBody::json(&Body { skip_ci: self.skip_ci })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+
+  fn map_error(err: HttpGitlabClientError) -> HttpGitlabClientError {
+    err.classify_rebase_failure()
+  }
+}
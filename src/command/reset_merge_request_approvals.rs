@@ -0,0 +1,77 @@
+use crate::common::merge_request::MergeRequestIid;
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_empty, DecodeError, Endpoint};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Remove every approval of a merge request
+///
+/// Only allowed for bot users of the project or group, with a project or group access token.
+///
+/// <https://docs.gitlab.com/ee/api/merge_request_approvals.html#reset-approvals-of-a-merge-request>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ResetMergeRequestApprovalsCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub merge_request_iid: MergeRequestIid,
+}
+
+pub type ResetMergeRequestApprovalsCommandView<'req, Cx> = ResetMergeRequestApprovalsCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> ResetMergeRequestApprovalsCommand<Cx, Str> {
+  pub fn as_view(&self) -> ResetMergeRequestApprovalsCommandView<'_, Cx> {
+    ResetMergeRequestApprovalsCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      merge_request_iid: self.merge_request_iid,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for ResetMergeRequestApprovalsCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = ();
+
+  const METHOD: http::Method = http::Method::PUT;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.merge_request_iid.with_str(|merge_request_iid| {
+        self.context.get_ref().url_join([
+          "projects",
+          project,
+          "merge_requests",
+          merge_request_iid,
+          "reset_approvals",
+        ])
+      })
+    }))
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_empty(res)
+  }
+}
@@ -0,0 +1,88 @@
+#[cfg(feature = "http")]
+use crate::common::note::Discussion;
+use crate::common::note::NoteableRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Resolve or reopen a discussion thread of a merge request
+///
+/// GitLab responds with `403 Forbidden` if the thread cannot be resolved, e.g. for a standalone comment.
+///
+/// <https://docs.gitlab.com/ee/api/discussions.html#resolve-a-merge-request-thread>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ResolveDiscussionCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub noteable: NoteableRef<Str>,
+  pub discussion_id: Str,
+  pub resolved: bool,
+}
+
+pub type ResolveDiscussionCommandView<'req, Cx> = ResolveDiscussionCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> ResolveDiscussionCommand<Cx, Str> {
+  pub fn as_view(&self) -> ResolveDiscussionCommandView<'_, Cx> {
+    ResolveDiscussionCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      noteable: self.noteable.as_view(),
+      discussion_id: self.discussion_id.as_ref(),
+      resolved: self.resolved,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for ResolveDiscussionCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Discussion;
+
+  const METHOD: http::Method = http::Method::PUT;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.noteable.with_path(|noteable| {
+      self.context.get_ref().url_join(
+        noteable
+          .iter()
+          .copied()
+          .chain(["discussions", self.discussion_id.as_ref()]),
+      )
+    }))
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body {
+      resolved: bool,
+    }
+
+    EndpointBody::json(&Body {
+      resolved: self.resolved,
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
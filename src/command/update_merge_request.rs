@@ -0,0 +1,167 @@
+#[cfg(feature = "http")]
+use crate::common::merge_request::MergeRequest;
+use crate::common::merge_request::{MergeRequestIid, MergeRequestStateEvent};
+use crate::common::milestone::MilestoneId;
+use crate::common::project::ProjectRef;
+use crate::common::user::UserId;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Update a merge request
+///
+/// Fields set to `None` are left unchanged.
+///
+/// <https://docs.gitlab.com/ee/api/merge_requests.html#update-mr>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UpdateMergeRequestCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub merge_request_iid: MergeRequestIid,
+  pub title: Option<Str>,
+  pub description: Option<Str>,
+  pub target_branch: Option<Str>,
+  /// Replace the assignees, `Some(Vec::new())` unassigns everyone
+  pub assignee_ids: Option<Vec<UserId>>,
+  /// Replace the reviewers, `Some(Vec::new())` removes every reviewer
+  pub reviewer_ids: Option<Vec<UserId>>,
+  /// Replace the labels, `Some(Vec::new())` removes every label
+  pub labels: Option<Vec<Str>>,
+  pub add_labels: Vec<Str>,
+  pub remove_labels: Vec<Str>,
+  /// Milestone to assign, `MilestoneId::new(0)` unassigns the milestone
+  pub milestone_id: Option<MilestoneId>,
+  /// Close or reopen the merge request
+  pub state_event: Option<MergeRequestStateEvent>,
+  /// Delete the source branch when merging
+  pub remove_source_branch: Option<bool>,
+  /// Squash the commits when merging
+  pub squash: Option<bool>,
+  /// Only allow project members to comment
+  pub discussion_locked: Option<bool>,
+}
+
+pub type UpdateMergeRequestCommandView<'req, Cx> = UpdateMergeRequestCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> UpdateMergeRequestCommand<Cx, Str> {
+  pub fn as_view(&self) -> UpdateMergeRequestCommandView<'_, Cx> {
+    UpdateMergeRequestCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      merge_request_iid: self.merge_request_iid,
+      title: self.title.as_ref().map(AsRef::as_ref),
+      description: self.description.as_ref().map(AsRef::as_ref),
+      target_branch: self.target_branch.as_ref().map(AsRef::as_ref),
+      assignee_ids: self.assignee_ids.clone(),
+      reviewer_ids: self.reviewer_ids.clone(),
+      labels: self
+        .labels
+        .as_ref()
+        .map(|labels| Vec::from_iter(labels.iter().map(AsRef::as_ref))),
+      add_labels: Vec::from_iter(self.add_labels.iter().map(AsRef::as_ref)),
+      remove_labels: Vec::from_iter(self.remove_labels.iter().map(AsRef::as_ref)),
+      milestone_id: self.milestone_id,
+      state_event: self.state_event,
+      remove_source_branch: self.remove_source_branch,
+      squash: self.squash,
+      discussion_locked: self.discussion_locked,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for UpdateMergeRequestCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = MergeRequest;
+
+  const METHOD: http::Method = http::Method::PUT;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.merge_request_iid.with_str(|merge_request_iid| {
+        self
+          .context
+          .get_ref()
+          .url_join(["projects", project, "merge_requests", merge_request_iid])
+      })
+    }))
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      #[serde(skip_serializing_if = "Option::is_none")]
+      title: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      description: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      target_branch: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      assignee_ids: Option<Vec<u64>>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      reviewer_ids: Option<Vec<u64>>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      labels: Option<Vec<&'r str>>,
+      #[serde(skip_serializing_if = "Vec::is_empty")]
+      add_labels: Vec<&'r str>,
+      #[serde(skip_serializing_if = "Vec::is_empty")]
+      remove_labels: Vec<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      milestone_id: Option<u64>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      state_event: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      remove_source_branch: Option<bool>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      squash: Option<bool>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      discussion_locked: Option<bool>,
+    }
+
+    let user_ids = |ids: &Vec<UserId>| Vec::from_iter(ids.iter().copied().map(UserId::into_u64));
+    EndpointBody::json(&Body {
+      title: self.title.as_ref().map(AsRef::as_ref),
+      description: self.description.as_ref().map(AsRef::as_ref),
+      target_branch: self.target_branch.as_ref().map(AsRef::as_ref),
+      assignee_ids: self.assignee_ids.as_ref().map(user_ids),
+      reviewer_ids: self.reviewer_ids.as_ref().map(user_ids),
+      labels: self
+        .labels
+        .as_ref()
+        .map(|labels| Vec::from_iter(labels.iter().map(AsRef::as_ref))),
+      add_labels: Vec::from_iter(self.add_labels.iter().map(AsRef::as_ref)),
+      remove_labels: Vec::from_iter(self.remove_labels.iter().map(AsRef::as_ref)),
+      milestone_id: self.milestone_id.map(MilestoneId::into_u64),
+      state_event: self.state_event.map(MergeRequestStateEvent::as_str),
+      remove_source_branch: self.remove_source_branch,
+      squash: self.squash,
+      discussion_locked: self.discussion_locked,
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
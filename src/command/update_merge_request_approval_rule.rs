@@ -0,0 +1,131 @@
+use crate::common::approval::ApprovalRuleId;
+#[cfg(feature = "http")]
+use crate::common::approval::MergeRequestApprovalRule;
+use crate::common::group::GroupId;
+use crate::common::merge_request::MergeRequestIid;
+use crate::common::project::ProjectRef;
+use crate::common::user::UserId;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Update an approval rule of a merge request
+///
+/// Fields set to `None` are left unchanged.
+///
+/// <https://docs.gitlab.com/ee/api/merge_request_approvals.html#update-merge-request-level-rule>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UpdateMergeRequestApprovalRuleCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub merge_request_iid: MergeRequestIid,
+  pub approval_rule_id: ApprovalRuleId,
+  pub name: Option<Str>,
+  pub approvals_required: Option<u64>,
+  /// Replace the users allowed to approve
+  pub user_ids: Option<Vec<UserId>>,
+  /// Replace the groups whose members are allowed to approve
+  pub group_ids: Option<Vec<GroupId>>,
+  /// Also remove the groups hidden from the current user
+  pub remove_hidden_groups: Option<bool>,
+}
+
+pub type UpdateMergeRequestApprovalRuleCommandView<'req, Cx> =
+  UpdateMergeRequestApprovalRuleCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> UpdateMergeRequestApprovalRuleCommand<Cx, Str> {
+  pub fn as_view(&self) -> UpdateMergeRequestApprovalRuleCommandView<'_, Cx> {
+    UpdateMergeRequestApprovalRuleCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      merge_request_iid: self.merge_request_iid,
+      approval_rule_id: self.approval_rule_id,
+      name: self.name.as_ref().map(AsRef::as_ref),
+      approvals_required: self.approvals_required,
+      user_ids: self.user_ids.clone(),
+      group_ids: self.group_ids.clone(),
+      remove_hidden_groups: self.remove_hidden_groups,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for UpdateMergeRequestApprovalRuleCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = MergeRequestApprovalRule;
+
+  const METHOD: http::Method = http::Method::PUT;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.merge_request_iid.with_str(|merge_request_iid| {
+        self.approval_rule_id.with_str(|approval_rule_id| {
+          self.context.get_ref().url_join([
+            "projects",
+            project,
+            "merge_requests",
+            merge_request_iid,
+            "approval_rules",
+            approval_rule_id,
+          ])
+        })
+      })
+    }))
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      #[serde(skip_serializing_if = "Option::is_none")]
+      name: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      approvals_required: Option<u64>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      user_ids: Option<Vec<u64>>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      group_ids: Option<Vec<u64>>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      remove_hidden_groups: Option<bool>,
+    }
+
+    EndpointBody::json(&Body {
+      name: self.name.as_ref().map(AsRef::as_ref),
+      approvals_required: self.approvals_required,
+      user_ids: self
+        .user_ids
+        .as_ref()
+        .map(|ids| Vec::from_iter(ids.iter().copied().map(UserId::into_u64))),
+      group_ids: self
+        .group_ids
+        .as_ref()
+        .map(|ids| Vec::from_iter(ids.iter().copied().map(GroupId::into_u64))),
+      remove_hidden_groups: self.remove_hidden_groups,
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
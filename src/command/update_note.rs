@@ -0,0 +1,84 @@
+#[cfg(feature = "http")]
+use crate::common::note::Note;
+use crate::common::note::{NoteId, NoteLocation};
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Edit the body of a note
+///
+/// <https://docs.gitlab.com/ee/api/notes.html#modify-existing-issue-note>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UpdateNoteCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  /// Parent or discussion thread of the note, the notes of a commit are only available in their threads
+  pub location: NoteLocation<Str>,
+  pub note_id: NoteId,
+  pub body: Str,
+}
+
+pub type UpdateNoteCommandView<'req, Cx> = UpdateNoteCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> UpdateNoteCommand<Cx, Str> {
+  pub fn as_view(&self) -> UpdateNoteCommandView<'_, Cx> {
+    UpdateNoteCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      location: self.location.as_view(),
+      note_id: self.note_id,
+      body: self.body.as_ref(),
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for UpdateNoteCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Note;
+
+  const METHOD: http::Method = http::Method::PUT;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.location.with_path(|notes| {
+      self
+        .note_id
+        .with_str(|note_id| self.context.get_ref().url_join(notes.iter().copied().chain([note_id])))
+    }))
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      body: &'r str,
+    }
+
+    EndpointBody::json(&Body {
+      body: self.body.as_ref(),
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
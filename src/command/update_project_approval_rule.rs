@@ -0,0 +1,136 @@
+use crate::common::approval::ApprovalRuleId;
+#[cfg(feature = "http")]
+use crate::common::approval::ProjectApprovalRule;
+use crate::common::group::GroupId;
+use crate::common::project::ProjectRef;
+use crate::common::protected_branch::ProtectedBranchId;
+use crate::common::user::UserId;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Update an approval rule of a project
+///
+/// Fields set to `None` are left unchanged.
+///
+/// <https://docs.gitlab.com/ee/api/merge_request_approvals.html#update-project-level-rule>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UpdateProjectApprovalRuleCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub approval_rule_id: ApprovalRuleId,
+  pub name: Option<Str>,
+  pub approvals_required: Option<u64>,
+  /// Replace the users allowed to approve
+  pub user_ids: Option<Vec<UserId>>,
+  /// Replace the groups whose members are allowed to approve
+  pub group_ids: Option<Vec<GroupId>>,
+  /// Replace the protected branches the rule applies to
+  pub protected_branch_ids: Option<Vec<ProtectedBranchId>>,
+  pub applies_to_all_protected_branches: Option<bool>,
+  /// Also remove the groups hidden from the current user
+  pub remove_hidden_groups: Option<bool>,
+}
+
+pub type UpdateProjectApprovalRuleCommandView<'req, Cx> = UpdateProjectApprovalRuleCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> UpdateProjectApprovalRuleCommand<Cx, Str> {
+  pub fn as_view(&self) -> UpdateProjectApprovalRuleCommandView<'_, Cx> {
+    UpdateProjectApprovalRuleCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      approval_rule_id: self.approval_rule_id,
+      name: self.name.as_ref().map(AsRef::as_ref),
+      approvals_required: self.approvals_required,
+      user_ids: self.user_ids.clone(),
+      group_ids: self.group_ids.clone(),
+      protected_branch_ids: self.protected_branch_ids.clone(),
+      applies_to_all_protected_branches: self.applies_to_all_protected_branches,
+      remove_hidden_groups: self.remove_hidden_groups,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for UpdateProjectApprovalRuleCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = ProjectApprovalRule;
+
+  const METHOD: http::Method = http::Method::PUT;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.approval_rule_id.with_str(|approval_rule_id| {
+        self
+          .context
+          .get_ref()
+          .url_join(["projects", project, "approval_rules", approval_rule_id])
+      })
+    }))
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      #[serde(skip_serializing_if = "Option::is_none")]
+      name: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      approvals_required: Option<u64>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      user_ids: Option<Vec<u64>>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      group_ids: Option<Vec<u64>>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      protected_branch_ids: Option<Vec<u64>>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      applies_to_all_protected_branches: Option<bool>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      remove_hidden_groups: Option<bool>,
+    }
+
+    EndpointBody::json(&Body {
+      name: self.name.as_ref().map(AsRef::as_ref),
+      approvals_required: self.approvals_required,
+      user_ids: self
+        .user_ids
+        .as_ref()
+        .map(|ids| Vec::from_iter(ids.iter().copied().map(UserId::into_u64))),
+      group_ids: self
+        .group_ids
+        .as_ref()
+        .map(|ids| Vec::from_iter(ids.iter().copied().map(GroupId::into_u64))),
+      protected_branch_ids: self
+        .protected_branch_ids
+        .as_ref()
+        .map(|ids| Vec::from_iter(ids.iter().copied().map(ProtectedBranchId::into_u64))),
+      applies_to_all_protected_branches: self.applies_to_all_protected_branches,
+      remove_hidden_groups: self.remove_hidden_groups,
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
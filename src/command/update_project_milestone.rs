@@ -0,0 +1,112 @@
+#[cfg(feature = "http")]
+use crate::common::milestone::Milestone;
+use crate::common::milestone::{MilestoneId, MilestoneStateEvent};
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use chrono::NaiveDate;
+use compact_str::CompactString;
+
+/// Update a project milestone
+///
+/// Fields set to `None` are left unchanged.
+///
+/// <https://docs.gitlab.com/ee/api/milestones.html#edit-milestone>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UpdateProjectMilestoneCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub milestone_id: MilestoneId,
+  pub title: Option<Str>,
+  pub description: Option<Str>,
+  pub due_date: Option<NaiveDate>,
+  pub start_date: Option<NaiveDate>,
+  /// Close or reopen the milestone
+  pub state_event: Option<MilestoneStateEvent>,
+}
+
+pub type UpdateProjectMilestoneCommandView<'req, Cx> = UpdateProjectMilestoneCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> UpdateProjectMilestoneCommand<Cx, Str> {
+  pub fn as_view(&self) -> UpdateProjectMilestoneCommandView<'_, Cx> {
+    UpdateProjectMilestoneCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      milestone_id: self.milestone_id,
+      title: self.title.as_ref().map(AsRef::as_ref),
+      description: self.description.as_ref().map(AsRef::as_ref),
+      due_date: self.due_date,
+      start_date: self.start_date,
+      state_event: self.state_event,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for UpdateProjectMilestoneCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Milestone;
+
+  const METHOD: http::Method = http::Method::PUT;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.milestone_id.with_str(|milestone_id| {
+        self
+          .context
+          .get_ref()
+          .url_join(["projects", project, "milestones", milestone_id])
+      })
+    }))
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      #[serde(skip_serializing_if = "Option::is_none")]
+      title: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      description: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      due_date: Option<NaiveDate>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      start_date: Option<NaiveDate>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      state_event: Option<&'r str>,
+    }
+
+    EndpointBody::json(&Body {
+      title: self.title.as_ref().map(AsRef::as_ref),
+      description: self.description.as_ref().map(AsRef::as_ref),
+      due_date: self.due_date,
+      start_date: self.start_date,
+      state_event: self.state_event.map(MilestoneStateEvent::as_str),
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
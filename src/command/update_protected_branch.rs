@@ -0,0 +1,113 @@
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::protected_access::ProtectedAccessBody;
+use crate::common::protected_access::ProtectedAccessChange;
+#[cfg(feature = "http")]
+use crate::common::protected_branch::ProtectedBranch;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Update a protected branch or wildcard
+///
+/// <https://docs.gitlab.com/ee/api/protected_branches.html#update-a-protected-branch>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UpdateProtectedBranchCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  /// Branch name or wildcard
+  pub name: Str,
+  pub allow_force_push: Option<bool>,
+  /// Entries to add to or remove from `push_access_levels`
+  pub allowed_to_push: Vec<ProtectedAccessChange>,
+  /// Entries to add to or remove from `merge_access_levels`
+  pub allowed_to_merge: Vec<ProtectedAccessChange>,
+  /// Entries to add to or remove from `unprotect_access_levels`
+  pub allowed_to_unprotect: Vec<ProtectedAccessChange>,
+  /// Require the approval of code owners to push (Premium)
+  pub code_owner_approval_required: Option<bool>,
+}
+
+pub type UpdateProtectedBranchCommandView<'req, Cx> = UpdateProtectedBranchCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> UpdateProtectedBranchCommand<Cx, Str> {
+  pub fn as_view(&self) -> UpdateProtectedBranchCommandView<'_, Cx> {
+    UpdateProtectedBranchCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      name: self.name.as_ref(),
+      allow_force_push: self.allow_force_push,
+      allowed_to_push: self.allowed_to_push.clone(),
+      allowed_to_merge: self.allowed_to_merge.clone(),
+      allowed_to_unprotect: self.allowed_to_unprotect.clone(),
+      code_owner_approval_required: self.code_owner_approval_required,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for UpdateProtectedBranchCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = ProtectedBranch;
+
+  const METHOD: http::Method = http::Method::PATCH;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self
+        .context
+        .get_ref()
+        .url_join(["projects", project, "protected_branches", self.name.as_ref()])
+    }))
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body {
+      #[serde(skip_serializing_if = "Option::is_none")]
+      allow_force_push: Option<bool>,
+      #[serde(skip_serializing_if = "Vec::is_empty")]
+      allowed_to_push: Vec<ProtectedAccessBody>,
+      #[serde(skip_serializing_if = "Vec::is_empty")]
+      allowed_to_merge: Vec<ProtectedAccessBody>,
+      #[serde(skip_serializing_if = "Vec::is_empty")]
+      allowed_to_unprotect: Vec<ProtectedAccessBody>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      code_owner_approval_required: Option<bool>,
+    }
+
+    EndpointBody::json(&Body {
+      allow_force_push: self.allow_force_push,
+      allowed_to_push: Vec::from_iter(self.allowed_to_push.iter().copied().map(ProtectedAccessBody::from)),
+      allowed_to_merge: Vec::from_iter(self.allowed_to_merge.iter().copied().map(ProtectedAccessBody::from)),
+      allowed_to_unprotect: Vec::from_iter(self.allowed_to_unprotect.iter().copied().map(ProtectedAccessBody::from)),
+      code_owner_approval_required: self.code_owner_approval_required,
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
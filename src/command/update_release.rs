@@ -1,5 +1,15 @@
 use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::release::Release;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
 use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
 use chrono::{DateTime, Utc};
 use compact_str::CompactString;
 
@@ -7,7 +17,7 @@ use compact_str::CompactString;
 ///
 /// <https://docs.gitlab.com/ee/api/releases/#update-a-release>
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct UpdateReleaseCommand<Cx, Str = CompactString> {
   pub context: Cx,
   pub auth: Option<GitlabAuth<Str>>,
@@ -15,7 +25,9 @@ pub struct UpdateReleaseCommand<Cx, Str = CompactString> {
   pub tag_name: Str,
   pub name: Option<Str>,
   pub description: Option<Str>,
-  // milestones: Vec<Str>,
+  /// Titles of the milestones to associate with the release, replacing the current ones (`Some(Vec::new())` removes
+  /// all the milestones, `None` keeps them unchanged)
+  pub milestones: Option<Vec<Str>>,
   pub released_at: Option<DateTime<Utc>>,
 }
 
@@ -30,8 +42,68 @@ impl<Cx, Str: AsRef<str>> UpdateReleaseCommand<Cx, Str> {
       tag_name: self.tag_name.as_ref(),
       name: self.name.as_ref().map(|s| s.as_ref()),
       description: self.description.as_ref().map(|s| s.as_ref()),
-      // milestones: Vec<Str>,
+      milestones: self
+        .milestones
+        .as_ref()
+        .map(|milestones| Vec::from_iter(milestones.iter().map(|s| s.as_ref()))),
       released_at: self.released_at,
     }
   }
 }
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for UpdateReleaseCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Release;
+
+  const METHOD: http::Method = http::Method::PUT;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self
+        .context
+        .get_ref()
+        .url_join(["projects", project, "releases", self.tag_name.as_ref()])
+    }))
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      #[serde(skip_serializing_if = "Option::is_none")]
+      name: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      description: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      milestones: Option<Vec<&'r str>>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      released_at: Option<DateTime<Utc>>,
+    }
+
+    EndpointBody::json(&Body {
+      name: self.name.as_ref().map(|s| s.as_ref()),
+      description: self.description.as_ref().map(|s| s.as_ref()),
+      milestones: self
+        .milestones
+        .as_ref()
+        .map(|milestones| Vec::from_iter(milestones.iter().map(|s| s.as_ref()))),
+      released_at: self.released_at,
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
@@ -0,0 +1,112 @@
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::release::ReleaseLink;
+use crate::common::release::{ReleaseLinkId, ReleaseLinkType};
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Update an asset link of a project release
+///
+/// Fields set to `None` are left unchanged.
+///
+/// <https://docs.gitlab.com/ee/api/releases/links.html#update-a-release-link>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UpdateReleaseLinkCommand<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub tag_name: Str,
+  pub link_id: ReleaseLinkId,
+  pub name: Option<Str>,
+  pub url: Option<Str>,
+  pub direct_asset_path: Option<Str>,
+  pub link_type: Option<ReleaseLinkType>,
+}
+
+pub type UpdateReleaseLinkCommandView<'req, Cx> = UpdateReleaseLinkCommand<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> UpdateReleaseLinkCommand<Cx, Str> {
+  pub fn as_view(&self) -> UpdateReleaseLinkCommandView<'_, Cx> {
+    UpdateReleaseLinkCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      tag_name: self.tag_name.as_ref(),
+      link_id: self.link_id,
+      name: self.name.as_ref().map(AsRef::as_ref),
+      url: self.url.as_ref().map(AsRef::as_ref),
+      direct_asset_path: self.direct_asset_path.as_ref().map(AsRef::as_ref),
+      link_type: self.link_type,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for UpdateReleaseLinkCommand<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = ReleaseLink;
+
+  const METHOD: http::Method = http::Method::PUT;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.link_id.with_str(|link_id| {
+        self.context.get_ref().url_join([
+          "projects",
+          project,
+          "releases",
+          self.tag_name.as_ref(),
+          "assets",
+          "links",
+          link_id,
+        ])
+      })
+    }))
+  }
+
+  fn body(&self) -> EndpointBody {
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      #[serde(skip_serializing_if = "Option::is_none")]
+      name: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      url: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      direct_asset_path: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      link_type: Option<&'r str>,
+    }
+
+    EndpointBody::json(&Body {
+      name: self.name.as_ref().map(AsRef::as_ref),
+      url: self.url.as_ref().map(AsRef::as_ref),
+      direct_asset_path: self.direct_asset_path.as_ref().map(AsRef::as_ref),
+      link_type: self.link_type.map(ReleaseLinkType::as_str),
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
@@ -0,0 +1,126 @@
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::repository_file::RepositoryFileCommit;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, EndpointBody};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Update the content of an existing repository file, by committing it to a branch
+///
+/// <https://docs.gitlab.com/ee/api/repository_files.html#update-existing-file-in-repository>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UpdateRepositoryFileCommand<Cx, Str = CompactString, Bytes = Vec<u8>> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  /// Path of the file, relative to the repository root (e.g. `src/lib.rs`)
+  pub file_path: Str,
+  /// Branch receiving the commit
+  pub branch: Str,
+  /// Create `branch` from this branch if it does not exist yet
+  pub start_branch: Option<Str>,
+  pub commit_message: Str,
+  pub author_email: Option<Str>,
+  pub author_name: Option<Str>,
+  pub content: Bytes,
+  pub execute_filemode: Option<bool>,
+  /// Expected last commit which changed the file; the request fails if the file was changed since
+  pub last_commit_id: Option<Str>,
+}
+
+pub type UpdateRepositoryFileCommandView<'req, Cx> = UpdateRepositoryFileCommand<&'req Cx, &'req str, &'req [u8]>;
+
+impl<Cx, Str: AsRef<str>, Bytes: AsRef<[u8]>> UpdateRepositoryFileCommand<Cx, Str, Bytes> {
+  pub fn as_view(&self) -> UpdateRepositoryFileCommandView<'_, Cx> {
+    UpdateRepositoryFileCommandView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      file_path: self.file_path.as_ref(),
+      branch: self.branch.as_ref(),
+      start_branch: self.start_branch.as_ref().map(AsRef::as_ref),
+      commit_message: self.commit_message.as_ref(),
+      author_email: self.author_email.as_ref().map(AsRef::as_ref),
+      author_name: self.author_name.as_ref().map(AsRef::as_ref),
+      content: self.content.as_ref(),
+      execute_filemode: self.execute_filemode,
+      last_commit_id: self.last_commit_id.as_ref().map(AsRef::as_ref),
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str, Bytes> Endpoint for UpdateRepositoryFileCommand<Cx, Str, Bytes>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+  Bytes: AsRef<[u8]>,
+{
+  type Context = Cx;
+  type Output = RepositoryFileCommit;
+
+  const METHOD: http::Method = http::Method::PUT;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self
+        .context
+        .get_ref()
+        .url_join(["projects", project, "repository", "files", self.file_path.as_ref()])
+    }))
+  }
+
+  fn body(&self) -> EndpointBody {
+    use base64::Engine;
+
+    #[derive(Debug, serde::Serialize)]
+    struct Body<'r> {
+      branch: &'r str,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      start_branch: Option<&'r str>,
+      commit_message: &'r str,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      author_email: Option<&'r str>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      author_name: Option<&'r str>,
+      encoding: &'r str,
+      content: String,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      execute_filemode: Option<bool>,
+      #[serde(skip_serializing_if = "Option::is_none")]
+      last_commit_id: Option<&'r str>,
+    }
+
+    EndpointBody::json(&Body {
+      branch: self.branch.as_ref(),
+      start_branch: self.start_branch.as_ref().map(AsRef::as_ref),
+      commit_message: self.commit_message.as_ref(),
+      author_email: self.author_email.as_ref().map(AsRef::as_ref),
+      author_name: self.author_name.as_ref().map(AsRef::as_ref),
+      encoding: "base64",
+      content: base64::engine::general_purpose::STANDARD.encode(self.content.as_ref()),
+      execute_filemode: self.execute_filemode,
+      last_commit_id: self.last_commit_id.as_ref().map(AsRef::as_ref),
+    })
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
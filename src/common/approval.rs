@@ -0,0 +1,196 @@
+use crate::common::group::Group;
+use crate::common::protected_branch::ProtectedBranch;
+use crate::common::user::User;
+use compact_str::CompactString;
+
+/// Id of a project or merge request approval rule
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ApprovalRuleId(u64);
+
+impl ApprovalRuleId {
+  pub const fn new(id: u64) -> Self {
+    Self(id)
+  }
+
+  pub const fn into_u64(self) -> u64 {
+    self.0
+  }
+
+  /// Calls `f` with the string representation of this id as an argument.
+  #[inline]
+  pub fn with_str<R, F>(self, f: F) -> R
+  where
+    F: for<'a> FnOnce(&'a str) -> R,
+  {
+    let mut buf = ::itoa::Buffer::new();
+    f(buf.format(self.0))
+  }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ApprovalRuleType {
+  /// Approval by the eligible users and groups of the rule
+  #[cfg_attr(feature = "serde", serde(rename = "regular"))]
+  Regular,
+  /// Approval by any member with the developer role
+  #[cfg_attr(feature = "serde", serde(rename = "any_approver"))]
+  AnyApprover,
+  /// Approval by the code owners of the changed files
+  #[cfg_attr(feature = "serde", serde(rename = "code_owner"))]
+  CodeOwner,
+  /// Approval required by a security or license report
+  #[cfg_attr(feature = "serde", serde(rename = "report_approver"))]
+  ReportApprover,
+}
+
+impl ApprovalRuleType {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::Regular => "regular",
+      Self::AnyApprover => "any_approver",
+      Self::CodeOwner => "code_owner",
+      Self::ReportApprover => "report_approver",
+    }
+  }
+}
+
+/// Approval rule of a project, copied to the merge requests it applies to
+///
+/// <https://docs.gitlab.com/ee/api/merge_request_approvals.html#get-all-approval-rules-for-project>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProjectApprovalRule {
+  pub id: ApprovalRuleId,
+  pub name: CompactString,
+  pub rule_type: ApprovalRuleType,
+  /// Users allowed to approve: the `users` and the members of the `groups`
+  pub eligible_approvers: Vec<User>,
+  pub approvals_required: u64,
+  pub users: Vec<User>,
+  pub groups: Vec<Group>,
+  /// Some groups of the rule are not visible to the current user
+  pub contains_hidden_groups: bool,
+  /// Protected branches targeted by the merge requests the rule applies to, empty for every branch
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub protected_branches: Vec<ProtectedBranch>,
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub applies_to_all_protected_branches: bool,
+}
+
+/// Approval rule of a merge request
+///
+/// <https://docs.gitlab.com/ee/api/merge_request_approvals.html#get-merge-request-level-rules>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MergeRequestApprovalRule {
+  pub id: ApprovalRuleId,
+  pub name: CompactString,
+  pub rule_type: ApprovalRuleType,
+  /// Users allowed to approve: the `users` and the members of the `groups`
+  pub eligible_approvers: Vec<User>,
+  pub approvals_required: u64,
+  pub users: Vec<User>,
+  pub groups: Vec<Group>,
+  /// Some groups of the rule are not visible to the current user
+  pub contains_hidden_groups: bool,
+  /// Project rule this rule was copied from
+  pub source_rule: Option<ApprovalRuleSource>,
+  /// The rule overrides its project rule
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub overridden: bool,
+}
+
+/// Project rule a merge request approval rule was copied from
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ApprovalRuleSource {
+  pub approvals_required: u64,
+}
+
+/// Approval state of a merge request, for each of its rules
+///
+/// <https://docs.gitlab.com/ee/api/merge_request_approvals.html#get-the-approval-state-of-merge-requests>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MergeRequestApprovalState {
+  /// The rules of the merge request were edited and no longer follow the project rules
+  pub approval_rules_overwritten: bool,
+  pub rules: Vec<MergeRequestApprovalRuleState>,
+}
+
+/// Approval rule of a merge request with the approvals it received
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MergeRequestApprovalRuleState {
+  #[cfg_attr(feature = "serde", serde(flatten))]
+  pub rule: MergeRequestApprovalRule,
+  /// Eligible approvers who approved the merge request
+  pub approved_by: Vec<User>,
+  /// The rule received `approvals_required` approvals
+  pub approved: bool,
+}
+
+/// Approvals of a merge request
+///
+/// The fields about the required approvals are only returned by GitLab Premium.
+///
+/// <https://docs.gitlab.com/ee/api/merge_request_approvals.html#single-merge-request-approval-state>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MergeRequestApprovals {
+  /// Every approval rule is satisfied
+  pub approved: bool,
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub approvals_required: Option<u64>,
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub approvals_left: Option<u64>,
+  pub approved_by: Vec<MergeRequestApprover>,
+  /// The current user approved the merge request
+  pub user_has_approved: bool,
+  /// The current user is allowed to approve the merge request
+  pub user_can_approve: bool,
+}
+
+/// Approval of a merge request by a user
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MergeRequestApprover {
+  pub user: User,
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+  use super::*;
+  use crate::common::group::GroupId;
+  use crate::common::user::UserId;
+
+  #[test]
+  fn read_merge_request_approval_state() {
+    let raw = r#"{"approval_rules_overwritten":true,"rules":[{"id":1,"name":"Security","rule_type":"regular","eligible_approvers":[{"id":5,"username":"security","name":"Security","state":"active","locked":false,"avatar_url":null,"web_url":"https://gitlab.example.com/security"}],"approvals_required":1,"source_rule":{"approvals_required":2},"users":[],"groups":[{"id":7,"name":"Security","path":"security","full_name":"Group / Security","full_path":"group/security","parent_id":3,"avatar_url":null,"web_url":"https://gitlab.example.com/groups/group/security","description":"","visibility":"private"}],"contains_hidden_groups":false,"approved_by":[{"id":5,"username":"security","name":"Security","state":"active","locked":false,"avatar_url":null,"web_url":"https://gitlab.example.com/security"}],"approved":true,"overridden":true}]}"#;
+    let actual: MergeRequestApprovalState = serde_json::from_str(raw).unwrap();
+    assert!(actual.approval_rules_overwritten);
+    let rule = &actual.rules[0];
+    assert!(rule.approved);
+    assert_eq!(rule.approved_by[0].id, UserId::new(5));
+    assert_eq!(rule.rule.rule_type, ApprovalRuleType::Regular);
+    assert_eq!(rule.rule.groups[0].id, GroupId::new(7));
+    assert_eq!(rule.rule.groups[0].parent_id, Some(GroupId::new(3)));
+    assert_eq!(
+      rule.rule.source_rule,
+      Some(ApprovalRuleSource { approvals_required: 2 })
+    );
+    assert!(rule.rule.overridden);
+  }
+
+  #[test]
+  fn read_merge_request_approvals() {
+    let raw = r#"{"id":84,"iid":3,"project_id":1,"title":"Add approvals","description":null,"state":"opened","created_at":"2024-01-01T00:00:00Z","updated_at":"2024-01-01T00:00:00Z","merge_status":"can_be_merged","approved":false,"approvals_required":2,"approvals_left":1,"require_password_to_approve":false,"approved_by":[{"user":{"id":1,"username":"admin","name":"Administrator","state":"active","locked":false,"avatar_url":null,"web_url":"https://gitlab.example.com/admin"}}],"suggested_approvers":[],"approvers":[],"approver_groups":[],"user_has_approved":true,"user_can_approve":false,"approval_rules_left":[],"has_approval_rules":true,"merge_request_approvers_available":true,"multiple_approval_rules_available":true,"invalid_approvers_rules":[]}"#;
+    let actual: MergeRequestApprovals = serde_json::from_str(raw).unwrap();
+    assert!(!actual.approved);
+    assert_eq!(actual.approvals_left, Some(1));
+    assert_eq!(actual.approved_by[0].user.username, "admin");
+    assert!(actual.user_has_approved);
+  }
+}
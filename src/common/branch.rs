@@ -0,0 +1,38 @@
+use crate::common::commit::Commit;
+use compact_str::CompactString;
+
+/// Repository branch
+///
+/// <https://docs.gitlab.com/ee/api/branches.html>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Branch {
+  pub name: CompactString,
+  /// Head commit of the branch
+  pub commit: Commit,
+  /// The branch is merged into the default branch
+  pub merged: bool,
+  pub protected: bool,
+  /// The branch is the default branch of the project
+  pub default: bool,
+  pub developers_can_push: bool,
+  pub developers_can_merge: bool,
+  /// The current user can push to the branch
+  pub can_push: bool,
+  pub web_url: Option<String>,
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+  use super::*;
+
+  #[test]
+  fn read_branch() {
+    let raw = r#"{"name":"main","merged":false,"protected":true,"default":true,"developers_can_push":false,"developers_can_merge":false,"can_push":true,"web_url":"https://gitlab.example.com/group/project/-/tree/main","commit":{"id":"7b5c3cc8be40ee161ae89a06bba6229da1032a0c","short_id":"7b5c3cc","created_at":"2012-06-28T03:44:20-07:00","parent_ids":["4ad91d3c1144c406e50c7b33bae684bd6837faf8"],"title":"add projects API","message":"add projects API","author_name":"John Smith","author_email":"john@example.com","authored_date":"2012-06-27T05:51:39-07:00","committer_name":"John Smith","committer_email":"john@example.com","committed_date":"2012-06-28T03:44:20-07:00","trailers":{},"web_url":"https://gitlab.example.com/group/project/-/commit/7b5c3cc8be40ee161ae89a06bba6229da1032a0c"}}"#;
+    let actual: Branch = serde_json::from_str(raw).unwrap();
+    assert_eq!(actual.name, "main");
+    assert!(actual.protected && actual.default && !actual.merged);
+    assert_eq!(actual.commit.short_id, "7b5c3cc");
+    assert_eq!(actual.commit.stats, None);
+  }
+}
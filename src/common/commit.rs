@@ -0,0 +1,272 @@
+use crate::common::user::User;
+use chrono::{DateTime, Utc};
+use compact_str::CompactString;
+use std::collections::BTreeMap;
+
+/// Commit of a repository
+///
+/// This model is shared by every API returning commits. Some fields are only present in some responses: for
+/// example `stats` is only returned for single commits or when explicitly requested.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Commit {
+  pub id: String,
+  pub short_id: String,
+  pub title: String,
+  pub created_at: DateTime<Utc>,
+  pub parent_ids: Vec<String>,
+  pub message: String,
+  pub author_name: String,
+  pub author_email: String,
+  pub authored_date: DateTime<Utc>,
+  pub committer_name: String,
+  pub committer_email: String,
+  pub committed_date: DateTime<Utc>,
+  /// Git trailers of the commit message (e.g. `Signed-off-by`), by key
+  pub trailers: Option<BTreeMap<String, String>>,
+  pub web_url: Option<String>,
+  pub stats: Option<CommitStats>,
+}
+
+/// Number of lines changed by a commit
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CommitStats {
+  pub additions: u64,
+  pub deletions: u64,
+  pub total: u64,
+}
+
+/// File change applied by a commit created with the commits API
+///
+/// <https://docs.gitlab.com/ee/api/commits.html#create-a-commit-with-multiple-files-and-actions>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "action", rename_all = "snake_case"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CommitAction<Str = CompactString, Bytes = Vec<u8>> {
+  Create {
+    file_path: Str,
+    content: CommitContent<Str, Bytes>,
+    execute_filemode: Option<bool>,
+  },
+  Delete {
+    file_path: Str,
+    last_commit_id: Option<Str>,
+  },
+  /// Move a file from `previous_path` to `file_path`, optionally replacing its content
+  Move {
+    file_path: Str,
+    previous_path: Str,
+    content: Option<CommitContent<Str, Bytes>>,
+    last_commit_id: Option<Str>,
+  },
+  Update {
+    file_path: Str,
+    content: CommitContent<Str, Bytes>,
+    last_commit_id: Option<Str>,
+    execute_filemode: Option<bool>,
+  },
+  Chmod {
+    file_path: Str,
+    execute_filemode: bool,
+  },
+}
+
+pub type CommitActionView<'req> = CommitAction<&'req str, &'req [u8]>;
+
+impl<Str: AsRef<str>, Bytes: AsRef<[u8]>> CommitAction<Str, Bytes> {
+  pub fn as_view(&self) -> CommitActionView<'_> {
+    match self {
+      Self::Create {
+        file_path,
+        content,
+        execute_filemode,
+      } => CommitActionView::Create {
+        file_path: file_path.as_ref(),
+        content: content.as_view(),
+        execute_filemode: *execute_filemode,
+      },
+      Self::Delete {
+        file_path,
+        last_commit_id,
+      } => CommitActionView::Delete {
+        file_path: file_path.as_ref(),
+        last_commit_id: last_commit_id.as_ref().map(AsRef::as_ref),
+      },
+      Self::Move {
+        file_path,
+        previous_path,
+        content,
+        last_commit_id,
+      } => CommitActionView::Move {
+        file_path: file_path.as_ref(),
+        previous_path: previous_path.as_ref(),
+        content: content.as_ref().map(CommitContent::as_view),
+        last_commit_id: last_commit_id.as_ref().map(AsRef::as_ref),
+      },
+      Self::Update {
+        file_path,
+        content,
+        last_commit_id,
+        execute_filemode,
+      } => CommitActionView::Update {
+        file_path: file_path.as_ref(),
+        content: content.as_view(),
+        last_commit_id: last_commit_id.as_ref().map(AsRef::as_ref),
+        execute_filemode: *execute_filemode,
+      },
+      Self::Chmod {
+        file_path,
+        execute_filemode,
+      } => CommitActionView::Chmod {
+        file_path: file_path.as_ref(),
+        execute_filemode: *execute_filemode,
+      },
+    }
+  }
+
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Create { .. } => "create",
+      Self::Delete { .. } => "delete",
+      Self::Move { .. } => "move",
+      Self::Update { .. } => "update",
+      Self::Chmod { .. } => "chmod",
+    }
+  }
+
+  /// Path of the file after the action
+  pub fn file_path(&self) -> &str {
+    match self {
+      Self::Create { file_path, .. }
+      | Self::Delete { file_path, .. }
+      | Self::Move { file_path, .. }
+      | Self::Update { file_path, .. }
+      | Self::Chmod { file_path, .. } => file_path.as_ref(),
+    }
+  }
+}
+
+/// Content of a file in a commit action
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CommitContent<Str = CompactString, Bytes = Vec<u8>> {
+  /// UTF-8 text, sent as is
+  Text(Str),
+  /// Raw bytes, sent base64-encoded
+  Base64(Bytes),
+}
+
+pub type CommitContentView<'req> = CommitContent<&'req str, &'req [u8]>;
+
+impl<Str: AsRef<str>, Bytes: AsRef<[u8]>> CommitContent<Str, Bytes> {
+  pub fn as_view(&self) -> CommitContentView<'_> {
+    match self {
+      Self::Text(text) => CommitContentView::Text(text.as_ref()),
+      Self::Base64(bytes) => CommitContentView::Base64(bytes.as_ref()),
+    }
+  }
+
+  /// Raw bytes of the content
+  pub fn as_bytes(&self) -> &[u8] {
+    match self {
+      Self::Text(text) => text.as_ref().as_bytes(),
+      Self::Base64(bytes) => bytes.as_ref(),
+    }
+  }
+}
+
+/// Branch or tag containing a commit
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CommitRef {
+  pub r#type: CommitRefType,
+  pub name: CompactString,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CommitRefType {
+  #[cfg_attr(feature = "serde", serde(rename = "branch"))]
+  Branch,
+  #[cfg_attr(feature = "serde", serde(rename = "tag"))]
+  Tag,
+}
+
+impl CommitRefType {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::Branch => "branch",
+      Self::Tag => "tag",
+    }
+  }
+}
+
+/// Comment on a commit, optionally attached to a line of its diff
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CommitComment {
+  pub note: String,
+  pub author: User,
+  pub created_at: Option<DateTime<Utc>>,
+  pub path: Option<CompactString>,
+  pub line: Option<u64>,
+  /// Side of the diff the line belongs to: `new` or `old`
+  pub line_type: Option<CompactString>,
+}
+
+/// Reason why a cherry-pick or revert could not be committed
+///
+/// GitLab reports it in the `error_code` field of the error response, see
+/// [`GitlabErrorBody::error_code`](crate::common::error::GitlabErrorBody::error_code).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CommitApplyFailure {
+  /// The changes of the commit conflict with the target branch
+  Conflict,
+  /// The changes of the commit are already applied to the target branch
+  Empty,
+}
+
+impl CommitApplyFailure {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::Conflict => "conflict",
+      Self::Empty => "empty",
+    }
+  }
+
+  /// Parse the `error_code` of a GitLab error response
+  pub fn from_error_code(code: &str) -> Option<Self> {
+    match code {
+      "conflict" => Some(Self::Conflict),
+      "empty" => Some(Self::Empty),
+      _ => None,
+    }
+  }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+  use super::*;
+
+  #[test]
+  fn read_commit_with_stats() {
+    let raw = r#"{"id":"ed899a2f4b50b4370feeea94676502b42383c746","short_id":"ed899a2f","title":"Bump version","author_name":"Bot","author_email":"bot@example.com","authored_date":"2024-01-01T00:00:00Z","committer_name":"Bot","committer_email":"bot@example.com","committed_date":"2024-01-01T00:00:00Z","created_at":"2024-01-01T00:00:00Z","message":"Bump version\n\nSigned-off-by: Bot <bot@example.com>\n","parent_ids":["6104942438c14ec7bd21c6cd5bd995272b3faff6"],"trailers":{"Signed-off-by":"Bot <bot@example.com>"},"web_url":"https://gitlab.example.com/group/project/-/commit/ed899a2f4b50b4370feeea94676502b42383c746","stats":{"additions":1,"deletions":1,"total":2},"status":null,"project_id":1,"last_pipeline":null}"#;
+    let actual: Commit = serde_json::from_str(raw).unwrap();
+    assert_eq!(
+      actual.trailers,
+      Some(BTreeMap::from([(
+        "Signed-off-by".to_string(),
+        "Bot <bot@example.com>".to_string()
+      )]))
+    );
+    assert_eq!(
+      actual.stats,
+      Some(CommitStats {
+        additions: 1,
+        deletions: 1,
+        total: 2
+      })
+    );
+  }
+}
@@ -0,0 +1,47 @@
+use compact_str::CompactString;
+
+/// Changes of a single file, as returned by the commit, compare and merge request diff APIs
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Diff {
+  pub old_path: CompactString,
+  pub new_path: CompactString,
+  /// Git file mode before the change (e.g. `100644`), `0` for new files
+  pub a_mode: CompactString,
+  /// Git file mode after the change (e.g. `100755`), `0` for deleted files
+  pub b_mode: CompactString,
+  pub new_file: bool,
+  pub renamed_file: bool,
+  pub deleted_file: bool,
+  /// Patch of the file: a unified diff when requested with `unidiff`, otherwise without the `---`/`+++` headers
+  pub diff: String,
+  pub generated_file: Option<bool>,
+  /// The diff is too large to be returned, `diff` is then empty
+  pub too_large: Option<bool>,
+  pub collapsed: Option<bool>,
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+  use super::*;
+
+  #[test]
+  fn read_diff() {
+    let raw = r#"{"diff":"@@ -1 +1 @@\n-1.2.3\n+1.2.4\n","new_path":"VERSION","old_path":"VERSION","a_mode":"100644","b_mode":"100644","new_file":false,"renamed_file":false,"deleted_file":false,"generated_file":null}"#;
+    let actual: Diff = serde_json::from_str(raw).unwrap();
+    let expected = Diff {
+      old_path: CompactString::new("VERSION"),
+      new_path: CompactString::new("VERSION"),
+      a_mode: CompactString::new("100644"),
+      b_mode: CompactString::new("100644"),
+      new_file: false,
+      renamed_file: false,
+      deleted_file: false,
+      diff: "@@ -1 +1 @@\n-1.2.3\n+1.2.4\n".to_string(),
+      generated_file: None,
+      too_large: None,
+      collapsed: None,
+    };
+    assert_eq!(actual, expected);
+  }
+}
@@ -0,0 +1,149 @@
+use crate::common::commit::CommitApplyFailure;
+use compact_str::CompactString;
+use std::collections::BTreeMap;
+
+/// Error payload returned by the GitLab API
+///
+/// GitLab uses either `{"message": ...}` or `{"error": ...}` objects to describe failures.
+///
+/// <https://docs.gitlab.com/ee/api/rest/troubleshooting.html#status-codes>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GitlabErrorBody {
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub message: Option<GitlabErrorMessage>,
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub error: Option<CompactString>,
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub error_description: Option<CompactString>,
+  /// Machine-readable reason of the failure, returned by some endpoints (e.g. `conflict` for a cherry-pick)
+  #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+  pub error_code: Option<CompactString>,
+}
+
+impl GitlabErrorBody {
+  /// Validation messages for each field, when the request was rejected by the model validation.
+  pub fn field_errors(&self) -> Option<&BTreeMap<CompactString, Vec<CompactString>>> {
+    match &self.message {
+      Some(GitlabErrorMessage::Fields(fields)) => Some(fields),
+      _ => None,
+    }
+  }
+
+  /// Reason why a cherry-pick or revert failed, when GitLab reported a known `error_code`.
+  pub fn commit_apply_failure(&self) -> Option<CommitApplyFailure> {
+    self.error_code.as_deref().and_then(CommitApplyFailure::from_error_code)
+  }
+}
+
+impl core::fmt::Display for GitlabErrorBody {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match (&self.message, &self.error) {
+      (Some(message), _) => core::fmt::Display::fmt(message, f),
+      (None, Some(error)) => {
+        f.write_str(error)?;
+        if let Some(description) = self.error_description.as_deref() {
+          write!(f, ": {description}")?;
+        }
+        Ok(())
+      }
+      (None, None) => f.write_str("no error message"),
+    }
+  }
+}
+
+/// Content of the `message` field of a GitLab error
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(untagged))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GitlabErrorMessage {
+  /// Single message, e.g. `"404 Project Not Found"`
+  Text(CompactString),
+  /// List of messages
+  List(Vec<CompactString>),
+  /// Validation messages for each field, e.g. `{"released_at": ["is invalid"]}`
+  Fields(BTreeMap<CompactString, Vec<CompactString>>),
+}
+
+impl core::fmt::Display for GitlabErrorMessage {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+      Self::Text(message) => f.write_str(message),
+      Self::List(messages) => f.write_str(&messages.join(", ")),
+      Self::Fields(fields) => {
+        for (i, (field, messages)) in fields.iter().enumerate() {
+          if i > 0 {
+            f.write_str("; ")?;
+          }
+          write!(f, "{field} {}", messages.join(", "))?;
+        }
+        Ok(())
+      }
+    }
+  }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+  use super::*;
+
+  #[test]
+  fn read_not_found_error() {
+    let raw = r#"{"message":"404 Project Not Found"}"#;
+    let actual: GitlabErrorBody = serde_json::from_str(raw).unwrap();
+    let expected = GitlabErrorBody {
+      message: Some(GitlabErrorMessage::Text(CompactString::new("404 Project Not Found"))),
+      error: None,
+      error_description: None,
+      error_code: None,
+    };
+    assert_eq!(actual, expected);
+    assert_eq!(actual.to_string(), "404 Project Not Found");
+  }
+
+  #[test]
+  fn read_validation_error() {
+    let raw = r#"{"message":{"released_at":["is invalid","is too late"],"tag_name":["is missing"]}}"#;
+    let actual: GitlabErrorBody = serde_json::from_str(raw).unwrap();
+    let expected = GitlabErrorBody {
+      message: Some(GitlabErrorMessage::Fields(BTreeMap::from([
+        (
+          CompactString::new("released_at"),
+          vec![CompactString::new("is invalid"), CompactString::new("is too late")],
+        ),
+        (CompactString::new("tag_name"), vec![CompactString::new("is missing")]),
+      ]))),
+      error: None,
+      error_description: None,
+      error_code: None,
+    };
+    assert_eq!(actual, expected);
+    assert_eq!(
+      actual.to_string(),
+      "released_at is invalid, is too late; tag_name is missing"
+    );
+  }
+
+  #[test]
+  fn read_oauth_error() {
+    let raw =
+      r#"{"error":"invalid_token","error_description":"Token was revoked. You have to re-authorize from the user."}"#;
+    let actual: GitlabErrorBody = serde_json::from_str(raw).unwrap();
+    let expected = GitlabErrorBody {
+      message: None,
+      error: Some(CompactString::new("invalid_token")),
+      error_description: Some(CompactString::new(
+        "Token was revoked. You have to re-authorize from the user.",
+      )),
+      error_code: None,
+    };
+    assert_eq!(actual, expected);
+  }
+
+  #[test]
+  fn read_cherry_pick_conflict_error() {
+    let raw = r#"{"message":"Sorry, we cannot cherry-pick this commit automatically.","error_code":"conflict"}"#;
+    let actual: GitlabErrorBody = serde_json::from_str(raw).unwrap();
+    assert_eq!(actual.error_code.as_deref(), Some("conflict"));
+    assert_eq!(actual.commit_apply_failure(), Some(CommitApplyFailure::Conflict));
+  }
+}
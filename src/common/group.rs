@@ -1,3 +1,5 @@
+use compact_str::CompactString;
+
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct GroupId(u64);
@@ -21,3 +23,21 @@ impl GroupId {
     f(buf.format(self.0))
   }
 }
+
+/// Group, as embedded in other resources (e.g. the groups of an approval rule)
+///
+/// <https://docs.gitlab.com/ee/api/groups.html#details-of-a-group>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Group {
+  pub id: GroupId,
+  pub name: CompactString,
+  pub path: CompactString,
+  /// Names of the group and its ancestors, e.g. `Group / Subgroup`
+  pub full_name: CompactString,
+  /// Paths of the group and its ancestors, e.g. `group/subgroup`
+  pub full_path: CompactString,
+  pub parent_id: Option<GroupId>,
+  pub avatar_url: Option<CompactString>,
+  pub web_url: CompactString,
+}
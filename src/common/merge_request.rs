@@ -0,0 +1,462 @@
+use crate::common::commit::Commit;
+use crate::common::diff::Diff;
+use crate::common::milestone::Milestone;
+use crate::common::project::ProjectId;
+use crate::common::user::User;
+use chrono::{DateTime, Utc};
+use compact_str::CompactString;
+
+/// Global merge request id
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MergeRequestId(u64);
+
+impl MergeRequestId {
+  pub const fn new(id: u64) -> Self {
+    Self(id)
+  }
+
+  pub const fn into_u64(self) -> u64 {
+    self.0
+  }
+}
+
+/// Merge request id, local to its target project
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MergeRequestIid(u64);
+
+impl MergeRequestIid {
+  pub const fn new(id: u64) -> Self {
+    Self(id)
+  }
+
+  pub const fn into_u64(self) -> u64 {
+    self.0
+  }
+
+  /// Calls `f` with the string representation of this id as an argument.
+  #[inline]
+  pub fn with_str<R, F>(self, f: F) -> R
+  where
+    F: for<'a> FnOnce(&'a str) -> R,
+  {
+    let mut buf = ::itoa::Buffer::new();
+    f(buf.format(self.0))
+  }
+}
+
+/// <https://docs.gitlab.com/ee/api/merge_requests.html#get-single-mr>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MergeRequest {
+  pub id: MergeRequestId,
+  pub iid: MergeRequestIid,
+  /// Target project
+  pub project_id: ProjectId,
+  pub title: CompactString,
+  pub description: Option<CompactString>,
+  pub state: MergeRequestState,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+  pub merged_at: Option<DateTime<Utc>>,
+  /// User who merged the merge request, or enabled auto-merge
+  pub merge_user: Option<User>,
+  pub closed_at: Option<DateTime<Utc>>,
+  pub closed_by: Option<User>,
+  pub source_project_id: ProjectId,
+  pub source_branch: CompactString,
+  pub target_project_id: ProjectId,
+  pub target_branch: CompactString,
+  pub author: User,
+  pub assignees: Vec<User>,
+  pub reviewers: Vec<User>,
+  pub labels: Vec<CompactString>,
+  pub draft: bool,
+  pub milestone: Option<Milestone>,
+  pub merge_when_pipeline_succeeds: bool,
+  /// Detailed reason why the merge request can or cannot be merged (e.g. `mergeable`, `conflict`, `draft_status`)
+  ///
+  /// <https://docs.gitlab.com/ee/api/merge_requests.html#merge-status>
+  pub detailed_merge_status: CompactString,
+  pub has_conflicts: bool,
+  /// Head commit of the source branch
+  pub sha: Option<CompactString>,
+  pub merge_commit_sha: Option<CompactString>,
+  pub squash_commit_sha: Option<CompactString>,
+  /// Squash the commits when merging
+  pub squash: bool,
+  /// Delete the source branch when merging, as requested by the author
+  pub force_remove_source_branch: Option<bool>,
+  /// Delete the source branch when merging, as requested by the merge user
+  pub should_remove_source_branch: Option<bool>,
+  pub discussion_locked: Option<bool>,
+  pub user_notes_count: u64,
+  pub references: MergeRequestReferences,
+  pub web_url: CompactString,
+  /// Error of the last failed merge or rebase, only returned for a single merge request
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub merge_error: Option<CompactString>,
+  /// Whether a rebase is running, only returned when requested with `include_rebase_in_progress`
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub rebase_in_progress: Option<bool>,
+}
+
+/// References to a merge request, relative to different namespaces
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MergeRequestReferences {
+  /// Reference within the project, e.g. `!1`
+  pub short: CompactString,
+  /// Reference relative to the namespace of the query, e.g. `project!1`
+  pub relative: CompactString,
+  /// Reference with the full project path, e.g. `group/project!1`
+  pub full: CompactString,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MergeRequestState {
+  #[cfg_attr(feature = "serde", serde(rename = "opened"))]
+  Opened,
+  #[cfg_attr(feature = "serde", serde(rename = "closed"))]
+  Closed,
+  /// Transitional state while the merge request is being merged
+  #[cfg_attr(feature = "serde", serde(rename = "locked"))]
+  Locked,
+  #[cfg_attr(feature = "serde", serde(rename = "merged"))]
+  Merged,
+}
+
+impl MergeRequestState {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::Opened => "opened",
+      Self::Closed => "closed",
+      Self::Locked => "locked",
+      Self::Merged => "merged",
+    }
+  }
+}
+
+/// State transition applied when updating a merge request
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MergeRequestStateEvent {
+  #[cfg_attr(feature = "serde", serde(rename = "close"))]
+  Close,
+  #[cfg_attr(feature = "serde", serde(rename = "reopen"))]
+  Reopen,
+}
+
+impl MergeRequestStateEvent {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::Close => "close",
+      Self::Reopen => "reopen",
+    }
+  }
+}
+
+/// Criteria used to order merge requests
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MergeRequestOrder {
+  CreatedAt,
+  UpdatedAt,
+  MergedAt,
+  Title,
+}
+
+impl MergeRequestOrder {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::CreatedAt => "created_at",
+      Self::UpdatedAt => "updated_at",
+      Self::MergedAt => "merged_at",
+      Self::Title => "title",
+    }
+  }
+}
+
+/// Relation of the merge requests to the current user
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MergeRequestScope {
+  CreatedByMe,
+  AssignedToMe,
+  All,
+}
+
+impl MergeRequestScope {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::CreatedByMe => "created_by_me",
+      Self::AssignedToMe => "assigned_to_me",
+      Self::All => "all",
+    }
+  }
+}
+
+/// Response to a rebase request
+///
+/// <https://docs.gitlab.com/ee/api/merge_requests.html#rebase-a-merge-request>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MergeRequestRebase {
+  /// The rebase is running in the background: its failure is reported in [`MergeRequest::merge_error`]
+  pub rebase_in_progress: bool,
+}
+
+/// Merge request with the diffs of its changes
+///
+/// <https://docs.gitlab.com/ee/api/merge_requests.html#get-single-merge-request-changes>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MergeRequestChanges {
+  #[cfg_attr(feature = "serde", serde(flatten))]
+  pub merge_request: MergeRequest,
+  pub changes: Vec<Diff>,
+  /// Some changes were left out because the diff exceeds the size limits, use `access_raw_diffs` to get them all
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub overflow: bool,
+}
+
+/// Id of a merge request diff version
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MergeRequestDiffVersionId(u64);
+
+impl MergeRequestDiffVersionId {
+  pub const fn new(id: u64) -> Self {
+    Self(id)
+  }
+
+  pub const fn into_u64(self) -> u64 {
+    self.0
+  }
+
+  /// Calls `f` with the string representation of this id as an argument.
+  #[inline]
+  pub fn with_str<R, F>(self, f: F) -> R
+  where
+    F: for<'a> FnOnce(&'a str) -> R,
+  {
+    let mut buf = ::itoa::Buffer::new();
+    f(buf.format(self.0))
+  }
+}
+
+/// Diff version of a merge request
+///
+/// GitLab records a new version each time the source branch is pushed to.
+///
+/// <https://docs.gitlab.com/ee/api/merge_requests.html#get-merge-request-diff-versions>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MergeRequestDiffVersion {
+  pub id: MergeRequestDiffVersionId,
+  /// Head of the source branch
+  pub head_commit_sha: CompactString,
+  /// Merge base of the source and target branches
+  pub base_commit_sha: CompactString,
+  /// Head of the target branch
+  pub start_commit_sha: CompactString,
+  pub created_at: DateTime<Utc>,
+  pub merge_request_id: MergeRequestId,
+  /// State of the diff collection (e.g. `collected`, `overflow`, `empty`)
+  pub state: CompactString,
+  /// Number of changed files, with a `+` suffix when over the limit (e.g. `100+`)
+  pub real_size: CompactString,
+  pub patch_id_sha: Option<CompactString>,
+  /// Commits of the version, newest first, only returned for a single version
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub commits: Option<Vec<Commit>>,
+  /// Diffs of the version, only returned for a single version
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub diffs: Option<Vec<Diff>>,
+}
+
+/// Reason why GitLab refused to merge a merge request
+///
+/// GitLab reports these failures through the status of the response to `AcceptMergeRequestCommand`, see
+/// <https://docs.gitlab.com/ee/api/merge_requests.html#merge-a-merge-request>. The HTTP clients report them as
+/// `HttpGitlabClientError::MergeFailed`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MergeFailure {
+  /// The merge request is not open, is a draft, or has unresolved blockers (`405 Method Not Allowed`)
+  NotMergeable,
+  /// The source branch conflicts with the target branch (`406 Not Acceptable`)
+  Conflict,
+  /// The `sha` of the request is not the head of the source branch (`409 Conflict`)
+  ShaMismatch,
+}
+
+impl MergeFailure {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::NotMergeable => "not_mergeable",
+      Self::Conflict => "conflict",
+      Self::ShaMismatch => "sha_mismatch",
+    }
+  }
+
+  /// Status code of the GitLab error response for this failure
+  pub fn status(self) -> u16 {
+    match self {
+      Self::NotMergeable => 405,
+      Self::Conflict => 406,
+      Self::ShaMismatch => 409,
+    }
+  }
+
+  /// Parse the status code of a GitLab error response to a merge request
+  pub fn from_status(status: u16) -> Option<Self> {
+    match status {
+      405 => Some(Self::NotMergeable),
+      406 => Some(Self::Conflict),
+      409 => Some(Self::ShaMismatch),
+      _ => None,
+    }
+  }
+}
+
+/// Reason why GitLab refused to rebase a merge request
+///
+/// GitLab reports these failures through the status of the response to `RebaseMergeRequestCommand`, see
+/// <https://docs.gitlab.com/ee/api/merge_requests.html#rebase-a-merge-request>. The HTTP clients report them as
+/// `HttpGitlabClientError::RebaseFailed`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RebaseFailure {
+  /// The source branch cannot be pushed to (`403 Forbidden`)
+  Forbidden,
+  /// Another rebase is holding the lock of the merge request (`409 Conflict`)
+  Locked,
+}
+
+impl RebaseFailure {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::Forbidden => "forbidden",
+      Self::Locked => "locked",
+    }
+  }
+
+  /// Status code of the GitLab error response for this failure
+  pub fn status(self) -> u16 {
+    match self {
+      Self::Forbidden => 403,
+      Self::Locked => 409,
+    }
+  }
+
+  /// Parse the status code of a GitLab error response to a rebase
+  pub fn from_status(status: u16) -> Option<Self> {
+    match status {
+      403 => Some(Self::Forbidden),
+      409 => Some(Self::Locked),
+      _ => None,
+    }
+  }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+  use super::*;
+  use crate::common::user::UserId;
+  use chrono::TimeZone;
+
+  #[test]
+  fn read_merge_request() {
+    let raw = r#"{"id":84,"iid":3,"project_id":12,"title":"Draft: Add merge requests","description":"Closes #1","state":"opened","created_at":"2024-02-01T10:00:00.000Z","updated_at":"2024-02-02T10:00:00.000Z","merged_by":null,"merge_user":null,"merged_at":null,"closed_by":null,"closed_at":null,"target_branch":"main","source_branch":"feature","user_notes_count":2,"upvotes":0,"downvotes":0,"author":{"id":1,"username":"admin","name":"Administrator","state":"active","locked":false,"avatar_url":null,"web_url":"https://gitlab.example.com/admin"},"assignees":[],"assignee":null,"reviewers":[],"source_project_id":12,"target_project_id":12,"labels":["backend"],"draft":true,"work_in_progress":true,"milestone":null,"merge_when_pipeline_succeeds":false,"merge_status":"can_be_merged","detailed_merge_status":"draft_status","sha":"ed899a2f4b50b4370feeea94676502b42383c746","merge_commit_sha":null,"squash_commit_sha":null,"discussion_locked":null,"should_remove_source_branch":null,"force_remove_source_branch":true,"prepared_at":"2024-02-01T10:00:05.000Z","reference":"!3","references":{"short":"!3","relative":"!3","full":"group/project!3"},"web_url":"https://gitlab.example.com/group/project/-/merge_requests/3","time_stats":{"time_estimate":0,"total_time_spent":0,"human_time_estimate":null,"human_total_time_spent":null},"squash":false,"squash_on_merge":false,"task_completion_status":{"count":0,"completed_count":0},"has_conflicts":false,"blocking_discussions_resolved":true}"#;
+    let actual: MergeRequest = serde_json::from_str(raw).unwrap();
+    let author = User {
+      id: UserId::new(1),
+      username: CompactString::new("admin"),
+      name: CompactString::new("Administrator"),
+      state: CompactString::new("active"),
+      locked: false,
+      avatar_url: None,
+      web_url: "https://gitlab.example.com/admin".parse().unwrap(),
+    };
+    let expected = MergeRequest {
+      id: MergeRequestId::new(84),
+      iid: MergeRequestIid::new(3),
+      project_id: ProjectId::new(12),
+      title: CompactString::new("Draft: Add merge requests"),
+      description: Some(CompactString::new("Closes #1")),
+      state: MergeRequestState::Opened,
+      created_at: Utc.with_ymd_and_hms(2024, 2, 1, 10, 0, 0).unwrap(),
+      updated_at: Utc.with_ymd_and_hms(2024, 2, 2, 10, 0, 0).unwrap(),
+      merged_at: None,
+      merge_user: None,
+      closed_at: None,
+      closed_by: None,
+      source_project_id: ProjectId::new(12),
+      source_branch: CompactString::new("feature"),
+      target_project_id: ProjectId::new(12),
+      target_branch: CompactString::new("main"),
+      author,
+      assignees: Vec::new(),
+      reviewers: Vec::new(),
+      labels: vec![CompactString::new("backend")],
+      draft: true,
+      milestone: None,
+      merge_when_pipeline_succeeds: false,
+      detailed_merge_status: CompactString::new("draft_status"),
+      has_conflicts: false,
+      sha: Some(CompactString::new("ed899a2f4b50b4370feeea94676502b42383c746")),
+      merge_commit_sha: None,
+      squash_commit_sha: None,
+      squash: false,
+      force_remove_source_branch: Some(true),
+      should_remove_source_branch: None,
+      discussion_locked: None,
+      user_notes_count: 2,
+      references: MergeRequestReferences {
+        short: CompactString::new("!3"),
+        relative: CompactString::new("!3"),
+        full: CompactString::new("group/project!3"),
+      },
+      web_url: CompactString::new("https://gitlab.example.com/group/project/-/merge_requests/3"),
+      merge_error: None,
+      rebase_in_progress: None,
+    };
+    assert_eq!(actual, expected);
+  }
+
+  #[test]
+  fn read_merge_request_diff_version() {
+    let raw = r#"{"id":110,"head_commit_sha":"33e2ee8579fda5bc36accc9c6fbd0b4fefda9e30","base_commit_sha":"eeb57dffe83deb686a60a71c16c32f71046868fd","start_commit_sha":"eeb57dffe83deb686a60a71c16c32f71046868fd","created_at":"2024-02-01T10:00:00.000Z","merge_request_id":105,"state":"collected","real_size":"1","patch_id_sha":"d504412d5b6e6739647e752aff8e468dde093f2f","commits":[],"diffs":[{"old_path":"LICENSE","new_path":"LICENSE","a_mode":"0","b_mode":"100644","diff":"@@ -0,0 +1 @@\n+MIT\n","new_file":true,"renamed_file":false,"deleted_file":false,"generated_file":false}]}"#;
+    let actual: MergeRequestDiffVersion = serde_json::from_str(raw).unwrap();
+    assert_eq!(actual.id, MergeRequestDiffVersionId::new(110));
+    assert_eq!(actual.merge_request_id, MergeRequestId::new(105));
+    assert_eq!(actual.base_commit_sha, actual.start_commit_sha);
+    assert_eq!(actual.commits, Some(Vec::new()));
+    let diffs = actual.diffs.unwrap();
+    assert!(diffs[0].new_file);
+    assert_eq!(diffs[0].diff, "@@ -0,0 +1 @@\n+MIT\n");
+  }
+
+  #[test]
+  fn merge_failure_status() {
+    for failure in [
+      MergeFailure::NotMergeable,
+      MergeFailure::Conflict,
+      MergeFailure::ShaMismatch,
+    ] {
+      assert_eq!(MergeFailure::from_status(failure.status()), Some(failure));
+    }
+    assert_eq!(MergeFailure::from_status(404), None);
+  }
+
+  #[test]
+  fn rebase_failure_status() {
+    for failure in [RebaseFailure::Forbidden, RebaseFailure::Locked] {
+      assert_eq!(RebaseFailure::from_status(failure.status()), Some(failure));
+    }
+    assert_eq!(RebaseFailure::from_status(405), None);
+  }
+}
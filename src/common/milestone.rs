@@ -0,0 +1,139 @@
+use crate::common::group::GroupId;
+use crate::common::project::ProjectId;
+use chrono::{DateTime, NaiveDate, Utc};
+use compact_str::CompactString;
+
+/// Global milestone id
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MilestoneId(u64);
+
+impl MilestoneId {
+  pub const fn new(id: u64) -> Self {
+    Self(id)
+  }
+
+  pub const fn into_u64(self) -> u64 {
+    self.0
+  }
+
+  /// Calls `f` with the string representation of this id as an argument.
+  #[inline]
+  pub fn with_str<R, F>(self, f: F) -> R
+  where
+    F: for<'a> FnOnce(&'a str) -> R,
+  {
+    let mut buf = ::itoa::Buffer::new();
+    f(buf.format(self.0))
+  }
+}
+
+/// Milestone id, local to its project or group
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MilestoneIid(u64);
+
+impl MilestoneIid {
+  pub const fn new(id: u64) -> Self {
+    Self(id)
+  }
+
+  pub const fn into_u64(self) -> u64 {
+    self.0
+  }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Milestone {
+  pub id: MilestoneId,
+  pub iid: MilestoneIid,
+  /// Owner project, for project milestones
+  pub project_id: Option<ProjectId>,
+  /// Owner group, for group milestones
+  pub group_id: Option<GroupId>,
+  pub title: CompactString,
+  pub description: Option<CompactString>,
+  pub state: MilestoneState,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+  pub due_date: Option<NaiveDate>,
+  pub start_date: Option<NaiveDate>,
+  pub expired: Option<bool>,
+  pub web_url: CompactString,
+  /// Issue counts, only present in the milestones of a release
+  pub issue_stats: Option<MilestoneIssueStats>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MilestoneIssueStats {
+  pub total: u64,
+  pub closed: u64,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MilestoneState {
+  #[cfg_attr(feature = "serde", serde(rename = "active"))]
+  Active,
+  #[cfg_attr(feature = "serde", serde(rename = "closed"))]
+  Closed,
+}
+
+impl MilestoneState {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::Active => "active",
+      Self::Closed => "closed",
+    }
+  }
+}
+
+/// State transition applied when updating a milestone
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MilestoneStateEvent {
+  #[cfg_attr(feature = "serde", serde(rename = "activate"))]
+  Activate,
+  #[cfg_attr(feature = "serde", serde(rename = "close"))]
+  Close,
+}
+
+impl MilestoneStateEvent {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::Activate => "activate",
+      Self::Close => "close",
+    }
+  }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+  use super::*;
+  use chrono::TimeZone;
+
+  #[test]
+  fn read_milestone() {
+    let raw = r#"{"id":12,"iid":3,"project_id":16,"title":"10.0","description":"Version","due_date":"2013-11-29","start_date":"2013-11-10","state":"active","updated_at":"2013-10-02T09:24:18Z","created_at":"2013-10-02T09:24:18Z","expired":false,"web_url":"https://gitlab.com/group/project/-/milestones/3","issue_stats":{"total":5,"closed":2}}"#;
+    let actual: Milestone = serde_json::from_str(raw).unwrap();
+    let expected = Milestone {
+      id: MilestoneId::new(12),
+      iid: MilestoneIid::new(3),
+      project_id: Some(ProjectId::new(16)),
+      group_id: None,
+      title: CompactString::new("10.0"),
+      description: Some(CompactString::new("Version")),
+      state: MilestoneState::Active,
+      created_at: Utc.with_ymd_and_hms(2013, 10, 2, 9, 24, 18).unwrap(),
+      updated_at: Utc.with_ymd_and_hms(2013, 10, 2, 9, 24, 18).unwrap(),
+      due_date: NaiveDate::from_ymd_opt(2013, 11, 29),
+      start_date: NaiveDate::from_ymd_opt(2013, 11, 10),
+      expired: Some(false),
+      web_url: CompactString::new("https://gitlab.com/group/project/-/milestones/3"),
+      issue_stats: Some(MilestoneIssueStats { total: 5, closed: 2 }),
+    };
+    assert_eq!(actual, expected);
+  }
+}
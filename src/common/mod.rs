@@ -1,11 +1,27 @@
 use compact_str::CompactString;
 use std::num::NonZeroU8;
 
+pub mod approval;
+pub mod branch;
+pub mod commit;
+pub mod diff;
+pub mod epic;
+pub mod error;
 pub mod group;
+pub mod issue;
+pub mod merge_request;
+pub mod milestone;
 pub mod namespace;
+pub mod note;
 pub mod package;
 pub mod project;
+pub mod protected_access;
+pub mod protected_branch;
+pub mod protected_tag;
 pub mod release;
+pub mod repository_file;
+pub mod snippet;
+pub mod tag;
 pub mod topic;
 pub mod tree;
 pub mod user;
@@ -39,6 +55,16 @@ pub enum Visibility {
   Private,
 }
 
+impl Visibility {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::Public => "public",
+      Self::Internal => "internal",
+      Self::Private => "private",
+    }
+  }
+}
+
 /// Access Level, also referred as Role
 ///
 /// See <https://docs.gitlab.com/ee/api/members.html#roles>
@@ -0,0 +1,555 @@
+use crate::common::epic::EpicIid;
+use crate::common::group::GroupId;
+use crate::common::issue::IssueIid;
+use crate::common::merge_request::MergeRequestIid;
+use crate::common::project::{ProjectId, ProjectRef};
+use crate::common::snippet::SnippetId;
+use crate::common::user::User;
+use chrono::{DateTime, Utc};
+use compact_str::CompactString;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NoteId(u64);
+
+impl NoteId {
+  pub const fn new(id: u64) -> Self {
+    Self(id)
+  }
+
+  pub const fn into_u64(self) -> u64 {
+    self.0
+  }
+
+  /// Calls `f` with the string representation of this id as an argument.
+  #[inline]
+  pub fn with_str<R, F>(self, f: F) -> R
+  where
+    F: for<'a> FnOnce(&'a str) -> R,
+  {
+    let mut buf = ::itoa::Buffer::new();
+    f(buf.format(self.0))
+  }
+}
+
+/// Resource holding discussions
+///
+/// GitLab does not expose the notes of a commit outside of its discussions, so the notes API takes a
+/// [`NoteParentRef`] instead: list the notes of a commit with its discussions, and use
+/// [`NoteLocation::Discussion`] to reply to, update or delete them.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NoteableRef<Str = CompactString> {
+  Issue {
+    project: ProjectRef<Str>,
+    issue_iid: IssueIid,
+  },
+  MergeRequest {
+    project: ProjectRef<Str>,
+    merge_request_iid: MergeRequestIid,
+  },
+  Commit {
+    project: ProjectRef<Str>,
+    sha: Str,
+  },
+  Snippet {
+    project: ProjectRef<Str>,
+    snippet_id: SnippetId,
+  },
+  Epic {
+    group: GroupId,
+    epic_iid: EpicIid,
+  },
+}
+
+pub type NoteableRefView<'a> = NoteableRef<&'a str>;
+
+impl<Str: AsRef<str>> NoteableRef<Str> {
+  pub fn as_view(&self) -> NoteableRefView<'_> {
+    match self {
+      Self::Issue { project, issue_iid } => NoteableRef::Issue {
+        project: project.as_view(),
+        issue_iid: *issue_iid,
+      },
+      Self::MergeRequest {
+        project,
+        merge_request_iid,
+      } => NoteableRef::MergeRequest {
+        project: project.as_view(),
+        merge_request_iid: *merge_request_iid,
+      },
+      Self::Commit { project, sha } => NoteableRef::Commit {
+        project: project.as_view(),
+        sha: sha.as_ref(),
+      },
+      Self::Snippet { project, snippet_id } => NoteableRef::Snippet {
+        project: project.as_view(),
+        snippet_id: *snippet_id,
+      },
+      Self::Epic { group, epic_iid } => NoteableRef::Epic {
+        group: *group,
+        epic_iid: *epic_iid,
+      },
+    }
+  }
+
+  pub fn noteable_type(&self) -> NoteableType {
+    match self {
+      Self::Issue { .. } => NoteableType::Issue,
+      Self::MergeRequest { .. } => NoteableType::MergeRequest,
+      Self::Commit { .. } => NoteableType::Commit,
+      Self::Snippet { .. } => NoteableType::Snippet,
+      Self::Epic { .. } => NoteableType::Epic,
+    }
+  }
+
+  /// Calls `f` with the URL path segments of the noteable (e.g. `["projects", "1", "issues", "3"]`) as an argument.
+  #[inline]
+  pub fn with_path<R, F>(&self, f: F) -> R
+  where
+    F: for<'a> FnOnce(&[&'a str]) -> R,
+  {
+    match self {
+      Self::Issue { project, issue_iid } => {
+        project.with_str(|project| issue_iid.with_str(|iid| f(&["projects", project, "issues", iid])))
+      }
+      Self::MergeRequest {
+        project,
+        merge_request_iid,
+      } => {
+        project.with_str(|project| merge_request_iid.with_str(|iid| f(&["projects", project, "merge_requests", iid])))
+      }
+      Self::Commit { project, sha } => {
+        project.with_str(|project| f(&["projects", project, "repository", "commits", sha.as_ref()]))
+      }
+      Self::Snippet { project, snippet_id } => {
+        project.with_str(|project| snippet_id.with_str(|id| f(&["projects", project, "snippets", id])))
+      }
+      Self::Epic { group, epic_iid } => {
+        group.with_str(|group| epic_iid.with_str(|iid| f(&["groups", group, "epics", iid])))
+      }
+    }
+  }
+}
+
+/// Resource whose notes are exposed by the notes API: an issue, merge request, snippet or epic
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NoteParentRef<Str = CompactString> {
+  Issue {
+    project: ProjectRef<Str>,
+    issue_iid: IssueIid,
+  },
+  MergeRequest {
+    project: ProjectRef<Str>,
+    merge_request_iid: MergeRequestIid,
+  },
+  Snippet {
+    project: ProjectRef<Str>,
+    snippet_id: SnippetId,
+  },
+  Epic {
+    group: GroupId,
+    epic_iid: EpicIid,
+  },
+}
+
+pub type NoteParentRefView<'a> = NoteParentRef<&'a str>;
+
+impl<Str: AsRef<str>> NoteParentRef<Str> {
+  pub fn as_view(&self) -> NoteParentRefView<'_> {
+    match self {
+      Self::Issue { project, issue_iid } => NoteParentRef::Issue {
+        project: project.as_view(),
+        issue_iid: *issue_iid,
+      },
+      Self::MergeRequest {
+        project,
+        merge_request_iid,
+      } => NoteParentRef::MergeRequest {
+        project: project.as_view(),
+        merge_request_iid: *merge_request_iid,
+      },
+      Self::Snippet { project, snippet_id } => NoteParentRef::Snippet {
+        project: project.as_view(),
+        snippet_id: *snippet_id,
+      },
+      Self::Epic { group, epic_iid } => NoteParentRef::Epic {
+        group: *group,
+        epic_iid: *epic_iid,
+      },
+    }
+  }
+
+  pub fn noteable_type(&self) -> NoteableType {
+    NoteableRef::from(self.as_view()).noteable_type()
+  }
+
+  /// Calls `f` with the URL path segments of the noteable (e.g. `["projects", "1", "issues", "3"]`) as an argument.
+  #[inline]
+  pub fn with_path<R, F>(&self, f: F) -> R
+  where
+    F: for<'a> FnOnce(&[&'a str]) -> R,
+  {
+    NoteableRef::from(self.as_view()).with_path(f)
+  }
+}
+
+impl<Str> From<NoteParentRef<Str>> for NoteableRef<Str> {
+  fn from(parent: NoteParentRef<Str>) -> Self {
+    match parent {
+      NoteParentRef::Issue { project, issue_iid } => Self::Issue { project, issue_iid },
+      NoteParentRef::MergeRequest {
+        project,
+        merge_request_iid,
+      } => Self::MergeRequest {
+        project,
+        merge_request_iid,
+      },
+      NoteParentRef::Snippet { project, snippet_id } => Self::Snippet { project, snippet_id },
+      NoteParentRef::Epic { group, epic_iid } => Self::Epic { group, epic_iid },
+    }
+  }
+}
+
+/// Location of a note: directly on its parent, or in a discussion thread
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NoteLocation<Str = CompactString> {
+  /// Note of an issue, merge request, snippet or epic, through the notes API
+  Parent(NoteParentRef<Str>),
+  /// Note of a discussion thread, through the discussions API (required for the notes of a commit)
+  Discussion {
+    noteable: NoteableRef<Str>,
+    discussion_id: Str,
+  },
+}
+
+pub type NoteLocationView<'a> = NoteLocation<&'a str>;
+
+impl<Str: AsRef<str>> NoteLocation<Str> {
+  pub fn as_view(&self) -> NoteLocationView<'_> {
+    match self {
+      Self::Parent(parent) => NoteLocation::Parent(parent.as_view()),
+      Self::Discussion {
+        noteable,
+        discussion_id,
+      } => NoteLocation::Discussion {
+        noteable: noteable.as_view(),
+        discussion_id: discussion_id.as_ref(),
+      },
+    }
+  }
+
+  /// Resource holding the note
+  pub fn noteable(&self) -> NoteableRefView<'_> {
+    match self {
+      Self::Parent(parent) => NoteableRef::from(parent.as_view()),
+      Self::Discussion { noteable, .. } => noteable.as_view(),
+    }
+  }
+
+  pub fn discussion_id(&self) -> Option<&str> {
+    match self {
+      Self::Parent(_) => None,
+      Self::Discussion { discussion_id, .. } => Some(discussion_id.as_ref()),
+    }
+  }
+
+  /// Calls `f` with the URL path segments of the notes holding the note as an argument (e.g.
+  /// `["projects", "1", "issues", "3", "notes"]`, or `[..., "discussions", "<discussion id>", "notes"]`).
+  #[inline]
+  pub fn with_path<R, F>(&self, f: F) -> R
+  where
+    F: for<'a> FnOnce(&[&'a str]) -> R,
+  {
+    self.noteable().with_path(|noteable| {
+      let mut path = noteable.to_vec();
+      if let Some(discussion_id) = self.discussion_id() {
+        path.extend(["discussions", discussion_id]);
+      }
+      path.push("notes");
+      f(&path)
+    })
+  }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NoteableType {
+  Issue,
+  MergeRequest,
+  Commit,
+  Snippet,
+  Epic,
+}
+
+/// Kind of a note which is not a plain comment
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NoteType {
+  /// Note of a discussion thread
+  DiscussionNote,
+  /// Note of a discussion thread attached to a diff `position`
+  DiffNote,
+}
+
+/// Criteria used to order notes
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NoteOrder {
+  CreatedAt,
+  UpdatedAt,
+}
+
+impl NoteOrder {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::CreatedAt => "created_at",
+      Self::UpdatedAt => "updated_at",
+    }
+  }
+}
+
+/// Comment on an issue, merge request, commit, snippet or epic
+///
+/// <https://docs.gitlab.com/ee/api/notes.html>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Note {
+  pub id: NoteId,
+  #[cfg_attr(feature = "serde", serde(rename = "type"))]
+  pub note_type: Option<NoteType>,
+  pub body: String,
+  pub author: User,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+  /// The note was generated by GitLab to record a change (e.g. "changed the description")
+  pub system: bool,
+  /// Global id of the noteable, missing for commits
+  pub noteable_id: Option<u64>,
+  pub noteable_type: NoteableType,
+  /// Id of the issue or merge request, local to its project
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub noteable_iid: Option<u64>,
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub project_id: Option<ProjectId>,
+  /// Commit the note was made on, for commit notes and merge request diff notes
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub commit_id: Option<CompactString>,
+  /// Lines of the diff the note is attached to
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub position: Option<Position>,
+  /// The note belongs to a merge request thread which can be resolved
+  pub resolvable: bool,
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub resolved: Option<bool>,
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub resolved_by: Option<User>,
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub resolved_at: Option<DateTime<Utc>>,
+  /// Only visible to members with at least the reporter role
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub internal: bool,
+}
+
+/// Thread of notes
+///
+/// Standalone comments are returned as discussions with a single note and `individual_note` set.
+///
+/// <https://docs.gitlab.com/ee/api/discussions.html>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Discussion {
+  pub id: CompactString,
+  pub individual_note: bool,
+  pub notes: Vec<Note>,
+}
+
+/// Location of a note in the diff of a merge request or commit
+///
+/// For a merge request, the shas come from its latest diff version
+/// ([`MergeRequestDiffVersion`](crate::common::merge_request::MergeRequestDiffVersion)). Set `new_line` to comment an
+/// added line, `old_line` to comment a removed line, and both for an unchanged line.
+///
+/// <https://docs.gitlab.com/ee/api/discussions.html#create-a-new-thread-in-the-merge-request-diff>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position<Str = CompactString> {
+  /// Base commit of the source branch
+  pub base_sha: Str,
+  /// Head of the target branch when the diff was computed
+  pub start_sha: Str,
+  /// Head of the source branch
+  pub head_sha: Str,
+  pub position_type: PositionType,
+  /// Path of the file before the change
+  pub old_path: Str,
+  /// Path of the file after the change
+  pub new_path: Str,
+  #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+  pub old_line: Option<u64>,
+  #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+  pub new_line: Option<u64>,
+}
+
+pub type PositionView<'a> = Position<&'a str>;
+
+impl<Str: AsRef<str>> Position<Str> {
+  pub fn as_view(&self) -> PositionView<'_> {
+    PositionView {
+      base_sha: self.base_sha.as_ref(),
+      start_sha: self.start_sha.as_ref(),
+      head_sha: self.head_sha.as_ref(),
+      position_type: self.position_type,
+      old_path: self.old_path.as_ref(),
+      new_path: self.new_path.as_ref(),
+      old_line: self.old_line,
+      new_line: self.new_line,
+    }
+  }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PositionType {
+  /// Line of a text diff
+  #[cfg_attr(feature = "serde", serde(rename = "text"))]
+  Text,
+  /// Area of an image diff
+  #[cfg_attr(feature = "serde", serde(rename = "image"))]
+  Image,
+  /// Whole file
+  #[cfg_attr(feature = "serde", serde(rename = "file"))]
+  File,
+}
+
+impl PositionType {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::Text => "text",
+      Self::Image => "image",
+      Self::File => "file",
+    }
+  }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+  use super::*;
+  use crate::common::user::UserId;
+
+  #[test]
+  fn read_discussion() {
+    let raw = r#"{"id":"6a9c1750b37d513a43987b574953fceb50b03ce7","individual_note":false,"notes":[{"id":1128,"type":"DiffNote","body":"Off by one?","attachment":null,"author":{"id":1,"username":"admin","name":"Administrator","state":"active","locked":false,"avatar_url":null,"web_url":"https://gitlab.example.com/admin"},"created_at":"2024-01-01T00:00:00.000Z","updated_at":"2024-01-01T00:00:00.000Z","system":false,"noteable_id":84,"noteable_type":"MergeRequest","project_id":1,"commit_id":null,"position":{"base_sha":"b5d6e7b1613fca24d250fa8e5bc7bcfb4a6d1a5d","start_sha":"7c4ba6f8f3b1c6a7d5e6f4a1c2b3d4e5f6a7b8c9","head_sha":"ed899a2f4b50b4370feeea94676502b42383c746","old_path":"VERSION","new_path":"VERSION","position_type":"text","old_line":null,"new_line":1,"line_range":null},"resolvable":true,"resolved":false,"resolved_by":null,"resolved_at":null,"confidential":false,"internal":false,"noteable_iid":3,"commands_changes":{}}]}"#;
+    let actual: Discussion = serde_json::from_str(raw).unwrap();
+    assert!(!actual.individual_note);
+    let note = &actual.notes[0];
+    assert_eq!(note.note_type, Some(NoteType::DiffNote));
+    assert_eq!(note.author.id, UserId::new(1));
+    assert_eq!(note.noteable_type, NoteableType::MergeRequest);
+    assert_eq!(note.noteable_iid, Some(3));
+    let position = note.position.as_ref().unwrap();
+    assert_eq!(position.position_type, PositionType::Text);
+    assert_eq!(position.old_line, None);
+    assert_eq!(position.new_line, Some(1));
+  }
+
+  #[test]
+  fn read_commit_note() {
+    let raw = r#"{"id":1129,"type":null,"body":"Looks good","attachment":null,"author":{"id":1,"username":"admin","name":"Administrator","state":"active","locked":false,"avatar_url":null,"web_url":"https://gitlab.example.com/admin"},"created_at":"2024-01-01T00:00:00.000Z","updated_at":"2024-01-01T00:00:00.000Z","system":false,"noteable_id":null,"noteable_type":"Commit","project_id":1,"commit_id":"ed899a2f4b50b4370feeea94676502b42383c746","resolvable":false,"confidential":false,"internal":false,"noteable_iid":null,"commands_changes":{}}"#;
+    let actual: Note = serde_json::from_str(raw).unwrap();
+    assert_eq!(actual.note_type, None);
+    assert_eq!(actual.noteable_type, NoteableType::Commit);
+    assert_eq!(actual.noteable_id, None);
+    assert_eq!(
+      actual.commit_id.as_deref(),
+      Some("ed899a2f4b50b4370feeea94676502b42383c746")
+    );
+    assert_eq!(actual.resolved, None);
+  }
+  #[cfg(feature = "http")]
+  #[test]
+  fn note_urls() {
+    use crate::command::delete_note::DeleteNoteCommand;
+    use crate::common::project::ProjectSlug;
+    use crate::context::{Context, GitlabUrl};
+    use crate::endpoint::endpoint_url;
+    use crate::query::get_discussion_list::GetDiscussionListQuery;
+    use url::Url;
+
+    let context = Context::new().set_gitlab_url(GitlabUrl(Url::parse("https://gitlab.example.com/").unwrap()));
+    let project = ProjectRef::Slug(ProjectSlug::new(CompactString::new("group/project")));
+    let parents = [
+      (
+        NoteParentRef::Issue {
+          project: project.clone(),
+          issue_iid: IssueIid::new(5),
+        },
+        "projects/group%2Fproject/issues/5",
+      ),
+      (
+        NoteParentRef::MergeRequest {
+          project: project.clone(),
+          merge_request_iid: MergeRequestIid::new(3),
+        },
+        "projects/group%2Fproject/merge_requests/3",
+      ),
+      (
+        NoteParentRef::Snippet {
+          project: project.clone(),
+          snippet_id: SnippetId::new(9),
+        },
+        "projects/group%2Fproject/snippets/9",
+      ),
+      (
+        NoteParentRef::Epic {
+          group: GroupId::new(7),
+          epic_iid: EpicIid::new(2),
+        },
+        "groups/7/epics/2",
+      ),
+    ];
+    let commit = (
+      NoteableRef::Commit {
+        project,
+        sha: CompactString::new("ed899a2f"),
+      },
+      "projects/group%2Fproject/repository/commits/ed899a2f",
+    );
+    let delete = |location| DeleteNoteCommand {
+      context: &context,
+      auth: None,
+      location,
+      note_id: NoteId::new(1128),
+    };
+
+    for (parent, path) in parents.iter().cloned() {
+      let actual = endpoint_url(&delete(NoteLocation::Parent(parent))).unwrap();
+      assert_eq!(
+        actual.as_str(),
+        format!("https://gitlab.example.com/api/v4/{path}/notes/1128")
+      );
+    }
+    for (noteable, path) in parents
+      .into_iter()
+      .map(|(parent, path)| (parent.into(), path))
+      .chain([commit])
+    {
+      let actual = endpoint_url(&GetDiscussionListQuery::new(noteable.clone()).set_context(&context)).unwrap();
+      assert_eq!(
+        actual.as_str(),
+        format!("https://gitlab.example.com/api/v4/{path}/discussions")
+      );
+      let actual = endpoint_url(&delete(NoteLocation::Discussion {
+        noteable,
+        discussion_id: CompactString::new("6a9c1750"),
+      }))
+      .unwrap();
+      assert_eq!(
+        actual.as_str(),
+        format!("https://gitlab.example.com/api/v4/{path}/discussions/6a9c1750/notes/1128")
+      );
+    }
+  }
+}
@@ -1,3 +1,4 @@
+use crate::PackageStatus;
 use chrono::{DateTime, Utc};
 use compact_str::CompactString;
 
@@ -33,6 +34,103 @@ pub struct GitlabFile {
   pub url: CompactString,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PackageId(u64);
+
+impl PackageId {
+  pub const fn new(id: u64) -> Self {
+    Self(id)
+  }
+
+  pub const fn into_u64(self) -> u64 {
+    self.0
+  }
+
+  /// Calls `f` with the string representation of this id as an argument.
+  #[inline]
+  pub fn with_str<R, F>(self, f: F) -> R
+  where
+    F: for<'a> FnOnce(&'a str) -> R,
+  {
+    let mut buf = ::itoa::Buffer::new();
+    f(buf.format(self.0))
+  }
+}
+
+/// Package of the package registry
+///
+/// <https://docs.gitlab.com/ee/api/packages.html>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Package {
+  pub id: PackageId,
+  pub name: CompactString,
+  pub version: CompactString,
+  pub package_type: PackageType,
+  pub status: PackageStatus,
+  pub _links: PackageLinks,
+  pub created_at: DateTime<Utc>,
+  pub last_downloaded_at: Option<DateTime<Utc>>,
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub pipelines: Vec<PackagePipeline>,
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub tags: Vec<PackageTag>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PackageLinks {
+  pub web_path: CompactString,
+  /// Only present if the user is allowed to delete the package
+  pub delete_api_path: Option<CompactString>,
+}
+
+/// Pipeline which built a package
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PackagePipeline {
+  pub id: u64,
+  pub iid: Option<u64>,
+  pub project_id: Option<u64>,
+  pub sha: CompactString,
+  pub r#ref: CompactString,
+  pub status: CompactString,
+  pub source: Option<CompactString>,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+  pub web_url: CompactString,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PackageTag {
+  pub id: u64,
+  pub package_id: PackageId,
+  pub name: CompactString,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+}
+
+/// File of a package, as listed by the package file list
+///
+/// <https://docs.gitlab.com/ee/api/packages.html#list-package-files>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PackageFile {
+  pub id: u64,
+  pub package_id: PackageId,
+  pub created_at: DateTime<Utc>,
+  pub file_name: CompactString,
+  pub size: u64,
+  pub file_md5: Option<CompactString>,
+  pub file_sha1: Option<CompactString>,
+  /// Hex-encoded SHA-256 digest of the file content
+  pub file_sha256: Option<CompactString>,
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub pipelines: Vec<PackagePipeline>,
+}
+
 /// Criteria used to order packages
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -43,27 +141,67 @@ pub enum PackageOrder {
   Type,
 }
 
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+impl PackageOrder {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::CreatedAt => "created_at",
+      Self::Name => "name",
+      Self::Version => "version",
+      Self::Type => "type",
+    }
+  }
+}
+
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(rename_all = "snake_case")
+)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum PackageType {
   Composer,
   Conan,
+  Debian,
   Generic,
   Golang,
   Helm,
   Maven,
+  MlModel,
   Npm,
   Nuget,
   Pypi,
+  Rpm,
+  Rubygems,
   TerraformModule,
 }
 
-#[cfg(test)]
+impl PackageType {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::Composer => "composer",
+      Self::Conan => "conan",
+      Self::Debian => "debian",
+      Self::Generic => "generic",
+      Self::Golang => "golang",
+      Self::Helm => "helm",
+      Self::Maven => "maven",
+      Self::MlModel => "ml_model",
+      Self::Npm => "npm",
+      Self::Nuget => "nuget",
+      Self::Pypi => "pypi",
+      Self::Rpm => "rpm",
+      Self::Rubygems => "rubygems",
+      Self::TerraformModule => "terraform_module",
+    }
+  }
+}
+
+#[cfg(all(test, feature = "serde"))]
 mod test {
   use super::*;
   use chrono::TimeZone;
 
-  #[cfg_attr(feature = "serde", test)]
+  #[test]
   #[allow(deprecated)]
   fn read_publish_package_file_response() {
     let raw = r#"{"id":72696499,"package_id":13447789,"created_at":"2023-03-22T21:37:30.948Z","updated_at":"2023-03-22T21:37:30.948Z","size":11,"file_store":2,"file_md5":null,"file_sha1":null,"file_name":"eternaltwin3","file":{"url":"https://storage.googleapis.com/gitlab-gprd-package-repo/4a/ac/4aac49a9b7a3711a7fc154e49d6751b1b0d75358c4657d14c0bd55291a20c250/packages/13447789/files/72696499/eternaltwin3?GoogleAccessId=gitlab-object-storage-prd@gitlab-production.iam.gserviceaccount.com\u0026Signature=RozKlG66y15cnKPi5kNZZ2PVdJlYlzCGJ7fOOGnPU9kkOHoQqqAY3EwIBqhE%0Aydqr4u%2FYx9A%2BaoNPv9XUNlqnyHl0BLzFYjh5hUwFktix8IwD%2BkMgbrhJN0Yj%0AcHbJsVrDBEDSyMRjNWaBDo%2BzeAe9exYQdQ9iOyQct3zLXTkEwjS5fnnBoF0L%0APohSUcBLOeTm0gzZJJ1n4UB5yPb7RoDPZelN%2BCZxNdcY2AMjahQcnLgnzbnJ%0AFEHliT9107dZU33iKFprMQGcqCKupTFO%2FIu11uik2S%2BKAlAOjZySsKiV8%2BBE%0A%2Bi2pttVA%2FEW2P8soM9ZtYj4ReP1N8ZPPpW0AG6iTzA%3D%3D\u0026Expires=1679521651"},"file_sha256":"0ca093111f402faa55be1cd71006270644b58619eb0c2408b97b7d24bb70dd09","verification_retry_at":null,"verified_at":null,"verification_failure":null,"verification_retry_count":null,"verification_checksum":null,"verification_state":0,"verification_started_at":null,"status":"default","new_file_path":null}"#;
@@ -98,4 +236,62 @@ mod test {
     };
     assert_eq!(actual, expected);
   }
+
+  #[test]
+  fn read_package() {
+    let raw = r#"{"id":1,"name":"com/mycompany/my-app","version":"1.0-SNAPSHOT","package_type":"maven","status":"default","_links":{"web_path":"/namespace1/project1/-/packages/1","delete_api_path":"/namespace1/project1/-/packages/1"},"created_at":"2019-11-27T03:37:38.711Z","last_downloaded_at":null,"pipelines":[{"id":123,"status":"pending","ref":"new-pipeline","sha":"a91957a858320c0e17f3a0eca7cfacbff50ea29a","web_url":"https://example.com/foo/bar/pipelines/47","created_at":"2016-08-11T11:28:34.085Z","updated_at":"2016-08-11T11:32:35.169Z","user":{"name":"Administrator","avatar_url":"https://www.gravatar.com/avatar/e64c7d89f26bd1972efa854d13d7dd61?s=80&d=identicon"}}],"tags":[{"id":2,"package_id":1,"name":"latest","created_at":"2019-11-27T03:37:38.711Z","updated_at":"2019-11-27T03:37:38.711Z"}]}"#;
+    let actual: Package = serde_json::from_str(raw).unwrap();
+    let created_at = Utc.with_ymd_and_hms(2019, 11, 27, 3, 37, 38).unwrap() + chrono::Duration::milliseconds(711);
+    let expected = Package {
+      id: PackageId::new(1),
+      name: CompactString::new("com/mycompany/my-app"),
+      version: CompactString::new("1.0-SNAPSHOT"),
+      package_type: PackageType::Maven,
+      status: PackageStatus::Default,
+      _links: PackageLinks {
+        web_path: CompactString::new("/namespace1/project1/-/packages/1"),
+        delete_api_path: Some(CompactString::new("/namespace1/project1/-/packages/1")),
+      },
+      created_at,
+      last_downloaded_at: None,
+      pipelines: vec![PackagePipeline {
+        id: 123,
+        iid: None,
+        project_id: None,
+        sha: CompactString::new("a91957a858320c0e17f3a0eca7cfacbff50ea29a"),
+        r#ref: CompactString::new("new-pipeline"),
+        status: CompactString::new("pending"),
+        source: None,
+        created_at: Utc.with_ymd_and_hms(2016, 8, 11, 11, 28, 34).unwrap() + chrono::Duration::milliseconds(85),
+        updated_at: Utc.with_ymd_and_hms(2016, 8, 11, 11, 32, 35).unwrap() + chrono::Duration::milliseconds(169),
+        web_url: CompactString::new("https://example.com/foo/bar/pipelines/47"),
+      }],
+      tags: vec![PackageTag {
+        id: 2,
+        package_id: PackageId::new(1),
+        name: CompactString::new("latest"),
+        created_at,
+        updated_at: created_at,
+      }],
+    };
+    assert_eq!(actual, expected);
+  }
+
+  #[test]
+  fn read_package_file() {
+    let raw = r#"{"id":25,"package_id":4,"created_at":"2018-11-07T15:25:52.199Z","file_name":"my-app-1.5-20181107.152550-1.jar","size":2421,"file_md5":"58e6a45a629910c6ff99145a688971ac","file_sha1":"ebd193463d3915d7e22219f52740056dfd26cbfe","file_sha256":"a903393463d3915d7e22219f52740056dfd26cbfeff321b","pipelines":[]}"#;
+    let actual: PackageFile = serde_json::from_str(raw).unwrap();
+    let expected = PackageFile {
+      id: 25,
+      package_id: PackageId::new(4),
+      created_at: Utc.with_ymd_and_hms(2018, 11, 7, 15, 25, 52).unwrap() + chrono::Duration::milliseconds(199),
+      file_name: CompactString::new("my-app-1.5-20181107.152550-1.jar"),
+      size: 2421,
+      file_md5: Some(CompactString::new("58e6a45a629910c6ff99145a688971ac")),
+      file_sha1: Some(CompactString::new("ebd193463d3915d7e22219f52740056dfd26cbfe")),
+      file_sha256: Some(CompactString::new("a903393463d3915d7e22219f52740056dfd26cbfeff321b")),
+      pipelines: Vec::new(),
+    };
+    assert_eq!(actual, expected);
+  }
 }
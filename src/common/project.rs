@@ -238,6 +238,24 @@ pub enum ProjectOrder {
   WikiSize,
 }
 
+impl ProjectOrder {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::Id => "id",
+      Self::Name => "name",
+      Self::Path => "path",
+      Self::CreatedAt => "created_at",
+      Self::UpdatedAt => "updated_at",
+      Self::LastActivityAt => "last_activity_at",
+      Self::Similarity => "similarity",
+      Self::RespositorySize => "repository_size",
+      Self::StorageSize => "storage_size",
+      Self::PackageSize => "package_size",
+      Self::WikiSize => "wiki_size",
+    }
+  }
+}
+
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ProjectLinks {
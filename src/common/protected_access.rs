@@ -0,0 +1,103 @@
+use crate::common::group::GroupId;
+use crate::common::user::UserId;
+use crate::common::AccessLevel;
+use compact_str::CompactString;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProtectedAccessId(u64);
+
+impl ProtectedAccessId {
+  pub const fn new(id: u64) -> Self {
+    Self(id)
+  }
+
+  pub const fn into_u64(self) -> u64 {
+    self.0
+  }
+}
+
+/// Entry of the access lists of a protected branch or tag (e.g. `push_access_levels`)
+///
+/// An entry grants access either to a role (`access_level`), a user, a group or a deploy key.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProtectedAccess {
+  pub id: ProtectedAccessId,
+  pub access_level: Option<AccessLevel>,
+  /// Human-readable description of the entry (e.g. `Maintainers`, or the user name)
+  pub access_level_description: CompactString,
+  pub user_id: Option<UserId>,
+  pub group_id: Option<GroupId>,
+  pub deploy_key_id: Option<u64>,
+}
+
+/// Access granted when protecting a branch or tag
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProtectedAccessRule {
+  /// Every member with at least this role (`NoAccess` for nobody)
+  AccessLevel(AccessLevel),
+  User(UserId),
+  Group(GroupId),
+}
+
+/// Change to the access list of an existing protected branch or tag
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProtectedAccessChange {
+  Add(ProtectedAccessRule),
+  /// Remove the entry with this id
+  Remove(ProtectedAccessId),
+}
+
+/// JSON entry of an `allowed_to_*` list, in the protected branch and tag requests
+#[cfg(feature = "http")]
+#[derive(Debug, serde::Serialize)]
+pub(crate) struct ProtectedAccessBody {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  id: Option<u64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  access_level: Option<u8>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  user_id: Option<u64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  group_id: Option<u64>,
+  #[serde(rename = "_destroy", skip_serializing_if = "Option::is_none")]
+  destroy: Option<bool>,
+}
+
+#[cfg(feature = "http")]
+impl From<ProtectedAccessRule> for ProtectedAccessBody {
+  fn from(rule: ProtectedAccessRule) -> Self {
+    let mut body = Self {
+      id: None,
+      access_level: None,
+      user_id: None,
+      group_id: None,
+      destroy: None,
+    };
+    match rule {
+      ProtectedAccessRule::AccessLevel(level) => body.access_level = Some(level.to_u8()),
+      ProtectedAccessRule::User(user) => body.user_id = Some(user.into_u64()),
+      ProtectedAccessRule::Group(group) => body.group_id = Some(group.into_u64()),
+    }
+    body
+  }
+}
+
+#[cfg(feature = "http")]
+impl From<ProtectedAccessChange> for ProtectedAccessBody {
+  fn from(change: ProtectedAccessChange) -> Self {
+    match change {
+      ProtectedAccessChange::Add(rule) => Self::from(rule),
+      ProtectedAccessChange::Remove(id) => Self {
+        id: Some(id.into_u64()),
+        access_level: None,
+        user_id: None,
+        group_id: None,
+        destroy: Some(true),
+      },
+    }
+  }
+}
@@ -0,0 +1,72 @@
+use crate::common::protected_access::ProtectedAccess;
+use compact_str::CompactString;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProtectedBranchId(u64);
+
+impl ProtectedBranchId {
+  pub const fn new(id: u64) -> Self {
+    Self(id)
+  }
+
+  pub const fn into_u64(self) -> u64 {
+    self.0
+  }
+}
+
+/// Protection rule of the branches matching a name or wildcard (e.g. `release/*`)
+///
+/// <https://docs.gitlab.com/ee/api/protected_branches.html>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProtectedBranch {
+  pub id: ProtectedBranchId,
+  /// Branch name or wildcard
+  pub name: CompactString,
+  pub push_access_levels: Vec<ProtectedAccess>,
+  pub merge_access_levels: Vec<ProtectedAccess>,
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub unprotect_access_levels: Vec<ProtectedAccess>,
+  pub allow_force_push: bool,
+  /// Require the approval of code owners (Premium)
+  pub code_owner_approval_required: Option<bool>,
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+  use super::*;
+  use crate::common::protected_access::ProtectedAccessId;
+  use crate::common::user::UserId;
+  use crate::common::AccessLevel;
+
+  #[test]
+  fn read_protected_branch() {
+    let raw = r#"{"id":1,"name":"main","push_access_levels":[{"id":1,"access_level":40,"access_level_description":"Maintainers","deploy_key_id":null,"user_id":null,"group_id":null}],"merge_access_levels":[{"id":1,"access_level":null,"access_level_description":"John Smith","user_id":7,"group_id":null}],"allow_force_push":false,"code_owner_approval_required":false}"#;
+    let actual: ProtectedBranch = serde_json::from_str(raw).unwrap();
+    let expected = ProtectedBranch {
+      id: ProtectedBranchId::new(1),
+      name: CompactString::new("main"),
+      push_access_levels: vec![ProtectedAccess {
+        id: ProtectedAccessId::new(1),
+        access_level: Some(AccessLevel::Maintainer),
+        access_level_description: CompactString::new("Maintainers"),
+        user_id: None,
+        group_id: None,
+        deploy_key_id: None,
+      }],
+      merge_access_levels: vec![ProtectedAccess {
+        id: ProtectedAccessId::new(1),
+        access_level: None,
+        access_level_description: CompactString::new("John Smith"),
+        user_id: Some(UserId::new(7)),
+        group_id: None,
+        deploy_key_id: None,
+      }],
+      unprotect_access_levels: Vec::new(),
+      allow_force_push: false,
+      code_owner_approval_required: Some(false),
+    };
+    assert_eq!(actual, expected);
+  }
+}
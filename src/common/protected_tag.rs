@@ -0,0 +1,13 @@
+use crate::common::protected_access::ProtectedAccess;
+use compact_str::CompactString;
+
+/// Protection rule of the tags matching a name or wildcard (e.g. `v*`)
+///
+/// <https://docs.gitlab.com/ee/api/protected_tags.html>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProtectedTag {
+  /// Tag name or wildcard
+  pub name: CompactString,
+  pub create_access_levels: Vec<ProtectedAccess>,
+}
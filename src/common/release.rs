@@ -1,3 +1,5 @@
+use crate::common::commit::Commit;
+use crate::common::milestone::Milestone;
 use chrono::{DateTime, Utc};
 use compact_str::CompactString;
 
@@ -45,29 +47,6 @@ pub struct Author {
   pub web_url: String,
 }
 
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Commit {
-  pub id: String,
-  pub short_id: String,
-  pub title: String,
-  pub created_at: DateTime<Utc>,
-  pub parent_ids: Vec<String>,
-  pub message: String,
-  pub author_name: String,
-  pub author_email: String,
-  pub authored_date: DateTime<Utc>,
-  pub committer_name: String,
-  pub committer_email: String,
-  pub committed_date: DateTime<Utc>,
-}
-
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Milestone {
-  // TODO
-}
-
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ReleaseAssets {
@@ -86,20 +65,24 @@ pub struct ReleaseSource {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ReleaseEvidence {
-  // TODO
+  /// SHA-256 digest of the evidence
+  pub sha: String,
+  /// URL of the evidence JSON document
+  pub filepath: String,
+  pub collected_at: DateTime<Utc>,
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ReleaseLinks {
-  closed_issues_url: String,
-  closed_merge_requests_url: String,
-  edit_url: Option<String>,
-  merged_merge_requests_url: String,
-  opened_issues_url: String,
-  opened_merge_requests_url: String,
+  pub closed_issues_url: String,
+  pub closed_merge_requests_url: String,
+  pub edit_url: Option<String>,
+  pub merged_merge_requests_url: String,
+  pub opened_issues_url: String,
+  pub opened_merge_requests_url: String,
   #[cfg_attr(feature = "serde", serde(rename = "self"))]
-  this: String,
+  pub this: String,
 }
 
 /// Criteria used to order releases
@@ -131,6 +114,14 @@ impl ReleaseLinkId {
   pub const fn into_u64(self) -> u64 {
     self.0
   }
+
+  pub fn with_str<R, F>(self, f: F) -> R
+  where
+    F: for<'a> FnOnce(&'a str) -> R,
+  {
+    let mut buf = ::itoa::Buffer::new();
+    f(buf.format(self.0))
+  }
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -140,6 +131,8 @@ pub struct ReleaseLink {
   pub name: String,
   pub url: String,
   pub direct_asset_url: String,
+  /// Path of the direct asset link, relative to the release downloads (only returned by some GitLab versions)
+  pub direct_asset_path: Option<String>,
   pub link_type: ReleaseLinkType,
 }
 
@@ -208,12 +201,12 @@ impl<Str: AsRef<str>> InputReleaseLink<Str> {
   }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "serde"))]
 mod test {
   use super::*;
   use chrono::{TimeZone, Utc};
 
-  #[cfg_attr(feature = "serde", test)]
+  #[test]
   #[allow(deprecated)]
   fn read_release() {
     let raw = r#"{"name": "v0.12.5","tag_name": "v0.12.5","description": null,"created_at": "2023-03-25T23:03:17.165Z","released_at": "2023-03-25T23:03:17.165Z","upcoming_release": false,"author": {"id": 743516,"username": "demurgos","name": "Charles Samborski","state": "active","avatar_url": "https://secure.gravatar.com/avatar/4ccbb457b6d50bb79ec32b66c2f4e301?s=80&d=identicon","web_url": "https://gitlab.com/demurgos"},"commit": {"id": "e8cfd320ee2b8dcee22b0bc0ceaf52d7dd667c2d","short_id": "e8cfd320","created_at": "2023-03-17T00:39:35.000+01:00","parent_ids": ["c8528d12e05c9ee803547eb556cb5394ac3d1abc"],"title": "[bin] Add support for precompiled binary","message": "[bin] Add support for precompiled binary\n\nThis commit adds support for precompiled binaries built in GitLab CI.\n","author_name": "Charles Samborski","author_email": "demurgos@demurgos.net","authored_date": "2023-03-16T23:56:54.000+01:00","committer_name": "Charles Samborski","committer_email": "demurgos@demurgos.net","committed_date": "2023-03-17T00:39:35.000+01:00","trailers": {},"web_url": "https://gitlab.com/eternaltwin/eternaltwin/-/commit/e8cfd320ee2b8dcee22b0bc0ceaf52d7dd667c2d"},"commit_path": "/eternaltwin/eternaltwin/-/commit/e8cfd320ee2b8dcee22b0bc0ceaf52d7dd667c2d","tag_path": "/eternaltwin/eternaltwin/-/tags/v0.12.5","assets": {"count": 5,"sources": [{"format": "zip","url": "https://gitlab.com/eternaltwin/eternaltwin/-/archive/v0.12.5/eternaltwin-v0.12.5.zip"},{"format": "tar.gz","url": "https://gitlab.com/eternaltwin/eternaltwin/-/archive/v0.12.5/eternaltwin-v0.12.5.tar.gz"},{"format": "tar.bz2","url": "https://gitlab.com/eternaltwin/eternaltwin/-/archive/v0.12.5/eternaltwin-v0.12.5.tar.bz2"},{"format": "tar","url": "https://gitlab.com/eternaltwin/eternaltwin/-/archive/v0.12.5/eternaltwin-v0.12.5.tar"}],"links": [{"id": 1492034,"name": "eternaltwin-x86_64-apple-darwin","url": "https://gitlab.com/eternaltwin/eternaltwin/-/packages/13511182","direct_asset_url": "https://gitlab.com/eternaltwin/eternaltwin/-/releases/v0.12.5/downloads/eternaltwin-x86_64-apple-darwin","link_type": "package"}]},"evidences": [{"sha": "f9021712d926f78fc9272ac24733492facd5fb4353ed","filepath": "https://gitlab.com/eternaltwin/eternaltwin/-/releases/v0.12.5/evidences/4460894.json","collected_at": "2023-03-25T23:03:17.328Z"}],"_links": {"closed_issues_url": "https://gitlab.com/eternaltwin/eternaltwin/-/issues?release_tag=v0.12.5&scope=all&state=closed","closed_merge_requests_url": "https://gitlab.com/eternaltwin/eternaltwin/-/merge_requests?release_tag=v0.12.5&scope=all&state=closed","edit_url": "https://gitlab.com/eternaltwin/eternaltwin/-/releases/v0.12.5/edit","merged_merge_requests_url": "https://gitlab.com/eternaltwin/eternaltwin/-/merge_requests?release_tag=v0.12.5&scope=all&state=merged","opened_issues_url": "https://gitlab.com/eternaltwin/eternaltwin/-/issues?release_tag=v0.12.5&scope=all&state=opened","opened_merge_requests_url": "https://gitlab.com/eternaltwin/eternaltwin/-/merge_requests?release_tag=v0.12.5&scope=all&state=opened","self": "https://gitlab.com/eternaltwin/eternaltwin/-/releases/v0.12.5"}}"#;
@@ -248,6 +241,11 @@ mod test {
         committer_name: "Charles Samborski".to_string(),
         committer_email: "demurgos@demurgos.net".to_string(),
         committed_date: Utc.ymd(2023, 3, 16).and_hms(23, 39, 35),
+        trailers: Some(Default::default()),
+        web_url: Some(
+          "https://gitlab.com/eternaltwin/eternaltwin/-/commit/e8cfd320ee2b8dcee22b0bc0ceaf52d7dd667c2d".to_string(),
+        ),
+        stats: None,
       },
       milestones: None,
       commit_path: "/eternaltwin/eternaltwin/-/commit/e8cfd320ee2b8dcee22b0bc0ceaf52d7dd667c2d".to_string(),
@@ -278,12 +276,17 @@ mod test {
             name: "eternaltwin-x86_64-apple-darwin".to_string(),
             url: "https://gitlab.com/eternaltwin/eternaltwin/-/packages/13511182".to_string(),
             direct_asset_url: "https://gitlab.com/eternaltwin/eternaltwin/-/releases/v0.12.5/downloads/eternaltwin-x86_64-apple-darwin".to_string(),
+            direct_asset_path: None,
             link_type: ReleaseLinkType::Package,
           }
         ],
       },
       evidences: vec![
-        ReleaseEvidence {},
+        ReleaseEvidence {
+          sha: "f9021712d926f78fc9272ac24733492facd5fb4353ed".to_string(),
+          filepath: "https://gitlab.com/eternaltwin/eternaltwin/-/releases/v0.12.5/evidences/4460894.json".to_string(),
+          collected_at: Utc.ymd(2023, 3, 25).and_hms_milli(23, 3, 17, 328),
+        },
       ],
       _links: ReleaseLinks {
         closed_issues_url: "https://gitlab.com/eternaltwin/eternaltwin/-/issues?release_tag=v0.12.5&scope=all&state=closed".to_string(),
@@ -298,7 +301,7 @@ mod test {
     assert_eq!(actual, expected);
   }
 
-  #[cfg_attr(feature = "serde", test)]
+  #[test]
   #[allow(deprecated)]
   fn read_release_link() {
     let raw = r#"{"id":1492019,"name":"eternaltwin-x86_64-unknown-linux-gnu","url":"https://gitlab.com/eternaltwin/eternaltwin/-/packages/13511234","direct_asset_url":"https://gitlab.com/eternaltwin/eternaltwin/-/releases/v0.12.5/downloads/eternaltwin-x86_64-unknown-linux-gnu","link_type":"package"}"#;
@@ -310,12 +313,13 @@ mod test {
       direct_asset_url:
         "https://gitlab.com/eternaltwin/eternaltwin/-/releases/v0.12.5/downloads/eternaltwin-x86_64-unknown-linux-gnu"
           .to_string(),
+      direct_asset_path: None,
       link_type: ReleaseLinkType::Package,
     };
     assert_eq!(actual, expected);
   }
 
-  #[cfg_attr(feature = "serde", test)]
+  #[test]
   #[allow(deprecated)]
   fn read_xml2_release_list() {
     let raw = include_str!("../../test-resources/get-project-release-list/libxml2/output.json");
@@ -0,0 +1,32 @@
+use bytes::Bytes;
+use compact_str::CompactString;
+
+/// File of a repository, with its content
+///
+/// The content is decoded: it holds the raw bytes of the file, not the base64 representation returned by GitLab.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RepositoryFile {
+  pub file_name: CompactString,
+  pub file_path: CompactString,
+  pub size: u64,
+  pub content: Bytes,
+  /// Hex-encoded SHA-256 digest of the content
+  pub content_sha256: CompactString,
+  /// Branch, tag or commit the file was read from
+  pub r#ref: CompactString,
+  pub blob_id: CompactString,
+  /// Commit the file was read from
+  pub commit_id: CompactString,
+  /// Last commit which changed the file, used for optimistic locking when updating or deleting it
+  pub last_commit_id: CompactString,
+  pub execute_filemode: Option<bool>,
+}
+
+/// Response to a repository file creation or update
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RepositoryFileCommit {
+  pub file_path: CompactString,
+  pub branch: CompactString,
+}
@@ -0,0 +1,213 @@
+use crate::common::commit::Commit;
+use chrono::{DateTime, Utc};
+use compact_str::CompactString;
+
+/// Repository tag
+///
+/// <https://docs.gitlab.com/ee/api/tags.html>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Tag {
+  pub name: CompactString,
+  /// Message of an annotated tag, `None` for a lightweight tag
+  pub message: Option<String>,
+  /// Id of the tag object for an annotated tag, or of the tagged commit for a lightweight tag
+  pub target: CompactString,
+  pub commit: Commit,
+  /// Release associated with the tag
+  pub release: Option<TagRelease>,
+  pub protected: bool,
+  /// Creation date of an annotated tag
+  pub created_at: Option<DateTime<Utc>>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TagRelease {
+  pub tag_name: CompactString,
+  pub description: Option<String>,
+}
+
+/// Criteria used to order tags
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TagOrder {
+  Name,
+  Updated,
+  /// Semantic version of the tag name
+  Version,
+}
+
+impl TagOrder {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::Name => "name",
+      Self::Updated => "updated",
+      Self::Version => "version",
+    }
+  }
+}
+
+/// Signature of a tag, with its verification status
+///
+/// <https://docs.gitlab.com/ee/api/tags.html#get-x509-signature-of-a-tag>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "signature_type", try_from = "RawTagSignature"))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TagSignature {
+  #[cfg_attr(feature = "serde", serde(rename = "PGP"))]
+  Gpg(GpgSignature),
+  #[cfg_attr(feature = "serde", serde(rename = "X509"))]
+  X509(X509Signature),
+}
+
+impl TagSignature {
+  pub fn verification_status(&self) -> SignatureVerificationStatus {
+    match self {
+      Self::Gpg(signature) => signature.verification_status,
+      Self::X509(signature) => signature.verification_status,
+    }
+  }
+}
+
+/// Flat representation of a [`TagSignature`], used to deserialize it
+///
+/// Serde buffers internally tagged enums, and its buffer does not support the `u128` serial numbers of the X.509
+/// certificates.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawTagSignature {
+  signature_type: CompactString,
+  verification_status: SignatureVerificationStatus,
+  gpg_key_id: Option<u64>,
+  gpg_key_primary_keyid: Option<CompactString>,
+  gpg_key_user_name: Option<CompactString>,
+  gpg_key_user_email: Option<CompactString>,
+  gpg_key_subkey_id: Option<u64>,
+  x509_certificate: Option<X509Certificate>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawTagSignature> for TagSignature {
+  type Error = String;
+
+  fn try_from(raw: RawTagSignature) -> Result<Self, Self::Error> {
+    match raw.signature_type.as_str() {
+      "PGP" => Ok(Self::Gpg(GpgSignature {
+        verification_status: raw.verification_status,
+        gpg_key_id: raw.gpg_key_id,
+        gpg_key_primary_keyid: raw.gpg_key_primary_keyid,
+        gpg_key_user_name: raw.gpg_key_user_name,
+        gpg_key_user_email: raw.gpg_key_user_email,
+        gpg_key_subkey_id: raw.gpg_key_subkey_id,
+      })),
+      "X509" => Ok(Self::X509(X509Signature {
+        verification_status: raw.verification_status,
+        x509_certificate: raw
+          .x509_certificate
+          .ok_or_else(|| String::from("missing field `x509_certificate`"))?,
+      })),
+      other => Err(format!("unknown signature type `{other}`")),
+    }
+  }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GpgSignature {
+  pub verification_status: SignatureVerificationStatus,
+  pub gpg_key_id: Option<u64>,
+  pub gpg_key_primary_keyid: Option<CompactString>,
+  pub gpg_key_user_name: Option<CompactString>,
+  pub gpg_key_user_email: Option<CompactString>,
+  pub gpg_key_subkey_id: Option<u64>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct X509Signature {
+  pub verification_status: SignatureVerificationStatus,
+  pub x509_certificate: X509Certificate,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct X509Certificate {
+  pub id: u64,
+  pub subject: String,
+  pub subject_key_identifier: CompactString,
+  pub email: CompactString,
+  pub serial_number: u128,
+  /// Revocation status of the certificate: `good` or `revoked`
+  pub certificate_status: CompactString,
+  pub x509_issuer: X509Issuer,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct X509Issuer {
+  pub id: u64,
+  pub subject: String,
+  pub subject_key_identifier: CompactString,
+  pub crl_url: Option<String>,
+}
+
+/// Result of the verification of a signature
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SignatureVerificationStatus {
+  Verified,
+  /// Signed by GitLab itself
+  VerifiedSystem,
+  /// Signed with a certificate issued by a trusted certificate authority
+  VerifiedCa,
+  Unverified,
+  UnverifiedKey,
+  UnknownKey,
+  SameUserDifferentEmail,
+  OtherUser,
+  MultipleSignatures,
+  RevokedKey,
+}
+
+impl SignatureVerificationStatus {
+  /// The signature is valid and matches its signer
+  pub fn is_verified(self) -> bool {
+    matches!(self, Self::Verified | Self::VerifiedSystem | Self::VerifiedCa)
+  }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+  use super::*;
+
+  #[test]
+  fn read_x509_signature() {
+    let raw = r#"{"signature_type":"X509","verification_status":"unverified","x509_certificate":{"id":1,"subject":"CN=gitlab@example.org,OU=Example,O=World","subject_key_identifier":"BC:BC:BC:BC:BC:BC:BC:BC:BC:BC:BC:BC:BC:BC:BC:BC:BC:BC:BC:BC","email":"gitlab@example.org","serial_number":278969561018901340486471282831158785578,"certificate_status":"good","x509_issuer":{"id":1,"subject":"CN=PKI,OU=Example,O=World","subject_key_identifier":"AB:AB:AB:AB:AB:AB:AB:AB:AB:AB:AB:AB:AB:AB:AB:AB:AB:AB:AB:AB","crl_url":"http://example.com/pki.crl"}}}"#;
+    let actual: TagSignature = serde_json::from_str(raw).unwrap();
+    let TagSignature::X509(signature) = &actual else {
+      panic!("expected an X.509 signature: {actual:?}");
+    };
+    assert_eq!(
+      signature.x509_certificate.serial_number,
+      278969561018901340486471282831158785578
+    );
+    assert!(!actual.verification_status().is_verified());
+  }
+
+  #[test]
+  fn read_gpg_signature() {
+    let raw = r#"{"signature_type":"PGP","verification_status":"verified","gpg_key_id":1,"gpg_key_primary_keyid":"8254AAB3FBD54AC9","gpg_key_user_name":"John Doe","gpg_key_user_email":"johndoe@example.com","gpg_key_subkey_id":null}"#;
+    let actual: TagSignature = serde_json::from_str(raw).unwrap();
+    let expected = TagSignature::Gpg(GpgSignature {
+      verification_status: SignatureVerificationStatus::Verified,
+      gpg_key_id: Some(1),
+      gpg_key_primary_keyid: Some(CompactString::new("8254AAB3FBD54AC9")),
+      gpg_key_user_name: Some(CompactString::new("John Doe")),
+      gpg_key_user_email: Some(CompactString::new("johndoe@example.com")),
+      gpg_key_subkey_id: None,
+    });
+    assert_eq!(actual, expected);
+  }
+}
@@ -45,6 +45,12 @@ pub trait GetRef<T: ?Sized> {
   fn get_ref(&self) -> &T;
 }
 
+impl<T: ?Sized, C: GetRef<T> + ?Sized> GetRef<T> for &C {
+  fn get_ref(&self) -> &T {
+    (**self).get_ref()
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct GitlabUrl(pub Url);
 
@@ -0,0 +1,480 @@
+//! Declarative description of the GitLab API endpoints
+//!
+//! Each query and command implements [`Endpoint`] to describe the HTTP request it corresponds to and how to decode
+//! the response. Clients then provide a single generic `Service` implementation executing any endpoint.
+
+use crate::client::http::HttpGitlabClientError;
+use crate::common::merge_request::{MergeRequestOrder, MergeRequestScope, MergeRequestState};
+use crate::common::milestone::{MilestoneIid, MilestoneState};
+use crate::common::note::NoteOrder;
+use crate::common::package::{PackageOrder, PackageType};
+use crate::common::project::{ProjectId, ProjectOrder};
+use crate::common::release::ReleaseOrder;
+use crate::common::tag::TagOrder;
+use crate::common::topic::TopicId;
+use crate::common::user::UserId;
+use crate::common::{AccessLevel, KeysetPagination, Page, SortOrder, Visibility};
+use crate::{GitlabAuthView, PackageStatus};
+use bytes::Bytes;
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use compact_str::CompactString;
+use demurgos_headers::link::{Link, RelationType};
+use http::{HeaderMap, Method, Response};
+use serde::de::DeserializeOwned;
+use std::num::NonZeroU8;
+use url::form_urlencoded::Serializer;
+use url::{Url, UrlQuery};
+
+/// Query string builder passed to [`Endpoint::query`]
+pub type QueryPairs<'a> = Serializer<'a, UrlQuery<'a>>;
+
+/// Description of a GitLab API endpoint
+pub trait Endpoint {
+  /// Request context (GitLab URL, user agent, ...)
+  type Context;
+  /// Decoded response
+  type Output;
+
+  /// HTTP method used to call this endpoint
+  const METHOD: Method;
+
+  fn context(&self) -> &Self::Context;
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>>;
+
+  /// URL of the endpoint, without query parameters
+  fn url(&self) -> Result<Url, url::ParseError>;
+
+  /// Append the query parameters of the request
+  fn query(&self, _query: &mut QueryPairs<'_>) {}
+
+  /// Request body
+  fn body(&self) -> EndpointBody {
+    EndpointBody::Empty
+  }
+
+  /// Decode a successful response
+  fn decode(res: Response<Bytes>) -> Result<Self::Output, DecodeError>;
+
+  /// Refine the error of a failed request, e.g. to classify the error responses specific to this endpoint
+  fn map_error(err: HttpGitlabClientError) -> HttpGitlabClientError {
+    err
+  }
+}
+
+/// Body of an endpoint request
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EndpointBody {
+  Empty,
+  /// Serialized JSON document, sent as `application/json`
+  Json(Vec<u8>),
+  /// Raw bytes
+  Raw(Bytes),
+}
+
+impl EndpointBody {
+  pub fn json<T: serde::Serialize>(value: &T) -> Self {
+    Self::Json(serde_json::to_vec(value).expect("request body serialization never fails"))
+  }
+
+  pub fn content_type(&self) -> Option<&'static str> {
+    match self {
+      Self::Json(_) => Some("application/json"),
+      Self::Empty | Self::Raw(_) => None,
+    }
+  }
+
+  pub fn into_bytes(self) -> Bytes {
+    match self {
+      Self::Empty => Bytes::new(),
+      Self::Json(body) => Bytes::from(body),
+      Self::Raw(body) => body,
+    }
+  }
+}
+
+/// Failure to decode an endpoint response
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DecodeError {
+  pub message: String,
+  pub body: Bytes,
+}
+
+/// Build the full URL of an endpoint request, including its query string.
+pub fn endpoint_url<E: Endpoint + ?Sized>(endpoint: &E) -> Result<Url, url::ParseError> {
+  let mut url = endpoint.url()?;
+  {
+    let mut query = url.query_pairs_mut();
+    endpoint.query(&mut query);
+  }
+  if url.query() == Some("") {
+    url.set_query(None);
+  }
+  Ok(url)
+}
+
+/// Decode a JSON response body
+pub fn decode_json<T: DeserializeOwned>(res: Response<Bytes>) -> Result<T, DecodeError> {
+  let body = res.into_body();
+  serde_json::from_slice(&body).map_err(|e| DecodeError {
+    message: format!("{e:?}"),
+    body,
+  })
+}
+
+/// Decode a JSON list response body, with the page cursors from the `Link` header
+pub fn decode_page<T: DeserializeOwned>(res: Response<Bytes>) -> Result<Page<T>, DecodeError> {
+  let cursors = get_cursors(res.headers());
+  let items: Vec<T> = decode_json(res)?;
+  Ok(Page {
+    first: cursors.first,
+    next: cursors.next,
+    last: cursors.last,
+    items,
+  })
+}
+
+/// Return the raw response body
+pub fn decode_bytes(res: Response<Bytes>) -> Result<Bytes, DecodeError> {
+  Ok(res.into_body())
+}
+
+/// Ignore the response body (e.g. `204 No Content`)
+pub fn decode_empty(_res: Response<Bytes>) -> Result<(), DecodeError> {
+  Ok(())
+}
+
+struct Cursors<Str> {
+  first: Option<Str>,
+  next: Option<Str>,
+  last: Option<Str>,
+}
+
+fn get_cursors(headers: &HeaderMap) -> Cursors<CompactString> {
+  use demurgos_headers::HeaderMapExt;
+
+  let mut next: Option<CompactString> = None;
+  let mut first: Option<CompactString> = None;
+  let mut last: Option<CompactString> = None;
+
+  let links: Option<Link> = headers.typed_get::<Link>();
+
+  if let Some(links) = links {
+    for value in links.values() {
+      let rel = match value.rel() {
+        Some(rel) => rel,
+        None => continue,
+      };
+      for r in rel {
+        // todo: detect when there are multiple different links for the same rel type
+        if *r == RelationType::NEXT {
+          next = Some(CompactString::new(value.link()));
+        }
+        if *r == RelationType::FIRST {
+          first = Some(CompactString::new(value.link()));
+        }
+        if *r == RelationType::LAST {
+          last = Some(CompactString::new(value.link()));
+        }
+      }
+    }
+  }
+
+  Cursors { first, next, last }
+}
+
+/// Value of a query string parameter, encoded with the GitLab conventions
+///
+/// - booleans as `true` or `false`
+/// - dates as ISO 8601 strings
+/// - enums as their snake_case name, except [`AccessLevel`] encoded as an integer
+/// - lists as repeated `name[]` parameters
+/// - `None` values are skipped
+pub trait QueryValue {
+  /// Append this value to `query` as the parameter `name`
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str);
+}
+
+/// Extension trait to append typed parameters to [`QueryPairs`]
+pub trait QueryPairsExt {
+  /// Append the parameter `name`, see [`QueryValue`]
+  fn append<V: QueryValue + ?Sized>(&mut self, name: &str, value: &V) -> &mut Self;
+
+  /// Append the parameters requesting keyset-based pagination
+  ///
+  /// <https://docs.gitlab.com/ee/api/rest/index.html#keyset-based-pagination>
+  fn append_keyset_pagination<O: QueryValue>(&mut self, pagination: &KeysetPagination<O>) -> &mut Self;
+}
+
+impl QueryPairsExt for QueryPairs<'_> {
+  fn append<V: QueryValue + ?Sized>(&mut self, name: &str, value: &V) -> &mut Self {
+    value.append_to(self, name);
+    self
+  }
+
+  fn append_keyset_pagination<O: QueryValue>(&mut self, pagination: &KeysetPagination<O>) -> &mut Self {
+    self
+      .append("pagination", "keyset")
+      .append("per_page", &pagination.per_page)
+      .append("order_by", &pagination.order_by)
+      .append("sort", &pagination.sort)
+  }
+}
+
+impl<T: QueryValue + ?Sized> QueryValue for &T {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    (**self).append_to(query, name)
+  }
+}
+
+impl<T: QueryValue> QueryValue for Option<T> {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    if let Some(value) = self {
+      value.append_to(query, name);
+    }
+  }
+}
+
+impl<T: QueryValue> QueryValue for [T] {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    let name = format!("{name}[]");
+    for value in self {
+      value.append_to(query, &name);
+    }
+  }
+}
+
+impl<T: QueryValue> QueryValue for Vec<T> {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    self.as_slice().append_to(query, name)
+  }
+}
+
+impl QueryValue for str {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    query.append_pair(name, self);
+  }
+}
+
+impl QueryValue for CompactString {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    query.append_pair(name, self.as_str());
+  }
+}
+
+impl QueryValue for bool {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    query.append_pair(name, if *self { "true" } else { "false" });
+  }
+}
+
+macro_rules! query_value_int {
+  ($($ty:ty),* $(,)?) => {
+    $(
+      impl QueryValue for $ty {
+        fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+          query.append_pair(name, itoa::Buffer::new().format(*self));
+        }
+      }
+    )*
+  };
+}
+
+query_value_int!(u8, u16, u32, u64);
+
+impl QueryValue for NonZeroU8 {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    self.get().append_to(query, name)
+  }
+}
+
+impl QueryValue for DateTime<Utc> {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    query.append_pair(name, &self.to_rfc3339_opts(SecondsFormat::AutoSi, true));
+  }
+}
+
+impl QueryValue for NaiveDate {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    query.append_pair(name, &self.format("%Y-%m-%d").to_string());
+  }
+}
+
+impl QueryValue for AccessLevel {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    self.to_u8().append_to(query, name)
+  }
+}
+
+impl QueryValue for Visibility {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    query.append_pair(name, self.as_str());
+  }
+}
+
+impl QueryValue for SortOrder {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    query.append_pair(name, self.as_str());
+  }
+}
+
+impl QueryValue for ProjectOrder {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    query.append_pair(name, self.as_str());
+  }
+}
+
+impl QueryValue for ReleaseOrder {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    query.append_pair(name, self.as_str());
+  }
+}
+
+impl QueryValue for TagOrder {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    query.append_pair(name, self.as_str());
+  }
+}
+
+impl QueryValue for PackageOrder {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    query.append_pair(name, self.as_str());
+  }
+}
+
+impl QueryValue for PackageType {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    query.append_pair(name, self.as_str());
+  }
+}
+
+impl QueryValue for PackageStatus {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    query.append_pair(name, self.as_str());
+  }
+}
+
+impl QueryValue for MilestoneIid {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    self.into_u64().append_to(query, name)
+  }
+}
+
+impl QueryValue for MilestoneState {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    query.append_pair(name, self.as_str());
+  }
+}
+
+impl QueryValue for ProjectId {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    self.into_u64().append_to(query, name)
+  }
+}
+
+impl QueryValue for TopicId {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    self.into_u64().append_to(query, name)
+  }
+}
+
+impl QueryValue for MergeRequestState {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    query.append_pair(name, self.as_str());
+  }
+}
+
+impl QueryValue for MergeRequestOrder {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    query.append_pair(name, self.as_str());
+  }
+}
+
+impl QueryValue for MergeRequestScope {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    query.append_pair(name, self.as_str());
+  }
+}
+
+impl QueryValue for NoteOrder {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    query.append_pair(name, self.as_str());
+  }
+}
+
+impl QueryValue for UserId {
+  fn append_to(&self, query: &mut QueryPairs<'_>, name: &str) {
+    self.into_u64().append_to(query, name)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn encode(f: impl FnOnce(&mut QueryPairs<'_>)) -> String {
+    let mut url = Url::parse("https://gitlab.example.com/api/v4/projects").unwrap();
+    f(&mut url.query_pairs_mut());
+    url.query().unwrap_or_default().to_string()
+  }
+
+  #[test]
+  fn encode_query_values() {
+    let actual = encode(|query| {
+      query
+        .append("archived", &false)
+        .append("search", "foo bar")
+        .append("visibility", &None::<Visibility>)
+        .append("min_access_level", &AccessLevel::Developer)
+        .append(
+          "last_activity_after",
+          &DateTime::parse_from_rfc3339("2024-01-02T03:04:05Z").unwrap().to_utc(),
+        )
+        .append("topic", &vec!["a", "b"]);
+    });
+    assert_eq!(
+      actual,
+      "archived=false&search=foo+bar&min_access_level=30&last_activity_after=2024-01-02T03%3A04%3A05Z&topic%5B%5D=a&topic%5B%5D=b"
+    );
+  }
+
+  #[test]
+  fn encode_keyset_pagination() {
+    let actual = encode(|query| {
+      query.append_keyset_pagination(&KeysetPagination {
+        per_page: NonZeroU8::new(50),
+        order_by: ProjectOrder::Id,
+        sort: SortOrder::Asc,
+      });
+    });
+    assert_eq!(actual, "pagination=keyset&per_page=50&order_by=id&sort=asc");
+  }
+
+  #[test]
+  fn encode_project_list_query() {
+    use crate::common::topic::TopicId;
+    use crate::context::{Context, GitlabUrl};
+    use crate::query::get_project_list::GetProjectListQuery;
+
+    let mut query = GetProjectListQuery::<_, CompactString>::new()
+      .set_context(Context::new().set_gitlab_url(GitlabUrl(Url::parse("https://gitlab.example.com/").unwrap())));
+    query.pagination = Some(KeysetPagination {
+      per_page: NonZeroU8::new(100),
+      order_by: ProjectOrder::Id,
+      sort: SortOrder::Asc,
+    });
+    query.membership = Some(true);
+    query.min_access_level = Some(AccessLevel::Maintainer);
+    query.search = Some(CompactString::new("client"));
+    query.visibility = Some(Visibility::Internal);
+    query.topic = vec![CompactString::new("rust"), CompactString::new("gitlab")];
+    query.topic_id = Some(TopicId::new(7));
+    query.with_programming_language = Some(CompactString::new("Rust"));
+    query.last_activity_after = Some(DateTime::parse_from_rfc3339("2024-06-01T00:00:00Z").unwrap().to_utc());
+    let actual = endpoint_url(&query).unwrap();
+    assert_eq!(
+      actual.as_str(),
+      "https://gitlab.example.com/api/v4/projects?pagination=keyset&per_page=100&order_by=id&sort=asc&last_activity_after=2024-06-01T00%3A00%3A00Z&membership=true&min_access_level=40&search=client&topic_id=7&topic%5B%5D=rust&topic%5B%5D=gitlab&visibility=internal&with_programming_language=Rust"
+    );
+  }
+}
@@ -8,48 +8,1902 @@ pub use ::reqwest;
 pub use ::serde;
 pub use ::tower_service;
 pub use ::url;
+use bytes::Bytes;
 use compact_str::CompactString;
 use std::future::Future;
 
+use crate::command::accept_merge_request::AcceptMergeRequestCommand;
+use crate::command::approve_merge_request::ApproveMergeRequestCommand;
+use crate::command::cherry_pick_commit::CherryPickCommitCommand;
+use crate::command::create_branch::CreateBranchCommand;
+use crate::command::create_commit::CreateCommitCommand;
+use crate::command::create_discussion::CreateDiscussionCommand;
+use crate::command::create_group_milestone::CreateGroupMilestoneCommand;
+use crate::command::create_merge_request::CreateMergeRequestCommand;
+use crate::command::create_merge_request_approval_rule::CreateMergeRequestApprovalRuleCommand;
+use crate::command::create_note::CreateNoteCommand;
+use crate::command::create_project_approval_rule::CreateProjectApprovalRuleCommand;
+use crate::command::create_project_milestone::CreateProjectMilestoneCommand;
+use crate::command::create_release::CreateReleaseCommand;
+use crate::command::create_release_link::CreateReleaseLinkCommand;
+use crate::command::create_repository_file::CreateRepositoryFileCommand;
+use crate::command::create_tag::CreateTagCommand;
+use crate::command::delete_branch::DeleteBranchCommand;
+use crate::command::delete_group_milestone::DeleteGroupMilestoneCommand;
+use crate::command::delete_merge_request_approval_rule::DeleteMergeRequestApprovalRuleCommand;
+use crate::command::delete_merged_branches::DeleteMergedBranchesCommand;
+use crate::command::delete_note::DeleteNoteCommand;
+use crate::command::delete_project_approval_rule::DeleteProjectApprovalRuleCommand;
+use crate::command::delete_project_milestone::DeleteProjectMilestoneCommand;
+use crate::command::delete_release::DeleteReleaseCommand;
+use crate::command::delete_release_link::DeleteReleaseLinkCommand;
+use crate::command::delete_repository_file::DeleteRepositoryFileCommand;
+use crate::command::delete_tag::DeleteTagCommand;
+use crate::command::protect_branch::ProtectBranchCommand;
+use crate::command::protect_tag::ProtectTagCommand;
+use crate::command::publish_package_file::PublishPackageFileCommand;
+use crate::command::rebase_merge_request::RebaseMergeRequestCommand;
+use crate::command::reset_merge_request_approvals::ResetMergeRequestApprovalsCommand;
+use crate::command::resolve_discussion::ResolveDiscussionCommand;
+use crate::command::revert_commit::RevertCommitCommand;
+use crate::command::unapprove_merge_request::UnapproveMergeRequestCommand;
+use crate::command::unprotect_branch::UnprotectBranchCommand;
+use crate::command::unprotect_tag::UnprotectTagCommand;
+use crate::command::update_group_milestone::UpdateGroupMilestoneCommand;
+use crate::command::update_merge_request::UpdateMergeRequestCommand;
+use crate::command::update_merge_request_approval_rule::UpdateMergeRequestApprovalRuleCommand;
+use crate::command::update_note::UpdateNoteCommand;
+use crate::command::update_project_approval_rule::UpdateProjectApprovalRuleCommand;
+use crate::command::update_project_milestone::UpdateProjectMilestoneCommand;
+use crate::command::update_protected_branch::UpdateProtectedBranchCommand;
+use crate::command::update_release::UpdateReleaseCommand;
+use crate::command::update_release_link::UpdateReleaseLinkCommand;
+use crate::command::update_repository_file::UpdateRepositoryFileCommand;
+use crate::common::approval::{
+  MergeRequestApprovalRule, MergeRequestApprovalState, MergeRequestApprovals, ProjectApprovalRule,
+};
+use crate::common::branch::Branch;
+use crate::common::commit::{Commit, CommitComment, CommitRef};
+use crate::common::diff::Diff;
+use crate::common::merge_request::{MergeRequest, MergeRequestChanges, MergeRequestDiffVersion, MergeRequestRebase};
+use crate::common::milestone::Milestone;
+use crate::common::note::{Discussion, Note};
+use crate::common::package::{GenericPackageFile, Package, PackageFile};
 use crate::common::project::Project;
+use crate::common::protected_branch::ProtectedBranch;
+use crate::common::protected_tag::ProtectedTag;
+use crate::common::release::{Release, ReleaseLink};
+use crate::common::repository_file::{RepositoryFile, RepositoryFileCommit};
+use crate::common::tag::{Tag, TagSignature};
+use crate::common::tree::TreeRecord;
 use crate::common::Page;
+use crate::query::get_branch::GetBranchQuery;
+use crate::query::get_branch_list::GetBranchListQuery;
+use crate::query::get_branch_list_page::GetBranchListPageQuery;
+use crate::query::get_commit::GetCommitQuery;
+use crate::query::get_commit_comment_list::GetCommitCommentListQuery;
+use crate::query::get_commit_comment_list_page::GetCommitCommentListPageQuery;
+use crate::query::get_commit_diff::GetCommitDiffQuery;
+use crate::query::get_commit_list::GetCommitListQuery;
+use crate::query::get_commit_list_page::GetCommitListPageQuery;
+use crate::query::get_commit_ref_list::GetCommitRefListQuery;
+use crate::query::get_commit_ref_list_page::GetCommitRefListPageQuery;
+use crate::query::get_diff_list_page::GetDiffListPageQuery;
+use crate::query::get_discussion::GetDiscussionQuery;
+use crate::query::get_discussion_list::GetDiscussionListQuery;
+use crate::query::get_discussion_list_page::GetDiscussionListPageQuery;
+use crate::query::get_group_merge_request_list::GetGroupMergeRequestListQuery;
+use crate::query::get_group_milestone::GetGroupMilestoneQuery;
+use crate::query::get_group_milestone_list::GetGroupMilestoneListQuery;
+use crate::query::get_merge_request::GetMergeRequestQuery;
+use crate::query::get_merge_request_approval_rule::GetMergeRequestApprovalRuleQuery;
+use crate::query::get_merge_request_approval_rule_list::GetMergeRequestApprovalRuleListQuery;
+use crate::query::get_merge_request_approval_rule_list_page::GetMergeRequestApprovalRuleListPageQuery;
+use crate::query::get_merge_request_approval_state::GetMergeRequestApprovalStateQuery;
+use crate::query::get_merge_request_approvals::GetMergeRequestApprovalsQuery;
+use crate::query::get_merge_request_changes::GetMergeRequestChangesQuery;
+use crate::query::get_merge_request_commit_list::GetMergeRequestCommitListQuery;
+use crate::query::get_merge_request_diff_list::GetMergeRequestDiffListQuery;
+use crate::query::get_merge_request_diff_version::GetMergeRequestDiffVersionQuery;
+use crate::query::get_merge_request_diff_version_list::GetMergeRequestDiffVersionListQuery;
+use crate::query::get_merge_request_diff_version_list_page::GetMergeRequestDiffVersionListPageQuery;
+use crate::query::get_merge_request_list::GetMergeRequestListQuery;
+use crate::query::get_merge_request_list_page::GetMergeRequestListPageQuery;
+use crate::query::get_milestone_list_page::GetMilestoneListPageQuery;
+use crate::query::get_note::GetNoteQuery;
+use crate::query::get_note_list::GetNoteListQuery;
+use crate::query::get_note_list_page::GetNoteListPageQuery;
+use crate::query::get_package_file::GetPackageFileQuery;
+use crate::query::get_package_file_list::GetPackageFileListQuery;
+use crate::query::get_package_file_list_page::GetPackageFileListPageQuery;
+use crate::query::get_project::GetProjectQuery;
+use crate::query::get_project_approval_rule::GetProjectApprovalRuleQuery;
+use crate::query::get_project_approval_rule_list::GetProjectApprovalRuleListQuery;
+use crate::query::get_project_approval_rule_list_page::GetProjectApprovalRuleListPageQuery;
 use crate::query::get_project_list::GetProjectListQuery;
+use crate::query::get_project_list_page::GetProjectListPageQuery;
+use crate::query::get_project_merge_request_list::GetProjectMergeRequestListQuery;
+use crate::query::get_project_milestone::GetProjectMilestoneQuery;
+use crate::query::get_project_milestone_list::GetProjectMilestoneListQuery;
+use crate::query::get_project_package_list::GetProjectPackageListQuery;
+use crate::query::get_project_package_list_page::GetProjectPackageListPageQuery;
+use crate::query::get_project_release::GetProjectReleaseQuery;
+use crate::query::get_project_release_list::GetProjectReleaseListQuery;
+use crate::query::get_project_release_list_page::GetProjectReleaseListPageQuery;
+use crate::query::get_protected_branch::GetProtectedBranchQuery;
+use crate::query::get_protected_branch_list::GetProtectedBranchListQuery;
+use crate::query::get_protected_branch_list_page::GetProtectedBranchListPageQuery;
+use crate::query::get_protected_tag::GetProtectedTagQuery;
+use crate::query::get_protected_tag_list::GetProtectedTagListQuery;
+use crate::query::get_protected_tag_list_page::GetProtectedTagListPageQuery;
+use crate::query::get_raw_repository_file::GetRawRepositoryFileQuery;
+use crate::query::get_release_link::GetReleaseLinkQuery;
+use crate::query::get_release_link_list::GetReleaseLinkListQuery;
+use crate::query::get_repository_file::GetRepositoryFileQuery;
+use crate::query::get_tag::GetTagQuery;
+use crate::query::get_tag_list::GetTagListQuery;
+use crate::query::get_tag_list_page::GetTagListPageQuery;
+use crate::query::get_tag_signature::GetTagSignatureQuery;
+use crate::query::get_tree_record_list::GetTreeRecordListQuery;
 use tower_service::Service;
 
+#[cfg(feature = "http")]
+pub mod body;
 pub mod client;
 pub mod command;
 pub mod common;
 pub mod context;
 #[cfg(feature = "http")]
+pub mod endpoint;
+#[cfg(feature = "http")]
 pub mod http;
+pub mod pagination;
+#[cfg(feature = "http")]
+pub mod publish;
 pub mod query;
 pub mod url_util;
 
-pub trait GitlabClient<Cx>: Send + Sync {
-  type Error<'req>
-  where
-    Cx: 'req;
+/// Typed facade over the project handlers.
+///
+/// Implemented for any [`Service`] supporting the queries and commands below with the same error type.
+pub trait ProjectClient<Cx>: Send + Sync {
+  type Error;
+
+  /// List all projects
   fn get_project_list(
-    self,
+    &mut self,
     query: &GetProjectListQuery<Cx>,
-  ) -> impl Send + Future<Output = Result<Page<Project>, Self::Error<'_>>>;
+  ) -> impl Send + Future<Output = Result<Page<Project>, Self::Error>>;
+
+  /// Get a page from the project list
+  fn get_project_list_page(
+    &mut self,
+    query: &GetProjectListPageQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<Project>, Self::Error>>;
+
+  /// Get a single project
+  fn get_project(&mut self, query: &GetProjectQuery<Cx>) -> impl Send + Future<Output = Result<Project, Self::Error>>;
 }
 
-impl<S, Cx> GitlabClient<Cx> for &'_ mut S
+impl<S, Cx, E> ProjectClient<Cx> for S
 where
-  Self: Send + Sync,
+  S: Send + Sync,
   Cx: 'static + Send + Sync,
-  for<'req> S: Service<&'req GetProjectListQuery<Cx>, Response = Page<Project>>,
+  for<'req> S: Service<&'req GetProjectListQuery<Cx>, Response = Page<Project>, Error = E>,
   for<'req> <S as Service<&'req GetProjectListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetProjectListPageQuery<Cx>, Response = Page<Project>, Error = E>,
+  for<'req> <S as Service<&'req GetProjectListPageQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetProjectQuery<Cx>, Response = Project, Error = E>,
+  for<'req> <S as Service<&'req GetProjectQuery<Cx>>>::Future: Send,
+{
+  type Error = E;
+
+  async fn get_project_list(&mut self, query: &GetProjectListQuery<Cx>) -> Result<Page<Project>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_project_list_page(&mut self, query: &GetProjectListPageQuery<Cx>) -> Result<Page<Project>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_project(&mut self, query: &GetProjectQuery<Cx>) -> Result<Project, Self::Error> {
+    call(self, query).await
+  }
+}
+
+/// Typed facade over the repository tree and file handlers.
+///
+/// Implemented for any [`Service`] supporting the queries and commands below with the same error type.
+pub trait RepositoryClient<Cx>: Send + Sync {
+  type Error;
+
+  /// Get a list of repository files and directories in a project
+  fn get_tree_record_list(
+    &mut self,
+    query: &GetTreeRecordListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<TreeRecord>, Self::Error>>;
+
+  /// Get a repository file, with its metadata and decoded content
+  fn get_repository_file(
+    &mut self,
+    query: &GetRepositoryFileQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<RepositoryFile, Self::Error>>;
+
+  /// Get the raw content of a repository file
+  fn get_raw_repository_file(
+    &mut self,
+    query: &GetRawRepositoryFileQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Bytes, Self::Error>>;
+
+  /// Create a file in a repository
+  fn create_repository_file(
+    &mut self,
+    command: &CreateRepositoryFileCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<RepositoryFileCommit, Self::Error>>;
+
+  /// Delete a repository file
+  fn delete_repository_file(
+    &mut self,
+    command: &DeleteRepositoryFileCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<(), Self::Error>>;
+
+  /// Update the content of a repository file
+  fn update_repository_file(
+    &mut self,
+    command: &UpdateRepositoryFileCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<RepositoryFileCommit, Self::Error>>;
+}
+
+impl<S, Cx, E> RepositoryClient<Cx> for S
+where
+  S: Send + Sync,
+  Cx: 'static + Send + Sync,
+  for<'req> S: Service<&'req GetTreeRecordListQuery<Cx>, Response = Page<TreeRecord>, Error = E>,
+  for<'req> <S as Service<&'req GetTreeRecordListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetRepositoryFileQuery<Cx>, Response = RepositoryFile, Error = E>,
+  for<'req> <S as Service<&'req GetRepositoryFileQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetRawRepositoryFileQuery<Cx>, Response = Bytes, Error = E>,
+  for<'req> <S as Service<&'req GetRawRepositoryFileQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req CreateRepositoryFileCommand<Cx>, Response = RepositoryFileCommit, Error = E>,
+  for<'req> <S as Service<&'req CreateRepositoryFileCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req DeleteRepositoryFileCommand<Cx>, Response = (), Error = E>,
+  for<'req> <S as Service<&'req DeleteRepositoryFileCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req UpdateRepositoryFileCommand<Cx>, Response = RepositoryFileCommit, Error = E>,
+  for<'req> <S as Service<&'req UpdateRepositoryFileCommand<Cx>>>::Future: Send,
+{
+  type Error = E;
+
+  async fn get_tree_record_list(
+    &mut self,
+    query: &GetTreeRecordListQuery<Cx>,
+  ) -> Result<Page<TreeRecord>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_repository_file(&mut self, query: &GetRepositoryFileQuery<Cx>) -> Result<RepositoryFile, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_raw_repository_file(&mut self, query: &GetRawRepositoryFileQuery<Cx>) -> Result<Bytes, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn create_repository_file(
+    &mut self,
+    command: &CreateRepositoryFileCommand<Cx>,
+  ) -> Result<RepositoryFileCommit, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn delete_repository_file(&mut self, command: &DeleteRepositoryFileCommand<Cx>) -> Result<(), Self::Error> {
+    call(self, command).await
+  }
+
+  async fn update_repository_file(
+    &mut self,
+    command: &UpdateRepositoryFileCommand<Cx>,
+  ) -> Result<RepositoryFileCommit, Self::Error> {
+    call(self, command).await
+  }
+}
+
+/// Typed facade over the commit handlers.
+///
+/// Implemented for any [`Service`] supporting the queries and commands below with the same error type.
+pub trait CommitClient<Cx>: Send + Sync {
+  type Error;
+
+  /// List repository commits
+  fn get_commit_list(
+    &mut self,
+    query: &GetCommitListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<Commit>, Self::Error>>;
+
+  /// Get a page from a commit list
+  fn get_commit_list_page(
+    &mut self,
+    query: &GetCommitListPageQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<Commit>, Self::Error>>;
+
+  /// Get a single commit
+  fn get_commit(&mut self, query: &GetCommitQuery<Cx>) -> impl Send + Future<Output = Result<Commit, Self::Error>>;
+
+  /// Get the diff of a commit
+  fn get_commit_diff(
+    &mut self,
+    query: &GetCommitDiffQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<Diff>, Self::Error>>;
+
+  /// Get a page from a diff list
+  fn get_diff_list_page(
+    &mut self,
+    query: &GetDiffListPageQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<Diff>, Self::Error>>;
+
+  /// List the branches and tags a commit is pushed to
+  fn get_commit_ref_list(
+    &mut self,
+    query: &GetCommitRefListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<CommitRef>, Self::Error>>;
+
+  /// Get a page from a commit ref list
+  fn get_commit_ref_list_page(
+    &mut self,
+    query: &GetCommitRefListPageQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<CommitRef>, Self::Error>>;
+
+  /// List the comments of a commit
+  fn get_commit_comment_list(
+    &mut self,
+    query: &GetCommitCommentListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<CommitComment>, Self::Error>>;
+
+  /// Get a page from a commit comment list
+  fn get_commit_comment_list_page(
+    &mut self,
+    query: &GetCommitCommentListPageQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<CommitComment>, Self::Error>>;
+
+  /// Create a commit applying several file actions at once
+  fn create_commit(
+    &mut self,
+    command: &CreateCommitCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<Commit, Self::Error>>;
+
+  /// Cherry-pick a commit into a branch
+  fn cherry_pick_commit(
+    &mut self,
+    command: &CherryPickCommitCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<Commit, Self::Error>>;
+
+  /// Revert a commit in a branch
+  fn revert_commit(
+    &mut self,
+    command: &RevertCommitCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<Commit, Self::Error>>;
+}
+
+impl<S, Cx, E> CommitClient<Cx> for S
+where
+  S: Send + Sync,
+  Cx: 'static + Send + Sync,
+  for<'req> S: Service<&'req GetCommitListQuery<Cx>, Response = Page<Commit>, Error = E>,
+  for<'req> <S as Service<&'req GetCommitListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetCommitListPageQuery<Cx>, Response = Page<Commit>, Error = E>,
+  for<'req> <S as Service<&'req GetCommitListPageQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetCommitQuery<Cx>, Response = Commit, Error = E>,
+  for<'req> <S as Service<&'req GetCommitQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetCommitDiffQuery<Cx>, Response = Page<Diff>, Error = E>,
+  for<'req> <S as Service<&'req GetCommitDiffQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetDiffListPageQuery<Cx>, Response = Page<Diff>, Error = E>,
+  for<'req> <S as Service<&'req GetDiffListPageQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetCommitRefListQuery<Cx>, Response = Page<CommitRef>, Error = E>,
+  for<'req> <S as Service<&'req GetCommitRefListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetCommitRefListPageQuery<Cx>, Response = Page<CommitRef>, Error = E>,
+  for<'req> <S as Service<&'req GetCommitRefListPageQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetCommitCommentListQuery<Cx>, Response = Page<CommitComment>, Error = E>,
+  for<'req> <S as Service<&'req GetCommitCommentListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetCommitCommentListPageQuery<Cx>, Response = Page<CommitComment>, Error = E>,
+  for<'req> <S as Service<&'req GetCommitCommentListPageQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req CreateCommitCommand<Cx>, Response = Commit, Error = E>,
+  for<'req> <S as Service<&'req CreateCommitCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req CherryPickCommitCommand<Cx>, Response = Commit, Error = E>,
+  for<'req> <S as Service<&'req CherryPickCommitCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req RevertCommitCommand<Cx>, Response = Commit, Error = E>,
+  for<'req> <S as Service<&'req RevertCommitCommand<Cx>>>::Future: Send,
+{
+  type Error = E;
+
+  async fn get_commit_list(&mut self, query: &GetCommitListQuery<Cx>) -> Result<Page<Commit>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_commit_list_page(&mut self, query: &GetCommitListPageQuery<Cx>) -> Result<Page<Commit>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_commit(&mut self, query: &GetCommitQuery<Cx>) -> Result<Commit, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_commit_diff(&mut self, query: &GetCommitDiffQuery<Cx>) -> Result<Page<Diff>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_diff_list_page(&mut self, query: &GetDiffListPageQuery<Cx>) -> Result<Page<Diff>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_commit_ref_list(&mut self, query: &GetCommitRefListQuery<Cx>) -> Result<Page<CommitRef>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_commit_ref_list_page(
+    &mut self,
+    query: &GetCommitRefListPageQuery<Cx>,
+  ) -> Result<Page<CommitRef>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_commit_comment_list(
+    &mut self,
+    query: &GetCommitCommentListQuery<Cx>,
+  ) -> Result<Page<CommitComment>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_commit_comment_list_page(
+    &mut self,
+    query: &GetCommitCommentListPageQuery<Cx>,
+  ) -> Result<Page<CommitComment>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn create_commit(&mut self, command: &CreateCommitCommand<Cx>) -> Result<Commit, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn cherry_pick_commit(&mut self, command: &CherryPickCommitCommand<Cx>) -> Result<Commit, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn revert_commit(&mut self, command: &RevertCommitCommand<Cx>) -> Result<Commit, Self::Error> {
+    call(self, command).await
+  }
+}
+
+/// Typed facade over the branch and protected branch handlers.
+///
+/// Implemented for any [`Service`] supporting the queries and commands below with the same error type.
+pub trait BranchClient<Cx>: Send + Sync {
+  type Error;
+
+  /// List repository branches
+  fn get_branch_list(
+    &mut self,
+    query: &GetBranchListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<Branch>, Self::Error>>;
+
+  /// Get a page from a branch list
+  fn get_branch_list_page(
+    &mut self,
+    query: &GetBranchListPageQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<Branch>, Self::Error>>;
+
+  /// Get a single repository branch
+  fn get_branch(&mut self, query: &GetBranchQuery<Cx>) -> impl Send + Future<Output = Result<Branch, Self::Error>>;
+
+  /// List protected branches
+  fn get_protected_branch_list(
+    &mut self,
+    query: &GetProtectedBranchListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<ProtectedBranch>, Self::Error>>;
+
+  /// Get a page from a protected branch list
+  fn get_protected_branch_list_page(
+    &mut self,
+    query: &GetProtectedBranchListPageQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<ProtectedBranch>, Self::Error>>;
+
+  /// Get a single protected branch or wildcard
+  fn get_protected_branch(
+    &mut self,
+    query: &GetProtectedBranchQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<ProtectedBranch, Self::Error>>;
+
+  /// Create a repository branch
+  fn create_branch(
+    &mut self,
+    command: &CreateBranchCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<Branch, Self::Error>>;
+
+  /// Delete a repository branch
+  fn delete_branch(
+    &mut self,
+    command: &DeleteBranchCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<(), Self::Error>>;
+
+  /// Delete the branches merged into the default branch
+  fn delete_merged_branches(
+    &mut self,
+    command: &DeleteMergedBranchesCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<(), Self::Error>>;
+
+  /// Protect the branches matching a name or wildcard
+  fn protect_branch(
+    &mut self,
+    command: &ProtectBranchCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<ProtectedBranch, Self::Error>>;
+
+  /// Update a protected branch or wildcard
+  fn update_protected_branch(
+    &mut self,
+    command: &UpdateProtectedBranchCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<ProtectedBranch, Self::Error>>;
+
+  /// Unprotect the branches matching a name or wildcard
+  fn unprotect_branch(
+    &mut self,
+    command: &UnprotectBranchCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<(), Self::Error>>;
+}
+
+impl<S, Cx, E> BranchClient<Cx> for S
+where
+  S: Send + Sync,
+  Cx: 'static + Send + Sync,
+  for<'req> S: Service<&'req GetBranchListQuery<Cx>, Response = Page<Branch>, Error = E>,
+  for<'req> <S as Service<&'req GetBranchListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetBranchListPageQuery<Cx>, Response = Page<Branch>, Error = E>,
+  for<'req> <S as Service<&'req GetBranchListPageQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetBranchQuery<Cx>, Response = Branch, Error = E>,
+  for<'req> <S as Service<&'req GetBranchQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetProtectedBranchListQuery<Cx>, Response = Page<ProtectedBranch>, Error = E>,
+  for<'req> <S as Service<&'req GetProtectedBranchListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetProtectedBranchListPageQuery<Cx>, Response = Page<ProtectedBranch>, Error = E>,
+  for<'req> <S as Service<&'req GetProtectedBranchListPageQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetProtectedBranchQuery<Cx>, Response = ProtectedBranch, Error = E>,
+  for<'req> <S as Service<&'req GetProtectedBranchQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req CreateBranchCommand<Cx>, Response = Branch, Error = E>,
+  for<'req> <S as Service<&'req CreateBranchCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req DeleteBranchCommand<Cx>, Response = (), Error = E>,
+  for<'req> <S as Service<&'req DeleteBranchCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req DeleteMergedBranchesCommand<Cx>, Response = (), Error = E>,
+  for<'req> <S as Service<&'req DeleteMergedBranchesCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req ProtectBranchCommand<Cx>, Response = ProtectedBranch, Error = E>,
+  for<'req> <S as Service<&'req ProtectBranchCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req UpdateProtectedBranchCommand<Cx>, Response = ProtectedBranch, Error = E>,
+  for<'req> <S as Service<&'req UpdateProtectedBranchCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req UnprotectBranchCommand<Cx>, Response = (), Error = E>,
+  for<'req> <S as Service<&'req UnprotectBranchCommand<Cx>>>::Future: Send,
+{
+  type Error = E;
+
+  async fn get_branch_list(&mut self, query: &GetBranchListQuery<Cx>) -> Result<Page<Branch>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_branch_list_page(&mut self, query: &GetBranchListPageQuery<Cx>) -> Result<Page<Branch>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_branch(&mut self, query: &GetBranchQuery<Cx>) -> Result<Branch, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_protected_branch_list(
+    &mut self,
+    query: &GetProtectedBranchListQuery<Cx>,
+  ) -> Result<Page<ProtectedBranch>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_protected_branch_list_page(
+    &mut self,
+    query: &GetProtectedBranchListPageQuery<Cx>,
+  ) -> Result<Page<ProtectedBranch>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_protected_branch(
+    &mut self,
+    query: &GetProtectedBranchQuery<Cx>,
+  ) -> Result<ProtectedBranch, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn create_branch(&mut self, command: &CreateBranchCommand<Cx>) -> Result<Branch, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn delete_branch(&mut self, command: &DeleteBranchCommand<Cx>) -> Result<(), Self::Error> {
+    call(self, command).await
+  }
+
+  async fn delete_merged_branches(&mut self, command: &DeleteMergedBranchesCommand<Cx>) -> Result<(), Self::Error> {
+    call(self, command).await
+  }
+
+  async fn protect_branch(&mut self, command: &ProtectBranchCommand<Cx>) -> Result<ProtectedBranch, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn update_protected_branch(
+    &mut self,
+    command: &UpdateProtectedBranchCommand<Cx>,
+  ) -> Result<ProtectedBranch, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn unprotect_branch(&mut self, command: &UnprotectBranchCommand<Cx>) -> Result<(), Self::Error> {
+    call(self, command).await
+  }
+}
+
+/// Typed facade over the tag and protected tag handlers.
+///
+/// Implemented for any [`Service`] supporting the queries and commands below with the same error type.
+pub trait TagClient<Cx>: Send + Sync {
+  type Error;
+
+  /// List project repository tags
+  fn get_tag_list(
+    &mut self,
+    query: &GetTagListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<Tag>, Self::Error>>;
+
+  /// Get a page from a tag list
+  fn get_tag_list_page(
+    &mut self,
+    query: &GetTagListPageQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<Tag>, Self::Error>>;
+
+  /// Get a single repository tag
+  fn get_tag(&mut self, query: &GetTagQuery<Cx>) -> impl Send + Future<Output = Result<Tag, Self::Error>>;
+
+  /// Get the signature of a tag, with its verification status
+  fn get_tag_signature(
+    &mut self,
+    query: &GetTagSignatureQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<TagSignature, Self::Error>>;
+
+  /// List protected tags
+  fn get_protected_tag_list(
+    &mut self,
+    query: &GetProtectedTagListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<ProtectedTag>, Self::Error>>;
+
+  /// Get a page from a protected tag list
+  fn get_protected_tag_list_page(
+    &mut self,
+    query: &GetProtectedTagListPageQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<ProtectedTag>, Self::Error>>;
+
+  /// Get a single protected tag or wildcard
+  fn get_protected_tag(
+    &mut self,
+    query: &GetProtectedTagQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<ProtectedTag, Self::Error>>;
+
+  /// Create a repository tag
+  fn create_tag(&mut self, command: &CreateTagCommand<Cx>) -> impl Send + Future<Output = Result<Tag, Self::Error>>;
+
+  /// Delete a repository tag
+  fn delete_tag(&mut self, command: &DeleteTagCommand<Cx>) -> impl Send + Future<Output = Result<(), Self::Error>>;
+
+  /// Protect the tags matching a name or wildcard
+  fn protect_tag(
+    &mut self,
+    command: &ProtectTagCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<ProtectedTag, Self::Error>>;
+
+  /// Unprotect the tags matching a name or wildcard
+  fn unprotect_tag(
+    &mut self,
+    command: &UnprotectTagCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<(), Self::Error>>;
+}
+
+impl<S, Cx, E> TagClient<Cx> for S
+where
+  S: Send + Sync,
+  Cx: 'static + Send + Sync,
+  for<'req> S: Service<&'req GetTagListQuery<Cx>, Response = Page<Tag>, Error = E>,
+  for<'req> <S as Service<&'req GetTagListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetTagListPageQuery<Cx>, Response = Page<Tag>, Error = E>,
+  for<'req> <S as Service<&'req GetTagListPageQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetTagQuery<Cx>, Response = Tag, Error = E>,
+  for<'req> <S as Service<&'req GetTagQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetTagSignatureQuery<Cx>, Response = TagSignature, Error = E>,
+  for<'req> <S as Service<&'req GetTagSignatureQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetProtectedTagListQuery<Cx>, Response = Page<ProtectedTag>, Error = E>,
+  for<'req> <S as Service<&'req GetProtectedTagListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetProtectedTagListPageQuery<Cx>, Response = Page<ProtectedTag>, Error = E>,
+  for<'req> <S as Service<&'req GetProtectedTagListPageQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetProtectedTagQuery<Cx>, Response = ProtectedTag, Error = E>,
+  for<'req> <S as Service<&'req GetProtectedTagQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req CreateTagCommand<Cx>, Response = Tag, Error = E>,
+  for<'req> <S as Service<&'req CreateTagCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req DeleteTagCommand<Cx>, Response = (), Error = E>,
+  for<'req> <S as Service<&'req DeleteTagCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req ProtectTagCommand<Cx>, Response = ProtectedTag, Error = E>,
+  for<'req> <S as Service<&'req ProtectTagCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req UnprotectTagCommand<Cx>, Response = (), Error = E>,
+  for<'req> <S as Service<&'req UnprotectTagCommand<Cx>>>::Future: Send,
+{
+  type Error = E;
+
+  async fn get_tag_list(&mut self, query: &GetTagListQuery<Cx>) -> Result<Page<Tag>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_tag_list_page(&mut self, query: &GetTagListPageQuery<Cx>) -> Result<Page<Tag>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_tag(&mut self, query: &GetTagQuery<Cx>) -> Result<Tag, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_tag_signature(&mut self, query: &GetTagSignatureQuery<Cx>) -> Result<TagSignature, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_protected_tag_list(
+    &mut self,
+    query: &GetProtectedTagListQuery<Cx>,
+  ) -> Result<Page<ProtectedTag>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_protected_tag_list_page(
+    &mut self,
+    query: &GetProtectedTagListPageQuery<Cx>,
+  ) -> Result<Page<ProtectedTag>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_protected_tag(&mut self, query: &GetProtectedTagQuery<Cx>) -> Result<ProtectedTag, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn create_tag(&mut self, command: &CreateTagCommand<Cx>) -> Result<Tag, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn delete_tag(&mut self, command: &DeleteTagCommand<Cx>) -> Result<(), Self::Error> {
+    call(self, command).await
+  }
+
+  async fn protect_tag(&mut self, command: &ProtectTagCommand<Cx>) -> Result<ProtectedTag, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn unprotect_tag(&mut self, command: &UnprotectTagCommand<Cx>) -> Result<(), Self::Error> {
+    call(self, command).await
+  }
+}
+
+/// Typed facade over the merge request approval and approval rule handlers.
+///
+/// Implemented for any [`Service`] supporting the queries and commands below with the same error type.
+pub trait ApprovalClient<Cx>: Send + Sync {
+  type Error;
+
+  /// Get the approvals of a merge request
+  fn get_merge_request_approvals(
+    &mut self,
+    query: &GetMergeRequestApprovalsQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<MergeRequestApprovals, Self::Error>>;
+
+  /// Get the approval state of a merge request
+  fn get_merge_request_approval_state(
+    &mut self,
+    query: &GetMergeRequestApprovalStateQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<MergeRequestApprovalState, Self::Error>>;
+
+  /// List the approval rules of a merge request
+  fn get_merge_request_approval_rule_list(
+    &mut self,
+    query: &GetMergeRequestApprovalRuleListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<MergeRequestApprovalRule>, Self::Error>>;
+
+  /// Get a page from a merge request approval rule list
+  fn get_merge_request_approval_rule_list_page(
+    &mut self,
+    query: &GetMergeRequestApprovalRuleListPageQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<MergeRequestApprovalRule>, Self::Error>>;
+
+  /// Get an approval rule of a merge request
+  fn get_merge_request_approval_rule(
+    &mut self,
+    query: &GetMergeRequestApprovalRuleQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<MergeRequestApprovalRule, Self::Error>>;
+
+  /// List the approval rules of a project
+  fn get_project_approval_rule_list(
+    &mut self,
+    query: &GetProjectApprovalRuleListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<ProjectApprovalRule>, Self::Error>>;
+
+  /// Get a page from a project approval rule list
+  fn get_project_approval_rule_list_page(
+    &mut self,
+    query: &GetProjectApprovalRuleListPageQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<ProjectApprovalRule>, Self::Error>>;
+
+  /// Get an approval rule of a project
+  fn get_project_approval_rule(
+    &mut self,
+    query: &GetProjectApprovalRuleQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<ProjectApprovalRule, Self::Error>>;
+
+  /// Approve a merge request
+  fn approve_merge_request(
+    &mut self,
+    command: &ApproveMergeRequestCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<MergeRequestApprovals, Self::Error>>;
+
+  /// Remove the approval of the current user from a merge request
+  fn unapprove_merge_request(
+    &mut self,
+    command: &UnapproveMergeRequestCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<MergeRequestApprovals, Self::Error>>;
+
+  /// Remove every approval of a merge request
+  fn reset_merge_request_approvals(
+    &mut self,
+    command: &ResetMergeRequestApprovalsCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<(), Self::Error>>;
+
+  /// Create a project approval rule
+  fn create_project_approval_rule(
+    &mut self,
+    command: &CreateProjectApprovalRuleCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<ProjectApprovalRule, Self::Error>>;
+
+  /// Update a project approval rule
+  fn update_project_approval_rule(
+    &mut self,
+    command: &UpdateProjectApprovalRuleCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<ProjectApprovalRule, Self::Error>>;
+
+  /// Delete a project approval rule
+  fn delete_project_approval_rule(
+    &mut self,
+    command: &DeleteProjectApprovalRuleCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<(), Self::Error>>;
+
+  /// Create a merge request approval rule
+  fn create_merge_request_approval_rule(
+    &mut self,
+    command: &CreateMergeRequestApprovalRuleCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<MergeRequestApprovalRule, Self::Error>>;
+
+  /// Update a merge request approval rule
+  fn update_merge_request_approval_rule(
+    &mut self,
+    command: &UpdateMergeRequestApprovalRuleCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<MergeRequestApprovalRule, Self::Error>>;
+
+  /// Delete a merge request approval rule
+  fn delete_merge_request_approval_rule(
+    &mut self,
+    command: &DeleteMergeRequestApprovalRuleCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<(), Self::Error>>;
+}
+
+impl<S, Cx, E> ApprovalClient<Cx> for S
+where
+  S: Send + Sync,
+  Cx: 'static + Send + Sync,
+  for<'req> S: Service<&'req GetMergeRequestApprovalsQuery<Cx>, Response = MergeRequestApprovals, Error = E>,
+  for<'req> <S as Service<&'req GetMergeRequestApprovalsQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetMergeRequestApprovalStateQuery<Cx>, Response = MergeRequestApprovalState, Error = E>,
+  for<'req> <S as Service<&'req GetMergeRequestApprovalStateQuery<Cx>>>::Future: Send,
+  for<'req> S:
+    Service<&'req GetMergeRequestApprovalRuleListQuery<Cx>, Response = Page<MergeRequestApprovalRule>, Error = E>,
+  for<'req> <S as Service<&'req GetMergeRequestApprovalRuleListQuery<Cx>>>::Future: Send,
+  for<'req> S:
+    Service<&'req GetMergeRequestApprovalRuleListPageQuery<Cx>, Response = Page<MergeRequestApprovalRule>, Error = E>,
+  for<'req> <S as Service<&'req GetMergeRequestApprovalRuleListPageQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetMergeRequestApprovalRuleQuery<Cx>, Response = MergeRequestApprovalRule, Error = E>,
+  for<'req> <S as Service<&'req GetMergeRequestApprovalRuleQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetProjectApprovalRuleListQuery<Cx>, Response = Page<ProjectApprovalRule>, Error = E>,
+  for<'req> <S as Service<&'req GetProjectApprovalRuleListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetProjectApprovalRuleListPageQuery<Cx>, Response = Page<ProjectApprovalRule>, Error = E>,
+  for<'req> <S as Service<&'req GetProjectApprovalRuleListPageQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetProjectApprovalRuleQuery<Cx>, Response = ProjectApprovalRule, Error = E>,
+  for<'req> <S as Service<&'req GetProjectApprovalRuleQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req ApproveMergeRequestCommand<Cx>, Response = MergeRequestApprovals, Error = E>,
+  for<'req> <S as Service<&'req ApproveMergeRequestCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req UnapproveMergeRequestCommand<Cx>, Response = MergeRequestApprovals, Error = E>,
+  for<'req> <S as Service<&'req UnapproveMergeRequestCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req ResetMergeRequestApprovalsCommand<Cx>, Response = (), Error = E>,
+  for<'req> <S as Service<&'req ResetMergeRequestApprovalsCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req CreateProjectApprovalRuleCommand<Cx>, Response = ProjectApprovalRule, Error = E>,
+  for<'req> <S as Service<&'req CreateProjectApprovalRuleCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req UpdateProjectApprovalRuleCommand<Cx>, Response = ProjectApprovalRule, Error = E>,
+  for<'req> <S as Service<&'req UpdateProjectApprovalRuleCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req DeleteProjectApprovalRuleCommand<Cx>, Response = (), Error = E>,
+  for<'req> <S as Service<&'req DeleteProjectApprovalRuleCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req CreateMergeRequestApprovalRuleCommand<Cx>, Response = MergeRequestApprovalRule, Error = E>,
+  for<'req> <S as Service<&'req CreateMergeRequestApprovalRuleCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req UpdateMergeRequestApprovalRuleCommand<Cx>, Response = MergeRequestApprovalRule, Error = E>,
+  for<'req> <S as Service<&'req UpdateMergeRequestApprovalRuleCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req DeleteMergeRequestApprovalRuleCommand<Cx>, Response = (), Error = E>,
+  for<'req> <S as Service<&'req DeleteMergeRequestApprovalRuleCommand<Cx>>>::Future: Send,
+{
+  type Error = E;
+
+  async fn get_merge_request_approvals(
+    &mut self,
+    query: &GetMergeRequestApprovalsQuery<Cx>,
+  ) -> Result<MergeRequestApprovals, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_merge_request_approval_state(
+    &mut self,
+    query: &GetMergeRequestApprovalStateQuery<Cx>,
+  ) -> Result<MergeRequestApprovalState, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_merge_request_approval_rule_list(
+    &mut self,
+    query: &GetMergeRequestApprovalRuleListQuery<Cx>,
+  ) -> Result<Page<MergeRequestApprovalRule>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_merge_request_approval_rule_list_page(
+    &mut self,
+    query: &GetMergeRequestApprovalRuleListPageQuery<Cx>,
+  ) -> Result<Page<MergeRequestApprovalRule>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_merge_request_approval_rule(
+    &mut self,
+    query: &GetMergeRequestApprovalRuleQuery<Cx>,
+  ) -> Result<MergeRequestApprovalRule, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_project_approval_rule_list(
+    &mut self,
+    query: &GetProjectApprovalRuleListQuery<Cx>,
+  ) -> Result<Page<ProjectApprovalRule>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_project_approval_rule_list_page(
+    &mut self,
+    query: &GetProjectApprovalRuleListPageQuery<Cx>,
+  ) -> Result<Page<ProjectApprovalRule>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_project_approval_rule(
+    &mut self,
+    query: &GetProjectApprovalRuleQuery<Cx>,
+  ) -> Result<ProjectApprovalRule, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn approve_merge_request(
+    &mut self,
+    command: &ApproveMergeRequestCommand<Cx>,
+  ) -> Result<MergeRequestApprovals, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn unapprove_merge_request(
+    &mut self,
+    command: &UnapproveMergeRequestCommand<Cx>,
+  ) -> Result<MergeRequestApprovals, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn reset_merge_request_approvals(
+    &mut self,
+    command: &ResetMergeRequestApprovalsCommand<Cx>,
+  ) -> Result<(), Self::Error> {
+    call(self, command).await
+  }
+
+  async fn create_project_approval_rule(
+    &mut self,
+    command: &CreateProjectApprovalRuleCommand<Cx>,
+  ) -> Result<ProjectApprovalRule, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn update_project_approval_rule(
+    &mut self,
+    command: &UpdateProjectApprovalRuleCommand<Cx>,
+  ) -> Result<ProjectApprovalRule, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn delete_project_approval_rule(
+    &mut self,
+    command: &DeleteProjectApprovalRuleCommand<Cx>,
+  ) -> Result<(), Self::Error> {
+    call(self, command).await
+  }
+
+  async fn create_merge_request_approval_rule(
+    &mut self,
+    command: &CreateMergeRequestApprovalRuleCommand<Cx>,
+  ) -> Result<MergeRequestApprovalRule, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn update_merge_request_approval_rule(
+    &mut self,
+    command: &UpdateMergeRequestApprovalRuleCommand<Cx>,
+  ) -> Result<MergeRequestApprovalRule, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn delete_merge_request_approval_rule(
+    &mut self,
+    command: &DeleteMergeRequestApprovalRuleCommand<Cx>,
+  ) -> Result<(), Self::Error> {
+    call(self, command).await
+  }
+}
+
+/// Typed facade over the merge request handlers.
+///
+/// Implemented for any [`Service`] supporting the queries and commands below with the same error type.
+pub trait MergeRequestClient<Cx>: Send + Sync {
+  type Error;
+
+  /// List project merge requests
+  fn get_project_merge_request_list(
+    &mut self,
+    query: &GetProjectMergeRequestListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<MergeRequest>, Self::Error>>;
+
+  /// List group merge requests
+  fn get_group_merge_request_list(
+    &mut self,
+    query: &GetGroupMergeRequestListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<MergeRequest>, Self::Error>>;
+
+  /// List merge requests across all projects
+  fn get_merge_request_list(
+    &mut self,
+    query: &GetMergeRequestListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<MergeRequest>, Self::Error>>;
+
+  /// Get a page from a merge request list
+  fn get_merge_request_list_page(
+    &mut self,
+    query: &GetMergeRequestListPageQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<MergeRequest>, Self::Error>>;
+
+  /// Get a merge request
+  fn get_merge_request(
+    &mut self,
+    query: &GetMergeRequestQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<MergeRequest, Self::Error>>;
+
+  /// List the commits of a merge request
+  fn get_merge_request_commit_list(
+    &mut self,
+    query: &GetMergeRequestCommitListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<Commit>, Self::Error>>;
+
+  /// List the diffs of a merge request
+  fn get_merge_request_diff_list(
+    &mut self,
+    query: &GetMergeRequestDiffListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<Diff>, Self::Error>>;
+
+  /// Get a merge request with its changes
+  fn get_merge_request_changes(
+    &mut self,
+    query: &GetMergeRequestChangesQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<MergeRequestChanges, Self::Error>>;
+
+  /// List the diff versions of a merge request
+  fn get_merge_request_diff_version_list(
+    &mut self,
+    query: &GetMergeRequestDiffVersionListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<MergeRequestDiffVersion>, Self::Error>>;
+
+  /// Get a page from a merge request diff version list
+  fn get_merge_request_diff_version_list_page(
+    &mut self,
+    query: &GetMergeRequestDiffVersionListPageQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<MergeRequestDiffVersion>, Self::Error>>;
+
+  /// Get a merge request diff version
+  fn get_merge_request_diff_version(
+    &mut self,
+    query: &GetMergeRequestDiffVersionQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<MergeRequestDiffVersion, Self::Error>>;
+
+  /// Create a merge request
+  fn create_merge_request(
+    &mut self,
+    command: &CreateMergeRequestCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<MergeRequest, Self::Error>>;
+
+  /// Update a merge request
+  fn update_merge_request(
+    &mut self,
+    command: &UpdateMergeRequestCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<MergeRequest, Self::Error>>;
+
+  /// Merge a merge request
+  fn accept_merge_request(
+    &mut self,
+    command: &AcceptMergeRequestCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<MergeRequest, Self::Error>>;
+
+  /// Rebase a merge request on its target branch
+  fn rebase_merge_request(
+    &mut self,
+    command: &RebaseMergeRequestCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<MergeRequestRebase, Self::Error>>;
+}
+
+impl<S, Cx, E> MergeRequestClient<Cx> for S
+where
+  S: Send + Sync,
+  Cx: 'static + Send + Sync,
+  for<'req> S: Service<&'req GetProjectMergeRequestListQuery<Cx>, Response = Page<MergeRequest>, Error = E>,
+  for<'req> <S as Service<&'req GetProjectMergeRequestListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetGroupMergeRequestListQuery<Cx>, Response = Page<MergeRequest>, Error = E>,
+  for<'req> <S as Service<&'req GetGroupMergeRequestListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetMergeRequestListQuery<Cx>, Response = Page<MergeRequest>, Error = E>,
+  for<'req> <S as Service<&'req GetMergeRequestListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetMergeRequestListPageQuery<Cx>, Response = Page<MergeRequest>, Error = E>,
+  for<'req> <S as Service<&'req GetMergeRequestListPageQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetMergeRequestQuery<Cx>, Response = MergeRequest, Error = E>,
+  for<'req> <S as Service<&'req GetMergeRequestQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetMergeRequestCommitListQuery<Cx>, Response = Page<Commit>, Error = E>,
+  for<'req> <S as Service<&'req GetMergeRequestCommitListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetMergeRequestDiffListQuery<Cx>, Response = Page<Diff>, Error = E>,
+  for<'req> <S as Service<&'req GetMergeRequestDiffListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetMergeRequestChangesQuery<Cx>, Response = MergeRequestChanges, Error = E>,
+  for<'req> <S as Service<&'req GetMergeRequestChangesQuery<Cx>>>::Future: Send,
+  for<'req> S:
+    Service<&'req GetMergeRequestDiffVersionListQuery<Cx>, Response = Page<MergeRequestDiffVersion>, Error = E>,
+  for<'req> <S as Service<&'req GetMergeRequestDiffVersionListQuery<Cx>>>::Future: Send,
+  for<'req> S:
+    Service<&'req GetMergeRequestDiffVersionListPageQuery<Cx>, Response = Page<MergeRequestDiffVersion>, Error = E>,
+  for<'req> <S as Service<&'req GetMergeRequestDiffVersionListPageQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetMergeRequestDiffVersionQuery<Cx>, Response = MergeRequestDiffVersion, Error = E>,
+  for<'req> <S as Service<&'req GetMergeRequestDiffVersionQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req CreateMergeRequestCommand<Cx>, Response = MergeRequest, Error = E>,
+  for<'req> <S as Service<&'req CreateMergeRequestCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req UpdateMergeRequestCommand<Cx>, Response = MergeRequest, Error = E>,
+  for<'req> <S as Service<&'req UpdateMergeRequestCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req AcceptMergeRequestCommand<Cx>, Response = MergeRequest, Error = E>,
+  for<'req> <S as Service<&'req AcceptMergeRequestCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req RebaseMergeRequestCommand<Cx>, Response = MergeRequestRebase, Error = E>,
+  for<'req> <S as Service<&'req RebaseMergeRequestCommand<Cx>>>::Future: Send,
+{
+  type Error = E;
+
+  async fn get_project_merge_request_list(
+    &mut self,
+    query: &GetProjectMergeRequestListQuery<Cx>,
+  ) -> Result<Page<MergeRequest>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_group_merge_request_list(
+    &mut self,
+    query: &GetGroupMergeRequestListQuery<Cx>,
+  ) -> Result<Page<MergeRequest>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_merge_request_list(
+    &mut self,
+    query: &GetMergeRequestListQuery<Cx>,
+  ) -> Result<Page<MergeRequest>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_merge_request_list_page(
+    &mut self,
+    query: &GetMergeRequestListPageQuery<Cx>,
+  ) -> Result<Page<MergeRequest>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_merge_request(&mut self, query: &GetMergeRequestQuery<Cx>) -> Result<MergeRequest, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_merge_request_commit_list(
+    &mut self,
+    query: &GetMergeRequestCommitListQuery<Cx>,
+  ) -> Result<Page<Commit>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_merge_request_diff_list(
+    &mut self,
+    query: &GetMergeRequestDiffListQuery<Cx>,
+  ) -> Result<Page<Diff>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_merge_request_changes(
+    &mut self,
+    query: &GetMergeRequestChangesQuery<Cx>,
+  ) -> Result<MergeRequestChanges, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_merge_request_diff_version_list(
+    &mut self,
+    query: &GetMergeRequestDiffVersionListQuery<Cx>,
+  ) -> Result<Page<MergeRequestDiffVersion>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_merge_request_diff_version_list_page(
+    &mut self,
+    query: &GetMergeRequestDiffVersionListPageQuery<Cx>,
+  ) -> Result<Page<MergeRequestDiffVersion>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_merge_request_diff_version(
+    &mut self,
+    query: &GetMergeRequestDiffVersionQuery<Cx>,
+  ) -> Result<MergeRequestDiffVersion, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn create_merge_request(
+    &mut self,
+    command: &CreateMergeRequestCommand<Cx>,
+  ) -> Result<MergeRequest, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn update_merge_request(
+    &mut self,
+    command: &UpdateMergeRequestCommand<Cx>,
+  ) -> Result<MergeRequest, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn accept_merge_request(
+    &mut self,
+    command: &AcceptMergeRequestCommand<Cx>,
+  ) -> Result<MergeRequest, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn rebase_merge_request(
+    &mut self,
+    command: &RebaseMergeRequestCommand<Cx>,
+  ) -> Result<MergeRequestRebase, Self::Error> {
+    call(self, command).await
+  }
+}
+
+/// Typed facade over the note and discussion handlers.
+///
+/// Implemented for any [`Service`] supporting the queries and commands below with the same error type.
+pub trait NoteClient<Cx>: Send + Sync {
+  type Error;
+
+  /// List the notes of an issue, merge request, snippet or epic
+  fn get_note_list(
+    &mut self,
+    query: &GetNoteListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<Note>, Self::Error>>;
+
+  /// Get a page from a note list
+  fn get_note_list_page(
+    &mut self,
+    query: &GetNoteListPageQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<Note>, Self::Error>>;
+
+  /// Get a note of an issue, merge request, snippet or epic
+  fn get_note(&mut self, query: &GetNoteQuery<Cx>) -> impl Send + Future<Output = Result<Note, Self::Error>>;
+
+  /// List the discussion threads of an issue, merge request, commit, snippet or epic
+  fn get_discussion_list(
+    &mut self,
+    query: &GetDiscussionListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<Discussion>, Self::Error>>;
+
+  /// Get a page from a discussion list
+  fn get_discussion_list_page(
+    &mut self,
+    query: &GetDiscussionListPageQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<Discussion>, Self::Error>>;
+
+  /// Get a discussion thread
+  fn get_discussion(
+    &mut self,
+    query: &GetDiscussionQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Discussion, Self::Error>>;
+
+  /// Comment on a noteable, or reply to one of its discussion threads
+  fn create_note(&mut self, command: &CreateNoteCommand<Cx>) -> impl Send + Future<Output = Result<Note, Self::Error>>;
+
+  /// Edit the body of a note
+  fn update_note(&mut self, command: &UpdateNoteCommand<Cx>) -> impl Send + Future<Output = Result<Note, Self::Error>>;
+
+  /// Delete a note
+  fn delete_note(&mut self, command: &DeleteNoteCommand<Cx>) -> impl Send + Future<Output = Result<(), Self::Error>>;
+
+  /// Start a discussion thread
+  fn create_discussion(
+    &mut self,
+    command: &CreateDiscussionCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<Discussion, Self::Error>>;
+
+  /// Resolve or reopen a discussion thread of a merge request
+  fn resolve_discussion(
+    &mut self,
+    command: &ResolveDiscussionCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<Discussion, Self::Error>>;
+}
+
+impl<S, Cx, E> NoteClient<Cx> for S
+where
+  S: Send + Sync,
+  Cx: 'static + Send + Sync,
+  for<'req> S: Service<&'req GetNoteListQuery<Cx>, Response = Page<Note>, Error = E>,
+  for<'req> <S as Service<&'req GetNoteListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetNoteListPageQuery<Cx>, Response = Page<Note>, Error = E>,
+  for<'req> <S as Service<&'req GetNoteListPageQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetNoteQuery<Cx>, Response = Note, Error = E>,
+  for<'req> <S as Service<&'req GetNoteQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetDiscussionListQuery<Cx>, Response = Page<Discussion>, Error = E>,
+  for<'req> <S as Service<&'req GetDiscussionListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetDiscussionListPageQuery<Cx>, Response = Page<Discussion>, Error = E>,
+  for<'req> <S as Service<&'req GetDiscussionListPageQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetDiscussionQuery<Cx>, Response = Discussion, Error = E>,
+  for<'req> <S as Service<&'req GetDiscussionQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req CreateNoteCommand<Cx>, Response = Note, Error = E>,
+  for<'req> <S as Service<&'req CreateNoteCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req UpdateNoteCommand<Cx>, Response = Note, Error = E>,
+  for<'req> <S as Service<&'req UpdateNoteCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req DeleteNoteCommand<Cx>, Response = (), Error = E>,
+  for<'req> <S as Service<&'req DeleteNoteCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req CreateDiscussionCommand<Cx>, Response = Discussion, Error = E>,
+  for<'req> <S as Service<&'req CreateDiscussionCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req ResolveDiscussionCommand<Cx>, Response = Discussion, Error = E>,
+  for<'req> <S as Service<&'req ResolveDiscussionCommand<Cx>>>::Future: Send,
+{
+  type Error = E;
+
+  async fn get_note_list(&mut self, query: &GetNoteListQuery<Cx>) -> Result<Page<Note>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_note_list_page(&mut self, query: &GetNoteListPageQuery<Cx>) -> Result<Page<Note>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_note(&mut self, query: &GetNoteQuery<Cx>) -> Result<Note, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_discussion_list(&mut self, query: &GetDiscussionListQuery<Cx>) -> Result<Page<Discussion>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_discussion_list_page(
+    &mut self,
+    query: &GetDiscussionListPageQuery<Cx>,
+  ) -> Result<Page<Discussion>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_discussion(&mut self, query: &GetDiscussionQuery<Cx>) -> Result<Discussion, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn create_note(&mut self, command: &CreateNoteCommand<Cx>) -> Result<Note, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn update_note(&mut self, command: &UpdateNoteCommand<Cx>) -> Result<Note, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn delete_note(&mut self, command: &DeleteNoteCommand<Cx>) -> Result<(), Self::Error> {
+    call(self, command).await
+  }
+
+  async fn create_discussion(&mut self, command: &CreateDiscussionCommand<Cx>) -> Result<Discussion, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn resolve_discussion(&mut self, command: &ResolveDiscussionCommand<Cx>) -> Result<Discussion, Self::Error> {
+    call(self, command).await
+  }
+}
+
+/// Typed facade over the package handlers.
+///
+/// Implemented for any [`Service`] supporting the queries and commands below with the same error type.
+pub trait PackageClient<Cx>: Send + Sync {
+  type Error;
+
+  /// Get a generic package file
+  fn get_package_file(
+    &mut self,
+    query: &GetPackageFileQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Bytes, Self::Error>>;
+
+  /// List the files of a package
+  fn get_package_file_list(
+    &mut self,
+    query: &GetPackageFileListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<PackageFile>, Self::Error>>;
+
+  /// Get a page from a package file list
+  fn get_package_file_list_page(
+    &mut self,
+    query: &GetPackageFileListPageQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<PackageFile>, Self::Error>>;
+
+  /// List project packages
+  fn get_project_package_list(
+    &mut self,
+    query: &GetProjectPackageListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<Package>, Self::Error>>;
+
+  /// Get a page from the project package list
+  fn get_project_package_list_page(
+    &mut self,
+    query: &GetProjectPackageListPageQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<Package>, Self::Error>>;
+
+  /// Publish a generic package file
+  fn publish_package_file(
+    &mut self,
+    command: &PublishPackageFileCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<GenericPackageFile, Self::Error>>;
+}
+
+impl<S, Cx, E> PackageClient<Cx> for S
+where
+  S: Send + Sync,
+  Cx: 'static + Send + Sync,
+  for<'req> S: Service<&'req GetPackageFileQuery<Cx>, Response = Bytes, Error = E>,
+  for<'req> <S as Service<&'req GetPackageFileQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetPackageFileListQuery<Cx>, Response = Page<PackageFile>, Error = E>,
+  for<'req> <S as Service<&'req GetPackageFileListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetPackageFileListPageQuery<Cx>, Response = Page<PackageFile>, Error = E>,
+  for<'req> <S as Service<&'req GetPackageFileListPageQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetProjectPackageListQuery<Cx>, Response = Page<Package>, Error = E>,
+  for<'req> <S as Service<&'req GetProjectPackageListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetProjectPackageListPageQuery<Cx>, Response = Page<Package>, Error = E>,
+  for<'req> <S as Service<&'req GetProjectPackageListPageQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req PublishPackageFileCommand<Cx>, Response = GenericPackageFile, Error = E>,
+  for<'req> <S as Service<&'req PublishPackageFileCommand<Cx>>>::Future: Send,
 {
-  type Error<'req>
-    = <S as Service<&'req GetProjectListQuery<Cx>>>::Error
-  where
-    Cx: 'req;
+  type Error = E;
+
+  async fn get_package_file(&mut self, query: &GetPackageFileQuery<Cx>) -> Result<Bytes, Self::Error> {
+    call(self, query).await
+  }
 
-  async fn get_project_list(self, query: &GetProjectListQuery<Cx>) -> Result<Page<Project>, Self::Error<'_>> {
-    self.call(query).await
+  async fn get_package_file_list(
+    &mut self,
+    query: &GetPackageFileListQuery<Cx>,
+  ) -> Result<Page<PackageFile>, Self::Error> {
+    call(self, query).await
   }
+
+  async fn get_package_file_list_page(
+    &mut self,
+    query: &GetPackageFileListPageQuery<Cx>,
+  ) -> Result<Page<PackageFile>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_project_package_list(
+    &mut self,
+    query: &GetProjectPackageListQuery<Cx>,
+  ) -> Result<Page<Package>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_project_package_list_page(
+    &mut self,
+    query: &GetProjectPackageListPageQuery<Cx>,
+  ) -> Result<Page<Package>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn publish_package_file(
+    &mut self,
+    command: &PublishPackageFileCommand<Cx>,
+  ) -> Result<GenericPackageFile, Self::Error> {
+    call(self, command).await
+  }
+}
+
+/// Typed facade over the release and release link handlers.
+///
+/// Implemented for any [`Service`] supporting the queries and commands below with the same error type.
+pub trait ReleaseClient<Cx>: Send + Sync {
+  type Error;
+
+  /// Get a project release
+  fn get_project_release(
+    &mut self,
+    query: &GetProjectReleaseQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Release, Self::Error>>;
+
+  /// List project releases
+  fn get_project_release_list(
+    &mut self,
+    query: &GetProjectReleaseListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<Release>, Self::Error>>;
+
+  /// Get a page from the project release list
+  fn get_project_release_list_page(
+    &mut self,
+    query: &GetProjectReleaseListPageQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<Release>, Self::Error>>;
+
+  /// List the asset links of a project release
+  fn get_release_link_list(
+    &mut self,
+    query: &GetReleaseLinkListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<ReleaseLink>, Self::Error>>;
+
+  /// Get an asset link of a project release
+  fn get_release_link(
+    &mut self,
+    query: &GetReleaseLinkQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<ReleaseLink, Self::Error>>;
+
+  /// Create a project release
+  fn create_release(
+    &mut self,
+    command: &CreateReleaseCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<Release, Self::Error>>;
+
+  /// Create a project release link
+  fn create_release_link(
+    &mut self,
+    command: &CreateReleaseLinkCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<ReleaseLink, Self::Error>>;
+
+  /// Delete a project release
+  fn delete_release(
+    &mut self,
+    command: &DeleteReleaseCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<Release, Self::Error>>;
+
+  /// Delete an asset link of a project release
+  fn delete_release_link(
+    &mut self,
+    command: &DeleteReleaseLinkCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<ReleaseLink, Self::Error>>;
+
+  /// Update a project release
+  fn update_release(
+    &mut self,
+    command: &UpdateReleaseCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<Release, Self::Error>>;
+
+  /// Update an asset link of a project release
+  fn update_release_link(
+    &mut self,
+    command: &UpdateReleaseLinkCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<ReleaseLink, Self::Error>>;
+}
+
+impl<S, Cx, E> ReleaseClient<Cx> for S
+where
+  S: Send + Sync,
+  Cx: 'static + Send + Sync,
+  for<'req> S: Service<&'req GetProjectReleaseQuery<Cx>, Response = Release, Error = E>,
+  for<'req> <S as Service<&'req GetProjectReleaseQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetProjectReleaseListQuery<Cx>, Response = Page<Release>, Error = E>,
+  for<'req> <S as Service<&'req GetProjectReleaseListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetProjectReleaseListPageQuery<Cx>, Response = Page<Release>, Error = E>,
+  for<'req> <S as Service<&'req GetProjectReleaseListPageQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetReleaseLinkListQuery<Cx>, Response = Page<ReleaseLink>, Error = E>,
+  for<'req> <S as Service<&'req GetReleaseLinkListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetReleaseLinkQuery<Cx>, Response = ReleaseLink, Error = E>,
+  for<'req> <S as Service<&'req GetReleaseLinkQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req CreateReleaseCommand<Cx>, Response = Release, Error = E>,
+  for<'req> <S as Service<&'req CreateReleaseCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req CreateReleaseLinkCommand<Cx>, Response = ReleaseLink, Error = E>,
+  for<'req> <S as Service<&'req CreateReleaseLinkCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req DeleteReleaseCommand<Cx>, Response = Release, Error = E>,
+  for<'req> <S as Service<&'req DeleteReleaseCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req DeleteReleaseLinkCommand<Cx>, Response = ReleaseLink, Error = E>,
+  for<'req> <S as Service<&'req DeleteReleaseLinkCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req UpdateReleaseCommand<Cx>, Response = Release, Error = E>,
+  for<'req> <S as Service<&'req UpdateReleaseCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req UpdateReleaseLinkCommand<Cx>, Response = ReleaseLink, Error = E>,
+  for<'req> <S as Service<&'req UpdateReleaseLinkCommand<Cx>>>::Future: Send,
+{
+  type Error = E;
+
+  async fn get_project_release(&mut self, query: &GetProjectReleaseQuery<Cx>) -> Result<Release, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_project_release_list(
+    &mut self,
+    query: &GetProjectReleaseListQuery<Cx>,
+  ) -> Result<Page<Release>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_project_release_list_page(
+    &mut self,
+    query: &GetProjectReleaseListPageQuery<Cx>,
+  ) -> Result<Page<Release>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_release_link_list(
+    &mut self,
+    query: &GetReleaseLinkListQuery<Cx>,
+  ) -> Result<Page<ReleaseLink>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_release_link(&mut self, query: &GetReleaseLinkQuery<Cx>) -> Result<ReleaseLink, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn create_release(&mut self, command: &CreateReleaseCommand<Cx>) -> Result<Release, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn create_release_link(&mut self, command: &CreateReleaseLinkCommand<Cx>) -> Result<ReleaseLink, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn delete_release(&mut self, command: &DeleteReleaseCommand<Cx>) -> Result<Release, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn delete_release_link(&mut self, command: &DeleteReleaseLinkCommand<Cx>) -> Result<ReleaseLink, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn update_release(&mut self, command: &UpdateReleaseCommand<Cx>) -> Result<Release, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn update_release_link(&mut self, command: &UpdateReleaseLinkCommand<Cx>) -> Result<ReleaseLink, Self::Error> {
+    call(self, command).await
+  }
+}
+
+/// Typed facade over the project and group milestone handlers.
+///
+/// Implemented for any [`Service`] supporting the queries and commands below with the same error type.
+pub trait MilestoneClient<Cx>: Send + Sync {
+  type Error;
+
+  /// List project milestones
+  fn get_project_milestone_list(
+    &mut self,
+    query: &GetProjectMilestoneListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<Milestone>, Self::Error>>;
+
+  /// Get a project milestone
+  fn get_project_milestone(
+    &mut self,
+    query: &GetProjectMilestoneQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Milestone, Self::Error>>;
+
+  /// List group milestones
+  fn get_group_milestone_list(
+    &mut self,
+    query: &GetGroupMilestoneListQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<Milestone>, Self::Error>>;
+
+  /// Get a group milestone
+  fn get_group_milestone(
+    &mut self,
+    query: &GetGroupMilestoneQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Milestone, Self::Error>>;
+
+  /// Get a page from a project or group milestone list
+  fn get_milestone_list_page(
+    &mut self,
+    query: &GetMilestoneListPageQuery<Cx>,
+  ) -> impl Send + Future<Output = Result<Page<Milestone>, Self::Error>>;
+
+  /// Create a group milestone
+  fn create_group_milestone(
+    &mut self,
+    command: &CreateGroupMilestoneCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<Milestone, Self::Error>>;
+
+  /// Create a project milestone
+  fn create_project_milestone(
+    &mut self,
+    command: &CreateProjectMilestoneCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<Milestone, Self::Error>>;
+
+  /// Delete a group milestone
+  fn delete_group_milestone(
+    &mut self,
+    command: &DeleteGroupMilestoneCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<(), Self::Error>>;
+
+  /// Delete a project milestone
+  fn delete_project_milestone(
+    &mut self,
+    command: &DeleteProjectMilestoneCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<(), Self::Error>>;
+
+  /// Update a group milestone
+  fn update_group_milestone(
+    &mut self,
+    command: &UpdateGroupMilestoneCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<Milestone, Self::Error>>;
+
+  /// Update a project milestone
+  fn update_project_milestone(
+    &mut self,
+    command: &UpdateProjectMilestoneCommand<Cx>,
+  ) -> impl Send + Future<Output = Result<Milestone, Self::Error>>;
+}
+
+impl<S, Cx, E> MilestoneClient<Cx> for S
+where
+  S: Send + Sync,
+  Cx: 'static + Send + Sync,
+  for<'req> S: Service<&'req GetProjectMilestoneListQuery<Cx>, Response = Page<Milestone>, Error = E>,
+  for<'req> <S as Service<&'req GetProjectMilestoneListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetProjectMilestoneQuery<Cx>, Response = Milestone, Error = E>,
+  for<'req> <S as Service<&'req GetProjectMilestoneQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetGroupMilestoneListQuery<Cx>, Response = Page<Milestone>, Error = E>,
+  for<'req> <S as Service<&'req GetGroupMilestoneListQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetGroupMilestoneQuery<Cx>, Response = Milestone, Error = E>,
+  for<'req> <S as Service<&'req GetGroupMilestoneQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req GetMilestoneListPageQuery<Cx>, Response = Page<Milestone>, Error = E>,
+  for<'req> <S as Service<&'req GetMilestoneListPageQuery<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req CreateGroupMilestoneCommand<Cx>, Response = Milestone, Error = E>,
+  for<'req> <S as Service<&'req CreateGroupMilestoneCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req CreateProjectMilestoneCommand<Cx>, Response = Milestone, Error = E>,
+  for<'req> <S as Service<&'req CreateProjectMilestoneCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req DeleteGroupMilestoneCommand<Cx>, Response = (), Error = E>,
+  for<'req> <S as Service<&'req DeleteGroupMilestoneCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req DeleteProjectMilestoneCommand<Cx>, Response = (), Error = E>,
+  for<'req> <S as Service<&'req DeleteProjectMilestoneCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req UpdateGroupMilestoneCommand<Cx>, Response = Milestone, Error = E>,
+  for<'req> <S as Service<&'req UpdateGroupMilestoneCommand<Cx>>>::Future: Send,
+  for<'req> S: Service<&'req UpdateProjectMilestoneCommand<Cx>, Response = Milestone, Error = E>,
+  for<'req> <S as Service<&'req UpdateProjectMilestoneCommand<Cx>>>::Future: Send,
+{
+  type Error = E;
+
+  async fn get_project_milestone_list(
+    &mut self,
+    query: &GetProjectMilestoneListQuery<Cx>,
+  ) -> Result<Page<Milestone>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_project_milestone(&mut self, query: &GetProjectMilestoneQuery<Cx>) -> Result<Milestone, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_group_milestone_list(
+    &mut self,
+    query: &GetGroupMilestoneListQuery<Cx>,
+  ) -> Result<Page<Milestone>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_group_milestone(&mut self, query: &GetGroupMilestoneQuery<Cx>) -> Result<Milestone, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn get_milestone_list_page(
+    &mut self,
+    query: &GetMilestoneListPageQuery<Cx>,
+  ) -> Result<Page<Milestone>, Self::Error> {
+    call(self, query).await
+  }
+
+  async fn create_group_milestone(
+    &mut self,
+    command: &CreateGroupMilestoneCommand<Cx>,
+  ) -> Result<Milestone, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn create_project_milestone(
+    &mut self,
+    command: &CreateProjectMilestoneCommand<Cx>,
+  ) -> Result<Milestone, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn delete_group_milestone(&mut self, command: &DeleteGroupMilestoneCommand<Cx>) -> Result<(), Self::Error> {
+    call(self, command).await
+  }
+
+  async fn delete_project_milestone(&mut self, command: &DeleteProjectMilestoneCommand<Cx>) -> Result<(), Self::Error> {
+    call(self, command).await
+  }
+
+  async fn update_group_milestone(
+    &mut self,
+    command: &UpdateGroupMilestoneCommand<Cx>,
+  ) -> Result<Milestone, Self::Error> {
+    call(self, command).await
+  }
+
+  async fn update_project_milestone(
+    &mut self,
+    command: &UpdateProjectMilestoneCommand<Cx>,
+  ) -> Result<Milestone, Self::Error> {
+    call(self, command).await
+  }
+}
+
+/// Typed facade over all the query and command handlers.
+///
+/// This trait is implemented for any client implementing every per-area trait ([`ProjectClient`], [`ReleaseClient`],
+/// ...) with the same error type. Prefer depending on the per-area traits you actually use: a [`Service`] supporting
+/// only part of the API still gets the methods of the areas it fully supports.
+pub trait GitlabClient<Cx>:
+  ProjectClient<Cx, Error = <Self as GitlabClient<Cx>>::Error>
+  + RepositoryClient<Cx, Error = <Self as GitlabClient<Cx>>::Error>
+  + CommitClient<Cx, Error = <Self as GitlabClient<Cx>>::Error>
+  + BranchClient<Cx, Error = <Self as GitlabClient<Cx>>::Error>
+  + TagClient<Cx, Error = <Self as GitlabClient<Cx>>::Error>
+  + ApprovalClient<Cx, Error = <Self as GitlabClient<Cx>>::Error>
+  + MergeRequestClient<Cx, Error = <Self as GitlabClient<Cx>>::Error>
+  + NoteClient<Cx, Error = <Self as GitlabClient<Cx>>::Error>
+  + PackageClient<Cx, Error = <Self as GitlabClient<Cx>>::Error>
+  + ReleaseClient<Cx, Error = <Self as GitlabClient<Cx>>::Error>
+  + MilestoneClient<Cx, Error = <Self as GitlabClient<Cx>>::Error>
+{
+  type Error;
+}
+
+impl<S, Cx, E> GitlabClient<Cx> for S
+where
+  S: ProjectClient<Cx, Error = E>,
+  S: RepositoryClient<Cx, Error = E>,
+  S: CommitClient<Cx, Error = E>,
+  S: BranchClient<Cx, Error = E>,
+  S: TagClient<Cx, Error = E>,
+  S: ApprovalClient<Cx, Error = E>,
+  S: MergeRequestClient<Cx, Error = E>,
+  S: NoteClient<Cx, Error = E>,
+  S: PackageClient<Cx, Error = E>,
+  S: ReleaseClient<Cx, Error = E>,
+  S: MilestoneClient<Cx, Error = E>,
+{
+  type Error = E;
+}
+
+/// Wait for the service to be ready, then send the request.
+pub(crate) async fn call<S, Req>(service: &mut S, req: Req) -> Result<S::Response, S::Error>
+where
+  S: Service<Req>,
+{
+  futures::future::poll_fn(|cx| service.poll_ready(cx)).await?;
+  service.call(req).await
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -68,7 +1922,11 @@ impl InputPackageStatus {
   }
 }
 
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(rename_all = "snake_case")
+)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum PackageStatus {
   Default,
@@ -0,0 +1,353 @@
+use crate::common::approval::{MergeRequestApprovalRule, ProjectApprovalRule};
+use crate::common::branch::Branch;
+use crate::common::commit::{Commit, CommitComment, CommitRef};
+use crate::common::diff::Diff;
+use crate::common::merge_request::{MergeRequest, MergeRequestDiffVersion};
+use crate::common::milestone::Milestone;
+use crate::common::note::{Discussion, Note};
+use crate::common::package::{Package, PackageFile};
+use crate::common::project::Project;
+use crate::common::protected_branch::ProtectedBranch;
+use crate::common::protected_tag::ProtectedTag;
+use crate::common::release::Release;
+use crate::common::tag::Tag;
+use crate::common::Page;
+use crate::query::get_branch_list::GetBranchListQuery;
+use crate::query::get_branch_list_page::GetBranchListPageQuery;
+use crate::query::get_commit_comment_list::GetCommitCommentListQuery;
+use crate::query::get_commit_comment_list_page::GetCommitCommentListPageQuery;
+use crate::query::get_commit_diff::GetCommitDiffQuery;
+use crate::query::get_commit_list::GetCommitListQuery;
+use crate::query::get_commit_list_page::GetCommitListPageQuery;
+use crate::query::get_commit_ref_list::GetCommitRefListQuery;
+use crate::query::get_commit_ref_list_page::GetCommitRefListPageQuery;
+use crate::query::get_diff_list_page::GetDiffListPageQuery;
+use crate::query::get_discussion_list::GetDiscussionListQuery;
+use crate::query::get_discussion_list_page::GetDiscussionListPageQuery;
+use crate::query::get_group_merge_request_list::GetGroupMergeRequestListQuery;
+use crate::query::get_group_milestone_list::GetGroupMilestoneListQuery;
+use crate::query::get_merge_request_approval_rule_list::GetMergeRequestApprovalRuleListQuery;
+use crate::query::get_merge_request_approval_rule_list_page::GetMergeRequestApprovalRuleListPageQuery;
+use crate::query::get_merge_request_commit_list::GetMergeRequestCommitListQuery;
+use crate::query::get_merge_request_diff_list::GetMergeRequestDiffListQuery;
+use crate::query::get_merge_request_diff_version_list::GetMergeRequestDiffVersionListQuery;
+use crate::query::get_merge_request_diff_version_list_page::GetMergeRequestDiffVersionListPageQuery;
+use crate::query::get_merge_request_list::GetMergeRequestListQuery;
+use crate::query::get_merge_request_list_page::GetMergeRequestListPageQuery;
+use crate::query::get_milestone_list_page::GetMilestoneListPageQuery;
+use crate::query::get_note_list::GetNoteListQuery;
+use crate::query::get_note_list_page::GetNoteListPageQuery;
+use crate::query::get_package_file_list::GetPackageFileListQuery;
+use crate::query::get_package_file_list_page::GetPackageFileListPageQuery;
+use crate::query::get_project_approval_rule_list::GetProjectApprovalRuleListQuery;
+use crate::query::get_project_approval_rule_list_page::GetProjectApprovalRuleListPageQuery;
+use crate::query::get_project_list::GetProjectListQuery;
+use crate::query::get_project_list_page::GetProjectListPageQuery;
+use crate::query::get_project_merge_request_list::GetProjectMergeRequestListQuery;
+use crate::query::get_project_milestone_list::GetProjectMilestoneListQuery;
+use crate::query::get_project_package_list::GetProjectPackageListQuery;
+use crate::query::get_project_package_list_page::GetProjectPackageListPageQuery;
+use crate::query::get_project_release_list::GetProjectReleaseListQuery;
+use crate::query::get_project_release_list_page::GetProjectReleaseListPageQuery;
+use crate::query::get_protected_branch_list::GetProtectedBranchListQuery;
+use crate::query::get_protected_branch_list_page::GetProtectedBranchListPageQuery;
+use crate::query::get_protected_tag_list::GetProtectedTagListQuery;
+use crate::query::get_protected_tag_list_page::GetProtectedTagListPageQuery;
+use crate::query::get_tag_list::GetTagListQuery;
+use crate::query::get_tag_list_page::GetTagListPageQuery;
+use compact_str::CompactString;
+use futures::Stream;
+use tower_service::Service;
+
+/// A list query whose results can be continued using the page cursors of its response.
+pub trait PaginatedQuery {
+  /// Type of the items in each page
+  type Item;
+  /// Query used to retrieve the page at a given cursor
+  type PageQuery;
+
+  /// Build the query retrieving the page at `cursor`.
+  ///
+  /// The page query reuses the context and auth of the current query.
+  fn page_query(&self, cursor: CompactString) -> Self::PageQuery;
+}
+
+macro_rules! paginated_query {
+  ($(($ty:ident, $page:ident, $item:ty)),* $(,)?) => {
+    $(
+      impl<Cx: Clone> PaginatedQuery for $ty<Cx> {
+        type Item = $item;
+        type PageQuery = $page<Cx>;
+
+        fn page_query(&self, cursor: CompactString) -> Self::PageQuery {
+          $page {
+            context: self.context.clone(),
+            auth: self.auth.clone(),
+            cursor,
+          }
+        }
+      }
+    )*
+  };
+}
+
+paginated_query!(
+  (GetProjectListQuery, GetProjectListPageQuery, Project),
+  (GetProjectListPageQuery, GetProjectListPageQuery, Project),
+  (GetProjectPackageListQuery, GetProjectPackageListPageQuery, Package),
+  (GetProjectPackageListPageQuery, GetProjectPackageListPageQuery, Package),
+  (GetPackageFileListQuery, GetPackageFileListPageQuery, PackageFile),
+  (GetPackageFileListPageQuery, GetPackageFileListPageQuery, PackageFile),
+  (GetProjectReleaseListQuery, GetProjectReleaseListPageQuery, Release),
+  (GetProjectReleaseListPageQuery, GetProjectReleaseListPageQuery, Release),
+  (GetProjectMilestoneListQuery, GetMilestoneListPageQuery, Milestone),
+  (GetGroupMilestoneListQuery, GetMilestoneListPageQuery, Milestone),
+  (GetMilestoneListPageQuery, GetMilestoneListPageQuery, Milestone),
+  (GetCommitListQuery, GetCommitListPageQuery, Commit),
+  (GetCommitListPageQuery, GetCommitListPageQuery, Commit),
+  (GetCommitDiffQuery, GetDiffListPageQuery, Diff),
+  (GetDiffListPageQuery, GetDiffListPageQuery, Diff),
+  (GetCommitRefListQuery, GetCommitRefListPageQuery, CommitRef),
+  (GetCommitRefListPageQuery, GetCommitRefListPageQuery, CommitRef),
+  (GetCommitCommentListQuery, GetCommitCommentListPageQuery, CommitComment),
+  (
+    GetCommitCommentListPageQuery,
+    GetCommitCommentListPageQuery,
+    CommitComment
+  ),
+  (GetBranchListQuery, GetBranchListPageQuery, Branch),
+  (GetBranchListPageQuery, GetBranchListPageQuery, Branch),
+  (
+    GetProtectedBranchListQuery,
+    GetProtectedBranchListPageQuery,
+    ProtectedBranch
+  ),
+  (
+    GetProtectedBranchListPageQuery,
+    GetProtectedBranchListPageQuery,
+    ProtectedBranch
+  ),
+  (GetTagListQuery, GetTagListPageQuery, Tag),
+  (GetTagListPageQuery, GetTagListPageQuery, Tag),
+  (GetProtectedTagListQuery, GetProtectedTagListPageQuery, ProtectedTag),
+  (GetProtectedTagListPageQuery, GetProtectedTagListPageQuery, ProtectedTag),
+  (
+    GetProjectMergeRequestListQuery,
+    GetMergeRequestListPageQuery,
+    MergeRequest
+  ),
+  (
+    GetGroupMergeRequestListQuery,
+    GetMergeRequestListPageQuery,
+    MergeRequest
+  ),
+  (GetMergeRequestListQuery, GetMergeRequestListPageQuery, MergeRequest),
+  (GetMergeRequestListPageQuery, GetMergeRequestListPageQuery, MergeRequest),
+  (GetMergeRequestCommitListQuery, GetCommitListPageQuery, Commit),
+  (GetMergeRequestDiffListQuery, GetDiffListPageQuery, Diff),
+  (
+    GetMergeRequestDiffVersionListQuery,
+    GetMergeRequestDiffVersionListPageQuery,
+    MergeRequestDiffVersion
+  ),
+  (
+    GetMergeRequestDiffVersionListPageQuery,
+    GetMergeRequestDiffVersionListPageQuery,
+    MergeRequestDiffVersion
+  ),
+  (
+    GetMergeRequestApprovalRuleListQuery,
+    GetMergeRequestApprovalRuleListPageQuery,
+    MergeRequestApprovalRule
+  ),
+  (
+    GetMergeRequestApprovalRuleListPageQuery,
+    GetMergeRequestApprovalRuleListPageQuery,
+    MergeRequestApprovalRule
+  ),
+  (
+    GetProjectApprovalRuleListQuery,
+    GetProjectApprovalRuleListPageQuery,
+    ProjectApprovalRule
+  ),
+  (
+    GetProjectApprovalRuleListPageQuery,
+    GetProjectApprovalRuleListPageQuery,
+    ProjectApprovalRule
+  ),
+  (GetNoteListQuery, GetNoteListPageQuery, Note),
+  (GetNoteListPageQuery, GetNoteListPageQuery, Note),
+  (GetDiscussionListQuery, GetDiscussionListPageQuery, Discussion),
+  (GetDiscussionListPageQuery, GetDiscussionListPageQuery, Discussion),
+);
+
+enum Cursor {
+  Start,
+  Next(CompactString),
+  End,
+}
+
+struct PaginateState<'a, S, Q: PaginatedQuery> {
+  client: &'a mut S,
+  query: &'a Q,
+  cursor: Cursor,
+  items: std::vec::IntoIter<Q::Item>,
+  remaining: Option<usize>,
+}
+
+/// Iterate over all the items of a list query, following the `next` cursor of each page.
+///
+/// The first page is retrieved with `query`, the following ones with its page query. Iteration stops when there is
+/// no `next` page, after the first error, or once `limit` items were produced.
+pub fn paginate<'a, S, Q, E>(
+  client: &'a mut S,
+  query: &'a Q,
+  limit: Option<usize>,
+) -> impl Stream<Item = Result<Q::Item, E>> + 'a
+where
+  Q: PaginatedQuery,
+  for<'req> S: Service<&'req Q, Response = Page<Q::Item>, Error = E>
+    + Service<&'req Q::PageQuery, Response = Page<Q::Item>, Error = E>,
+{
+  let state = PaginateState {
+    client,
+    query,
+    cursor: Cursor::Start,
+    items: Vec::new().into_iter(),
+    remaining: limit,
+  };
+  futures::stream::unfold(state, |mut state| async move {
+    loop {
+      if state.remaining == Some(0) {
+        return None;
+      }
+      if let Some(item) = state.items.next() {
+        if let Some(remaining) = state.remaining.as_mut() {
+          *remaining -= 1;
+        }
+        return Some((Ok(item), state));
+      }
+      let page = match core::mem::replace(&mut state.cursor, Cursor::End) {
+        Cursor::Start => crate::call(state.client, state.query).await,
+        Cursor::Next(cursor) => {
+          let query = state.query.page_query(cursor);
+          crate::call(state.client, &query).await
+        }
+        Cursor::End => return None,
+      };
+      match page {
+        Ok(page) => {
+          if let Some(next) = page.next {
+            state.cursor = Cursor::Next(next);
+          }
+          state.items = page.items.into_iter();
+        }
+        Err(e) => return Some((Err(e), state)),
+      }
+    }
+  })
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use core::task::{Context, Poll};
+  use futures::future::{ready, Ready};
+  use futures::{StreamExt, TryStreamExt};
+
+  struct ListQuery {
+    auth: &'static str,
+  }
+
+  struct PageQuery {
+    auth: &'static str,
+    cursor: CompactString,
+  }
+
+  impl PaginatedQuery for ListQuery {
+    type Item = u32;
+    type PageQuery = PageQuery;
+
+    fn page_query(&self, cursor: CompactString) -> Self::PageQuery {
+      PageQuery {
+        auth: self.auth,
+        cursor,
+      }
+    }
+  }
+
+  /// Serves three pages: `[0, 1]`, `[2, 3]`, `[4]`
+  #[derive(Default)]
+  struct FakeService {
+    calls: Vec<CompactString>,
+  }
+
+  fn page(index: u32) -> Page<u32> {
+    let items = if index < 2 {
+      vec![2 * index, 2 * index + 1]
+    } else {
+      vec![2 * index]
+    };
+    Page {
+      first: Some(CompactString::new("page-0")),
+      next: if index < 2 {
+        Some(CompactString::new(format!("page-{}", index + 1)))
+      } else {
+        None
+      },
+      last: Some(CompactString::new("page-2")),
+      items,
+    }
+  }
+
+  impl<'req> Service<&'req ListQuery> for FakeService {
+    type Response = Page<u32>;
+    type Error = ();
+    type Future = Ready<Result<Page<u32>, ()>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), ()>> {
+      Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: &'req ListQuery) -> Self::Future {
+      assert_eq!(req.auth, "secret");
+      self.calls.push(CompactString::new("list"));
+      ready(Ok(page(0)))
+    }
+  }
+
+  impl<'req> Service<&'req PageQuery> for FakeService {
+    type Response = Page<u32>;
+    type Error = ();
+    type Future = Ready<Result<Page<u32>, ()>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), ()>> {
+      Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: &'req PageQuery) -> Self::Future {
+      assert_eq!(req.auth, "secret");
+      self.calls.push(req.cursor.clone());
+      let index: u32 = req.cursor.strip_prefix("page-").unwrap().parse().unwrap();
+      ready(Ok(page(index)))
+    }
+  }
+
+  #[test]
+  fn paginate_all() {
+    let mut client = FakeService::default();
+    let query = ListQuery { auth: "secret" };
+    let actual: Vec<u32> = futures::executor::block_on(paginate(&mut client, &query, None).try_collect()).unwrap();
+    assert_eq!(actual, vec![0, 1, 2, 3, 4]);
+    assert_eq!(client.calls, vec!["list", "page-1", "page-2"]);
+  }
+
+  #[test]
+  fn paginate_with_limit() {
+    let mut client = FakeService::default();
+    let query = ListQuery { auth: "secret" };
+    let actual: Vec<Result<u32, ()>> = futures::executor::block_on(paginate(&mut client, &query, Some(3)).collect());
+    assert_eq!(actual, vec![Ok(0), Ok(1), Ok(2)]);
+    assert_eq!(client.calls, vec!["list", "page-1"]);
+  }
+}
@@ -0,0 +1,547 @@
+//! High-level workflow publishing a release with its files
+
+use crate::body::{parse_sha256, UploadBody};
+use crate::client::http::HttpGitlabClientError;
+use crate::command::create_release::CreateReleaseCommand;
+use crate::command::create_release_link::CreateReleaseLinkCommand;
+use crate::command::delete_release_link::DeleteReleaseLinkCommand;
+use crate::command::publish_package_file::PublishPackageFileCommand;
+use crate::command::update_release::UpdateReleaseCommand;
+use crate::command::update_release_link::UpdateReleaseLinkCommand;
+use crate::common::package::{GenericPackageFile, PackageId, PackageType};
+use crate::common::project::ProjectRef;
+use crate::common::release::{InputReleaseAssets, Release, ReleaseLink, ReleaseLinkType};
+use crate::context::{GetRef, GitlabUrl};
+use crate::endpoint::endpoint_url;
+use crate::query::get_package_file::GetPackageFileQuery;
+use crate::query::get_package_file_list::GetPackageFileListQuery;
+use crate::query::get_package_file_list_page::GetPackageFileListPageQuery;
+use crate::query::get_project_package_list::GetProjectPackageListQuery;
+use crate::query::get_project_package_list_page::GetProjectPackageListPageQuery;
+use crate::query::get_project_release::GetProjectReleaseQuery;
+use crate::{call, GitlabAuth, InputPackageStatus, PackageClient, ReleaseClient};
+use bytes::Bytes;
+use compact_str::{format_compact, CompactString};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use tower_service::Service;
+
+/// Release to publish, with the files to upload to a generic package
+#[derive(Debug)]
+pub struct PublishRelease<Cx> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth>,
+  pub project: ProjectRef,
+  pub tag_name: CompactString,
+  /// Release name, defaults to the tag name when the release is created
+  pub name: Option<CompactString>,
+  pub description: Option<CompactString>,
+  /// Commit or branch to create the tag from, if it does not exist yet
+  pub r#ref: Option<CompactString>,
+  /// Generic package receiving the files
+  pub package_name: CompactString,
+  pub package_version: CompactString,
+  pub files: Vec<ReleaseFile>,
+}
+
+/// File uploaded to the generic package and linked from the release
+///
+/// The content is streamed from `data`, which is only read if the package does not already hold a file with the same
+/// name and SHA-256 digest.
+#[derive(Debug)]
+pub struct ReleaseFile {
+  pub filename: CompactString,
+  /// SHA-256 digest of the content
+  pub sha256: [u8; 32],
+  pub data: UploadBody,
+  /// Path of the permanent link, relative to the release downloads (defaults to `/<filename>`)
+  pub direct_asset_path: Option<CompactString>,
+  pub link_type: ReleaseLinkType,
+}
+
+impl ReleaseFile {
+  pub fn new(filename: CompactString, sha256: [u8; 32], data: UploadBody) -> Self {
+    Self {
+      filename,
+      sha256,
+      data,
+      direct_asset_path: None,
+      link_type: ReleaseLinkType::Package,
+    }
+  }
+
+  /// Create a file from its content, computing its digest
+  pub fn from_bytes(filename: CompactString, data: Bytes) -> Self {
+    let sha256 = Sha256::digest(&data).into();
+    Self::new(filename, sha256, UploadBody::from(data))
+  }
+
+  fn direct_asset_path(&self) -> CompactString {
+    match self.direct_asset_path.as_ref() {
+      Some(path) if path.starts_with('/') => path.clone(),
+      Some(path) => format_compact!("/{path}"),
+      None => format_compact!("/{}", self.filename),
+    }
+  }
+}
+
+/// Change applied to a resource by [`publish_release`]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PublishAction {
+  Created,
+  Updated,
+  /// The resource was already up to date
+  Skipped,
+  Deleted,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PublishedFile {
+  pub filename: CompactString,
+  pub action: PublishAction,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PublishedLink {
+  pub link: ReleaseLink,
+  pub action: PublishAction,
+}
+
+/// Changes applied by [`publish_release`]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublishReleaseReport {
+  /// Release, as returned before its links were reconciled
+  pub release: Release,
+  pub release_action: PublishAction,
+  pub files: Vec<PublishedFile>,
+  pub links: Vec<PublishedLink>,
+}
+
+/// Upload the release files, create or update the release, then link each file from the release.
+///
+/// The workflow is idempotent, so it can be run again after a partial failure:
+/// - a file is only uploaded if the package does not already hold a file with the same name and SHA-256 digest, as
+///   reported by the package file list;
+/// - the release is created, or updated if it already exists (`Conflict`);
+/// - each file ends with exactly one release link, matched by name or direct asset path. Missing links are created,
+///   outdated links are updated and duplicates are deleted. Links unrelated to the files are kept.
+pub async fn publish_release<C, Cx>(
+  client: &mut C,
+  mut release: PublishRelease<Cx>,
+) -> Result<PublishReleaseReport, HttpGitlabClientError>
+where
+  C: PackageClient<Cx, Error = HttpGitlabClientError>
+    + ReleaseClient<Cx, Error = HttpGitlabClientError>
+    + Service<
+      PublishPackageFileCommand<Cx, CompactString, UploadBody>,
+      Response = GenericPackageFile,
+      Error = HttpGitlabClientError,
+    >,
+  <C as Service<PublishPackageFileCommand<Cx, CompactString, UploadBody>>>::Future: Send,
+  Cx: Clone + GetRef<GitlabUrl> + Send + Sync + 'static,
+{
+  let digests = package_file_digests(client, &release).await?;
+  let mut files = Vec::with_capacity(release.files.len());
+  let mut file_urls = Vec::with_capacity(release.files.len());
+  for file in &mut release.files {
+    let query = GetPackageFileQuery {
+      context: release.context.clone(),
+      auth: release.auth.clone(),
+      project: release.project.clone(),
+      package_name: release.package_name.clone(),
+      package_version: release.package_version.clone(),
+      filename: file.filename.clone(),
+    };
+    let action = match digests.get(&file.filename) {
+      None => PublishAction::Created,
+      Some(digest) if *digest == Some(file.sha256) => PublishAction::Skipped,
+      Some(_) => PublishAction::Updated,
+    };
+    if action != PublishAction::Skipped {
+      // The body is consumed by the upload
+      let data = core::mem::replace(&mut file.data, UploadBody::from(Bytes::new()));
+      let command = PublishPackageFileCommand {
+        context: release.context.clone(),
+        auth: release.auth.clone(),
+        project: release.project.clone(),
+        package_name: release.package_name.clone(),
+        package_version: release.package_version.clone(),
+        filename: file.filename.clone(),
+        status: InputPackageStatus::Default,
+        data,
+      };
+      call(client, command).await?;
+    }
+    let url = endpoint_url(&query).map_err(|e| HttpGitlabClientError::Request(format!("{e:?}")))?;
+    file_urls.push(CompactString::new(url.as_str()));
+    files.push(PublishedFile {
+      filename: file.filename.clone(),
+      action,
+    });
+  }
+
+  let (current, release_action) = upsert_release(client, &release).await?;
+
+  let mut links = Vec::new();
+  let mut existing = current.assets.links.clone();
+  for (file, url) in release.files.iter().zip(file_urls) {
+    let path = file.direct_asset_path();
+    let downloads_suffix = format!("/-/releases/{}/downloads{path}", release.tag_name);
+    let matches = |link: &ReleaseLink| {
+      link.name == file.filename.as_str()
+        || link.direct_asset_path.as_deref() == Some(path.as_str())
+        || link.direct_asset_url.ends_with(&downloads_suffix)
+    };
+    let mut matching = existing
+      .iter()
+      .filter(|link| matches(link))
+      .cloned()
+      .collect::<Vec<_>>()
+      .into_iter();
+    existing.retain(|link| !matches(link));
+
+    match matching.next() {
+      None => {
+        let command = CreateReleaseLinkCommand {
+          context: release.context.clone(),
+          auth: release.auth.clone(),
+          project: release.project.clone(),
+          tag_name: release.tag_name.clone(),
+          name: file.filename.clone(),
+          url: url.clone(),
+          direct_asset_path: Some(path.clone()),
+          link_type: file.link_type,
+        };
+        let link = client.create_release_link(&command).await?;
+        links.push(PublishedLink {
+          link,
+          action: PublishAction::Created,
+        });
+      }
+      Some(link) => {
+        let up_to_date = link.name == file.filename.as_str()
+          && link.url == url.as_str()
+          && link.link_type == file.link_type
+          && link.direct_asset_url.ends_with(&downloads_suffix);
+        if up_to_date {
+          links.push(PublishedLink {
+            link,
+            action: PublishAction::Skipped,
+          });
+        } else {
+          let command = UpdateReleaseLinkCommand {
+            context: release.context.clone(),
+            auth: release.auth.clone(),
+            project: release.project.clone(),
+            tag_name: release.tag_name.clone(),
+            link_id: link.id,
+            name: Some(file.filename.clone()),
+            url: Some(url.clone()),
+            direct_asset_path: Some(path.clone()),
+            link_type: Some(file.link_type),
+          };
+          let link = client.update_release_link(&command).await?;
+          links.push(PublishedLink {
+            link,
+            action: PublishAction::Updated,
+          });
+        }
+      }
+    }
+    for duplicate in matching {
+      let command = DeleteReleaseLinkCommand {
+        context: release.context.clone(),
+        auth: release.auth.clone(),
+        project: release.project.clone(),
+        tag_name: release.tag_name.clone(),
+        link_id: duplicate.id,
+      };
+      let link = match client.delete_release_link(&command).await {
+        Ok(link) => link,
+        // Already deleted by a concurrent run
+        Err(HttpGitlabClientError::NotFound(_)) => duplicate,
+        Err(e) => return Err(e),
+      };
+      links.push(PublishedLink {
+        link,
+        action: PublishAction::Deleted,
+      });
+    }
+  }
+
+  Ok(PublishReleaseReport {
+    release: current,
+    release_action,
+    files,
+    links,
+  })
+}
+
+/// SHA-256 digests of the files of the package version, by file name
+///
+/// GitLab keeps every file uploaded with the same name and serves the most recent one, so it is the one compared.
+/// The digest is `None` if GitLab did not report it.
+async fn package_file_digests<C, Cx>(
+  client: &mut C,
+  release: &PublishRelease<Cx>,
+) -> Result<BTreeMap<CompactString, Option<[u8; 32]>>, HttpGitlabClientError>
+where
+  C: PackageClient<Cx, Error = HttpGitlabClientError>,
+  Cx: Clone + Send + Sync + 'static,
+{
+  let mut query = GetProjectPackageListQuery::new(release.project.clone()).set_context(release.context.clone());
+  query.auth = release.auth.clone();
+  query.package_type = Some(PackageType::Generic);
+  query.package_name = Some(release.package_name.clone());
+  let mut page = client.get_project_package_list(&query).await?;
+  let package_id: Option<PackageId> = loop {
+    // The name filter also matches packages whose name contains it
+    let package = page
+      .items
+      .iter()
+      .find(|p| p.name == release.package_name && p.version == release.package_version);
+    if let Some(package) = package {
+      break Some(package.id);
+    }
+    let Some(cursor) = page.next else {
+      break None;
+    };
+    let query = GetProjectPackageListPageQuery {
+      context: release.context.clone(),
+      auth: release.auth.clone(),
+      cursor,
+    };
+    page = client.get_project_package_list_page(&query).await?;
+  };
+  let Some(package_id) = package_id else {
+    return Ok(BTreeMap::new());
+  };
+
+  let mut query =
+    GetPackageFileListQuery::new(release.project.clone(), package_id).set_context(release.context.clone());
+  query.auth = release.auth.clone();
+  let mut page = client.get_package_file_list(&query).await?;
+  let mut files = Vec::new();
+  loop {
+    files.append(&mut page.items);
+    let Some(cursor) = page.next else {
+      break;
+    };
+    let query = GetPackageFileListPageQuery {
+      context: release.context.clone(),
+      auth: release.auth.clone(),
+      cursor,
+    };
+    page = client.get_package_file_list_page(&query).await?;
+  }
+  files.sort_by_key(|file| file.id);
+  Ok(
+    files
+      .into_iter()
+      .map(|file| {
+        let digest = file.file_sha256.as_deref().and_then(parse_sha256);
+        (file.file_name, digest)
+      })
+      .collect(),
+  )
+}
+
+/// Create the release, or update it if it already exists
+async fn upsert_release<C, Cx>(
+  client: &mut C,
+  release: &PublishRelease<Cx>,
+) -> Result<(Release, PublishAction), HttpGitlabClientError>
+where
+  C: ReleaseClient<Cx, Error = HttpGitlabClientError>,
+  Cx: Clone + Send + Sync + 'static,
+{
+  let command = CreateReleaseCommand {
+    context: release.context.clone(),
+    auth: release.auth.clone(),
+    project: release.project.clone(),
+    tag_name: release.tag_name.clone(),
+    name: release.name.clone(),
+    tag_message: None,
+    description: release.description.clone(),
+    r#ref: release.r#ref.clone(),
+    milestones: Vec::new(),
+    assets: InputReleaseAssets { links: Vec::new() },
+    released_at: None,
+  };
+  match client.create_release(&command).await {
+    Ok(current) => return Ok((current, PublishAction::Created)),
+    Err(HttpGitlabClientError::Conflict(_)) => {}
+    Err(e) => return Err(e),
+  }
+
+  let query = GetProjectReleaseQuery {
+    context: release.context.clone(),
+    auth: release.auth.clone(),
+    project: release.project.clone(),
+    tag_name: release.tag_name.clone(),
+    include_html_description: false,
+  };
+  let current = client.get_project_release(&query).await?;
+  let name_changed = release.name.as_ref().is_some_and(|name| current.name != name.as_str());
+  let description_changed = release
+    .description
+    .as_ref()
+    .is_some_and(|description| current.description.as_deref() != Some(description.as_str()));
+  if !name_changed && !description_changed {
+    return Ok((current, PublishAction::Skipped));
+  }
+  let command = UpdateReleaseCommand {
+    context: release.context.clone(),
+    auth: release.auth.clone(),
+    project: release.project.clone(),
+    tag_name: release.tag_name.clone(),
+    name: release.name.clone(),
+    description: release.description.clone(),
+    milestones: None,
+    released_at: None,
+  };
+  let current = client.update_release(&command).await?;
+  Ok((current, PublishAction::Updated))
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod test {
+  use super::*;
+  use crate::client::fake::FakeGitlabClient;
+  use crate::common::project::ProjectId;
+
+  type Cx = crate::context::Context<GitlabUrl, ()>;
+
+  fn release(project: ProjectId, files: Vec<ReleaseFile>) -> PublishRelease<Cx> {
+    PublishRelease {
+      context: crate::context::Context::new()
+        .set_gitlab_url(GitlabUrl(url::Url::parse("https://gitlab.example.com/").unwrap())),
+      auth: None,
+      project: ProjectRef::Id(project),
+      tag_name: CompactString::new("v1.0.0"),
+      name: None,
+      description: Some(CompactString::new("First release")),
+      r#ref: Some(CompactString::new("main")),
+      package_name: CompactString::new("app"),
+      package_version: CompactString::new("1.0.0"),
+      files,
+    }
+  }
+
+  fn actions(report: &PublishReleaseReport) -> (Vec<PublishAction>, Vec<PublishAction>) {
+    (
+      report.files.iter().map(|f| f.action).collect(),
+      report.links.iter().map(|l| l.action).collect(),
+    )
+  }
+
+  fn file(filename: &str, data: &'static [u8]) -> ReleaseFile {
+    ReleaseFile::from_bytes(CompactString::new(filename), Bytes::from_static(data))
+  }
+
+  #[test]
+  fn publish_release_converges() {
+    use PublishAction::*;
+
+    let mut client = FakeGitlabClient::new();
+    let project = client.create_project("group/project");
+    let files = || vec![file("app-linux", b"linux"), file("app-macos", b"macos")];
+
+    let report = futures::executor::block_on(publish_release(&mut client, release(project, files()))).unwrap();
+    assert_eq!(report.release_action, Created);
+    assert_eq!(actions(&report), (vec![Created, Created], vec![Created, Created]));
+    assert_eq!(
+      report.links[0].link.direct_asset_url,
+      "https://gitlab.example.com/group/project/-/releases/v1.0.0/downloads/app-linux"
+    );
+
+    let report = futures::executor::block_on(publish_release(&mut client, release(project, files()))).unwrap();
+    assert_eq!(report.release_action, Skipped);
+    assert_eq!(actions(&report), (vec![Skipped, Skipped], vec![Skipped, Skipped]));
+
+    let mut input = release(project, files());
+    input.description = Some(CompactString::new("Rebuilt release"));
+    input.files[1] = ReleaseFile {
+      link_type: ReleaseLinkType::Other,
+      ..file("app-macos", b"macos-v2")
+    };
+    let report = futures::executor::block_on(publish_release(&mut client, input)).unwrap();
+    assert_eq!(report.release_action, Updated);
+    assert_eq!(actions(&report), (vec![Skipped, Updated], vec![Skipped, Updated]));
+
+    let release = &client.releases(project)[0];
+    assert_eq!(release.description.as_deref(), Some("Rebuilt release"));
+    assert_eq!(release.assets.links.len(), 2);
+    assert_eq!(
+      client.package_file(project, "app", "1.0.0", "app-macos").as_deref(),
+      Some(b"macos-v2".as_slice())
+    );
+  }
+
+  #[test]
+  fn publish_release_skips_files_by_digest() {
+    use PublishAction::*;
+
+    let mut client = FakeGitlabClient::new();
+    let project = client.create_project("group/project");
+    futures::executor::block_on(publish_release(
+      &mut client,
+      release(project, vec![file("app-linux", b"linux")]),
+    ))
+    .unwrap();
+
+    // A file with the same digest is not read, so a failing body is never polled
+    let failing = || {
+      UploadBody::from_try_stream(
+        futures::stream::iter([Err::<Bytes, _>(std::io::Error::other("not readable"))]),
+        None,
+      )
+    };
+    let sha256 = Sha256::digest(b"linux").into();
+    let input = release(
+      project,
+      vec![ReleaseFile::new(CompactString::new("app-linux"), sha256, failing())],
+    );
+    let report = futures::executor::block_on(publish_release(&mut client, input)).unwrap();
+    assert_eq!(actions(&report), (vec![Skipped], vec![Skipped]));
+
+    let sha256 = Sha256::digest(b"linux-v2").into();
+    let input = release(
+      project,
+      vec![ReleaseFile::new(CompactString::new("app-linux"), sha256, failing())],
+    );
+    let actual = futures::executor::block_on(publish_release(&mut client, input)).unwrap_err();
+    assert!(matches!(actual, HttpGitlabClientError::Send(_)));
+  }
+
+  #[test]
+  fn publish_release_deletes_duplicate_links() {
+    use PublishAction::*;
+
+    let mut client = FakeGitlabClient::new();
+    let project = client.create_project("group/project");
+    let input = release(project, vec![file("app-linux", b"linux")]);
+    let context = input.context.clone();
+    futures::executor::block_on(publish_release(&mut client, input)).unwrap();
+    let command = CreateReleaseLinkCommand {
+      context,
+      auth: None,
+      project: ProjectRef::Id(project),
+      tag_name: CompactString::new("v1.0.0"),
+      name: CompactString::new("app-linux (mirror)"),
+      url: CompactString::new("https://mirror.example.com/app-linux"),
+      direct_asset_path: Some(CompactString::new("/app-linux")),
+      link_type: ReleaseLinkType::Package,
+    };
+    futures::executor::block_on(client.create_release_link(&command)).unwrap();
+
+    let input = release(project, vec![file("app-linux", b"linux")]);
+    let report = futures::executor::block_on(publish_release(&mut client, input)).unwrap();
+    assert_eq!(actions(&report), (vec![Skipped], vec![Skipped, Deleted]));
+    assert_eq!(client.releases(project)[0].assets.links.len(), 1);
+  }
+}
@@ -0,0 +1,118 @@
+#[cfg(feature = "http")]
+use crate::common::branch::Branch;
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::Page;
+use crate::context::EmptyContext;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_page, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+use std::num::NonZeroU8;
+
+/// List repository branches
+///
+/// <https://docs.gitlab.com/ee/api/branches.html#list-repository-branches>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetBranchListQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  /// Number of branches per page (default: `20`, max: `100`)
+  pub per_page: Option<NonZeroU8>,
+  /// Only return the branches whose name contains this string
+  ///
+  /// Use `^term` and `term$` to match the start and end of the name.
+  pub search: Option<Str>,
+  /// Only return the branches whose name matches this RE2 regular expression
+  pub regex: Option<Str>,
+}
+
+pub type GetBranchListQueryView<'req, Cx> = GetBranchListQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetBranchListQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetBranchListQuery<NewCx, Str> {
+    GetBranchListQuery {
+      context: new_context,
+      auth: self.auth,
+      project: self.project,
+      per_page: self.per_page,
+      search: self.search,
+      regex: self.regex,
+    }
+  }
+
+  pub fn as_view(&self) -> GetBranchListQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetBranchListQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      per_page: self.per_page,
+      search: self.search.as_ref().map(AsRef::as_ref),
+      regex: self.regex.as_ref().map(AsRef::as_ref),
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetBranchListQuery<EmptyContext, Str> {
+  pub const fn new(project: ProjectRef<Str>) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      project,
+      per_page: None,
+      search: None,
+      regex: None,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetBranchListQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Page<Branch>;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self
+        .context
+        .get_ref()
+        .url_join(["projects", project, "repository", "branches"])
+    }))
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    query
+      .append("per_page", &self.per_page)
+      .append("search", &self.search.as_ref().map(AsRef::as_ref))
+      .append("regex", &self.regex.as_ref().map(AsRef::as_ref));
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_page(res)
+  }
+}
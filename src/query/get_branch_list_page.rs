@@ -0,0 +1,79 @@
+#[cfg(feature = "http")]
+use crate::common::branch::Branch;
+#[cfg(feature = "http")]
+use crate::common::Page;
+use crate::context::EmptyContext;
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_page, DecodeError, Endpoint};
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Get a page from a branch list
+///
+/// <https://docs.gitlab.com/ee/api/branches.html#list-repository-branches>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetBranchListPageQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub cursor: Str,
+}
+
+pub type GetBranchListPageQueryView<'req, Cx> = GetBranchListPageQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetBranchListPageQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetBranchListPageQuery<NewCx, Str> {
+    GetBranchListPageQuery {
+      context: new_context,
+      auth: self.auth,
+      cursor: self.cursor,
+    }
+  }
+
+  pub fn as_view(&self) -> GetBranchListPageQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetBranchListPageQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      cursor: self.cursor.as_ref(),
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetBranchListPageQuery<EmptyContext, Str> {
+  pub const fn new(cursor: Str) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      cursor,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str: AsRef<str>> Endpoint for GetBranchListPageQuery<Cx, Str> {
+  type Context = Cx;
+  type Output = Page<Branch>;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    url::Url::parse(self.cursor.as_ref())
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_page(res)
+  }
+}
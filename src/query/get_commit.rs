@@ -0,0 +1,79 @@
+#[cfg(feature = "http")]
+use crate::common::commit::Commit;
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Get a single commit
+///
+/// <https://docs.gitlab.com/ee/api/commits.html#get-a-single-commit>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetCommitQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  /// Commit hash, or branch or tag name
+  pub sha: Str,
+  /// Include the commit stats (GitLab defaults to `true`)
+  pub stats: Option<bool>,
+}
+
+pub type GetCommitQueryView<'req, Cx> = GetCommitQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> GetCommitQuery<Cx, Str> {
+  pub fn as_view(&self) -> GetCommitQueryView<'_, Cx> {
+    GetCommitQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      sha: self.sha.as_ref(),
+      stats: self.stats,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetCommitQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Commit;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self
+        .context
+        .get_ref()
+        .url_join(["projects", project, "repository", "commits", self.sha.as_ref()])
+    }))
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    query.append("stats", &self.stats);
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
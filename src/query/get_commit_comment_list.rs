@@ -0,0 +1,112 @@
+#[cfg(feature = "http")]
+use crate::common::commit::CommitComment;
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::Page;
+use crate::context::EmptyContext;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_page, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+use std::num::NonZeroU8;
+
+/// List the comments of a commit
+///
+/// <https://docs.gitlab.com/ee/api/commits.html#get-the-comments-of-a-commit>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetCommitCommentListQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  /// Commit hash, or branch or tag name
+  pub sha: Str,
+  /// Number of comments per page (default: `20`, max: `100`)
+  pub per_page: Option<NonZeroU8>,
+}
+
+pub type GetCommitCommentListQueryView<'req, Cx> = GetCommitCommentListQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetCommitCommentListQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetCommitCommentListQuery<NewCx, Str> {
+    GetCommitCommentListQuery {
+      context: new_context,
+      auth: self.auth,
+      project: self.project,
+      sha: self.sha,
+      per_page: self.per_page,
+    }
+  }
+
+  pub fn as_view(&self) -> GetCommitCommentListQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetCommitCommentListQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      sha: self.sha.as_ref(),
+      per_page: self.per_page,
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetCommitCommentListQuery<EmptyContext, Str> {
+  pub const fn new(project: ProjectRef<Str>, sha: Str) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      project,
+      sha,
+      per_page: None,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetCommitCommentListQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Page<CommitComment>;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.context.get_ref().url_join([
+        "projects",
+        project,
+        "repository",
+        "commits",
+        self.sha.as_ref(),
+        "comments",
+      ])
+    }))
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    query.append("per_page", &self.per_page);
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_page(res)
+  }
+}
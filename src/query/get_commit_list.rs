@@ -0,0 +1,159 @@
+#[cfg(feature = "http")]
+use crate::common::commit::Commit;
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::Page;
+use crate::context::EmptyContext;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_page, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use chrono::{DateTime, Utc};
+use compact_str::CompactString;
+use std::num::NonZeroU8;
+
+/// List repository commits
+///
+/// <https://docs.gitlab.com/ee/api/commits.html#list-repository-commits>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetCommitListQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  /// Number of commits per page (default: `20`, max: `100`)
+  pub per_page: Option<NonZeroU8>,
+  /// Branch, tag or commit to list the history from (default: the default branch)
+  pub ref_name: Option<Str>,
+  /// Only return the commits committed after this date
+  pub since: Option<DateTime<Utc>>,
+  /// Only return the commits committed before this date
+  pub until: Option<DateTime<Utc>>,
+  /// Only return the commits changing this file path
+  pub path: Option<Str>,
+  /// Only return the commits by this author (name or email)
+  pub author: Option<Str>,
+  /// List the commits of every ref instead of `ref_name`
+  pub all: Option<bool>,
+  /// Include the `stats` of each commit
+  pub with_stats: Option<bool>,
+  /// Only follow the first parent of merge commits
+  pub first_parent: Option<bool>,
+  /// Parse the Git trailers of each commit
+  pub trailers: Option<bool>,
+}
+
+pub type GetCommitListQueryView<'req, Cx> = GetCommitListQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetCommitListQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetCommitListQuery<NewCx, Str> {
+    GetCommitListQuery {
+      context: new_context,
+      auth: self.auth,
+      project: self.project,
+      per_page: self.per_page,
+      ref_name: self.ref_name,
+      since: self.since,
+      until: self.until,
+      path: self.path,
+      author: self.author,
+      all: self.all,
+      with_stats: self.with_stats,
+      first_parent: self.first_parent,
+      trailers: self.trailers,
+    }
+  }
+
+  pub fn as_view(&self) -> GetCommitListQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetCommitListQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      per_page: self.per_page,
+      ref_name: self.ref_name.as_ref().map(AsRef::as_ref),
+      since: self.since,
+      until: self.until,
+      path: self.path.as_ref().map(AsRef::as_ref),
+      author: self.author.as_ref().map(AsRef::as_ref),
+      all: self.all,
+      with_stats: self.with_stats,
+      first_parent: self.first_parent,
+      trailers: self.trailers,
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetCommitListQuery<EmptyContext, Str> {
+  pub const fn new(project: ProjectRef<Str>) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      project,
+      per_page: None,
+      ref_name: None,
+      since: None,
+      until: None,
+      path: None,
+      author: None,
+      all: None,
+      with_stats: None,
+      first_parent: None,
+      trailers: None,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetCommitListQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Page<Commit>;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self
+        .context
+        .get_ref()
+        .url_join(["projects", project, "repository", "commits"])
+    }))
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    query
+      .append("per_page", &self.per_page)
+      .append("ref_name", &self.ref_name.as_ref().map(AsRef::as_ref))
+      .append("since", &self.since)
+      .append("until", &self.until)
+      .append("path", &self.path.as_ref().map(AsRef::as_ref))
+      .append("author", &self.author.as_ref().map(AsRef::as_ref))
+      .append("all", &self.all)
+      .append("with_stats", &self.with_stats)
+      .append("first_parent", &self.first_parent)
+      .append("trailers", &self.trailers);
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_page(res)
+  }
+}
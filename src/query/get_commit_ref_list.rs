@@ -0,0 +1,116 @@
+#[cfg(feature = "http")]
+use crate::common::commit::CommitRef;
+use crate::common::commit::CommitRefType;
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::Page;
+use crate::context::EmptyContext;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_page, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+use std::num::NonZeroU8;
+
+/// List the branches and tags a commit is pushed to
+///
+/// <https://docs.gitlab.com/ee/api/commits.html#get-references-a-commit-is-pushed-to>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetCommitRefListQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  /// Commit hash, or branch or tag name
+  pub sha: Str,
+  /// Number of refs per page (default: `20`, max: `100`)
+  pub per_page: Option<NonZeroU8>,
+  /// Only return the refs of this type (default: both branches and tags)
+  pub r#type: Option<CommitRefType>,
+}
+
+pub type GetCommitRefListQueryView<'req, Cx> = GetCommitRefListQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetCommitRefListQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetCommitRefListQuery<NewCx, Str> {
+    GetCommitRefListQuery {
+      context: new_context,
+      auth: self.auth,
+      project: self.project,
+      sha: self.sha,
+      per_page: self.per_page,
+      r#type: self.r#type,
+    }
+  }
+
+  pub fn as_view(&self) -> GetCommitRefListQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetCommitRefListQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      sha: self.sha.as_ref(),
+      per_page: self.per_page,
+      r#type: self.r#type,
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetCommitRefListQuery<EmptyContext, Str> {
+  pub const fn new(project: ProjectRef<Str>, sha: Str) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      project,
+      sha,
+      per_page: None,
+      r#type: None,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetCommitRefListQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Page<CommitRef>;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self
+        .context
+        .get_ref()
+        .url_join(["projects", project, "repository", "commits", self.sha.as_ref(), "refs"])
+    }))
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    query
+      .append("per_page", &self.per_page)
+      .append("type", &self.r#type.map_or("all", CommitRefType::as_str));
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_page(res)
+  }
+}
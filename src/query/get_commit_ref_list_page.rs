@@ -0,0 +1,79 @@
+#[cfg(feature = "http")]
+use crate::common::commit::CommitRef;
+#[cfg(feature = "http")]
+use crate::common::Page;
+use crate::context::EmptyContext;
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_page, DecodeError, Endpoint};
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Get a page from a commit ref list
+///
+/// <https://docs.gitlab.com/ee/api/commits.html#get-references-a-commit-is-pushed-to>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetCommitRefListPageQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub cursor: Str,
+}
+
+pub type GetCommitRefListPageQueryView<'req, Cx> = GetCommitRefListPageQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetCommitRefListPageQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetCommitRefListPageQuery<NewCx, Str> {
+    GetCommitRefListPageQuery {
+      context: new_context,
+      auth: self.auth,
+      cursor: self.cursor,
+    }
+  }
+
+  pub fn as_view(&self) -> GetCommitRefListPageQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetCommitRefListPageQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      cursor: self.cursor.as_ref(),
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetCommitRefListPageQuery<EmptyContext, Str> {
+  pub const fn new(cursor: Str) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      cursor,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str: AsRef<str>> Endpoint for GetCommitRefListPageQuery<Cx, Str> {
+  type Context = Cx;
+  type Output = Page<CommitRef>;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    url::Url::parse(self.cursor.as_ref())
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_page(res)
+  }
+}
@@ -0,0 +1,73 @@
+#[cfg(feature = "http")]
+use crate::common::note::Discussion;
+use crate::common::note::NoteableRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Get a discussion thread of an issue, merge request, commit, snippet or epic
+///
+/// <https://docs.gitlab.com/ee/api/discussions.html#get-single-issue-discussion-item>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetDiscussionQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub noteable: NoteableRef<Str>,
+  pub discussion_id: Str,
+}
+
+pub type GetDiscussionQueryView<'req, Cx> = GetDiscussionQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> GetDiscussionQuery<Cx, Str> {
+  pub fn as_view(&self) -> GetDiscussionQueryView<'_, Cx> {
+    GetDiscussionQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      noteable: self.noteable.as_view(),
+      discussion_id: self.discussion_id.as_ref(),
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetDiscussionQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Discussion;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.noteable.with_path(|noteable| {
+      self.context.get_ref().url_join(
+        noteable
+          .iter()
+          .copied()
+          .chain(["discussions", self.discussion_id.as_ref()]),
+      )
+    }))
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
@@ -0,0 +1,103 @@
+#[cfg(feature = "http")]
+use crate::common::note::Discussion;
+use crate::common::note::NoteableRef;
+#[cfg(feature = "http")]
+use crate::common::Page;
+use crate::context::EmptyContext;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_page, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+use std::num::NonZeroU8;
+
+/// List the discussion threads of an issue, merge request, commit, snippet or epic
+///
+/// <https://docs.gitlab.com/ee/api/discussions.html#list-project-issue-discussion-items>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetDiscussionListQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub noteable: NoteableRef<Str>,
+  /// Number of discussions per page (default: `20`, max: `100`)
+  pub per_page: Option<NonZeroU8>,
+}
+
+pub type GetDiscussionListQueryView<'req, Cx> = GetDiscussionListQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetDiscussionListQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetDiscussionListQuery<NewCx, Str> {
+    GetDiscussionListQuery {
+      context: new_context,
+      auth: self.auth,
+      noteable: self.noteable,
+      per_page: self.per_page,
+    }
+  }
+
+  pub fn as_view(&self) -> GetDiscussionListQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetDiscussionListQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      noteable: self.noteable.as_view(),
+      per_page: self.per_page,
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetDiscussionListQuery<EmptyContext, Str> {
+  pub const fn new(noteable: NoteableRef<Str>) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      noteable,
+      per_page: None,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetDiscussionListQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Page<Discussion>;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.noteable.with_path(|noteable| {
+      self
+        .context
+        .get_ref()
+        .url_join(noteable.iter().copied().chain(["discussions"]))
+    }))
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    query.append("per_page", &self.per_page);
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_page(res)
+  }
+}
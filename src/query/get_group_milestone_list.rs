@@ -0,0 +1,149 @@
+use crate::common::group::GroupId;
+#[cfg(feature = "http")]
+use crate::common::milestone::Milestone;
+use crate::common::milestone::{MilestoneIid, MilestoneState};
+#[cfg(feature = "http")]
+use crate::common::Page;
+use crate::context::EmptyContext;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_page, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use chrono::{DateTime, Utc};
+use compact_str::CompactString;
+use std::num::NonZeroU8;
+
+/// List group milestones
+///
+/// <https://docs.gitlab.com/ee/api/group_milestones.html#list-group-milestones>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetGroupMilestoneListQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub group: GroupId,
+  /// Number of milestones per page (default: `20`, max: `100`)
+  pub per_page: Option<NonZeroU8>,
+  /// Only return the milestones with these ids
+  pub iids: Vec<MilestoneIid>,
+  pub state: Option<MilestoneState>,
+  /// Only return the milestone with this exact title
+  pub title: Option<Str>,
+  /// Only return the milestones whose title or description contains this string
+  pub search: Option<Str>,
+  /// Include the milestones of the parent groups
+  pub include_ancestors: Option<bool>,
+  pub include_descendants: Option<bool>,
+  pub updated_before: Option<DateTime<Utc>>,
+  pub updated_after: Option<DateTime<Utc>>,
+}
+
+pub type GetGroupMilestoneListQueryView<'req, Cx> = GetGroupMilestoneListQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetGroupMilestoneListQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetGroupMilestoneListQuery<NewCx, Str> {
+    GetGroupMilestoneListQuery {
+      context: new_context,
+      auth: self.auth,
+      group: self.group,
+      per_page: self.per_page,
+      iids: self.iids,
+      state: self.state,
+      title: self.title,
+      search: self.search,
+      include_ancestors: self.include_ancestors,
+      include_descendants: self.include_descendants,
+      updated_before: self.updated_before,
+      updated_after: self.updated_after,
+    }
+  }
+
+  pub fn as_view(&self) -> GetGroupMilestoneListQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetGroupMilestoneListQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      group: self.group,
+      per_page: self.per_page,
+      iids: self.iids.clone(),
+      state: self.state,
+      title: self.title.as_ref().map(AsRef::as_ref),
+      search: self.search.as_ref().map(AsRef::as_ref),
+      include_ancestors: self.include_ancestors,
+      include_descendants: self.include_descendants,
+      updated_before: self.updated_before,
+      updated_after: self.updated_after,
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetGroupMilestoneListQuery<EmptyContext, Str> {
+  pub const fn new(group: GroupId) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      group,
+      per_page: None,
+      iids: Vec::new(),
+      state: None,
+      title: None,
+      search: None,
+      include_ancestors: None,
+      include_descendants: None,
+      updated_before: None,
+      updated_after: None,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetGroupMilestoneListQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Page<Milestone>;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(
+      self
+        .group
+        .with_str(|group| self.context.get_ref().url_join(["groups", group, "milestones"])),
+    )
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    query
+      .append("per_page", &self.per_page)
+      .append("iids", &self.iids)
+      .append("state", &self.state)
+      .append("title", &self.title.as_ref().map(AsRef::as_ref))
+      .append("search", &self.search.as_ref().map(AsRef::as_ref))
+      .append("include_ancestors", &self.include_ancestors)
+      .append("include_descendants", &self.include_descendants)
+      .append("updated_before", &self.updated_before)
+      .append("updated_after", &self.updated_after);
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_page(res)
+  }
+}
@@ -0,0 +1,83 @@
+use crate::common::approval::ApprovalRuleId;
+#[cfg(feature = "http")]
+use crate::common::approval::MergeRequestApprovalRule;
+use crate::common::merge_request::MergeRequestIid;
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Get an approval rule of a merge request
+///
+/// <https://docs.gitlab.com/ee/api/merge_request_approvals.html#get-a-single-merge-request-level-rule>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetMergeRequestApprovalRuleQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub merge_request_iid: MergeRequestIid,
+  pub approval_rule_id: ApprovalRuleId,
+}
+
+pub type GetMergeRequestApprovalRuleQueryView<'req, Cx> = GetMergeRequestApprovalRuleQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> GetMergeRequestApprovalRuleQuery<Cx, Str> {
+  pub fn as_view(&self) -> GetMergeRequestApprovalRuleQueryView<'_, Cx> {
+    GetMergeRequestApprovalRuleQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      merge_request_iid: self.merge_request_iid,
+      approval_rule_id: self.approval_rule_id,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetMergeRequestApprovalRuleQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = MergeRequestApprovalRule;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.merge_request_iid.with_str(|merge_request_iid| {
+        self.approval_rule_id.with_str(|approval_rule_id| {
+          self.context.get_ref().url_join([
+            "projects",
+            project,
+            "merge_requests",
+            merge_request_iid,
+            "approval_rules",
+            approval_rule_id,
+          ])
+        })
+      })
+    }))
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
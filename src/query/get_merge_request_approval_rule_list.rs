@@ -0,0 +1,113 @@
+#[cfg(feature = "http")]
+use crate::common::approval::MergeRequestApprovalRule;
+use crate::common::merge_request::MergeRequestIid;
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::Page;
+use crate::context::EmptyContext;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_page, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+use std::num::NonZeroU8;
+
+/// List the approval rules of a merge request
+///
+/// <https://docs.gitlab.com/ee/api/merge_request_approvals.html#get-merge-request-level-rules>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetMergeRequestApprovalRuleListQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub merge_request_iid: MergeRequestIid,
+  /// Number of approval rules per page (default: `20`, max: `100`)
+  pub per_page: Option<NonZeroU8>,
+}
+
+pub type GetMergeRequestApprovalRuleListQueryView<'req, Cx> = GetMergeRequestApprovalRuleListQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetMergeRequestApprovalRuleListQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetMergeRequestApprovalRuleListQuery<NewCx, Str> {
+    GetMergeRequestApprovalRuleListQuery {
+      context: new_context,
+      auth: self.auth,
+      project: self.project,
+      merge_request_iid: self.merge_request_iid,
+      per_page: self.per_page,
+    }
+  }
+
+  pub fn as_view(&self) -> GetMergeRequestApprovalRuleListQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetMergeRequestApprovalRuleListQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      merge_request_iid: self.merge_request_iid,
+      per_page: self.per_page,
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetMergeRequestApprovalRuleListQuery<EmptyContext, Str> {
+  pub const fn new(project: ProjectRef<Str>, merge_request_iid: MergeRequestIid) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      project,
+      merge_request_iid,
+      per_page: None,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetMergeRequestApprovalRuleListQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Page<MergeRequestApprovalRule>;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.merge_request_iid.with_str(|merge_request_iid| {
+        self.context.get_ref().url_join([
+          "projects",
+          project,
+          "merge_requests",
+          merge_request_iid,
+          "approval_rules",
+        ])
+      })
+    }))
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    query.append("per_page", &self.per_page);
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_page(res)
+  }
+}
@@ -0,0 +1,80 @@
+#[cfg(feature = "http")]
+use crate::common::approval::MergeRequestApprovalRule;
+#[cfg(feature = "http")]
+use crate::common::Page;
+use crate::context::EmptyContext;
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_page, DecodeError, Endpoint};
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Get a page from a merge request approval rule list
+///
+/// <https://docs.gitlab.com/ee/api/merge_request_approvals.html#get-merge-request-level-rules>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetMergeRequestApprovalRuleListPageQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub cursor: Str,
+}
+
+pub type GetMergeRequestApprovalRuleListPageQueryView<'req, Cx> =
+  GetMergeRequestApprovalRuleListPageQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetMergeRequestApprovalRuleListPageQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetMergeRequestApprovalRuleListPageQuery<NewCx, Str> {
+    GetMergeRequestApprovalRuleListPageQuery {
+      context: new_context,
+      auth: self.auth,
+      cursor: self.cursor,
+    }
+  }
+
+  pub fn as_view(&self) -> GetMergeRequestApprovalRuleListPageQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetMergeRequestApprovalRuleListPageQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      cursor: self.cursor.as_ref(),
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetMergeRequestApprovalRuleListPageQuery<EmptyContext, Str> {
+  pub const fn new(cursor: Str) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      cursor,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str: AsRef<str>> Endpoint for GetMergeRequestApprovalRuleListPageQuery<Cx, Str> {
+  type Context = Cx;
+  type Output = Page<MergeRequestApprovalRule>;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    url::Url::parse(self.cursor.as_ref())
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_page(res)
+  }
+}
@@ -0,0 +1,89 @@
+#[cfg(feature = "http")]
+use crate::common::merge_request::MergeRequestChanges;
+use crate::common::merge_request::MergeRequestIid;
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Get a merge request with the diffs of its changes
+///
+/// GitLab limits the size of the returned diffs and sets `overflow` when some were left out:
+/// `GetMergeRequestDiffListQuery` pages through the diffs instead.
+///
+/// <https://docs.gitlab.com/ee/api/merge_requests.html#get-single-merge-request-changes>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetMergeRequestChangesQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub merge_request_iid: MergeRequestIid,
+  /// Return the diffs from Gitaly, bypassing the size limits of the database
+  pub access_raw_diffs: Option<bool>,
+  /// Return the diffs in the unified diff format
+  pub unidiff: Option<bool>,
+}
+
+pub type GetMergeRequestChangesQueryView<'req, Cx> = GetMergeRequestChangesQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> GetMergeRequestChangesQuery<Cx, Str> {
+  pub fn as_view(&self) -> GetMergeRequestChangesQueryView<'_, Cx> {
+    GetMergeRequestChangesQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      merge_request_iid: self.merge_request_iid,
+      access_raw_diffs: self.access_raw_diffs,
+      unidiff: self.unidiff,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetMergeRequestChangesQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = MergeRequestChanges;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.merge_request_iid.with_str(|merge_request_iid| {
+        self
+          .context
+          .get_ref()
+          .url_join(["projects", project, "merge_requests", merge_request_iid, "changes"])
+      })
+    }))
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    query
+      .append("access_raw_diffs", &self.access_raw_diffs)
+      .append("unidiff", &self.unidiff);
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
@@ -0,0 +1,117 @@
+#[cfg(feature = "http")]
+use crate::common::diff::Diff;
+use crate::common::merge_request::MergeRequestIid;
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::Page;
+use crate::context::EmptyContext;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_page, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+use std::num::NonZeroU8;
+
+/// List the diffs of the files changed by a merge request
+///
+/// <https://docs.gitlab.com/ee/api/merge_requests.html#list-merge-request-diffs>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetMergeRequestDiffListQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub merge_request_iid: MergeRequestIid,
+  /// Number of diffs per page (default: `20`, max: `100`)
+  pub per_page: Option<NonZeroU8>,
+  /// Return the diffs in the unified diff format
+  pub unidiff: Option<bool>,
+}
+
+pub type GetMergeRequestDiffListQueryView<'req, Cx> = GetMergeRequestDiffListQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetMergeRequestDiffListQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetMergeRequestDiffListQuery<NewCx, Str> {
+    GetMergeRequestDiffListQuery {
+      context: new_context,
+      auth: self.auth,
+      project: self.project,
+      merge_request_iid: self.merge_request_iid,
+      per_page: self.per_page,
+      unidiff: self.unidiff,
+    }
+  }
+
+  pub fn as_view(&self) -> GetMergeRequestDiffListQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetMergeRequestDiffListQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      merge_request_iid: self.merge_request_iid,
+      per_page: self.per_page,
+      unidiff: self.unidiff,
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetMergeRequestDiffListQuery<EmptyContext, Str> {
+  pub const fn new(project: ProjectRef<Str>, merge_request_iid: MergeRequestIid) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      project,
+      merge_request_iid,
+      per_page: None,
+      unidiff: None,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetMergeRequestDiffListQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Page<Diff>;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.merge_request_iid.with_str(|merge_request_iid| {
+        self
+          .context
+          .get_ref()
+          .url_join(["projects", project, "merge_requests", merge_request_iid, "diffs"])
+      })
+    }))
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    query
+      .append("per_page", &self.per_page)
+      .append("unidiff", &self.unidiff);
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_page(res)
+  }
+}
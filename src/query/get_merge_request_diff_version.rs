@@ -0,0 +1,89 @@
+#[cfg(feature = "http")]
+use crate::common::merge_request::MergeRequestDiffVersion;
+use crate::common::merge_request::{MergeRequestDiffVersionId, MergeRequestIid};
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Get a diff version of a merge request, with its commits and diffs
+///
+/// <https://docs.gitlab.com/ee/api/merge_requests.html#get-a-single-merge-request-diff-version>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetMergeRequestDiffVersionQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub merge_request_iid: MergeRequestIid,
+  pub version_id: MergeRequestDiffVersionId,
+  /// Return the diffs in the unified diff format
+  pub unidiff: Option<bool>,
+}
+
+pub type GetMergeRequestDiffVersionQueryView<'req, Cx> = GetMergeRequestDiffVersionQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> GetMergeRequestDiffVersionQuery<Cx, Str> {
+  pub fn as_view(&self) -> GetMergeRequestDiffVersionQueryView<'_, Cx> {
+    GetMergeRequestDiffVersionQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      merge_request_iid: self.merge_request_iid,
+      version_id: self.version_id,
+      unidiff: self.unidiff,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetMergeRequestDiffVersionQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = MergeRequestDiffVersion;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.merge_request_iid.with_str(|merge_request_iid| {
+        self.version_id.with_str(|version_id| {
+          self.context.get_ref().url_join([
+            "projects",
+            project,
+            "merge_requests",
+            merge_request_iid,
+            "versions",
+            version_id,
+          ])
+        })
+      })
+    }))
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    query.append("unidiff", &self.unidiff);
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
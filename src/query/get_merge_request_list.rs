@@ -0,0 +1,178 @@
+#[cfg(feature = "http")]
+use crate::common::merge_request::MergeRequest;
+use crate::common::merge_request::{MergeRequestOrder, MergeRequestScope, MergeRequestState};
+use crate::common::user::UserId;
+use crate::common::KeysetPagination;
+#[cfg(feature = "http")]
+use crate::common::Page;
+use crate::context::EmptyContext;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_page, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use chrono::{DateTime, Utc};
+use compact_str::CompactString;
+
+/// List the merge requests visible to the current user, across all projects
+///
+/// GitLab only returns the merge requests created by the current user unless `scope` is set.
+///
+/// <https://docs.gitlab.com/ee/api/merge_requests.html#list-merge-requests>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetMergeRequestListQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub pagination: Option<KeysetPagination<MergeRequestOrder>>,
+  /// Only return the merge requests in this state (default: all states)
+  pub state: Option<MergeRequestState>,
+  /// Only return the merge requests created by or assigned to the current user
+  pub scope: Option<MergeRequestScope>,
+  /// Only return the merge requests with all these labels
+  ///
+  /// `None` lists the merge requests without labels, `Any` those with at least one label.
+  pub labels: Vec<Str>,
+  pub author_id: Option<UserId>,
+  pub author_username: Option<Str>,
+  pub reviewer_id: Option<UserId>,
+  pub reviewer_username: Option<Str>,
+  /// Only return the merge requests of the milestone with this title
+  ///
+  /// `None` lists the merge requests without milestone, `Any` those with a milestone.
+  pub milestone: Option<Str>,
+  /// Only return the draft (`true`) or ready (`false`) merge requests
+  pub draft: Option<bool>,
+  pub target_branch: Option<Str>,
+  pub updated_after: Option<DateTime<Utc>>,
+  pub updated_before: Option<DateTime<Utc>>,
+}
+
+pub type GetMergeRequestListQueryView<'req, Cx> = GetMergeRequestListQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetMergeRequestListQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetMergeRequestListQuery<NewCx, Str> {
+    GetMergeRequestListQuery {
+      context: new_context,
+      auth: self.auth,
+      pagination: self.pagination,
+      state: self.state,
+      scope: self.scope,
+      labels: self.labels,
+      author_id: self.author_id,
+      author_username: self.author_username,
+      reviewer_id: self.reviewer_id,
+      reviewer_username: self.reviewer_username,
+      milestone: self.milestone,
+      draft: self.draft,
+      target_branch: self.target_branch,
+      updated_after: self.updated_after,
+      updated_before: self.updated_before,
+    }
+  }
+
+  pub fn as_view(&self) -> GetMergeRequestListQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetMergeRequestListQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      pagination: self.pagination,
+      state: self.state,
+      scope: self.scope,
+      labels: Vec::from_iter(self.labels.iter().map(AsRef::as_ref)),
+      author_id: self.author_id,
+      author_username: self.author_username.as_ref().map(AsRef::as_ref),
+      reviewer_id: self.reviewer_id,
+      reviewer_username: self.reviewer_username.as_ref().map(AsRef::as_ref),
+      milestone: self.milestone.as_ref().map(AsRef::as_ref),
+      draft: self.draft,
+      target_branch: self.target_branch.as_ref().map(AsRef::as_ref),
+      updated_after: self.updated_after,
+      updated_before: self.updated_before,
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetMergeRequestListQuery<EmptyContext, Str> {
+  pub const fn new() -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      pagination: None,
+      state: None,
+      scope: None,
+      labels: Vec::new(),
+      author_id: None,
+      author_username: None,
+      reviewer_id: None,
+      reviewer_username: None,
+      milestone: None,
+      draft: None,
+      target_branch: None,
+      updated_after: None,
+      updated_before: None,
+    }
+  }
+}
+
+impl<Str: AsRef<str>> Default for GetMergeRequestListQuery<EmptyContext, Str> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetMergeRequestListQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Page<MergeRequest>;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.context.get_ref().url_join(["merge_requests"]))
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    if let Some(pagination) = self.pagination {
+      query
+        .append("per_page", &pagination.per_page)
+        .append("order_by", &pagination.order_by)
+        .append("sort", &pagination.sort);
+    }
+    query
+      .append("state", &self.state)
+      .append("scope", &self.scope)
+      .append("labels", &Vec::from_iter(self.labels.iter().map(AsRef::as_ref)))
+      .append("author_id", &self.author_id)
+      .append("author_username", &self.author_username.as_ref().map(AsRef::as_ref))
+      .append("reviewer_id", &self.reviewer_id)
+      .append("reviewer_username", &self.reviewer_username.as_ref().map(AsRef::as_ref))
+      .append("milestone", &self.milestone.as_ref().map(AsRef::as_ref))
+      .append("wip", &self.draft.map(|draft| if draft { "yes" } else { "no" }))
+      .append("target_branch", &self.target_branch.as_ref().map(AsRef::as_ref))
+      .append("updated_after", &self.updated_after)
+      .append("updated_before", &self.updated_before);
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_page(res)
+  }
+}
@@ -0,0 +1,73 @@
+#[cfg(feature = "http")]
+use crate::common::note::Note;
+use crate::common::note::{NoteId, NoteParentRef};
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Get a note of an issue, merge request, snippet or epic
+///
+/// <https://docs.gitlab.com/ee/api/notes.html#get-single-issue-note>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetNoteQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub noteable: NoteParentRef<Str>,
+  pub note_id: NoteId,
+}
+
+pub type GetNoteQueryView<'req, Cx> = GetNoteQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> GetNoteQuery<Cx, Str> {
+  pub fn as_view(&self) -> GetNoteQueryView<'_, Cx> {
+    GetNoteQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      noteable: self.noteable.as_view(),
+      note_id: self.note_id,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetNoteQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Note;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.noteable.with_path(|noteable| {
+      self.note_id.with_str(|note_id| {
+        self
+          .context
+          .get_ref()
+          .url_join(noteable.iter().copied().chain(["notes", note_id]))
+      })
+    }))
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
@@ -0,0 +1,115 @@
+#[cfg(feature = "http")]
+use crate::common::note::Note;
+use crate::common::note::{NoteOrder, NoteParentRef};
+#[cfg(feature = "http")]
+use crate::common::Page;
+use crate::common::SortOrder;
+use crate::context::EmptyContext;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_page, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+use std::num::NonZeroU8;
+
+/// List the notes of an issue, merge request, snippet or epic
+///
+/// <https://docs.gitlab.com/ee/api/notes.html#list-project-issue-notes>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetNoteListQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub noteable: NoteParentRef<Str>,
+  pub sort: Option<SortOrder>,
+  pub order_by: Option<NoteOrder>,
+  /// Number of notes per page (default: `20`, max: `100`)
+  pub per_page: Option<NonZeroU8>,
+}
+
+pub type GetNoteListQueryView<'req, Cx> = GetNoteListQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetNoteListQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetNoteListQuery<NewCx, Str> {
+    GetNoteListQuery {
+      context: new_context,
+      auth: self.auth,
+      noteable: self.noteable,
+      sort: self.sort,
+      order_by: self.order_by,
+      per_page: self.per_page,
+    }
+  }
+
+  pub fn as_view(&self) -> GetNoteListQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetNoteListQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      noteable: self.noteable.as_view(),
+      sort: self.sort,
+      order_by: self.order_by,
+      per_page: self.per_page,
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetNoteListQuery<EmptyContext, Str> {
+  pub const fn new(noteable: NoteParentRef<Str>) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      noteable,
+      sort: None,
+      order_by: None,
+      per_page: None,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetNoteListQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Page<Note>;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.noteable.with_path(|noteable| {
+      self
+        .context
+        .get_ref()
+        .url_join(noteable.iter().copied().chain(["notes"]))
+    }))
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    query
+      .append("sort", &self.sort)
+      .append("order_by", &self.order_by)
+      .append("per_page", &self.per_page);
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_page(res)
+  }
+}
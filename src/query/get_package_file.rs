@@ -1,5 +1,13 @@
 use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_bytes, DecodeError, Endpoint};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
 use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
 use compact_str::CompactString;
 
 /// Get a generic package file
@@ -30,3 +38,41 @@ impl<Cx, Str: AsRef<str>> GetPackageFileQuery<Cx, Str> {
     }
   }
 }
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetPackageFileQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = bytes::Bytes;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.context.get_ref().url_join([
+        "projects",
+        project,
+        "packages",
+        "generic",
+        self.package_name.as_ref(),
+        self.package_version.as_ref(),
+        self.filename.as_ref(),
+      ])
+    }))
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_bytes(res)
+  }
+}
@@ -0,0 +1,110 @@
+#[cfg(feature = "http")]
+use crate::common::package::PackageFile;
+use crate::common::package::PackageId;
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::Page;
+use crate::context::EmptyContext;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_page, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+use std::num::NonZeroU8;
+
+/// List the files of a package
+///
+/// <https://docs.gitlab.com/ee/api/packages.html#list-package-files>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetPackageFileListQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub package_id: PackageId,
+  /// Number of files per page (default: `20`, max: `100`)
+  pub per_page: Option<NonZeroU8>,
+}
+
+pub type GetPackageFileListQueryView<'req, Cx> = GetPackageFileListQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetPackageFileListQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetPackageFileListQuery<NewCx, Str> {
+    GetPackageFileListQuery {
+      context: new_context,
+      auth: self.auth,
+      project: self.project,
+      package_id: self.package_id,
+      per_page: self.per_page,
+    }
+  }
+
+  pub fn as_view(&self) -> GetPackageFileListQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetPackageFileListQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      package_id: self.package_id,
+      per_page: self.per_page,
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetPackageFileListQuery<EmptyContext, Str> {
+  pub const fn new(project: ProjectRef<Str>, package_id: PackageId) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      project,
+      package_id,
+      per_page: None,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetPackageFileListQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Page<PackageFile>;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.package_id.with_str(|package_id| {
+        self
+          .context
+          .get_ref()
+          .url_join(["projects", project, "packages", package_id, "package_files"])
+      })
+    }))
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    query.append("per_page", &self.per_page);
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_page(res)
+  }
+}
@@ -0,0 +1,50 @@
+use crate::common::project::ProjectRef;
+use crate::query::get_package_file::GetPackageFileQuery;
+use crate::GitlabAuth;
+use compact_str::CompactString;
+
+/// Get a generic package file as a stream of chunks
+///
+/// <https://docs.gitlab.com/ee/user/packages/generic_packages/#download-package-file>
+///
+/// If `sha256` is set (as a hex string, e.g. `GenericPackageFile::file_sha256`), the stream fails with
+/// `HttpGitlabClientError::ChecksumMismatch` when the digest of the received content does not match.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetPackageFileStreamQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub package_name: Str,
+  pub package_version: Str,
+  pub filename: Str,
+  pub sha256: Option<Str>,
+}
+
+pub type GetPackageFileStreamQueryView<'req, Cx> = GetPackageFileStreamQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> GetPackageFileStreamQuery<Cx, Str> {
+  pub fn as_view(&self) -> GetPackageFileStreamQueryView<'_, Cx> {
+    GetPackageFileStreamQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      package_name: self.package_name.as_ref(),
+      package_version: self.package_version.as_ref(),
+      filename: self.filename.as_ref(),
+      sha256: self.sha256.as_ref().map(|s| s.as_ref()),
+    }
+  }
+
+  /// Buffered query for the same file
+  pub fn as_file_query(&self) -> GetPackageFileQuery<&Cx, &str> {
+    GetPackageFileQuery {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      package_name: self.package_name.as_ref(),
+      package_version: self.package_version.as_ref(),
+      filename: self.filename.as_ref(),
+    }
+  }
+}
@@ -1,6 +1,16 @@
+#[cfg(feature = "http")]
+use crate::common::project::Project;
 use crate::common::project::ProjectRef;
 use crate::context::EmptyContext;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
 use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
 use compact_str::CompactString;
 
 /// Get a single project
@@ -58,3 +68,38 @@ impl<Str: AsRef<str>> GetProjectQuery<EmptyContext, Str> {
     }
   }
 }
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetProjectQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Project;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.id.with_str(|id| self.context.get_ref().url_join(["projects", id])))
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    query
+      .append("license", &self.license)
+      .append("statistics", &self.statistics)
+      .append("with_custom_attributes", &self.with_custom_attributes);
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
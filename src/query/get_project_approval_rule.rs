@@ -0,0 +1,74 @@
+use crate::common::approval::ApprovalRuleId;
+#[cfg(feature = "http")]
+use crate::common::approval::ProjectApprovalRule;
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Get an approval rule of a project
+///
+/// <https://docs.gitlab.com/ee/api/merge_request_approvals.html#get-a-single-approval-rule-for-project>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetProjectApprovalRuleQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub approval_rule_id: ApprovalRuleId,
+}
+
+pub type GetProjectApprovalRuleQueryView<'req, Cx> = GetProjectApprovalRuleQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> GetProjectApprovalRuleQuery<Cx, Str> {
+  pub fn as_view(&self) -> GetProjectApprovalRuleQueryView<'_, Cx> {
+    GetProjectApprovalRuleQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      approval_rule_id: self.approval_rule_id,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetProjectApprovalRuleQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = ProjectApprovalRule;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.approval_rule_id.with_str(|approval_rule_id| {
+        self
+          .context
+          .get_ref()
+          .url_join(["projects", project, "approval_rules", approval_rule_id])
+      })
+    }))
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
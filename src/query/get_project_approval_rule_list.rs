@@ -0,0 +1,102 @@
+#[cfg(feature = "http")]
+use crate::common::approval::ProjectApprovalRule;
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::Page;
+use crate::context::EmptyContext;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_page, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+use std::num::NonZeroU8;
+
+/// List the approval rules of a project
+///
+/// <https://docs.gitlab.com/ee/api/merge_request_approvals.html#get-all-approval-rules-for-project>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetProjectApprovalRuleListQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  /// Number of approval rules per page (default: `20`, max: `100`)
+  pub per_page: Option<NonZeroU8>,
+}
+
+pub type GetProjectApprovalRuleListQueryView<'req, Cx> = GetProjectApprovalRuleListQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetProjectApprovalRuleListQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetProjectApprovalRuleListQuery<NewCx, Str> {
+    GetProjectApprovalRuleListQuery {
+      context: new_context,
+      auth: self.auth,
+      project: self.project,
+      per_page: self.per_page,
+    }
+  }
+
+  pub fn as_view(&self) -> GetProjectApprovalRuleListQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetProjectApprovalRuleListQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      per_page: self.per_page,
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetProjectApprovalRuleListQuery<EmptyContext, Str> {
+  pub const fn new(project: ProjectRef<Str>) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      project,
+      per_page: None,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetProjectApprovalRuleListQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Page<ProjectApprovalRule>;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(
+      self
+        .project
+        .with_str(|project| self.context.get_ref().url_join(["projects", project, "approval_rules"])),
+    )
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    query.append("per_page", &self.per_page);
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_page(res)
+  }
+}
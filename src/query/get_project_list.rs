@@ -1,8 +1,20 @@
+#[cfg(feature = "http")]
+use crate::common::project::Project;
 use crate::common::project::{ProjectId, ProjectOrder};
 use crate::common::topic::TopicId;
+#[cfg(feature = "http")]
+use crate::common::Page;
 use crate::common::{AccessLevel, KeysetPagination, Visibility};
 use crate::context::EmptyContext;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_page, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
 use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
 use chrono::{DateTime, Utc};
 use compact_str::CompactString;
 
@@ -33,7 +45,7 @@ pub struct GetProjectListQuery<Cx, Str = CompactString> {
   pub simple: Option<bool>,
   pub starred: Option<bool>,
   pub statistics: Option<bool>,
-  pub topic_ic: Option<TopicId>,
+  pub topic_id: Option<TopicId>,
   pub topic: Vec<Str>,
   pub updated_after: Option<DateTime<Utc>>,
   pub updated_before: Option<DateTime<Utc>>,
@@ -42,7 +54,7 @@ pub struct GetProjectListQuery<Cx, Str = CompactString> {
   pub with_custom_attributes: Option<bool>,
   pub with_issues_enabled: Option<bool>,
   pub with_merge_requests_enabled: Option<bool>,
-  pub with_programming_language: Vec<Str>,
+  pub with_programming_language: Option<Str>,
 }
 
 pub type GetProjectListQueryView<'req, Cx> = GetProjectListQuery<&'req Cx, &'req str>;
@@ -71,7 +83,7 @@ impl<Cx, Str> GetProjectListQuery<Cx, Str> {
       simple: self.simple,
       starred: self.starred,
       statistics: self.statistics,
-      topic_ic: self.topic_ic,
+      topic_id: self.topic_id,
       topic: self.topic,
       updated_after: self.updated_after,
       updated_before: self.updated_before,
@@ -110,7 +122,7 @@ impl<Cx, Str> GetProjectListQuery<Cx, Str> {
       simple: self.simple,
       starred: self.starred,
       statistics: self.statistics,
-      topic_ic: self.topic_ic,
+      topic_id: self.topic_id,
       topic: Vec::from_iter(self.topic.iter().map(|s| s.as_ref())),
       updated_after: self.updated_after,
       updated_before: self.updated_before,
@@ -119,7 +131,7 @@ impl<Cx, Str> GetProjectListQuery<Cx, Str> {
       with_custom_attributes: self.with_custom_attributes,
       with_issues_enabled: self.with_issues_enabled,
       with_merge_requests_enabled: self.with_merge_requests_enabled,
-      with_programming_language: Vec::from_iter(self.topic.iter().map(|s| s.as_ref())),
+      with_programming_language: self.with_programming_language.as_ref().map(|s| s.as_ref()),
     }
   }
 }
@@ -148,7 +160,7 @@ impl<Str: AsRef<str>> GetProjectListQuery<EmptyContext, Str> {
       simple: None,
       starred: None,
       statistics: None,
-      topic_ic: None,
+      topic_id: None,
       topic: Vec::new(),
       updated_after: None,
       updated_before: None,
@@ -157,7 +169,7 @@ impl<Str: AsRef<str>> GetProjectListQuery<EmptyContext, Str> {
       with_custom_attributes: None,
       with_issues_enabled: None,
       with_merge_requests_enabled: None,
-      with_programming_language: Vec::new(),
+      with_programming_language: None,
     }
   }
 }
@@ -167,3 +179,72 @@ impl<Str: AsRef<str>> Default for GetProjectListQuery<EmptyContext, Str> {
     Self::new()
   }
 }
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetProjectListQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Page<Project>;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.context.get_ref().url_join(["projects"]))
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    if let Some(pagination) = self.pagination.as_ref() {
+      query.append_keyset_pagination(pagination);
+    }
+    query
+      .append("archived", &self.archived)
+      .append("id_after", &self.id_after)
+      .append("id_before", &self.id_before)
+      .append("imported", &self.imported)
+      .append("include_hidden", &self.include_hidden)
+      .append("include_pending_delete", &self.include_pending_delete)
+      .append("last_activity_after", &self.last_activity_after)
+      .append("last_activity_before", &self.last_activity_before)
+      .append("membership", &self.membership)
+      .append("min_access_level", &self.min_access_level)
+      .append("owned", &self.owned)
+      .append("repository_checksum_failed", &self.repository_checksum_failed)
+      .append(
+        "repository_storage",
+        &self.repository_storage.as_ref().map(AsRef::as_ref),
+      )
+      .append("search_namespaces", &self.search_namespaces)
+      .append("search", &self.search.as_ref().map(AsRef::as_ref))
+      .append("simple", &self.simple)
+      .append("starred", &self.starred)
+      .append("statistics", &self.statistics)
+      .append("topic_id", &self.topic_id)
+      .append("topic", &Vec::from_iter(self.topic.iter().map(AsRef::as_ref)))
+      .append("updated_after", &self.updated_after)
+      .append("updated_before", &self.updated_before)
+      .append("visibility", &self.visibility)
+      .append("wiki_checksum_failed", &self.wiki_checksum_failed)
+      .append("with_custom_attributes", &self.with_custom_attributes)
+      .append("with_issues_enabled", &self.with_issues_enabled)
+      .append("with_merge_requests_enabled", &self.with_merge_requests_enabled)
+      .append(
+        "with_programming_language",
+        &self.with_programming_language.as_ref().map(AsRef::as_ref),
+      );
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_page(res)
+  }
+}
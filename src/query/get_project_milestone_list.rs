@@ -0,0 +1,144 @@
+#[cfg(feature = "http")]
+use crate::common::milestone::Milestone;
+use crate::common::milestone::{MilestoneIid, MilestoneState};
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::Page;
+use crate::context::EmptyContext;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_page, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use chrono::{DateTime, Utc};
+use compact_str::CompactString;
+use std::num::NonZeroU8;
+
+/// List project milestones
+///
+/// <https://docs.gitlab.com/ee/api/milestones.html#list-project-milestones>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetProjectMilestoneListQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  /// Number of milestones per page (default: `20`, max: `100`)
+  pub per_page: Option<NonZeroU8>,
+  /// Only return the milestones with these ids
+  pub iids: Vec<MilestoneIid>,
+  pub state: Option<MilestoneState>,
+  /// Only return the milestone with this exact title
+  pub title: Option<Str>,
+  /// Only return the milestones whose title or description contains this string
+  pub search: Option<Str>,
+  /// Include the milestones of the parent groups
+  pub include_ancestors: Option<bool>,
+  pub updated_before: Option<DateTime<Utc>>,
+  pub updated_after: Option<DateTime<Utc>>,
+}
+
+pub type GetProjectMilestoneListQueryView<'req, Cx> = GetProjectMilestoneListQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetProjectMilestoneListQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetProjectMilestoneListQuery<NewCx, Str> {
+    GetProjectMilestoneListQuery {
+      context: new_context,
+      auth: self.auth,
+      project: self.project,
+      per_page: self.per_page,
+      iids: self.iids,
+      state: self.state,
+      title: self.title,
+      search: self.search,
+      include_ancestors: self.include_ancestors,
+      updated_before: self.updated_before,
+      updated_after: self.updated_after,
+    }
+  }
+
+  pub fn as_view(&self) -> GetProjectMilestoneListQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetProjectMilestoneListQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      per_page: self.per_page,
+      iids: self.iids.clone(),
+      state: self.state,
+      title: self.title.as_ref().map(AsRef::as_ref),
+      search: self.search.as_ref().map(AsRef::as_ref),
+      include_ancestors: self.include_ancestors,
+      updated_before: self.updated_before,
+      updated_after: self.updated_after,
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetProjectMilestoneListQuery<EmptyContext, Str> {
+  pub const fn new(project: ProjectRef<Str>) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      project,
+      per_page: None,
+      iids: Vec::new(),
+      state: None,
+      title: None,
+      search: None,
+      include_ancestors: None,
+      updated_before: None,
+      updated_after: None,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetProjectMilestoneListQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Page<Milestone>;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(
+      self
+        .project
+        .with_str(|project| self.context.get_ref().url_join(["projects", project, "milestones"])),
+    )
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    query
+      .append("per_page", &self.per_page)
+      .append("iids", &self.iids)
+      .append("state", &self.state)
+      .append("title", &self.title.as_ref().map(AsRef::as_ref))
+      .append("search", &self.search.as_ref().map(AsRef::as_ref))
+      .append("include_ancestors", &self.include_ancestors)
+      .append("updated_before", &self.updated_before)
+      .append("updated_after", &self.updated_after);
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_page(res)
+  }
+}
@@ -1,7 +1,19 @@
+#[cfg(feature = "http")]
+use crate::common::package::Package;
 use crate::common::package::{PackageOrder, PackageType};
 use crate::common::project::ProjectRef;
 use crate::common::KeysetPagination;
+#[cfg(feature = "http")]
+use crate::common::Page;
 use crate::context::EmptyContext;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_page, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
 use crate::{GitlabAuth, PackageStatus};
 use compact_str::CompactString;
 
@@ -68,3 +80,49 @@ impl<Str: AsRef<str>> GetProjectPackageListQuery<EmptyContext, Str> {
     }
   }
 }
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetProjectPackageListQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Page<Package>;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(
+      self
+        .project
+        .with_str(|project| self.context.get_ref().url_join(["projects", project, "packages"])),
+    )
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    if let Some(pagination) = self.pagination {
+      query
+        .append("per_page", &pagination.per_page)
+        .append("order_by", &pagination.order_by)
+        .append("sort", &pagination.sort);
+    }
+    query
+      .append("package_type", &self.package_type)
+      .append("package_name", &self.package_name.as_ref().map(AsRef::as_ref))
+      .append("include_versionless", &self.include_versionless)
+      .append("status", &self.status);
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_page(res)
+  }
+}
@@ -1,5 +1,13 @@
+#[cfg(feature = "http")]
+use crate::common::release::Release;
+#[cfg(feature = "http")]
+use crate::common::Page;
 use crate::context::EmptyContext;
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_page, DecodeError, Endpoint};
 use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
 use compact_str::CompactString;
 
 /// Get a page from the project release list
@@ -45,3 +53,27 @@ impl<Str: AsRef<str>> GetProjectReleaseListPageQuery<EmptyContext, Str> {
     }
   }
 }
+
+#[cfg(feature = "http")]
+impl<Cx, Str: AsRef<str>> Endpoint for GetProjectReleaseListPageQuery<Cx, Str> {
+  type Context = Cx;
+  type Output = Page<Release>;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    url::Url::parse(self.cursor.as_ref())
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_page(res)
+  }
+}
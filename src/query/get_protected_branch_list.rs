@@ -0,0 +1,110 @@
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::protected_branch::ProtectedBranch;
+#[cfg(feature = "http")]
+use crate::common::Page;
+use crate::context::EmptyContext;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_page, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+use std::num::NonZeroU8;
+
+/// List protected branches
+///
+/// <https://docs.gitlab.com/ee/api/protected_branches.html#list-protected-branches>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetProtectedBranchListQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  /// Number of protected branches per page (default: `20`, max: `100`)
+  pub per_page: Option<NonZeroU8>,
+  /// Only return the protected branches whose name contains this string
+  pub search: Option<Str>,
+}
+
+pub type GetProtectedBranchListQueryView<'req, Cx> = GetProtectedBranchListQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetProtectedBranchListQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetProtectedBranchListQuery<NewCx, Str> {
+    GetProtectedBranchListQuery {
+      context: new_context,
+      auth: self.auth,
+      project: self.project,
+      per_page: self.per_page,
+      search: self.search,
+    }
+  }
+
+  pub fn as_view(&self) -> GetProtectedBranchListQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetProtectedBranchListQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      per_page: self.per_page,
+      search: self.search.as_ref().map(AsRef::as_ref),
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetProtectedBranchListQuery<EmptyContext, Str> {
+  pub const fn new(project: ProjectRef<Str>) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      project,
+      per_page: None,
+      search: None,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetProtectedBranchListQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Page<ProtectedBranch>;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self
+        .context
+        .get_ref()
+        .url_join(["projects", project, "protected_branches"])
+    }))
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    query
+      .append("per_page", &self.per_page)
+      .append("search", &self.search.as_ref().map(AsRef::as_ref));
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_page(res)
+  }
+}
@@ -0,0 +1,85 @@
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_bytes, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Get the raw content of a repository file
+///
+/// <https://docs.gitlab.com/ee/api/repository_files.html#get-raw-file-from-repository>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetRawRepositoryFileQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  /// Path of the file, relative to the repository root (e.g. `src/lib.rs`)
+  pub file_path: Str,
+  /// Branch, tag or commit to read the file from, defaults to the default branch
+  pub r#ref: Option<Str>,
+  /// Resolve Git LFS pointers to the content of the LFS object
+  pub lfs: Option<bool>,
+}
+
+pub type GetRawRepositoryFileQueryView<'req, Cx> = GetRawRepositoryFileQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> GetRawRepositoryFileQuery<Cx, Str> {
+  pub fn as_view(&self) -> GetRawRepositoryFileQueryView<'_, Cx> {
+    GetRawRepositoryFileQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      file_path: self.file_path.as_ref(),
+      r#ref: self.r#ref.as_ref().map(AsRef::as_ref),
+      lfs: self.lfs,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetRawRepositoryFileQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = bytes::Bytes;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.context.get_ref().url_join([
+        "projects",
+        project,
+        "repository",
+        "files",
+        self.file_path.as_ref(),
+        "raw",
+      ])
+    }))
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    query.append("ref", &self.r#ref.as_ref().map(AsRef::as_ref));
+    query.append("lfs", &self.lfs);
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_bytes(res)
+  }
+}
@@ -0,0 +1,45 @@
+use crate::common::project::ProjectRef;
+use crate::query::get_raw_repository_file::GetRawRepositoryFileQuery;
+use crate::GitlabAuth;
+use compact_str::CompactString;
+
+/// Get the raw content of a repository file as a stream of chunks
+///
+/// <https://docs.gitlab.com/ee/api/repository_files.html#get-raw-file-from-repository>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetRawRepositoryFileStreamQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub file_path: Str,
+  pub r#ref: Option<Str>,
+  pub lfs: Option<bool>,
+}
+
+pub type GetRawRepositoryFileStreamQueryView<'req, Cx> = GetRawRepositoryFileStreamQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> GetRawRepositoryFileStreamQuery<Cx, Str> {
+  pub fn as_view(&self) -> GetRawRepositoryFileStreamQueryView<'_, Cx> {
+    GetRawRepositoryFileStreamQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      file_path: self.file_path.as_ref(),
+      r#ref: self.r#ref.as_ref().map(AsRef::as_ref),
+      lfs: self.lfs,
+    }
+  }
+
+  /// Buffered query for the same file
+  pub fn as_file_query(&self) -> GetRawRepositoryFileQuery<&Cx, &str> {
+    GetRawRepositoryFileQuery {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      file_path: self.file_path.as_ref(),
+      r#ref: self.r#ref.as_ref().map(AsRef::as_ref),
+      lfs: self.lfs,
+    }
+  }
+}
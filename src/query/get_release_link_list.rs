@@ -0,0 +1,77 @@
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::release::ReleaseLink;
+#[cfg(feature = "http")]
+use crate::common::Page;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_page, DecodeError, Endpoint};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Get the asset links of a project release
+///
+/// <https://docs.gitlab.com/ee/api/releases/links.html#list-links-of-a-release>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetReleaseLinkListQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  pub tag_name: Str,
+}
+
+pub type GetReleaseLinkListQueryView<'req, Cx> = GetReleaseLinkListQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> GetReleaseLinkListQuery<Cx, Str> {
+  pub fn as_view(&self) -> GetReleaseLinkListQueryView<'_, Cx> {
+    GetReleaseLinkListQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      tag_name: self.tag_name.as_ref(),
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetReleaseLinkListQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Page<ReleaseLink>;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.context.get_ref().url_join([
+        "projects",
+        project,
+        "releases",
+        self.tag_name.as_ref(),
+        "assets",
+        "links",
+      ])
+    }))
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_page(res)
+  }
+}
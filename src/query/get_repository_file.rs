@@ -0,0 +1,125 @@
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::repository_file::RepositoryFile;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Get a repository file, with its metadata and decoded content
+///
+/// <https://docs.gitlab.com/ee/api/repository_files.html#get-file-from-repository>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetRepositoryFileQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  /// Path of the file, relative to the repository root (e.g. `src/lib.rs`)
+  pub file_path: Str,
+  /// Branch, tag or commit to read the file from
+  pub r#ref: Str,
+}
+
+pub type GetRepositoryFileQueryView<'req, Cx> = GetRepositoryFileQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> GetRepositoryFileQuery<Cx, Str> {
+  pub fn as_view(&self) -> GetRepositoryFileQueryView<'_, Cx> {
+    GetRepositoryFileQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      file_path: self.file_path.as_ref(),
+      r#ref: self.r#ref.as_ref(),
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetRepositoryFileQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = RepositoryFile;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self
+        .context
+        .get_ref()
+        .url_join(["projects", project, "repository", "files", self.file_path.as_ref()])
+    }))
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    query.append("ref", self.r#ref.as_ref());
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    use base64::Engine;
+
+    /// File as returned by GitLab, with its encoded content
+    #[derive(serde::Deserialize)]
+    struct RawRepositoryFile {
+      file_name: CompactString,
+      file_path: CompactString,
+      size: u64,
+      encoding: CompactString,
+      content: String,
+      content_sha256: CompactString,
+      r#ref: CompactString,
+      blob_id: CompactString,
+      commit_id: CompactString,
+      last_commit_id: CompactString,
+      execute_filemode: Option<bool>,
+    }
+
+    let body = res.body().clone();
+    let raw: RawRepositoryFile = decode_json(res)?;
+    let content = match raw.encoding.as_str() {
+      "base64" => base64::engine::general_purpose::STANDARD
+        .decode(raw.content.as_bytes())
+        .map_err(|e| DecodeError {
+          message: format!("invalid base64 file content: {e}"),
+          body,
+        })?,
+      "text" => raw.content.into_bytes(),
+      encoding => {
+        return Err(DecodeError {
+          message: format!("unsupported file encoding: {encoding:?}"),
+          body,
+        })
+      }
+    };
+    Ok(RepositoryFile {
+      file_name: raw.file_name,
+      file_path: raw.file_path,
+      size: raw.size,
+      content: bytes::Bytes::from(content),
+      content_sha256: raw.content_sha256,
+      r#ref: raw.r#ref,
+      blob_id: raw.blob_id,
+      commit_id: raw.commit_id,
+      last_commit_id: raw.last_commit_id,
+      execute_filemode: raw.execute_filemode,
+    })
+  }
+}
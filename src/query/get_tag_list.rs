@@ -0,0 +1,126 @@
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::tag::Tag;
+use crate::common::tag::TagOrder;
+#[cfg(feature = "http")]
+use crate::common::Page;
+use crate::common::SortOrder;
+use crate::context::EmptyContext;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_page, DecodeError, Endpoint, QueryPairs, QueryPairsExt};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+use std::num::NonZeroU8;
+
+/// List project repository tags
+///
+/// <https://docs.gitlab.com/ee/api/tags.html#list-project-repository-tags>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetTagListQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  /// Number of tags per page (default: `20`, max: `100`)
+  pub per_page: Option<NonZeroU8>,
+  /// Criteria used to order tags (default: `Updated`)
+  pub order_by: Option<TagOrder>,
+  /// Sort order (default: `Desc`)
+  pub sort: Option<SortOrder>,
+  /// Only return the tags whose name contains this string
+  ///
+  /// Use `^term` and `term$` to match the start and end of the name.
+  pub search: Option<Str>,
+}
+
+pub type GetTagListQueryView<'req, Cx> = GetTagListQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetTagListQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetTagListQuery<NewCx, Str> {
+    GetTagListQuery {
+      context: new_context,
+      auth: self.auth,
+      project: self.project,
+      per_page: self.per_page,
+      order_by: self.order_by,
+      sort: self.sort,
+      search: self.search,
+    }
+  }
+
+  pub fn as_view(&self) -> GetTagListQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetTagListQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      per_page: self.per_page,
+      order_by: self.order_by,
+      sort: self.sort,
+      search: self.search.as_ref().map(AsRef::as_ref),
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetTagListQuery<EmptyContext, Str> {
+  pub const fn new(project: ProjectRef<Str>) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      project,
+      per_page: None,
+      order_by: None,
+      sort: None,
+      search: None,
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetTagListQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = Page<Tag>;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self
+        .context
+        .get_ref()
+        .url_join(["projects", project, "repository", "tags"])
+    }))
+  }
+
+  fn query(&self, query: &mut QueryPairs<'_>) {
+    query
+      .append("per_page", &self.per_page)
+      .append("order_by", &self.order_by)
+      .append("sort", &self.sort)
+      .append("search", &self.search.as_ref().map(AsRef::as_ref));
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_page(res)
+  }
+}
@@ -0,0 +1,78 @@
+use crate::common::project::ProjectRef;
+#[cfg(feature = "http")]
+use crate::common::tag::TagSignature;
+#[cfg(feature = "http")]
+use crate::context::{GetRef, GitlabUrl};
+#[cfg(feature = "http")]
+use crate::endpoint::{decode_json, DecodeError, Endpoint};
+#[cfg(feature = "http")]
+use crate::url_util::UrlExt;
+use crate::GitlabAuth;
+#[cfg(feature = "http")]
+use crate::GitlabAuthView;
+use compact_str::CompactString;
+
+/// Get the signature of a tag, with its verification status
+///
+/// GitLab responds with `404 Not Found` if the tag is not signed.
+///
+/// <https://docs.gitlab.com/ee/api/tags.html#get-x509-signature-of-a-tag>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetTagSignatureQuery<Cx, Str = CompactString> {
+  pub context: Cx,
+  pub auth: Option<GitlabAuth<Str>>,
+  pub project: ProjectRef<Str>,
+  /// Tag name
+  pub tag_name: Str,
+}
+
+pub type GetTagSignatureQueryView<'req, Cx> = GetTagSignatureQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str: AsRef<str>> GetTagSignatureQuery<Cx, Str> {
+  pub fn as_view(&self) -> GetTagSignatureQueryView<'_, Cx> {
+    GetTagSignatureQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GitlabAuth::as_view),
+      project: self.project.as_view(),
+      tag_name: self.tag_name.as_ref(),
+    }
+  }
+}
+
+#[cfg(feature = "http")]
+impl<Cx, Str> Endpoint for GetTagSignatureQuery<Cx, Str>
+where
+  Cx: GetRef<GitlabUrl>,
+  Str: AsRef<str>,
+{
+  type Context = Cx;
+  type Output = TagSignature;
+
+  const METHOD: http::Method = http::Method::GET;
+
+  fn context(&self) -> &Self::Context {
+    &self.context
+  }
+
+  fn auth(&self) -> Option<GitlabAuthView<'_>> {
+    self.auth.as_ref().map(GitlabAuth::as_view)
+  }
+
+  fn url(&self) -> Result<url::Url, url::ParseError> {
+    Ok(self.project.with_str(|project| {
+      self.context.get_ref().url_join([
+        "projects",
+        project,
+        "repository",
+        "tags",
+        self.tag_name.as_ref(),
+        "signature",
+      ])
+    }))
+  }
+
+  fn decode(res: http::Response<bytes::Bytes>) -> Result<Self::Output, DecodeError> {
+    decode_json(res)
+  }
+}
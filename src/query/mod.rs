@@ -1,9 +1,70 @@
+pub mod get_branch;
+pub mod get_branch_list;
+pub mod get_branch_list_page;
+pub mod get_commit;
+pub mod get_commit_comment_list;
+pub mod get_commit_comment_list_page;
+pub mod get_commit_diff;
+pub mod get_commit_list;
+pub mod get_commit_list_page;
+pub mod get_commit_ref_list;
+pub mod get_commit_ref_list_page;
+pub mod get_diff_list_page;
+pub mod get_discussion;
+pub mod get_discussion_list;
+pub mod get_discussion_list_page;
+pub mod get_group_merge_request_list;
+pub mod get_group_milestone;
+pub mod get_group_milestone_list;
+pub mod get_merge_request;
+pub mod get_merge_request_approval_rule;
+pub mod get_merge_request_approval_rule_list;
+pub mod get_merge_request_approval_rule_list_page;
+pub mod get_merge_request_approval_state;
+pub mod get_merge_request_approvals;
+pub mod get_merge_request_changes;
+pub mod get_merge_request_commit_list;
+pub mod get_merge_request_diff_list;
+pub mod get_merge_request_diff_version;
+pub mod get_merge_request_diff_version_list;
+pub mod get_merge_request_diff_version_list_page;
+pub mod get_merge_request_list;
+pub mod get_merge_request_list_page;
+pub mod get_milestone_list_page;
+pub mod get_note;
+pub mod get_note_list;
+pub mod get_note_list_page;
 pub mod get_package_file;
+pub mod get_package_file_list;
+pub mod get_package_file_list_page;
+pub mod get_package_file_stream;
 pub mod get_project;
+pub mod get_project_approval_rule;
+pub mod get_project_approval_rule_list;
+pub mod get_project_approval_rule_list_page;
 pub mod get_project_list;
 pub mod get_project_list_page;
+pub mod get_project_merge_request_list;
+pub mod get_project_milestone;
+pub mod get_project_milestone_list;
 pub mod get_project_package_list;
+pub mod get_project_package_list_page;
 pub mod get_project_release;
 pub mod get_project_release_list;
 pub mod get_project_release_list_page;
+pub mod get_protected_branch;
+pub mod get_protected_branch_list;
+pub mod get_protected_branch_list_page;
+pub mod get_protected_tag;
+pub mod get_protected_tag_list;
+pub mod get_protected_tag_list_page;
+pub mod get_raw_repository_file;
+pub mod get_raw_repository_file_stream;
+pub mod get_release_link;
+pub mod get_release_link_list;
+pub mod get_repository_file;
+pub mod get_tag;
+pub mod get_tag_list;
+pub mod get_tag_list_page;
+pub mod get_tag_signature;
 pub mod get_tree_record_list;
@@ -0,0 +1,2136 @@
+//! Shared test suite, run against every HTTP backend using a local stand-in GitLab server.
+
+use bytes::Bytes;
+use chrono::NaiveDate;
+use compact_str::CompactString;
+use futures::TryStreamExt;
+use gitlab_client::body::{DownloadStream, UploadBody};
+use gitlab_client::client::http::{HttpGitlabClient, HttpGitlabClientError, ResponseError};
+use gitlab_client::client::reqwest::ReqwestGitlabClient;
+use gitlab_client::command::accept_merge_request::AcceptMergeRequestCommand;
+use gitlab_client::command::approve_merge_request::ApproveMergeRequestCommand;
+use gitlab_client::command::cherry_pick_commit::CherryPickCommitCommand;
+use gitlab_client::command::create_branch::CreateBranchCommand;
+use gitlab_client::command::create_commit::CreateCommitCommand;
+use gitlab_client::command::create_discussion::CreateDiscussionCommand;
+use gitlab_client::command::create_group_milestone::CreateGroupMilestoneCommand;
+use gitlab_client::command::create_merge_request::CreateMergeRequestCommand;
+use gitlab_client::command::create_merge_request_approval_rule::CreateMergeRequestApprovalRuleCommand;
+use gitlab_client::command::create_note::CreateNoteCommand;
+use gitlab_client::command::create_project_approval_rule::CreateProjectApprovalRuleCommand;
+use gitlab_client::command::create_release::CreateReleaseCommand;
+use gitlab_client::command::create_repository_file::CreateRepositoryFileCommand;
+use gitlab_client::command::create_tag::CreateTagCommand;
+use gitlab_client::command::delete_branch::DeleteBranchCommand;
+use gitlab_client::command::delete_group_milestone::DeleteGroupMilestoneCommand;
+use gitlab_client::command::delete_merge_request_approval_rule::DeleteMergeRequestApprovalRuleCommand;
+use gitlab_client::command::delete_merged_branches::DeleteMergedBranchesCommand;
+use gitlab_client::command::delete_note::DeleteNoteCommand;
+use gitlab_client::command::delete_project_approval_rule::DeleteProjectApprovalRuleCommand;
+use gitlab_client::command::delete_release::DeleteReleaseCommand;
+use gitlab_client::command::delete_release_link::DeleteReleaseLinkCommand;
+use gitlab_client::command::delete_repository_file::DeleteRepositoryFileCommand;
+use gitlab_client::command::delete_tag::DeleteTagCommand;
+use gitlab_client::command::protect_branch::ProtectBranchCommand;
+use gitlab_client::command::protect_tag::ProtectTagCommand;
+use gitlab_client::command::publish_package_file::PublishPackageFileCommand;
+use gitlab_client::command::rebase_merge_request::RebaseMergeRequestCommand;
+use gitlab_client::command::reset_merge_request_approvals::ResetMergeRequestApprovalsCommand;
+use gitlab_client::command::resolve_discussion::ResolveDiscussionCommand;
+use gitlab_client::command::revert_commit::RevertCommitCommand;
+use gitlab_client::command::unapprove_merge_request::UnapproveMergeRequestCommand;
+use gitlab_client::command::unprotect_branch::UnprotectBranchCommand;
+use gitlab_client::command::unprotect_tag::UnprotectTagCommand;
+use gitlab_client::command::update_group_milestone::UpdateGroupMilestoneCommand;
+use gitlab_client::command::update_merge_request::UpdateMergeRequestCommand;
+use gitlab_client::command::update_merge_request_approval_rule::UpdateMergeRequestApprovalRuleCommand;
+use gitlab_client::command::update_note::UpdateNoteCommand;
+use gitlab_client::command::update_project_approval_rule::UpdateProjectApprovalRuleCommand;
+use gitlab_client::command::update_protected_branch::UpdateProtectedBranchCommand;
+use gitlab_client::command::update_release::UpdateReleaseCommand;
+use gitlab_client::command::update_release_link::UpdateReleaseLinkCommand;
+use gitlab_client::command::update_repository_file::UpdateRepositoryFileCommand;
+use gitlab_client::common::approval::{ApprovalRuleId, ApprovalRuleType};
+use gitlab_client::common::commit::{CommitAction, CommitApplyFailure, CommitContent, CommitRefType, CommitStats};
+use gitlab_client::common::epic::EpicIid;
+use gitlab_client::common::error::GitlabErrorMessage;
+use gitlab_client::common::group::GroupId;
+use gitlab_client::common::issue::IssueIid;
+use gitlab_client::common::merge_request::{
+  MergeFailure, MergeRequestDiffVersionId, MergeRequestIid, MergeRequestOrder, MergeRequestScope, MergeRequestState,
+  MergeRequestStateEvent, RebaseFailure,
+};
+use gitlab_client::common::milestone::{MilestoneId, MilestoneIid, MilestoneState, MilestoneStateEvent};
+use gitlab_client::common::note::{NoteId, NoteLocation, NoteOrder, NoteParentRef, NoteType, NoteableRef};
+use gitlab_client::common::package::{GenericPackageFile, PackageId, PackageOrder, PackageType};
+use gitlab_client::common::project::{ProjectId, ProjectRef, ProjectSlug};
+use gitlab_client::common::protected_access::{ProtectedAccessChange, ProtectedAccessId, ProtectedAccessRule};
+use gitlab_client::common::release::{InputReleaseAssets, ReleaseLinkId, ReleaseLinkType};
+use gitlab_client::common::snippet::SnippetId;
+use gitlab_client::common::tag::TagOrder;
+use gitlab_client::common::user::UserId;
+use gitlab_client::common::{AccessLevel, KeysetPagination, SortOrder};
+use gitlab_client::context::{Context, GitlabUrl};
+use gitlab_client::query::get_branch::GetBranchQuery;
+use gitlab_client::query::get_branch_list::GetBranchListQuery;
+use gitlab_client::query::get_commit::GetCommitQuery;
+use gitlab_client::query::get_commit_comment_list::GetCommitCommentListQuery;
+use gitlab_client::query::get_commit_diff::GetCommitDiffQuery;
+use gitlab_client::query::get_commit_list::GetCommitListQuery;
+use gitlab_client::query::get_commit_ref_list::GetCommitRefListQuery;
+use gitlab_client::query::get_discussion_list::GetDiscussionListQuery;
+use gitlab_client::query::get_group_merge_request_list::GetGroupMergeRequestListQuery;
+use gitlab_client::query::get_merge_request::GetMergeRequestQuery;
+use gitlab_client::query::get_merge_request_approval_rule::GetMergeRequestApprovalRuleQuery;
+use gitlab_client::query::get_merge_request_approval_rule_list::GetMergeRequestApprovalRuleListQuery;
+use gitlab_client::query::get_merge_request_approval_state::GetMergeRequestApprovalStateQuery;
+use gitlab_client::query::get_merge_request_approvals::GetMergeRequestApprovalsQuery;
+use gitlab_client::query::get_merge_request_changes::GetMergeRequestChangesQuery;
+use gitlab_client::query::get_merge_request_commit_list::GetMergeRequestCommitListQuery;
+use gitlab_client::query::get_merge_request_diff_list::GetMergeRequestDiffListQuery;
+use gitlab_client::query::get_merge_request_diff_version::GetMergeRequestDiffVersionQuery;
+use gitlab_client::query::get_merge_request_diff_version_list::GetMergeRequestDiffVersionListQuery;
+use gitlab_client::query::get_merge_request_list::GetMergeRequestListQuery;
+use gitlab_client::query::get_note::GetNoteQuery;
+use gitlab_client::query::get_note_list::GetNoteListQuery;
+use gitlab_client::query::get_package_file::GetPackageFileQuery;
+use gitlab_client::query::get_package_file_list::GetPackageFileListQuery;
+use gitlab_client::query::get_package_file_list_page::GetPackageFileListPageQuery;
+use gitlab_client::query::get_package_file_stream::GetPackageFileStreamQuery;
+use gitlab_client::query::get_project::GetProjectQuery;
+use gitlab_client::query::get_project_approval_rule::GetProjectApprovalRuleQuery;
+use gitlab_client::query::get_project_approval_rule_list::GetProjectApprovalRuleListQuery;
+use gitlab_client::query::get_project_list::GetProjectListQuery;
+use gitlab_client::query::get_project_list_page::GetProjectListPageQuery;
+use gitlab_client::query::get_project_merge_request_list::GetProjectMergeRequestListQuery;
+use gitlab_client::query::get_project_milestone_list::GetProjectMilestoneListQuery;
+use gitlab_client::query::get_project_package_list::GetProjectPackageListQuery;
+use gitlab_client::query::get_project_package_list_page::GetProjectPackageListPageQuery;
+use gitlab_client::query::get_project_release::GetProjectReleaseQuery;
+use gitlab_client::query::get_protected_branch_list::GetProtectedBranchListQuery;
+use gitlab_client::query::get_protected_tag_list::GetProtectedTagListQuery;
+use gitlab_client::query::get_raw_repository_file::GetRawRepositoryFileQuery;
+use gitlab_client::query::get_raw_repository_file_stream::GetRawRepositoryFileStreamQuery;
+use gitlab_client::query::get_release_link::GetReleaseLinkQuery;
+use gitlab_client::query::get_release_link_list::GetReleaseLinkListQuery;
+use gitlab_client::query::get_repository_file::GetRepositoryFileQuery;
+use gitlab_client::query::get_tag::GetTagQuery;
+use gitlab_client::query::get_tag_list::GetTagListQuery;
+use gitlab_client::query::get_tag_signature::GetTagSignatureQuery;
+use gitlab_client::{GitlabAuth, GitlabClient, InputPackageStatus, PackageStatus, UserAgent};
+use http::{HeaderMap, Method, Request, Response, StatusCode, Uri};
+use http_body_util::{BodyExt, Full};
+use hyper::body::Incoming;
+use hyper_util::client::legacy::connect::HttpConnector;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use std::convert::Infallible;
+use std::num::NonZeroU8;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tower_service::Service;
+use url::Url;
+
+type Cx = Context<GitlabUrl, UserAgent>;
+
+/// Request received by the stand-in server
+#[derive(Debug, Clone)]
+struct RecordedRequest {
+  method: Method,
+  uri: Uri,
+  headers: HeaderMap,
+  body: Bytes,
+}
+
+/// Local HTTP server answering the requests of a single test
+struct StandIn {
+  url: Url,
+  requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+type Route = fn(&str, &RecordedRequest) -> Response<Full<Bytes>>;
+
+impl StandIn {
+  async fn start(route: Route) -> Self {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+    let requests: Arc<Mutex<Vec<RecordedRequest>>> = Arc::default();
+    let base = url.to_string();
+    let recorded = Arc::clone(&requests);
+    tokio::spawn(async move {
+      loop {
+        let (stream, _) = listener.accept().await.unwrap();
+        let base = base.clone();
+        let recorded = Arc::clone(&recorded);
+        let service = hyper::service::service_fn(move |req: Request<Incoming>| {
+          let base = base.clone();
+          let recorded = Arc::clone(&recorded);
+          async move {
+            let (parts, body) = req.into_parts();
+            let req = RecordedRequest {
+              method: parts.method,
+              uri: parts.uri,
+              headers: parts.headers,
+              body: body.collect().await.unwrap().to_bytes(),
+            };
+            let res = route(&base, &req);
+            recorded.lock().unwrap().push(req);
+            Ok::<_, Infallible>(res)
+          }
+        });
+        tokio::spawn(hyper::server::conn::http1::Builder::new().serve_connection(TokioIo::new(stream), service));
+      }
+    });
+    Self { url, requests }
+  }
+
+  fn context(&self) -> Cx {
+    Context::new()
+      .set_gitlab_url(GitlabUrl(self.url.clone()))
+      .set_user_agent(UserAgent::from_static("gitlab_client_test/0.0.0"))
+  }
+
+  fn requests(&self) -> Vec<RecordedRequest> {
+    self.requests.lock().unwrap().clone()
+  }
+}
+
+fn json(status: StatusCode, body: impl Into<String>) -> Response<Full<Bytes>> {
+  Response::builder()
+    .status(status)
+    .header("content-type", "application/json")
+    .body(Full::new(Bytes::from(body.into())))
+    .unwrap()
+}
+
+fn not_found() -> Response<Full<Bytes>> {
+  json(StatusCode::NOT_FOUND, r#"{"message":"404 Not Found"}"#)
+}
+
+fn project_json(id: u64) -> String {
+  format!(
+    r#"{{"id":{id},"description":null,"name":"project{id}","name_with_namespace":"group / project{id}","path":"project{id}","path_with_namespace":"group/project{id}","created_at":"2024-01-01T00:00:00Z","default_branch":"main","tag_list":[],"topics":[],"ssh_url_to_repo":"git@gitlab.example.com:group/project{id}.git","http_url_to_repo":"https://gitlab.example.com/group/project{id}.git","web_url":"https://gitlab.example.com/group/project{id}","readme_url":null,"forks_count":0,"avatar_url":null,"star_count":0,"last_activity_at":"2024-01-01T00:00:00Z","namespace":{{"id":1,"name":"group","path":"group","kind":"group","full_path":"group","parent_id":null,"web_url":"https://gitlab.example.com/groups/group","avatar_url":null}}}}"#
+  )
+}
+
+fn release_json() -> String {
+  let releases: serde_json::Value = serde_json::from_str(include_str!(
+    "../test-resources/get-project-release-list/libxml2/output.json"
+  ))
+  .unwrap();
+  releases[0].to_string()
+}
+
+const PACKAGE_FILE_JSON: &str = r#"{"id":72696499,"package_id":13447789,"created_at":"2023-03-22T21:37:30.948Z","updated_at":"2023-03-22T21:37:30.948Z","size":11,"file_store":2,"file_md5":null,"file_sha1":null,"file_name":"hello.txt","file":{"url":"https://storage.example.com/hello.txt"},"file_sha256":null,"verification_retry_at":null,"verified_at":null,"verification_failure":null,"verification_retry_count":null,"verification_checksum":null,"verification_state":0,"verification_started_at":null,"status":"default","new_file_path":null}"#;
+
+async fn get_project<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|_, req| match (&req.method, req.uri.path()) {
+    (&Method::GET, "/api/v4/projects/group%2Fproject1") => json(StatusCode::OK, project_json(1)),
+    _ => not_found(),
+  })
+  .await;
+  let mut query = GetProjectQuery::<_>::new(ProjectRef::Slug(ProjectSlug::new(CompactString::new("group/project1"))))
+    .set_context(server.context());
+  query.auth = Some(GitlabAuth::PrivateToken(CompactString::new("secret")));
+
+  let actual = client.get_project(&query).await.unwrap();
+  assert_eq!(actual.id, ProjectId::new(1));
+  assert_eq!(actual.path_with_namespace, "group/project1");
+
+  let requests = server.requests();
+  assert_eq!(requests.len(), 1);
+  assert_eq!(requests[0].uri.query(), None);
+  assert_eq!(requests[0].headers["private-token"], "secret");
+  assert_eq!(requests[0].headers["user-agent"], "gitlab_client_test/0.0.0");
+}
+
+async fn get_project_list_pages<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|base, req| match (req.uri.path(), req.uri.query()) {
+    ("/api/v4/projects", Some("owned=true")) => {
+      let mut res = json(StatusCode::OK, format!("[{}]", project_json(1)));
+      let link = format!(
+        r#"<{base}api/v4/projects?owned=true&page=2>; rel="next", <{base}api/v4/projects?owned=true&page=1>; rel="first", <{base}api/v4/projects?owned=true&page=2>; rel="last""#
+      );
+      res.headers_mut().insert("link", link.parse().unwrap());
+      res
+    }
+    ("/api/v4/projects", Some("owned=true&page=2")) => json(StatusCode::OK, format!("[{}]", project_json(2))),
+    _ => not_found(),
+  })
+  .await;
+  let mut query = GetProjectListQuery::<_>::new().set_context(server.context());
+  query.owned = Some(true);
+
+  let first = client.get_project_list(&query).await.unwrap();
+  assert_eq!(first.items.len(), 1);
+  assert_eq!(first.items[0].id, ProjectId::new(1));
+  let next = first.next.unwrap();
+  assert_eq!(next, format!("{}api/v4/projects?owned=true&page=2", server.url));
+  assert_eq!(
+    first.first.as_deref(),
+    Some(format!("{}api/v4/projects?owned=true&page=1", server.url).as_str())
+  );
+  assert_eq!(first.last.as_ref(), Some(&next));
+
+  let query = GetProjectListPageQuery::new(next).set_context(server.context());
+  let second = client.get_project_list_page(&query).await.unwrap();
+  assert_eq!(second.items[0].id, ProjectId::new(2));
+  assert_eq!(second.next, None);
+}
+
+const PACKAGE_JSON: &str = r#"{"id":7,"name":"hello","version":"1.0.0","package_type":"generic","status":"default","_links":{"web_path":"/group/project1/-/packages/7"},"created_at":"2024-01-01T00:00:00Z","last_downloaded_at":null,"tags":[]}"#;
+
+async fn get_project_package_list_pages<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|base, req| match (req.uri.path(), req.uri.query()) {
+    (
+      "/api/v4/projects/1/packages",
+      Some("per_page=50&order_by=version&sort=desc&package_type=generic&package_name=hello&status=default"),
+    ) => {
+      let mut res = json(StatusCode::OK, format!("[{PACKAGE_JSON}]"));
+      let link = format!(r#"<{base}api/v4/projects/1/packages?page=2>; rel="next""#);
+      res.headers_mut().insert("link", link.parse().unwrap());
+      res
+    }
+    ("/api/v4/projects/1/packages", Some("page=2")) => json(StatusCode::OK, "[]"),
+    _ => not_found(),
+  })
+  .await;
+  let mut query = GetProjectPackageListQuery::<_>::new(ProjectRef::Id(ProjectId::new(1))).set_context(server.context());
+  query.pagination = Some(KeysetPagination {
+    per_page: NonZeroU8::new(50),
+    order_by: PackageOrder::Version,
+    sort: SortOrder::Desc,
+  });
+  query.package_type = Some(PackageType::Generic);
+  query.package_name = Some(CompactString::new("hello"));
+  query.status = Some(PackageStatus::Default);
+
+  let first = client.get_project_package_list(&query).await.unwrap();
+  assert_eq!(first.items.len(), 1);
+  assert_eq!(first.items[0].id, PackageId::new(7));
+  assert_eq!(first.items[0].package_type, PackageType::Generic);
+
+  let query = GetProjectPackageListPageQuery::new(first.next.unwrap()).set_context(server.context());
+  let second = client.get_project_package_list_page(&query).await.unwrap();
+  assert!(second.items.is_empty());
+  assert_eq!(second.next, None);
+}
+
+async fn get_package_file_list_pages<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|base, req| match (req.uri.path(), req.uri.query()) {
+    ("/api/v4/projects/1/packages/7/package_files", Some("per_page=50")) => {
+      let file = r#"{"id":25,"package_id":7,"created_at":"2024-01-01T00:00:00Z","file_name":"hello.tar.gz","size":5,"file_md5":null,"file_sha1":null,"file_sha256":"2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"}"#;
+      let mut res = json(StatusCode::OK, format!("[{file}]"));
+      let link = format!(r#"<{base}api/v4/projects/1/packages/7/package_files?page=2>; rel="next""#);
+      res.headers_mut().insert("link", link.parse().unwrap());
+      res
+    }
+    ("/api/v4/projects/1/packages/7/package_files", Some("page=2")) => json(StatusCode::OK, "[]"),
+    _ => not_found(),
+  })
+  .await;
+  let mut query = GetPackageFileListQuery::<_>::new(ProjectRef::Id(ProjectId::new(1)), PackageId::new(7))
+    .set_context(server.context());
+  query.per_page = NonZeroU8::new(50);
+
+  let first = client.get_package_file_list(&query).await.unwrap();
+  assert_eq!(first.items.len(), 1);
+  assert_eq!(first.items[0].file_name, "hello.tar.gz");
+  assert_eq!(
+    first.items[0].file_sha256.as_deref(),
+    Some("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824")
+  );
+
+  let query = GetPackageFileListPageQuery::new(first.next.unwrap()).set_context(server.context());
+  let second = client.get_package_file_list_page(&query).await.unwrap();
+  assert!(second.items.is_empty());
+  assert_eq!(second.next, None);
+}
+
+async fn get_project_release<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|_, req| match (&req.method, req.uri.path()) {
+    (&Method::GET, "/api/v4/projects/1/releases/v2.7.4") => json(StatusCode::OK, release_json()),
+    _ => not_found(),
+  })
+  .await;
+  let query = GetProjectReleaseQuery {
+    context: server.context(),
+    auth: None,
+    project: ProjectRef::Id(ProjectId::new(1)),
+    tag_name: CompactString::new("v2.7.4"),
+    include_html_description: false,
+  };
+
+  let actual = client.get_project_release(&query).await.unwrap();
+  assert_eq!(actual.tag_name, "v2.7.4");
+}
+
+async fn create_release<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|_, req| match (&req.method, req.uri.path()) {
+    (&Method::POST, "/api/v4/projects/1/releases") => json(StatusCode::CREATED, release_json()),
+    _ => not_found(),
+  })
+  .await;
+  let command = CreateReleaseCommand {
+    context: server.context(),
+    auth: Some(GitlabAuth::JobToken(CompactString::new("job"))),
+    project: ProjectRef::Id(ProjectId::new(1)),
+    tag_name: CompactString::new("v2.7.4"),
+    name: Some(CompactString::new("libxml2 2.7.4")),
+    tag_message: None,
+    description: None,
+    r#ref: Some(CompactString::new("main")),
+    milestones: Vec::new(),
+    assets: InputReleaseAssets { links: Vec::new() },
+    released_at: None,
+  };
+
+  let actual = client.create_release(&command).await.unwrap();
+  assert_eq!(actual.tag_name, "v2.7.4");
+
+  let requests = server.requests();
+  assert_eq!(requests[0].headers["job-token"], "job");
+  assert_eq!(requests[0].headers["content-type"], "application/json");
+  let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+  assert_eq!(body["tag_name"], "v2.7.4");
+  assert_eq!(body["ref"], "main");
+}
+
+async fn update_release<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|_, req| match (&req.method, req.uri.path()) {
+    (&Method::PUT, "/api/v4/projects/1/releases/v2.7.4") => json(StatusCode::OK, release_json()),
+    _ => not_found(),
+  })
+  .await;
+  let command = UpdateReleaseCommand {
+    context: server.context(),
+    auth: None,
+    project: ProjectRef::Id(ProjectId::new(1)),
+    tag_name: CompactString::new("v2.7.4"),
+    name: None,
+    description: Some(CompactString::new("Bug fixes")),
+    milestones: Some(vec![CompactString::new("v2.7"), CompactString::new("v2.8")]),
+    released_at: None,
+  };
+
+  let actual = client.update_release(&command).await.unwrap();
+  assert_eq!(actual.tag_name, "v2.7.4");
+
+  let requests = server.requests();
+  assert_eq!(requests.len(), 1);
+  assert_eq!(requests[0].headers["content-type"], "application/json");
+  let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+  assert_eq!(
+    body,
+    serde_json::json!({"description": "Bug fixes", "milestones": ["v2.7", "v2.8"]})
+  );
+}
+
+async fn delete_release<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|_, req| match (&req.method, req.uri.path()) {
+    (&Method::DELETE, "/api/v4/projects/1/releases/v2.7.4") => json(StatusCode::OK, release_json()),
+    _ => not_found(),
+  })
+  .await;
+  let command = DeleteReleaseCommand {
+    context: server.context(),
+    auth: None,
+    project: ProjectRef::Id(ProjectId::new(1)),
+    tag_name: CompactString::new("v2.7.4"),
+  };
+
+  let actual = client.delete_release(&command).await.unwrap();
+  assert_eq!(actual.tag_name, "v2.7.4");
+  let requests = server.requests();
+  assert_eq!(requests.len(), 1);
+  assert!(requests[0].body.is_empty());
+}
+
+const RELEASE_LINK_JSON: &str = r#"{"id":12,"name":"linux","url":"https://example.com/linux","direct_asset_url":"https://gitlab.example.com/group/project1/-/releases/v2.7.4/downloads/bin/linux","link_type":"package"}"#;
+
+async fn manage_release_links<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|_, req| match (&req.method, req.uri.path()) {
+    (&Method::GET, "/api/v4/projects/1/releases/v2.7.4/assets/links") => {
+      json(StatusCode::OK, format!("[{RELEASE_LINK_JSON}]"))
+    }
+    (&Method::GET | &Method::PUT | &Method::DELETE, "/api/v4/projects/1/releases/v2.7.4/assets/links/12") => {
+      json(StatusCode::OK, RELEASE_LINK_JSON)
+    }
+    _ => not_found(),
+  })
+  .await;
+  let project = ProjectRef::Id(ProjectId::new(1));
+  let tag_name = CompactString::new("v2.7.4");
+  let link_id = ReleaseLinkId::new(12);
+
+  let query = GetReleaseLinkListQuery {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    tag_name: tag_name.clone(),
+  };
+  let actual = client.get_release_link_list(&query).await.unwrap();
+  assert_eq!(actual.items.len(), 1);
+  assert_eq!(actual.items[0].id, link_id);
+  assert_eq!(actual.items[0].link_type, ReleaseLinkType::Package);
+
+  let query = GetReleaseLinkQuery {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    tag_name: tag_name.clone(),
+    link_id,
+  };
+  let actual = client.get_release_link(&query).await.unwrap();
+  assert_eq!(actual.name, "linux");
+
+  let command = UpdateReleaseLinkCommand {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    tag_name: tag_name.clone(),
+    link_id,
+    name: None,
+    url: Some(CompactString::new("https://example.com/linux")),
+    direct_asset_path: Some(CompactString::new("/bin/linux")),
+    link_type: Some(ReleaseLinkType::Package),
+  };
+  client.update_release_link(&command).await.unwrap();
+
+  let command = DeleteReleaseLinkCommand {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    tag_name: tag_name.clone(),
+    link_id,
+  };
+  client.delete_release_link(&command).await.unwrap();
+
+  let query = GetReleaseLinkQuery {
+    link_id: ReleaseLinkId::new(13),
+    ..query
+  };
+  let actual = client.get_release_link(&query).await.unwrap_err();
+  match actual {
+    HttpGitlabClientError::NotFound(res) => {
+      assert_eq!(res.uri.path(), "/api/v4/projects/1/releases/v2.7.4/assets/links/13");
+    }
+    e => panic!("unexpected error: {e:?}"),
+  }
+
+  let requests = server.requests();
+  let methods: Vec<&Method> = requests.iter().map(|r| &r.method).collect();
+  assert_eq!(
+    methods,
+    [&Method::GET, &Method::GET, &Method::PUT, &Method::DELETE, &Method::GET]
+  );
+  let body: serde_json::Value = serde_json::from_slice(&requests[2].body).unwrap();
+  assert_eq!(
+    body,
+    serde_json::json!({"url": "https://example.com/linux", "direct_asset_path": "/bin/linux", "link_type": "package"})
+  );
+}
+
+const MILESTONE_JSON: &str = r#"{"id":12,"iid":3,"group_id":7,"title":"v2.8","description":null,"due_date":"2024-06-30","start_date":null,"state":"active","updated_at":"2024-01-01T00:00:00Z","created_at":"2024-01-01T00:00:00Z","expired":false,"web_url":"https://gitlab.example.com/groups/group/-/milestones/3"}"#;
+
+async fn manage_milestones<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|_, req| match (&req.method, req.uri.path()) {
+    (&Method::GET, "/api/v4/projects/1/milestones") => json(StatusCode::OK, format!("[{MILESTONE_JSON}]")),
+    (&Method::POST, "/api/v4/groups/7/milestones") => json(StatusCode::CREATED, MILESTONE_JSON),
+    (&Method::PUT, "/api/v4/groups/7/milestones/12") => json(StatusCode::OK, MILESTONE_JSON),
+    (&Method::DELETE, "/api/v4/groups/7/milestones/12") => Response::builder()
+      .status(StatusCode::NO_CONTENT)
+      .body(Full::new(Bytes::new()))
+      .unwrap(),
+    _ => not_found(),
+  })
+  .await;
+
+  let mut query =
+    GetProjectMilestoneListQuery::<_>::new(ProjectRef::Id(ProjectId::new(1))).set_context(server.context());
+  query.iids = vec![MilestoneIid::new(3), MilestoneIid::new(4)];
+  query.state = Some(MilestoneState::Active);
+  query.search = Some(CompactString::new("v2"));
+  let actual = client.get_project_milestone_list(&query).await.unwrap();
+  assert_eq!(actual.items.len(), 1);
+  assert_eq!(actual.items[0].id, MilestoneId::new(12));
+  assert_eq!(actual.items[0].due_date, NaiveDate::from_ymd_opt(2024, 6, 30));
+
+  let command = CreateGroupMilestoneCommand {
+    context: server.context(),
+    auth: None,
+    group: GroupId::new(7),
+    title: CompactString::new("v2.8"),
+    description: None,
+    due_date: NaiveDate::from_ymd_opt(2024, 6, 30),
+    start_date: None,
+  };
+  client.create_group_milestone(&command).await.unwrap();
+
+  let command = UpdateGroupMilestoneCommand {
+    context: server.context(),
+    auth: None,
+    group: GroupId::new(7),
+    milestone_id: MilestoneId::new(12),
+    title: None,
+    description: None,
+    due_date: None,
+    start_date: None,
+    state_event: Some(MilestoneStateEvent::Close),
+  };
+  client.update_group_milestone(&command).await.unwrap();
+
+  let command = DeleteGroupMilestoneCommand {
+    context: server.context(),
+    auth: None,
+    group: GroupId::new(7),
+    milestone_id: MilestoneId::new(12),
+  };
+  client.delete_group_milestone(&command).await.unwrap();
+
+  let requests = server.requests();
+  assert_eq!(requests.len(), 4);
+  assert_eq!(
+    requests[0].uri.query(),
+    Some("iids%5B%5D=3&iids%5B%5D=4&state=active&search=v2")
+  );
+  let body: serde_json::Value = serde_json::from_slice(&requests[1].body).unwrap();
+  assert_eq!(body, serde_json::json!({"title": "v2.8", "due_date": "2024-06-30"}));
+  let body: serde_json::Value = serde_json::from_slice(&requests[2].body).unwrap();
+  assert_eq!(body, serde_json::json!({"state_event": "close"}));
+  assert_eq!(requests[3].method, Method::DELETE);
+}
+
+const REPOSITORY_FILE_JSON: &str = r#"{"file_name":"VERSION","file_path":"config/VERSION","size":6,"encoding":"base64","content":"MS4yLjMK","content_sha256":"4c294617b60715c1d218e61164a3abd4808a4284cbc30e6728a01ad9aada4481","ref":"main","blob_id":"79f7bbd25901e8334750839545a9bd021f0e4c83","commit_id":"d5a3ff139356ce33e37e73add446f16869741b50","last_commit_id":"570e7b2abdd848b95f2f578043fc23bd6f6fd24d","execute_filemode":false}"#;
+
+async fn manage_repository_files<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|_, req| match (&req.method, req.uri.path()) {
+    (&Method::GET, "/api/v4/projects/1/repository/files/config%2FVERSION") => {
+      json(StatusCode::OK, REPOSITORY_FILE_JSON)
+    }
+    (&Method::GET, "/api/v4/projects/1/repository/files/config%2FVERSION/raw") => {
+      Response::new(Full::new(Bytes::from_static(b"1.2.3\n")))
+    }
+    (&Method::POST, "/api/v4/projects/1/repository/files/config%2FCHANNEL") => {
+      json(StatusCode::CREATED, r#"{"file_path":"config/CHANNEL","branch":"bump"}"#)
+    }
+    (&Method::PUT, "/api/v4/projects/1/repository/files/config%2FVERSION") => {
+      json(StatusCode::OK, r#"{"file_path":"config/VERSION","branch":"bump"}"#)
+    }
+    (&Method::DELETE, "/api/v4/projects/1/repository/files/config%2FVERSION") => Response::builder()
+      .status(StatusCode::NO_CONTENT)
+      .body(Full::new(Bytes::new()))
+      .unwrap(),
+    _ => not_found(),
+  })
+  .await;
+
+  let query = GetRepositoryFileQuery {
+    context: server.context(),
+    auth: None,
+    project: ProjectRef::Id(ProjectId::new(1)),
+    file_path: CompactString::new("config/VERSION"),
+    r#ref: CompactString::new("main"),
+  };
+  let file = client.get_repository_file(&query).await.unwrap();
+  assert_eq!(file.content, Bytes::from_static(b"1.2.3\n"));
+  assert_eq!(file.blob_id, "79f7bbd25901e8334750839545a9bd021f0e4c83");
+  assert_eq!(file.last_commit_id, "570e7b2abdd848b95f2f578043fc23bd6f6fd24d");
+
+  let query = GetRawRepositoryFileQuery {
+    context: server.context(),
+    auth: None,
+    project: ProjectRef::Id(ProjectId::new(1)),
+    file_path: CompactString::new("config/VERSION"),
+    r#ref: None,
+    lfs: None,
+  };
+  let actual = client.get_raw_repository_file(&query).await.unwrap();
+  assert_eq!(actual, Bytes::from_static(b"1.2.3\n"));
+
+  let command = CreateRepositoryFileCommand {
+    context: server.context(),
+    auth: None,
+    project: ProjectRef::Id(ProjectId::new(1)),
+    file_path: CompactString::new("config/CHANNEL"),
+    branch: CompactString::new("bump"),
+    start_branch: Some(CompactString::new("main")),
+    commit_message: CompactString::new("Add channel"),
+    author_email: None,
+    author_name: None,
+    content: b"stable\n".to_vec(),
+    execute_filemode: None,
+  };
+  let actual = client.create_repository_file(&command).await.unwrap();
+  assert_eq!(actual.file_path, "config/CHANNEL");
+
+  let command = UpdateRepositoryFileCommand {
+    context: server.context(),
+    auth: None,
+    project: ProjectRef::Id(ProjectId::new(1)),
+    file_path: CompactString::new("config/VERSION"),
+    branch: CompactString::new("bump"),
+    start_branch: Some(CompactString::new("main")),
+    commit_message: CompactString::new("Bump version"),
+    author_email: Some(CompactString::new("bot@example.com")),
+    author_name: Some(CompactString::new("Bot")),
+    content: b"1.2.4\n".to_vec(),
+    execute_filemode: None,
+    last_commit_id: Some(file.last_commit_id.clone()),
+  };
+  let actual = client.update_repository_file(&command).await.unwrap();
+  assert_eq!(actual.branch, "bump");
+
+  let command = DeleteRepositoryFileCommand {
+    context: server.context(),
+    auth: None,
+    project: ProjectRef::Id(ProjectId::new(1)),
+    file_path: CompactString::new("config/VERSION"),
+    branch: CompactString::new("main"),
+    start_branch: None,
+    commit_message: CompactString::new("Remove version"),
+    author_email: None,
+    author_name: None,
+    last_commit_id: None,
+  };
+  client.delete_repository_file(&command).await.unwrap();
+
+  let requests = server.requests();
+  assert_eq!(requests.len(), 5);
+  assert_eq!(requests[0].uri.query(), Some("ref=main"));
+  assert_eq!(requests[1].uri.query(), None);
+  let body: serde_json::Value = serde_json::from_slice(&requests[2].body).unwrap();
+  assert_eq!(
+    body,
+    serde_json::json!({
+      "branch": "bump",
+      "start_branch": "main",
+      "commit_message": "Add channel",
+      "encoding": "base64",
+      "content": "c3RhYmxlCg==",
+    })
+  );
+  let body: serde_json::Value = serde_json::from_slice(&requests[3].body).unwrap();
+  assert_eq!(
+    body,
+    serde_json::json!({
+      "branch": "bump",
+      "start_branch": "main",
+      "commit_message": "Bump version",
+      "author_email": "bot@example.com",
+      "author_name": "Bot",
+      "encoding": "base64",
+      "content": "MS4yLjQK",
+      "last_commit_id": "570e7b2abdd848b95f2f578043fc23bd6f6fd24d",
+    })
+  );
+  assert_eq!(requests[4].method, Method::DELETE);
+  let body: serde_json::Value = serde_json::from_slice(&requests[4].body).unwrap();
+  assert_eq!(
+    body,
+    serde_json::json!({"branch": "main", "commit_message": "Remove version"})
+  );
+}
+
+const COMMIT_JSON: &str = r#"{"id":"ed899a2f4b50b4370feeea94676502b42383c746","short_id":"ed899a2f","title":"Bump version","author_name":"Bot","author_email":"bot@example.com","authored_date":"2024-01-01T00:00:00Z","committer_name":"Bot","committer_email":"bot@example.com","committed_date":"2024-01-01T00:00:00Z","created_at":"2024-01-01T00:00:00Z","message":"Bump version","parent_ids":["6104942438c14ec7bd21c6cd5bd995272b3faff6"],"trailers":{},"web_url":"https://gitlab.example.com/group/project1/-/commit/ed899a2f4b50b4370feeea94676502b42383c746","stats":{"additions":2,"deletions":1,"total":3},"status":null,"project_id":1,"last_pipeline":null}"#;
+
+async fn create_commit<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|_, req| match (&req.method, req.uri.path()) {
+    (&Method::POST, "/api/v4/projects/group%2Fproject1/repository/commits") => json(StatusCode::CREATED, COMMIT_JSON),
+    _ => not_found(),
+  })
+  .await;
+
+  let command = CreateCommitCommand {
+    context: server.context(),
+    auth: None,
+    project: ProjectRef::Slug(ProjectSlug::new(CompactString::new("group/project1"))),
+    branch: CompactString::new("bump"),
+    commit_message: CompactString::new("Bump version"),
+    start_branch: Some(CompactString::new("main")),
+    start_sha: None,
+    actions: vec![
+      CommitAction::Update {
+        file_path: CompactString::new("VERSION"),
+        content: CommitContent::Text(CompactString::new("1.2.4\n")),
+        last_commit_id: Some(CompactString::new("570e7b2abdd848b95f2f578043fc23bd6f6fd24d")),
+        execute_filemode: None,
+      },
+      CommitAction::Create {
+        file_path: CompactString::new("logo.png"),
+        content: CommitContent::Base64(vec![0x89, b'P', b'N', b'G']),
+        execute_filemode: None,
+      },
+      CommitAction::Move {
+        file_path: CompactString::new("docs/CHANGELOG.md"),
+        previous_path: CompactString::new("CHANGELOG.md"),
+        content: None,
+        last_commit_id: None,
+      },
+      CommitAction::Chmod {
+        file_path: CompactString::new("build.sh"),
+        execute_filemode: true,
+      },
+      CommitAction::Delete {
+        file_path: CompactString::new("OLD"),
+        last_commit_id: None,
+      },
+    ],
+    author_email: Some(CompactString::new("bot@example.com")),
+    author_name: Some(CompactString::new("Bot")),
+    stats: Some(true),
+    force: None,
+  };
+  let actual = client.create_commit(&command).await.unwrap();
+  assert_eq!(actual.id, "ed899a2f4b50b4370feeea94676502b42383c746");
+  assert_eq!(
+    actual.stats,
+    Some(CommitStats {
+      additions: 2,
+      deletions: 1,
+      total: 3,
+    })
+  );
+
+  let requests = server.requests();
+  let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+  assert_eq!(
+    body,
+    serde_json::json!({
+      "branch": "bump",
+      "commit_message": "Bump version",
+      "start_branch": "main",
+      "actions": [
+        {
+          "action": "update",
+          "file_path": "VERSION",
+          "content": "1.2.4\n",
+          "encoding": "text",
+          "last_commit_id": "570e7b2abdd848b95f2f578043fc23bd6f6fd24d",
+        },
+        {"action": "create", "file_path": "logo.png", "content": "iVBORw==", "encoding": "base64"},
+        {"action": "move", "file_path": "docs/CHANGELOG.md", "previous_path": "CHANGELOG.md"},
+        {"action": "chmod", "file_path": "build.sh", "execute_filemode": true},
+        {"action": "delete", "file_path": "OLD"},
+      ],
+      "author_email": "bot@example.com",
+      "author_name": "Bot",
+      "stats": true,
+    })
+  );
+}
+
+const DIFF_JSON: &str = r#"{"diff":"@@ -1 +1 @@\n-1.2.3\n+1.2.4\n","new_path":"VERSION","old_path":"VERSION","a_mode":"100644","b_mode":"100644","new_file":false,"renamed_file":false,"deleted_file":false,"generated_file":false}"#;
+
+async fn read_commits<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|_, req| {
+    let commits = "/api/v4/projects/group%2Fproject1/repository/commits";
+    let sha = "ed899a2f4b50b4370feeea94676502b42383c746";
+    match (&req.method, req.uri.path().strip_prefix(commits)) {
+      (&Method::GET, Some("")) => json(StatusCode::OK, format!("[{COMMIT_JSON}]")),
+      (&Method::GET, Some(path)) if path == format!("/{sha}") => json(StatusCode::OK, COMMIT_JSON),
+      (&Method::GET, Some(path)) if path == format!("/{sha}/diff") => json(StatusCode::OK, format!("[{DIFF_JSON}]")),
+      (&Method::GET, Some(path)) if path == format!("/{sha}/refs") => json(
+        StatusCode::OK,
+        r#"[{"type":"branch","name":"main"},{"type":"tag","name":"v1.2.4"}]"#,
+      ),
+      (&Method::GET, Some(path)) if path == format!("/{sha}/comments") => json(
+        StatusCode::OK,
+        r#"[{"note":"LGTM","path":"VERSION","line":1,"line_type":"new","created_at":"2024-01-01T00:00:00Z","author":{"id":1,"username":"bot","name":"Bot","state":"active","locked":false,"avatar_url":null,"web_url":"https://gitlab.example.com/bot"}}]"#,
+      ),
+      (&Method::POST, Some(path)) if path == format!("/{sha}/cherry_pick") => json(
+        StatusCode::BAD_REQUEST,
+        r#"{"message":"Sorry, we cannot cherry-pick this commit automatically.","error_code":"conflict"}"#,
+      ),
+      (&Method::POST, Some(path)) if path == format!("/{sha}/revert") => {
+        json(StatusCode::CREATED, COMMIT_JSON)
+      }
+      _ => not_found(),
+    }
+  })
+  .await;
+  let project = ProjectRef::Slug(ProjectSlug::new(CompactString::new("group/project1")));
+  let sha = CompactString::new("ed899a2f4b50b4370feeea94676502b42383c746");
+
+  let query = GetCommitListQuery {
+    ref_name: Some(CompactString::new("main")),
+    since: Some("2024-01-01T00:00:00Z".parse().unwrap()),
+    path: Some(CompactString::new("VERSION")),
+    author: Some(CompactString::new("Bot")),
+    with_stats: Some(true),
+    first_parent: Some(true),
+    ..GetCommitListQuery::new(project.clone()).set_context(server.context())
+  };
+  let commits = client.get_commit_list(&query).await.unwrap();
+  assert_eq!(commits.items[0].short_id, "ed899a2f");
+
+  let query = GetCommitQuery {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    sha: sha.clone(),
+    stats: Some(false),
+  };
+  let commit = client.get_commit(&query).await.unwrap();
+  assert_eq!(commit.title, "Bump version");
+
+  let query = GetCommitDiffQuery {
+    unidiff: Some(true),
+    ..GetCommitDiffQuery::new(project.clone(), sha.clone()).set_context(server.context())
+  };
+  let diff = client.get_commit_diff(&query).await.unwrap();
+  assert_eq!(diff.items[0].new_path, "VERSION");
+  assert_eq!(diff.items[0].diff, "@@ -1 +1 @@\n-1.2.3\n+1.2.4\n");
+
+  let query = GetCommitRefListQuery::new(project.clone(), sha.clone()).set_context(server.context());
+  let refs = client.get_commit_ref_list(&query).await.unwrap();
+  assert_eq!(refs.items[1].r#type, CommitRefType::Tag);
+  assert_eq!(refs.items[1].name, "v1.2.4");
+
+  let query = GetCommitCommentListQuery::new(project.clone(), sha.clone()).set_context(server.context());
+  let comments = client.get_commit_comment_list(&query).await.unwrap();
+  assert_eq!(comments.items[0].note, "LGTM");
+  assert_eq!(comments.items[0].author.username, "bot");
+
+  let command = CherryPickCommitCommand {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    sha: sha.clone(),
+    branch: CompactString::new("stable"),
+    message: None,
+  };
+  let actual = client.cherry_pick_commit(&command).await.unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::BAD_REQUEST));
+  assert_eq!(
+    actual.response().and_then(ResponseError::commit_apply_failure),
+    Some(CommitApplyFailure::Conflict)
+  );
+
+  let command = RevertCommitCommand {
+    context: server.context(),
+    auth: None,
+    project,
+    sha,
+    branch: CompactString::new("main"),
+  };
+  client.revert_commit(&command).await.unwrap();
+
+  let requests = server.requests();
+  assert_eq!(
+    requests[0].uri.query(),
+    Some("ref_name=main&since=2024-01-01T00%3A00%3A00Z&path=VERSION&author=Bot&with_stats=true&first_parent=true")
+  );
+  assert_eq!(requests[1].uri.query(), Some("stats=false"));
+  assert_eq!(requests[2].uri.query(), Some("unidiff=true"));
+  assert_eq!(requests[3].uri.query(), Some("type=all"));
+  let body: serde_json::Value = serde_json::from_slice(&requests[5].body).unwrap();
+  assert_eq!(body, serde_json::json!({"branch": "stable"}));
+  let body: serde_json::Value = serde_json::from_slice(&requests[6].body).unwrap();
+  assert_eq!(body, serde_json::json!({"branch": "main"}));
+}
+
+const PROTECTED_BRANCH_JSON: &str = r#"{"id":1,"name":"release/*","push_access_levels":[{"id":1,"access_level":30,"access_level_description":"Developers + Maintainers","user_id":null,"group_id":null},{"id":2,"access_level":null,"access_level_description":"Bot","user_id":7,"group_id":null}],"merge_access_levels":[{"id":3,"access_level":40,"access_level_description":"Maintainers","user_id":null,"group_id":null}],"unprotect_access_levels":[],"allow_force_push":false,"code_owner_approval_required":false}"#;
+
+async fn manage_branches<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|_, req| {
+    let branch_json = || format!(r#"{{"name":"release/1.0","merged":false,"protected":true,"default":false,"developers_can_push":true,"developers_can_merge":false,"can_push":true,"web_url":null,"commit":{COMMIT_JSON}}}"#);
+    match (&req.method, req.uri.path()) {
+      (&Method::GET, "/api/v4/projects/group%2Fproject1/repository/branches") => {
+        json(StatusCode::OK, format!("[{}]", branch_json()))
+      }
+      (&Method::POST, "/api/v4/projects/group%2Fproject1/repository/branches") => {
+        json(StatusCode::CREATED, branch_json())
+      }
+      (&Method::GET, "/api/v4/projects/group%2Fproject1/repository/branches/release%2F1.0") => {
+        json(StatusCode::OK, branch_json())
+      }
+      (&Method::DELETE, "/api/v4/projects/group%2Fproject1/repository/branches/release%2F1.0") => {
+        Response::builder().status(StatusCode::NO_CONTENT).body(Full::default()).unwrap()
+      }
+      (&Method::DELETE, "/api/v4/projects/group%2Fproject1/repository/merged_branches") => {
+        json(StatusCode::ACCEPTED, r#"{"message":"202 Accepted"}"#)
+      }
+      (&Method::GET, "/api/v4/projects/group%2Fproject1/protected_branches") => {
+        json(StatusCode::OK, format!("[{PROTECTED_BRANCH_JSON}]"))
+      }
+      (&Method::POST, "/api/v4/projects/group%2Fproject1/protected_branches") => {
+        json(StatusCode::CREATED, PROTECTED_BRANCH_JSON)
+      }
+      (&Method::PATCH, "/api/v4/projects/group%2Fproject1/protected_branches/release%2F*") => {
+        json(StatusCode::OK, PROTECTED_BRANCH_JSON)
+      }
+      (&Method::DELETE, "/api/v4/projects/group%2Fproject1/protected_branches/release%2F*") => {
+        Response::builder().status(StatusCode::NO_CONTENT).body(Full::default()).unwrap()
+      }
+      _ => not_found(),
+    }
+  })
+  .await;
+  let project = ProjectRef::Slug(ProjectSlug::new(CompactString::new("group/project1")));
+  let branch = CompactString::new("release/1.0");
+
+  let query = GetBranchListQuery {
+    search: Some(CompactString::new("^release")),
+    regex: Some(CompactString::new("^release/[0-9.]+$")),
+    ..GetBranchListQuery::new(project.clone()).set_context(server.context())
+  };
+  let branches = client.get_branch_list(&query).await.unwrap();
+  assert!(branches.items[0].protected);
+  assert_eq!(branches.items[0].commit.short_id, "ed899a2f");
+
+  let query = GetBranchQuery {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    branch: branch.clone(),
+  };
+  assert_eq!(client.get_branch(&query).await.unwrap().name, "release/1.0");
+
+  let command = CreateBranchCommand {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    branch: branch.clone(),
+    r#ref: CompactString::new("main"),
+  };
+  client.create_branch(&command).await.unwrap();
+
+  let command = DeleteBranchCommand {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    branch,
+  };
+  client.delete_branch(&command).await.unwrap();
+
+  let command = DeleteMergedBranchesCommand {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+  };
+  client.delete_merged_branches(&command).await.unwrap();
+
+  let query = GetProtectedBranchListQuery::new(project.clone()).set_context(server.context());
+  let protected = client.get_protected_branch_list(&query).await.unwrap();
+  assert_eq!(protected.items[0].push_access_levels[1].user_id, Some(UserId::new(7)));
+
+  let command = ProtectBranchCommand {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    name: CompactString::new("release/*"),
+    push_access_level: Some(AccessLevel::Developer),
+    merge_access_level: Some(AccessLevel::Maintainer),
+    unprotect_access_level: None,
+    allow_force_push: Some(false),
+    allowed_to_push: vec![ProtectedAccessRule::User(UserId::new(7))],
+    allowed_to_merge: vec![ProtectedAccessRule::Group(GroupId::new(3))],
+    allowed_to_unprotect: Vec::new(),
+    code_owner_approval_required: None,
+  };
+  let protection = client.protect_branch(&command).await.unwrap();
+  assert_eq!(
+    protection.push_access_levels[0].access_level,
+    Some(AccessLevel::Developer)
+  );
+
+  let command = UpdateProtectedBranchCommand {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    name: CompactString::new("release/*"),
+    allow_force_push: None,
+    allowed_to_push: vec![
+      ProtectedAccessChange::Remove(ProtectedAccessId::new(2)),
+      ProtectedAccessChange::Add(ProtectedAccessRule::AccessLevel(AccessLevel::Maintainer)),
+    ],
+    allowed_to_merge: Vec::new(),
+    allowed_to_unprotect: Vec::new(),
+    code_owner_approval_required: Some(true),
+  };
+  client.update_protected_branch(&command).await.unwrap();
+
+  let command = UnprotectBranchCommand {
+    context: server.context(),
+    auth: None,
+    project,
+    name: CompactString::new("release/*"),
+  };
+  client.unprotect_branch(&command).await.unwrap();
+
+  let requests = server.requests();
+  assert_eq!(
+    requests[0].uri.query(),
+    Some("search=%5Erelease&regex=%5Erelease%2F%5B0-9.%5D%2B%24")
+  );
+  let body: serde_json::Value = serde_json::from_slice(&requests[2].body).unwrap();
+  assert_eq!(body, serde_json::json!({"branch": "release/1.0", "ref": "main"}));
+  let body: serde_json::Value = serde_json::from_slice(&requests[6].body).unwrap();
+  assert_eq!(
+    body,
+    serde_json::json!({
+      "name": "release/*",
+      "push_access_level": 30,
+      "merge_access_level": 40,
+      "allow_force_push": false,
+      "allowed_to_push": [{"user_id": 7}],
+      "allowed_to_merge": [{"group_id": 3}],
+    })
+  );
+  let body: serde_json::Value = serde_json::from_slice(&requests[7].body).unwrap();
+  assert_eq!(
+    body,
+    serde_json::json!({
+      "allowed_to_push": [{"id": 2, "_destroy": true}, {"access_level": 40}],
+      "code_owner_approval_required": true,
+    })
+  );
+}
+
+async fn manage_tags<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|_, req| {
+    let tag_json = || format!(r#"{{"name":"v1.0.0","message":"Version 1.0.0","target":"2695effb5807a22ff3d138d593fd856244e155e7","commit":{COMMIT_JSON},"release":{{"tag_name":"v1.0.0","description":"First release"}},"protected":true,"created_at":"2024-01-01T00:00:00Z"}}"#);
+    match (&req.method, req.uri.path()) {
+      (&Method::GET, "/api/v4/projects/group%2Fproject1/repository/tags") => {
+        json(StatusCode::OK, format!("[{}]", tag_json()))
+      }
+      (&Method::POST, "/api/v4/projects/group%2Fproject1/repository/tags") => json(StatusCode::CREATED, tag_json()),
+      (&Method::GET, "/api/v4/projects/group%2Fproject1/repository/tags/v1.0.0") => json(StatusCode::OK, tag_json()),
+      (&Method::GET, "/api/v4/projects/group%2Fproject1/repository/tags/v1.0.0/signature") => json(
+        StatusCode::OK,
+        r#"{"signature_type":"PGP","verification_status":"verified","gpg_key_id":1,"gpg_key_primary_keyid":"8254AAB3FBD54AC9","gpg_key_user_name":"John Doe","gpg_key_user_email":"johndoe@example.com","gpg_key_subkey_id":null}"#,
+      ),
+      (&Method::DELETE, "/api/v4/projects/group%2Fproject1/repository/tags/v1.0.0") => {
+        Response::builder().status(StatusCode::NO_CONTENT).body(Full::default()).unwrap()
+      }
+      (&Method::GET, "/api/v4/projects/group%2Fproject1/protected_tags") => json(
+        StatusCode::OK,
+        r#"[{"name":"v*","create_access_levels":[{"id":1,"access_level":40,"access_level_description":"Maintainers"}]}]"#,
+      ),
+      (&Method::POST, "/api/v4/projects/group%2Fproject1/protected_tags") => json(
+        StatusCode::CREATED,
+        r#"{"name":"v*","create_access_levels":[{"id":1,"access_level":30,"access_level_description":"Developers + Maintainers"}]}"#,
+      ),
+      (&Method::DELETE, "/api/v4/projects/group%2Fproject1/protected_tags/v*") => {
+        Response::builder().status(StatusCode::NO_CONTENT).body(Full::default()).unwrap()
+      }
+      _ => not_found(),
+    }
+  })
+  .await;
+  let project = ProjectRef::Slug(ProjectSlug::new(CompactString::new("group/project1")));
+  let tag_name = CompactString::new("v1.0.0");
+
+  let query = GetTagListQuery {
+    order_by: Some(TagOrder::Version),
+    sort: Some(SortOrder::Desc),
+    search: Some(CompactString::new("^v1")),
+    ..GetTagListQuery::new(project.clone()).set_context(server.context())
+  };
+  let tags = client.get_tag_list(&query).await.unwrap();
+  assert!(tags.items[0].protected);
+  assert_eq!(tags.items[0].release.as_ref().unwrap().tag_name, "v1.0.0");
+
+  let query = GetTagQuery {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    tag_name: tag_name.clone(),
+  };
+  assert_eq!(
+    client.get_tag(&query).await.unwrap().message.as_deref(),
+    Some("Version 1.0.0")
+  );
+
+  let query = GetTagSignatureQuery {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    tag_name: tag_name.clone(),
+  };
+  let signature = client.get_tag_signature(&query).await.unwrap();
+  assert!(signature.verification_status().is_verified());
+
+  let command = CreateTagCommand {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    tag_name: tag_name.clone(),
+    r#ref: CompactString::new("main"),
+    message: Some(CompactString::new("Version 1.0.0")),
+  };
+  client.create_tag(&command).await.unwrap();
+
+  let command = DeleteTagCommand {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    tag_name,
+  };
+  client.delete_tag(&command).await.unwrap();
+
+  let query = GetProtectedTagListQuery::new(project.clone()).set_context(server.context());
+  let protected = client.get_protected_tag_list(&query).await.unwrap();
+  assert_eq!(
+    protected.items[0].create_access_levels[0].access_level,
+    Some(AccessLevel::Maintainer)
+  );
+
+  let command = ProtectTagCommand {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    name: CompactString::new("v*"),
+    create_access_level: Some(AccessLevel::Developer),
+    allowed_to_create: vec![ProtectedAccessRule::User(UserId::new(7))],
+  };
+  client.protect_tag(&command).await.unwrap();
+
+  let command = UnprotectTagCommand {
+    context: server.context(),
+    auth: None,
+    project,
+    name: CompactString::new("v*"),
+  };
+  client.unprotect_tag(&command).await.unwrap();
+
+  let requests = server.requests();
+  assert_eq!(requests[0].uri.query(), Some("order_by=version&sort=desc&search=%5Ev1"));
+  let body: serde_json::Value = serde_json::from_slice(&requests[3].body).unwrap();
+  assert_eq!(
+    body,
+    serde_json::json!({"tag_name": "v1.0.0", "ref": "main", "message": "Version 1.0.0"})
+  );
+  let body: serde_json::Value = serde_json::from_slice(&requests[6].body).unwrap();
+  assert_eq!(
+    body,
+    serde_json::json!({"name": "v*", "create_access_level": 30, "allowed_to_create": [{"user_id": 7}]})
+  );
+}
+
+const MERGE_REQUEST_JSON: &str = r#"{"id":84,"iid":3,"project_id":1,"title":"Add merge requests","description":null,"state":"opened","created_at":"2024-01-01T00:00:00Z","updated_at":"2024-01-01T00:00:00Z","merged_by":null,"merge_user":null,"merged_at":null,"closed_by":null,"closed_at":null,"target_branch":"main","source_branch":"feature","user_notes_count":0,"upvotes":0,"downvotes":0,"author":{"id":1,"username":"admin","name":"Administrator","state":"active","locked":false,"avatar_url":null,"web_url":"https://gitlab.example.com/admin"},"assignees":[],"assignee":null,"reviewers":[{"id":7,"username":"reviewer","name":"Reviewer","state":"active","locked":false,"avatar_url":null,"web_url":"https://gitlab.example.com/reviewer"}],"source_project_id":1,"target_project_id":1,"labels":["backend"],"draft":false,"work_in_progress":false,"milestone":null,"merge_when_pipeline_succeeds":false,"merge_status":"cannot_be_merged","detailed_merge_status":"conflict","sha":"ed899a2f4b50b4370feeea94676502b42383c746","merge_commit_sha":null,"squash_commit_sha":null,"discussion_locked":null,"should_remove_source_branch":null,"force_remove_source_branch":true,"reference":"!3","references":{"short":"!3","relative":"!3","full":"group/project1!3"},"web_url":"https://gitlab.example.com/group/project1/-/merge_requests/3","squash":false,"squash_on_merge":false,"has_conflicts":true,"blocking_discussions_resolved":true}"#;
+
+async fn manage_merge_requests<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|_, req| match (&req.method, req.uri.path()) {
+    (
+      &Method::GET,
+      "/api/v4/projects/group%2Fproject1/merge_requests" | "/api/v4/groups/7/merge_requests" | "/api/v4/merge_requests",
+    ) => json(StatusCode::OK, format!("[{MERGE_REQUEST_JSON}]")),
+    (&Method::POST, "/api/v4/projects/group%2Fproject1/merge_requests") => {
+      json(StatusCode::CREATED, MERGE_REQUEST_JSON)
+    }
+    (&Method::GET | &Method::PUT, "/api/v4/projects/group%2Fproject1/merge_requests/3") => {
+      json(StatusCode::OK, MERGE_REQUEST_JSON)
+    }
+    (&Method::PUT, "/api/v4/projects/group%2Fproject1/merge_requests/3/merge") => {
+      json(StatusCode::NOT_ACCEPTABLE, r#"{"message":"Branch cannot be merged"}"#)
+    }
+    (&Method::PUT, "/api/v4/projects/group%2Fproject1/merge_requests/3/rebase") => {
+      json(StatusCode::ACCEPTED, r#"{"rebase_in_progress":true}"#)
+    }
+    _ => not_found(),
+  })
+  .await;
+  let project = ProjectRef::Slug(ProjectSlug::new(CompactString::new("group/project1")));
+  let iid = MergeRequestIid::new(3);
+
+  let query = GetProjectMergeRequestListQuery {
+    pagination: Some(KeysetPagination {
+      per_page: NonZeroU8::new(50),
+      order_by: MergeRequestOrder::UpdatedAt,
+      sort: SortOrder::Desc,
+    }),
+    state: Some(MergeRequestState::Opened),
+    labels: vec![CompactString::new("backend"), CompactString::new("api")],
+    reviewer_username: Some(CompactString::new("reviewer")),
+    draft: Some(false),
+    target_branch: Some(CompactString::new("main")),
+    ..GetProjectMergeRequestListQuery::new(project.clone()).set_context(server.context())
+  };
+  let merge_requests = client.get_project_merge_request_list(&query).await.unwrap();
+  assert_eq!(merge_requests.items[0].reviewers[0].username, "reviewer");
+
+  let query = GetGroupMergeRequestListQuery {
+    author_id: Some(UserId::new(1)),
+    milestone: Some(CompactString::new("v2.8")),
+    ..GetGroupMergeRequestListQuery::new(GroupId::new(7)).set_context(server.context())
+  };
+  client.get_group_merge_request_list(&query).await.unwrap();
+
+  let query = GetMergeRequestListQuery {
+    scope: Some(MergeRequestScope::All),
+    updated_after: Some("2024-06-01T00:00:00Z".parse().unwrap()),
+    ..GetMergeRequestListQuery::new().set_context(server.context())
+  };
+  client.get_merge_request_list(&query).await.unwrap();
+
+  let query = GetMergeRequestQuery {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    merge_request_iid: iid,
+    include_rebase_in_progress: None,
+  };
+  let merge_request = client.get_merge_request(&query).await.unwrap();
+  assert!(merge_request.has_conflicts);
+
+  let command = CreateMergeRequestCommand {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    source_branch: CompactString::new("feature"),
+    target_branch: CompactString::new("main"),
+    title: CompactString::new("Add merge requests"),
+    description: None,
+    assignee_ids: Vec::new(),
+    reviewer_ids: vec![UserId::new(7)],
+    labels: vec![CompactString::new("backend")],
+    milestone_id: None,
+    remove_source_branch: Some(true),
+    squash: None,
+  };
+  client.create_merge_request(&command).await.unwrap();
+
+  let command = UpdateMergeRequestCommand {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    merge_request_iid: iid,
+    title: None,
+    description: None,
+    target_branch: None,
+    assignee_ids: Some(Vec::new()),
+    reviewer_ids: None,
+    labels: None,
+    add_labels: vec![CompactString::new("api")],
+    remove_labels: Vec::new(),
+    milestone_id: None,
+    state_event: Some(MergeRequestStateEvent::Close),
+    remove_source_branch: None,
+    squash: Some(true),
+    discussion_locked: None,
+  };
+  client.update_merge_request(&command).await.unwrap();
+
+  let command = AcceptMergeRequestCommand {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    merge_request_iid: iid,
+    sha: Some(CompactString::new("ed899a2f4b50b4370feeea94676502b42383c746")),
+    squash: None,
+    should_remove_source_branch: None,
+    merge_commit_message: None,
+    squash_commit_message: None,
+    auto_merge: None,
+  };
+  let actual = client.accept_merge_request(&command).await.unwrap_err();
+  assert_eq!(actual.merge_failure(), Some(MergeFailure::Conflict));
+
+  let command = RebaseMergeRequestCommand {
+    context: server.context(),
+    auth: None,
+    project,
+    merge_request_iid: iid,
+    skip_ci: None,
+  };
+  assert!(client.rebase_merge_request(&command).await.unwrap().rebase_in_progress);
+
+  let requests = server.requests();
+  assert_eq!(
+    requests[0].uri.query(),
+    Some("per_page=50&order_by=updated_at&sort=desc&state=opened&labels%5B%5D=backend&labels%5B%5D=api&reviewer_username=reviewer&wip=no&target_branch=main")
+  );
+  assert_eq!(requests[1].uri.query(), Some("author_id=1&milestone=v2.8"));
+  assert_eq!(
+    requests[2].uri.query(),
+    Some("scope=all&updated_after=2024-06-01T00%3A00%3A00Z")
+  );
+  let body: serde_json::Value = serde_json::from_slice(&requests[4].body).unwrap();
+  assert_eq!(
+    body,
+    serde_json::json!({"source_branch": "feature", "target_branch": "main", "title": "Add merge requests", "reviewer_ids": [7], "labels": ["backend"], "remove_source_branch": true})
+  );
+  let body: serde_json::Value = serde_json::from_slice(&requests[5].body).unwrap();
+  assert_eq!(
+    body,
+    serde_json::json!({"assignee_ids": [], "add_labels": ["api"], "state_event": "close", "squash": true})
+  );
+}
+
+/// Only the response to a merge is classified as a `MergeFailure`: a `409` from another endpoint stays a `Conflict`
+async fn merge_failures<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|_, req| match (&req.method, req.uri.path()) {
+    (&Method::PUT, "/api/v4/projects/1/merge_requests/3/merge") => json(
+      StatusCode::CONFLICT,
+      r#"{"message":"SHA does not match HEAD of source branch: ed899a2f4b50b4370feeea94676502b42383c746"}"#,
+    ),
+    (&Method::PUT, "/api/v4/projects/1/merge_requests/3/rebase") => json(
+      StatusCode::CONFLICT,
+      r#"{"message":"Failed to enqueue the rebase operation, possibly due to a long-lived transaction. Try again later."}"#,
+    ),
+    (&Method::PUT, "/api/v4/projects/1/merge_requests/4/rebase" | "/api/v4/projects/1/merge_requests/4/merge") => {
+      json(StatusCode::FORBIDDEN, r#"{"message":"403 Forbidden"}"#)
+    }
+    _ => not_found(),
+  })
+  .await;
+  let command = AcceptMergeRequestCommand {
+    context: server.context(),
+    auth: None,
+    project: ProjectRef::Id(ProjectId::new(1)),
+    merge_request_iid: MergeRequestIid::new(3),
+    sha: Some(CompactString::new("6104942438c14ec7bd21c6cd5bd995272b3faff6")),
+    squash: None,
+    should_remove_source_branch: None,
+    merge_commit_message: None,
+    squash_commit_message: None,
+    auto_merge: None,
+  };
+  let actual = client.accept_merge_request(&command).await.unwrap_err();
+  assert!(matches!(
+    actual,
+    HttpGitlabClientError::MergeFailed(MergeFailure::ShaMismatch, _)
+  ));
+  assert_eq!(actual.status(), Some(StatusCode::CONFLICT));
+
+  let command = RebaseMergeRequestCommand {
+    context: server.context(),
+    auth: None,
+    project: ProjectRef::Id(ProjectId::new(1)),
+    merge_request_iid: MergeRequestIid::new(3),
+    skip_ci: None,
+  };
+  let actual = client.rebase_merge_request(&command).await.unwrap_err();
+  assert!(matches!(
+    actual,
+    HttpGitlabClientError::RebaseFailed(RebaseFailure::Locked, _)
+  ));
+  assert_eq!(actual.status(), Some(StatusCode::CONFLICT));
+  assert_eq!(actual.merge_failure(), None);
+
+  let command = RebaseMergeRequestCommand {
+    merge_request_iid: MergeRequestIid::new(4),
+    ..command
+  };
+  let actual = client.rebase_merge_request(&command).await.unwrap_err();
+  assert_eq!(actual.rebase_failure(), Some(RebaseFailure::Forbidden));
+  assert_eq!(actual.status(), Some(StatusCode::FORBIDDEN));
+
+  let command = AcceptMergeRequestCommand {
+    context: server.context(),
+    auth: None,
+    project: ProjectRef::Id(ProjectId::new(1)),
+    merge_request_iid: MergeRequestIid::new(4),
+    sha: None,
+    squash: None,
+    should_remove_source_branch: None,
+    merge_commit_message: None,
+    squash_commit_message: None,
+    auto_merge: None,
+  };
+  let actual = client.accept_merge_request(&command).await.unwrap_err();
+  assert!(matches!(actual, HttpGitlabClientError::Forbidden(_)));
+  assert_eq!(actual.rebase_failure(), None);
+}
+
+const MERGE_REQUEST_DIFF_VERSION_JSON: &str = r#"{"id":110,"head_commit_sha":"ed899a2f4b50b4370feeea94676502b42383c746","base_commit_sha":"570e7b2abdd848b95f2f578043fc23bd6f6fd24d","start_commit_sha":"570e7b2abdd848b95f2f578043fc23bd6f6fd24d","created_at":"2024-01-01T00:00:00Z","merge_request_id":84,"state":"collected","real_size":"1","patch_id_sha":"d504412d5b6e6739647e752aff8e468dde093f2f"}"#;
+
+async fn read_merge_request_changes<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|_, req| {
+    let merge_request = "/api/v4/projects/group%2Fproject1/merge_requests/3";
+    match (&req.method, req.uri.path().strip_prefix(merge_request)) {
+      (&Method::GET, Some("/commits")) => json(StatusCode::OK, format!("[{COMMIT_JSON}]")),
+      (&Method::GET, Some("/diffs")) => json(StatusCode::OK, format!("[{DIFF_JSON}]")),
+      (&Method::GET, Some("/changes")) => json(
+        StatusCode::OK,
+        format!(
+          r#"{},"changes":[{DIFF_JSON}],"overflow":false}}"#,
+          MERGE_REQUEST_JSON.strip_suffix('}').unwrap()
+        ),
+      ),
+      (&Method::GET, Some("/versions")) => json(StatusCode::OK, format!("[{MERGE_REQUEST_DIFF_VERSION_JSON}]")),
+      (&Method::GET, Some("/versions/110")) => json(
+        StatusCode::OK,
+        format!(
+          r#"{},"commits":[{COMMIT_JSON}],"diffs":[{DIFF_JSON}]}}"#,
+          MERGE_REQUEST_DIFF_VERSION_JSON.strip_suffix('}').unwrap()
+        ),
+      ),
+      _ => not_found(),
+    }
+  })
+  .await;
+  let project = ProjectRef::Slug(ProjectSlug::new(CompactString::new("group/project1")));
+  let iid = MergeRequestIid::new(3);
+
+  let query = GetMergeRequestCommitListQuery {
+    per_page: NonZeroU8::new(100),
+    ..GetMergeRequestCommitListQuery::new(project.clone(), iid).set_context(server.context())
+  };
+  let commits = client.get_merge_request_commit_list(&query).await.unwrap();
+  assert_eq!(commits.items[0].title, "Bump version");
+
+  let query = GetMergeRequestDiffListQuery {
+    unidiff: Some(true),
+    ..GetMergeRequestDiffListQuery::new(project.clone(), iid).set_context(server.context())
+  };
+  let diffs = client.get_merge_request_diff_list(&query).await.unwrap();
+  assert_eq!(diffs.items[0].new_path, "VERSION");
+
+  let query = GetMergeRequestChangesQuery {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    merge_request_iid: iid,
+    access_raw_diffs: Some(true),
+    unidiff: None,
+  };
+  let changes = client.get_merge_request_changes(&query).await.unwrap();
+  assert_eq!(changes.merge_request.iid, iid);
+  assert_eq!(changes.changes[0].diff, "@@ -1 +1 @@\n-1.2.3\n+1.2.4\n");
+  assert!(!changes.overflow);
+
+  let query = GetMergeRequestDiffVersionListQuery::new(project.clone(), iid).set_context(server.context());
+  let versions = client.get_merge_request_diff_version_list(&query).await.unwrap();
+  assert_eq!(versions.items[0].id, MergeRequestDiffVersionId::new(110));
+  assert_eq!(versions.items[0].diffs, None);
+
+  let query = GetMergeRequestDiffVersionQuery {
+    context: server.context(),
+    auth: None,
+    project,
+    merge_request_iid: iid,
+    version_id: versions.items[0].id,
+    unidiff: None,
+  };
+  let version = client.get_merge_request_diff_version(&query).await.unwrap();
+  assert_eq!(version.commits.unwrap()[0].short_id, "ed899a2f");
+  assert_eq!(version.diffs.unwrap()[0].new_path, "VERSION");
+
+  let requests = server.requests();
+  assert_eq!(requests[0].uri.query(), Some("per_page=100"));
+  assert_eq!(requests[1].uri.query(), Some("unidiff=true"));
+  assert_eq!(requests[2].uri.query(), Some("access_raw_diffs=true"));
+}
+
+const APPROVAL_RULE_JSON: &str = r#"{"id":1,"name":"Security","rule_type":"regular","eligible_approvers":[{"id":7,"username":"reviewer","name":"Reviewer","state":"active","locked":false,"avatar_url":null,"web_url":"https://gitlab.example.com/reviewer"}],"approvals_required":1,"users":[{"id":7,"username":"reviewer","name":"Reviewer","state":"active","locked":false,"avatar_url":null,"web_url":"https://gitlab.example.com/reviewer"}],"groups":[],"contains_hidden_groups":false,"protected_branches":[],"applies_to_all_protected_branches":false}"#;
+
+const APPROVALS_JSON: &str = r#"{"id":84,"iid":3,"project_id":1,"title":"Add merge requests","state":"opened","approved":true,"approvals_required":1,"approvals_left":0,"require_password_to_approve":false,"approved_by":[{"user":{"id":7,"username":"reviewer","name":"Reviewer","state":"active","locked":false,"avatar_url":null,"web_url":"https://gitlab.example.com/reviewer"}}],"suggested_approvers":[],"approvers":[],"approver_groups":[],"user_has_approved":true,"user_can_approve":false}"#;
+
+async fn manage_approvals<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|_, req| {
+    let project = "/api/v4/projects/group%2Fproject1";
+    match (&req.method, req.uri.path().strip_prefix(project)) {
+      (&Method::GET, Some("/approval_rules" | "/merge_requests/3/approval_rules")) => {
+        json(StatusCode::OK, format!("[{APPROVAL_RULE_JSON}]"))
+      }
+      (&Method::GET | &Method::PUT, Some("/approval_rules/1" | "/merge_requests/3/approval_rules/1")) => {
+        json(StatusCode::OK, APPROVAL_RULE_JSON)
+      }
+      (&Method::POST, Some("/approval_rules" | "/merge_requests/3/approval_rules")) => {
+        json(StatusCode::CREATED, APPROVAL_RULE_JSON)
+      }
+      (&Method::DELETE, Some("/approval_rules/1" | "/merge_requests/3/approval_rules/1")) => {
+        Response::builder().status(StatusCode::NO_CONTENT).body(Full::new(Bytes::new())).unwrap()
+      }
+      (&Method::GET, Some("/merge_requests/3/approvals")) => json(StatusCode::OK, APPROVALS_JSON),
+      (&Method::POST, Some("/merge_requests/3/approve" | "/merge_requests/3/unapprove")) => {
+        json(StatusCode::CREATED, APPROVALS_JSON)
+      }
+      (&Method::PUT, Some("/merge_requests/3/reset_approvals")) => {
+        Response::builder().status(StatusCode::ACCEPTED).body(Full::new(Bytes::new())).unwrap()
+      }
+      (&Method::GET, Some("/merge_requests/3/approval_state")) => json(
+        StatusCode::OK,
+        format!(
+          r#"{{"approval_rules_overwritten":false,"rules":[{},"source_rule":null,"approved_by":[],"approved":false}}]}}"#,
+          APPROVAL_RULE_JSON.strip_suffix('}').unwrap()
+        ),
+      ),
+      _ => not_found(),
+    }
+  })
+  .await;
+  let project = ProjectRef::Slug(ProjectSlug::new(CompactString::new("group/project1")));
+  let iid = MergeRequestIid::new(3);
+  let rule_id = ApprovalRuleId::new(1);
+
+  let query = GetProjectApprovalRuleListQuery::new(project.clone()).set_context(server.context());
+  let rules = client.get_project_approval_rule_list(&query).await.unwrap();
+  assert_eq!(rules.items[0].rule_type, ApprovalRuleType::Regular);
+
+  let query = GetProjectApprovalRuleQuery {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    approval_rule_id: rule_id,
+  };
+  client.get_project_approval_rule(&query).await.unwrap();
+
+  let command = CreateProjectApprovalRuleCommand {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    name: CompactString::new("Security"),
+    approvals_required: 1,
+    rule_type: None,
+    user_ids: vec![UserId::new(7)],
+    group_ids: vec![GroupId::new(5)],
+    protected_branch_ids: Vec::new(),
+    applies_to_all_protected_branches: Some(true),
+  };
+  client.create_project_approval_rule(&command).await.unwrap();
+
+  let command = UpdateProjectApprovalRuleCommand {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    approval_rule_id: rule_id,
+    name: None,
+    approvals_required: Some(2),
+    user_ids: None,
+    group_ids: Some(Vec::new()),
+    protected_branch_ids: None,
+    applies_to_all_protected_branches: None,
+    remove_hidden_groups: None,
+  };
+  client.update_project_approval_rule(&command).await.unwrap();
+
+  let command = DeleteProjectApprovalRuleCommand {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    approval_rule_id: rule_id,
+  };
+  client.delete_project_approval_rule(&command).await.unwrap();
+
+  let query = GetMergeRequestApprovalRuleListQuery::new(project.clone(), iid).set_context(server.context());
+  let rules = client.get_merge_request_approval_rule_list(&query).await.unwrap();
+  assert_eq!(rules.items[0].users[0].username, "reviewer");
+
+  let query = GetMergeRequestApprovalRuleQuery {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    merge_request_iid: iid,
+    approval_rule_id: rule_id,
+  };
+  client.get_merge_request_approval_rule(&query).await.unwrap();
+
+  let command = CreateMergeRequestApprovalRuleCommand {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    merge_request_iid: iid,
+    name: CompactString::new("Security"),
+    approvals_required: 1,
+    approval_project_rule_id: Some(rule_id),
+    user_ids: Vec::new(),
+    group_ids: Vec::new(),
+  };
+  client.create_merge_request_approval_rule(&command).await.unwrap();
+
+  let command = UpdateMergeRequestApprovalRuleCommand {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    merge_request_iid: iid,
+    approval_rule_id: rule_id,
+    name: Some(CompactString::new("Security review")),
+    approvals_required: None,
+    user_ids: Some(vec![UserId::new(7)]),
+    group_ids: None,
+    remove_hidden_groups: None,
+  };
+  client.update_merge_request_approval_rule(&command).await.unwrap();
+
+  let command = DeleteMergeRequestApprovalRuleCommand {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    merge_request_iid: iid,
+    approval_rule_id: rule_id,
+  };
+  client.delete_merge_request_approval_rule(&command).await.unwrap();
+
+  let query = GetMergeRequestApprovalsQuery {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    merge_request_iid: iid,
+  };
+  let approvals = client.get_merge_request_approvals(&query).await.unwrap();
+  assert_eq!(approvals.approvals_left, Some(0));
+
+  let query = GetMergeRequestApprovalStateQuery {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    merge_request_iid: iid,
+  };
+  let state = client.get_merge_request_approval_state(&query).await.unwrap();
+  assert!(!state.rules[0].approved);
+
+  let command = ApproveMergeRequestCommand {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    merge_request_iid: iid,
+    sha: Some(CompactString::new("ed899a2f4b50b4370feeea94676502b42383c746")),
+  };
+  let approvals = client.approve_merge_request(&command).await.unwrap();
+  assert_eq!(approvals.approved_by[0].user.id, UserId::new(7));
+
+  let command = UnapproveMergeRequestCommand {
+    context: server.context(),
+    auth: None,
+    project: project.clone(),
+    merge_request_iid: iid,
+  };
+  client.unapprove_merge_request(&command).await.unwrap();
+
+  let command = ResetMergeRequestApprovalsCommand {
+    context: server.context(),
+    auth: None,
+    project,
+    merge_request_iid: iid,
+  };
+  client.reset_merge_request_approvals(&command).await.unwrap();
+
+  let requests = server.requests();
+  let body: serde_json::Value = serde_json::from_slice(&requests[2].body).unwrap();
+  assert_eq!(
+    body,
+    serde_json::json!({"name": "Security", "approvals_required": 1, "user_ids": [7], "group_ids": [5], "applies_to_all_protected_branches": true})
+  );
+  let body: serde_json::Value = serde_json::from_slice(&requests[3].body).unwrap();
+  assert_eq!(body, serde_json::json!({"approvals_required": 2, "group_ids": []}));
+  let body: serde_json::Value = serde_json::from_slice(&requests[7].body).unwrap();
+  assert_eq!(
+    body,
+    serde_json::json!({"name": "Security", "approvals_required": 1, "approval_project_rule_id": 1})
+  );
+  let body: serde_json::Value = serde_json::from_slice(&requests[8].body).unwrap();
+  assert_eq!(body, serde_json::json!({"name": "Security review", "user_ids": [7]}));
+  let body: serde_json::Value = serde_json::from_slice(&requests[12].body).unwrap();
+  assert_eq!(
+    body,
+    serde_json::json!({"sha": "ed899a2f4b50b4370feeea94676502b42383c746"})
+  );
+}
+
+const NOTE_JSON: &str = r#"{"id":1128,"type":"DiffNote","body":"Off by one?","attachment":null,"author":{"id":1,"username":"admin","name":"Administrator","state":"active","locked":false,"avatar_url":null,"web_url":"https://gitlab.example.com/admin"},"created_at":"2024-01-01T00:00:00.000Z","updated_at":"2024-01-01T00:00:00.000Z","system":false,"noteable_id":84,"noteable_type":"MergeRequest","project_id":1,"commit_id":null,"position":{"base_sha":"b5d6e7b1613fca24d250fa8e5bc7bcfb4a6d1a5d","start_sha":"b5d6e7b1613fca24d250fa8e5bc7bcfb4a6d1a5d","head_sha":"ed899a2f4b50b4370feeea94676502b42383c746","old_path":"VERSION","new_path":"VERSION","position_type":"text","old_line":null,"new_line":1,"line_range":null},"resolvable":true,"resolved":false,"resolved_by":null,"resolved_at":null,"confidential":false,"internal":false,"noteable_iid":3,"commands_changes":{}}"#;
+
+async fn manage_discussions<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|_, req| {
+    let discussion = format!(
+      r#"{{"id":"6a9c1750b37d513a43987b574953fceb50b03ce7","individual_note":false,"notes":[{NOTE_JSON}]}}"#
+    );
+    match (&req.method, req.uri.path()) {
+      (&Method::GET, "/api/v4/projects/group%2Fproject1/issues/5/notes") => {
+        json(StatusCode::OK, format!("[{NOTE_JSON}]"))
+      }
+      (&Method::GET, "/api/v4/groups/7/epics/2/notes/1128") => json(StatusCode::OK, NOTE_JSON),
+      (&Method::POST, "/api/v4/projects/1/snippets/9/notes") => json(StatusCode::CREATED, NOTE_JSON),
+      (
+        &Method::PUT,
+        "/api/v4/projects/1/repository/commits/ed899a2f/discussions/6a9c1750b37d513a43987b574953fceb50b03ce7/notes/1128",
+      ) => json(StatusCode::OK, NOTE_JSON),
+      (&Method::DELETE, "/api/v4/projects/1/merge_requests/3/notes/1128") => Response::builder()
+        .status(StatusCode::NO_CONTENT)
+        .body(Full::new(Bytes::new()))
+        .unwrap(),
+      (&Method::GET, "/api/v4/projects/1/merge_requests/3/discussions") => {
+        json(StatusCode::OK, format!("[{discussion}]"))
+      }
+      (&Method::POST, "/api/v4/projects/1/merge_requests/3/discussions") => json(StatusCode::CREATED, discussion),
+      (&Method::PUT, "/api/v4/projects/1/merge_requests/3/discussions/6a9c1750b37d513a43987b574953fceb50b03ce7") => {
+        json(StatusCode::OK, discussion)
+      }
+      _ => not_found(),
+    }
+  })
+  .await;
+  let merge_request = NoteParentRef::MergeRequest {
+    project: ProjectRef::Id(ProjectId::new(1)),
+    merge_request_iid: MergeRequestIid::new(3),
+  };
+  let note_id = NoteId::new(1128);
+
+  let query = GetNoteListQuery {
+    sort: Some(SortOrder::Asc),
+    order_by: Some(NoteOrder::UpdatedAt),
+    ..GetNoteListQuery::new(NoteParentRef::Issue {
+      project: ProjectRef::Slug(ProjectSlug::new(CompactString::new("group/project1"))),
+      issue_iid: IssueIid::new(5),
+    })
+    .set_context(server.context())
+  };
+  let notes = client.get_note_list(&query).await.unwrap();
+  assert_eq!(notes.items[0].note_type, Some(NoteType::DiffNote));
+
+  let query = GetNoteQuery {
+    context: server.context(),
+    auth: None,
+    noteable: NoteParentRef::Epic {
+      group: GroupId::new(7),
+      epic_iid: EpicIid::new(2),
+    },
+    note_id,
+  };
+  client.get_note(&query).await.unwrap();
+
+  let command = CreateNoteCommand {
+    context: server.context(),
+    auth: None,
+    location: NoteLocation::Parent(NoteParentRef::Snippet {
+      project: ProjectRef::Id(ProjectId::new(1)),
+      snippet_id: SnippetId::new(9),
+    }),
+    body: CompactString::new("Thanks!"),
+    internal: Some(true),
+  };
+  client.create_note(&command).await.unwrap();
+
+  let command = UpdateNoteCommand {
+    context: server.context(),
+    auth: None,
+    location: NoteLocation::Discussion {
+      noteable: NoteableRef::Commit {
+        project: ProjectRef::Id(ProjectId::new(1)),
+        sha: CompactString::new("ed899a2f"),
+      },
+      discussion_id: CompactString::new("6a9c1750b37d513a43987b574953fceb50b03ce7"),
+    },
+    note_id,
+    body: CompactString::new("Off by two?"),
+  };
+  client.update_note(&command).await.unwrap();
+
+  let command = DeleteNoteCommand {
+    context: server.context(),
+    auth: None,
+    location: NoteLocation::Parent(merge_request.clone()),
+    note_id,
+  };
+  client.delete_note(&command).await.unwrap();
+
+  let merge_request = NoteableRef::from(merge_request);
+  let query = GetDiscussionListQuery::new(merge_request.clone()).set_context(server.context());
+  let discussions = client.get_discussion_list(&query).await.unwrap();
+  let position = discussions.items[0].notes[0].position.clone().unwrap();
+  assert_eq!(position.new_line, Some(1));
+
+  let command = CreateDiscussionCommand {
+    context: server.context(),
+    auth: None,
+    noteable: merge_request.clone(),
+    body: CompactString::new("Off by one?"),
+    position: Some(position),
+  };
+  client.create_discussion(&command).await.unwrap();
+
+  let command = ResolveDiscussionCommand {
+    context: server.context(),
+    auth: None,
+    noteable: merge_request,
+    discussion_id: discussions.items[0].id.clone(),
+    resolved: true,
+  };
+  client.resolve_discussion(&command).await.unwrap();
+
+  let requests = server.requests();
+  assert_eq!(requests[0].uri.query(), Some("sort=asc&order_by=updated_at"));
+  let body: serde_json::Value = serde_json::from_slice(&requests[2].body).unwrap();
+  assert_eq!(body, serde_json::json!({"body": "Thanks!", "internal": true}));
+  let body: serde_json::Value = serde_json::from_slice(&requests[3].body).unwrap();
+  assert_eq!(body, serde_json::json!({"body": "Off by two?"}));
+  let body: serde_json::Value = serde_json::from_slice(&requests[6].body).unwrap();
+  assert_eq!(
+    body,
+    serde_json::json!({
+      "body": "Off by one?",
+      "position": {
+        "base_sha": "b5d6e7b1613fca24d250fa8e5bc7bcfb4a6d1a5d",
+        "start_sha": "b5d6e7b1613fca24d250fa8e5bc7bcfb4a6d1a5d",
+        "head_sha": "ed899a2f4b50b4370feeea94676502b42383c746",
+        "position_type": "text",
+        "old_path": "VERSION",
+        "new_path": "VERSION",
+        "new_line": 1
+      }
+    })
+  );
+  let body: serde_json::Value = serde_json::from_slice(&requests[7].body).unwrap();
+  assert_eq!(body, serde_json::json!({"resolved": true}));
+}
+
+async fn publish_and_get_package_file<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|_, req| match (&req.method, req.uri.path(), req.uri.query()) {
+    (
+      &Method::PUT,
+      "/api/v4/projects/1/packages/generic/hello/1.0.0/hello.txt",
+      Some("status=default&select=package_file"),
+    ) => json(StatusCode::CREATED, PACKAGE_FILE_JSON),
+    (&Method::GET, "/api/v4/projects/1/packages/generic/hello/1.0.0/hello.txt", None) => {
+      Response::new(Full::new(Bytes::from_static(b"Hello, World!")))
+    }
+    _ => not_found(),
+  })
+  .await;
+  let command = PublishPackageFileCommand {
+    context: server.context(),
+    auth: None,
+    project: ProjectRef::Id(ProjectId::new(1)),
+    package_name: CompactString::new("hello"),
+    package_version: CompactString::new("1.0.0"),
+    filename: CompactString::new("hello.txt"),
+    status: InputPackageStatus::Default,
+    data: b"Hello, World!".to_vec(),
+  };
+  let actual = client.publish_package_file(&command).await.unwrap();
+  assert_eq!(actual.file_name, "hello.txt");
+
+  let query = GetPackageFileQuery {
+    context: server.context(),
+    auth: None,
+    project: ProjectRef::Id(ProjectId::new(1)),
+    package_name: CompactString::new("hello"),
+    package_version: CompactString::new("1.0.0"),
+    filename: CompactString::new("hello.txt"),
+  };
+  let actual = client.get_package_file(&query).await.unwrap();
+  assert_eq!(actual, Bytes::from_static(b"Hello, World!"));
+
+  let requests = server.requests();
+  assert_eq!(requests[0].body, Bytes::from_static(b"Hello, World!"));
+}
+
+async fn not_found_error<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|_, _| {
+    let mut res = not_found();
+    res.headers_mut().insert("x-request-id", "01JXQ5Z5Y8".parse().unwrap());
+    res
+  })
+  .await;
+  let query = GetProjectQuery::<_>::new(ProjectRef::Id(ProjectId::new(404))).set_context(server.context());
+
+  let actual = client.get_project(&query).await.unwrap_err();
+  let res = match &actual {
+    HttpGitlabClientError::NotFound(res) => res,
+    e => panic!("unexpected error: {e:?}"),
+  };
+  assert_eq!(res.method, Method::GET);
+  assert_eq!(res.uri.path(), "/api/v4/projects/404");
+  assert_eq!(res.status, StatusCode::NOT_FOUND);
+  assert_eq!(res.request_id.as_deref(), Some("01JXQ5Z5Y8"));
+  assert_eq!(
+    res.error.as_ref().and_then(|e| e.message.as_ref()),
+    Some(&GitlabErrorMessage::Text(CompactString::new("404 Not Found")))
+  );
+}
+
+async fn rate_limited_error<C: GitlabClient<Cx, Error = HttpGitlabClientError>>(client: &mut C) {
+  let server = StandIn::start(|_, _| {
+    let mut res = json(StatusCode::TOO_MANY_REQUESTS, r#"{"message":"Retry later"}"#);
+    res.headers_mut().insert("retry-after", "7".parse().unwrap());
+    res
+  })
+  .await;
+  let query = GetProjectQuery::<_>::new(ProjectRef::Id(ProjectId::new(1))).set_context(server.context());
+
+  let actual = client.get_project(&query).await.unwrap_err();
+  match actual {
+    HttpGitlabClientError::RateLimited(res, rate_limit) => {
+      assert_eq!(res.status, StatusCode::TOO_MANY_REQUESTS);
+      assert_eq!(rate_limit.retry_after, Some(Duration::from_secs(7)));
+    }
+    e => panic!("unexpected error: {e:?}"),
+  }
+}
+
+/// Upload a package file streamed from an `UploadBody`, with a known then an unknown length
+async fn publish_streamed_package_file<C>(client: &mut C)
+where
+  C: Service<
+    PublishPackageFileCommand<Cx, CompactString, UploadBody>,
+    Response = GenericPackageFile,
+    Error = HttpGitlabClientError,
+  >,
+{
+  let server = StandIn::start(|_, req| match (&req.method, req.uri.path()) {
+    (&Method::PUT, "/api/v4/projects/1/packages/generic/hello/1.0.0/hello.txt") => {
+      json(StatusCode::CREATED, PACKAGE_FILE_JSON)
+    }
+    _ => not_found(),
+  })
+  .await;
+  let chunks = || futures::stream::iter([Bytes::from_static(b"Hello, "), Bytes::from_static(b"World!")]);
+  let sent = Arc::new(Mutex::new(Vec::new()));
+  for len in [Some(13), None] {
+    let data = UploadBody::from_stream(chunks(), len).with_progress({
+      let sent = Arc::clone(&sent);
+      move |progress| sent.lock().unwrap().push(progress.sent)
+    });
+    let command = PublishPackageFileCommand {
+      context: server.context(),
+      auth: None,
+      project: ProjectRef::Id(ProjectId::new(1)),
+      package_name: CompactString::new("hello"),
+      package_version: CompactString::new("1.0.0"),
+      filename: CompactString::new("hello.txt"),
+      status: InputPackageStatus::Default,
+      data,
+    };
+    futures::future::poll_fn(|cx| {
+      Service::<PublishPackageFileCommand<Cx, CompactString, UploadBody>>::poll_ready(client, cx)
+    })
+    .await
+    .unwrap();
+    let actual = client.call(command).await.unwrap();
+    assert_eq!(actual.file_name, "hello.txt");
+  }
+  assert_eq!(*sent.lock().unwrap(), [7, 13, 7, 13]);
+
+  let requests = server.requests();
+  assert_eq!(requests[0].headers["content-length"], "13");
+  assert_eq!(requests[0].body, Bytes::from_static(b"Hello, World!"));
+  assert_eq!(requests[1].headers["transfer-encoding"], "chunked");
+  assert_eq!(requests[1].body, Bytes::from_static(b"Hello, World!"));
+}
+
+/// Download a package file as a stream, verifying its SHA-256 digest
+async fn get_package_file_stream<C>(client: &mut C)
+where
+  C: for<'req> Service<&'req GetPackageFileStreamQuery<Cx>, Response = DownloadStream, Error = HttpGitlabClientError>,
+{
+  let server = StandIn::start(|_, req| match (&req.method, req.uri.path()) {
+    (&Method::GET, "/api/v4/projects/1/packages/generic/hello/1.0.0/hello.txt") => {
+      Response::new(Full::new(Bytes::from_static(b"Hello, World!")))
+    }
+    _ => not_found(),
+  })
+  .await;
+  let query = |filename: &str, sha256: &str| GetPackageFileStreamQuery {
+    context: server.context(),
+    auth: None,
+    project: ProjectRef::Id(ProjectId::new(1)),
+    package_name: CompactString::new("hello"),
+    package_version: CompactString::new("1.0.0"),
+    filename: CompactString::new(filename),
+    sha256: Some(CompactString::new(sha256)),
+  };
+  let hello_sha256 = "dffd6021bb2bd5b0af676290809ec3a53191dd81c7f70a4b28688a362182986f";
+  let other_sha256 = "0ca093111f402faa55be1cd71006270644b58619eb0c2408b97b7d24bb70dd09";
+
+  let stream = client.call(&query("hello.txt", hello_sha256)).await.unwrap();
+  assert_eq!(stream.len(), Some(13));
+  let actual: Vec<Bytes> = stream.try_collect().await.unwrap();
+  assert_eq!(actual.concat(), b"Hello, World!");
+
+  let stream = client.call(&query("hello.txt", other_sha256)).await.unwrap();
+  let actual = stream.try_collect::<Vec<Bytes>>().await.unwrap_err();
+  assert_eq!(
+    actual,
+    HttpGitlabClientError::ChecksumMismatch(CompactString::new(other_sha256), CompactString::new(hello_sha256))
+  );
+
+  let actual = client.call(&query("missing.txt", hello_sha256)).await.unwrap_err();
+  assert!(matches!(actual, HttpGitlabClientError::NotFound(_)));
+}
+
+/// Download a raw repository file as a stream
+async fn get_raw_repository_file_stream<C>(client: &mut C)
+where
+  C: for<'req> Service<
+    &'req GetRawRepositoryFileStreamQuery<Cx>,
+    Response = DownloadStream,
+    Error = HttpGitlabClientError,
+  >,
+{
+  let server = StandIn::start(|_, req| match (&req.method, req.uri.path()) {
+    (&Method::GET, "/api/v4/projects/1/repository/files/config%2FVERSION/raw") => {
+      Response::new(Full::new(Bytes::from_static(b"1.2.3\n")))
+    }
+    _ => not_found(),
+  })
+  .await;
+  let query = GetRawRepositoryFileStreamQuery {
+    context: server.context(),
+    auth: None,
+    project: ProjectRef::Id(ProjectId::new(1)),
+    file_path: CompactString::new("config/VERSION"),
+    r#ref: Some(CompactString::new("v1.2.3")),
+    lfs: Some(true),
+  };
+  let stream = client.call(&query).await.unwrap();
+  assert_eq!(stream.len(), Some(6));
+  let actual: Vec<Bytes> = stream.try_collect().await.unwrap();
+  assert_eq!(actual.concat(), b"1.2.3\n");
+
+  let requests = server.requests();
+  assert_eq!(requests[0].uri.query(), Some("ref=v1.2.3&lfs=true"));
+}
+
+/// HTTP backend, streaming its request bodies so it also supports package file uploads from an `UploadBody`
+fn http_client() -> HttpGitlabClient<hyper_util::client::legacy::Client<HttpConnector, UploadBody>, UploadBody> {
+  HttpGitlabClient::with_request_body(hyper_util::client::legacy::Client::builder(TokioExecutor::new()).build_http())
+}
+
+fn reqwest_client() -> ReqwestGitlabClient<reqwest::Client> {
+  ReqwestGitlabClient::new(reqwest::Client::new())
+}
+
+/// Run each test function against each backend, in a module per backend
+macro_rules! backend_tests {
+  ([$($backend:ident => $client:expr),* $(,)?], $tests:tt) => {
+    $(backend_tests!(@backend $backend, $client, $tests);)*
+  };
+  (@backend $backend:ident, $client:expr, [$($name:ident),* $(,)?]) => {
+    mod $backend {
+      use super::*;
+
+      $(
+        #[tokio::test]
+        async fn $name() {
+          let mut client = $client;
+          super::$name(&mut client).await;
+        }
+      )*
+    }
+  };
+}
+
+backend_tests!(
+  [http_backend => http_client(), reqwest_backend => reqwest_client()],
+  [
+    get_project,
+    get_project_list_pages,
+    get_project_package_list_pages,
+    get_package_file_list_pages,
+    get_project_release,
+    create_release,
+    update_release,
+    delete_release,
+    manage_release_links,
+    manage_milestones,
+    manage_repository_files,
+    create_commit,
+    read_commits,
+    manage_branches,
+    manage_tags,
+    manage_merge_requests,
+    merge_failures,
+    read_merge_request_changes,
+    manage_approvals,
+    manage_discussions,
+    publish_and_get_package_file,
+    publish_streamed_package_file,
+    get_package_file_stream,
+    get_raw_repository_file_stream,
+    not_found_error,
+    rate_limited_error,
+  ]
+);
@@ -0,0 +1,237 @@
+use crate::{context, create_commit, Fixture};
+use compact_str::{format_compact, CompactString};
+use gitlab_client::client::fake::FAKE_USER_ID;
+use gitlab_client::command::approve_merge_request::ApproveMergeRequestCommand;
+use gitlab_client::command::create_commit::CreateCommitCommand;
+use gitlab_client::command::create_merge_request::CreateMergeRequestCommand;
+use gitlab_client::command::create_merge_request_approval_rule::CreateMergeRequestApprovalRuleCommand;
+use gitlab_client::command::create_project_approval_rule::CreateProjectApprovalRuleCommand;
+use gitlab_client::command::delete_merge_request_approval_rule::DeleteMergeRequestApprovalRuleCommand;
+use gitlab_client::command::delete_project_approval_rule::DeleteProjectApprovalRuleCommand;
+use gitlab_client::command::reset_merge_request_approvals::ResetMergeRequestApprovalsCommand;
+use gitlab_client::command::unapprove_merge_request::UnapproveMergeRequestCommand;
+use gitlab_client::command::update_merge_request_approval_rule::UpdateMergeRequestApprovalRuleCommand;
+use gitlab_client::command::update_project_approval_rule::UpdateProjectApprovalRuleCommand;
+use gitlab_client::common::approval::{ApprovalRuleSource, ApprovalRuleType};
+use gitlab_client::common::commit::{CommitAction, CommitContent};
+use gitlab_client::common::project::ProjectRef;
+use gitlab_client::common::user::UserId;
+use gitlab_client::query::get_merge_request_approval_rule_list::GetMergeRequestApprovalRuleListQuery;
+use gitlab_client::query::get_merge_request_approval_state::GetMergeRequestApprovalStateQuery;
+use gitlab_client::query::get_merge_request_approvals::GetMergeRequestApprovalsQuery;
+use gitlab_client::query::get_project_approval_rule::GetProjectApprovalRuleQuery;
+use gitlab_client::{ApprovalClient, CommitClient, MergeRequestClient};
+use http::StatusCode;
+
+#[test]
+fn manage_approvals() {
+  let Fixture { mut client, project } = Fixture::new();
+  let security = client.create_group("group/security");
+  for (branch, start_branch, file_path) in [("main", None, "README.md"), ("feature", Some("main"), "src/lib.rs")] {
+    futures::executor::block_on(client.create_commit(&CreateCommitCommand {
+      commit_message: format_compact!("Add {file_path}"),
+      start_branch: start_branch.map(CompactString::new),
+      ..create_commit(
+        project,
+        branch,
+        vec![CommitAction::Create {
+          file_path: CompactString::new(file_path),
+          content: CommitContent::Text(CompactString::new("\n")),
+          execute_filemode: None,
+        }],
+      )
+    }))
+    .unwrap();
+  }
+  let create_rule =
+    |name: &str, rule_type: ApprovalRuleType, approvals_required: u64| CreateProjectApprovalRuleCommand {
+      context: context(),
+      auth: None,
+      project: ProjectRef::Id(project),
+      name: CompactString::new(name),
+      approvals_required,
+      rule_type: Some(rule_type),
+      user_ids: vec![FAKE_USER_ID],
+      group_ids: vec![security],
+      protected_branch_ids: Vec::new(),
+      applies_to_all_protected_branches: None,
+    };
+  let security_rule = futures::executor::block_on(client.create_project_approval_rule(&create_rule(
+    "Security",
+    ApprovalRuleType::Regular,
+    1,
+  )))
+  .unwrap();
+  assert_eq!(security_rule.groups[0].full_name, "group / security");
+  futures::executor::block_on(client.create_project_approval_rule(&create_rule(
+    "All members",
+    ApprovalRuleType::AnyApprover,
+    2,
+  )))
+  .unwrap();
+  let actual = futures::executor::block_on(client.create_project_approval_rule(&create_rule(
+    "Security",
+    ApprovalRuleType::Regular,
+    1,
+  )))
+  .unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::BAD_REQUEST));
+
+  let merge_request = futures::executor::block_on(client.create_merge_request(&CreateMergeRequestCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    source_branch: CompactString::new("feature"),
+    target_branch: CompactString::new("main"),
+    title: CompactString::new("Add lib"),
+    description: None,
+    assignee_ids: Vec::new(),
+    reviewer_ids: Vec::new(),
+    labels: Vec::new(),
+    milestone_id: None,
+    remove_source_branch: None,
+    squash: None,
+  }))
+  .unwrap();
+  let iid = merge_request.iid;
+  let query = GetMergeRequestApprovalRuleListQuery::new(ProjectRef::Id(project), iid).set_context(context());
+  let rules = futures::executor::block_on(client.get_merge_request_approval_rule_list(&query)).unwrap();
+  assert_eq!(
+    Vec::from_iter(rules.items.iter().map(|rule| (rule.name.as_str(), rule.source_rule))),
+    vec![
+      ("Security", Some(ApprovalRuleSource { approvals_required: 1 })),
+      ("All members", Some(ApprovalRuleSource { approvals_required: 2 })),
+    ]
+  );
+
+  let approve = |sha: Option<&str>| ApproveMergeRequestCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    merge_request_iid: iid,
+    sha: sha.map(CompactString::new),
+  };
+  let actual = futures::executor::block_on(client.approve_merge_request(&approve(Some("0000")))).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::CONFLICT));
+  let approvals =
+    futures::executor::block_on(client.approve_merge_request(&approve(merge_request.sha.as_deref()))).unwrap();
+  assert!(!approvals.approved);
+  assert_eq!(approvals.approvals_left, Some(1));
+  assert!(approvals.user_has_approved);
+  let actual = futures::executor::block_on(client.approve_merge_request(&approve(None))).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::UNAUTHORIZED));
+
+  let all_members = rules.items[1].id;
+  let rule = futures::executor::block_on(client.update_merge_request_approval_rule(
+    &UpdateMergeRequestApprovalRuleCommand {
+      context: context(),
+      auth: None,
+      project: ProjectRef::Id(project),
+      merge_request_iid: iid,
+      approval_rule_id: all_members,
+      name: None,
+      approvals_required: Some(1),
+      user_ids: None,
+      group_ids: None,
+      remove_hidden_groups: None,
+    },
+  ))
+  .unwrap();
+  assert!(rule.overridden);
+  let query = GetMergeRequestApprovalStateQuery {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    merge_request_iid: iid,
+  };
+  let state = futures::executor::block_on(client.get_merge_request_approval_state(&query)).unwrap();
+  assert!(state.approval_rules_overwritten);
+  assert!(state.rules.iter().all(|rule| rule.approved));
+
+  let unapprove = UnapproveMergeRequestCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    merge_request_iid: iid,
+  };
+  let approvals = futures::executor::block_on(client.unapprove_merge_request(&unapprove)).unwrap();
+  assert_eq!(approvals.approvals_left, Some(2));
+  let actual = futures::executor::block_on(client.unapprove_merge_request(&unapprove)).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::NOT_FOUND));
+  futures::executor::block_on(client.approve_merge_request(&approve(None))).unwrap();
+  futures::executor::block_on(
+    client.reset_merge_request_approvals(&ResetMergeRequestApprovalsCommand {
+      context: context(),
+      auth: None,
+      project: ProjectRef::Id(project),
+      merge_request_iid: iid,
+    }),
+  )
+  .unwrap();
+  let query = GetMergeRequestApprovalsQuery {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    merge_request_iid: iid,
+  };
+  let approvals = futures::executor::block_on(client.get_merge_request_approvals(&query)).unwrap();
+  assert!(approvals.approved_by.is_empty());
+
+  let rule = futures::executor::block_on(client.create_merge_request_approval_rule(
+    &CreateMergeRequestApprovalRuleCommand {
+      context: context(),
+      auth: None,
+      project: ProjectRef::Id(project),
+      merge_request_iid: iid,
+      name: CompactString::new("Security review"),
+      approvals_required: 2,
+      approval_project_rule_id: Some(security_rule.id),
+      user_ids: Vec::new(),
+      group_ids: Vec::new(),
+    },
+  ))
+  .unwrap();
+  assert_eq!(rule.users, security_rule.users);
+  let delete = DeleteMergeRequestApprovalRuleCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    merge_request_iid: iid,
+    approval_rule_id: rule.id,
+  };
+  futures::executor::block_on(client.delete_merge_request_approval_rule(&delete)).unwrap();
+  let actual = futures::executor::block_on(client.delete_merge_request_approval_rule(&delete)).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::NOT_FOUND));
+
+  let rule = futures::executor::block_on(client.update_project_approval_rule(&UpdateProjectApprovalRuleCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    approval_rule_id: security_rule.id,
+    name: None,
+    approvals_required: None,
+    user_ids: Some(vec![UserId::new(2)]),
+    group_ids: Some(Vec::new()),
+    protected_branch_ids: None,
+    applies_to_all_protected_branches: None,
+    remove_hidden_groups: None,
+  }))
+  .unwrap();
+  assert_eq!(rule.eligible_approvers[0].username, "user2");
+  assert!(rule.groups.is_empty());
+  futures::executor::block_on(client.delete_project_approval_rule(&DeleteProjectApprovalRuleCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    approval_rule_id: security_rule.id,
+  }))
+  .unwrap();
+  let query = GetProjectApprovalRuleQuery {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    approval_rule_id: security_rule.id,
+  };
+  let actual = futures::executor::block_on(client.get_project_approval_rule(&query)).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::NOT_FOUND));
+}
@@ -0,0 +1,136 @@
+use crate::{context, create_commit, Fixture};
+use compact_str::{format_compact, CompactString};
+use gitlab_client::command::create_branch::CreateBranchCommand;
+use gitlab_client::command::create_commit::CreateCommitCommand;
+use gitlab_client::command::delete_branch::DeleteBranchCommand;
+use gitlab_client::command::delete_merged_branches::DeleteMergedBranchesCommand;
+use gitlab_client::command::protect_branch::ProtectBranchCommand;
+use gitlab_client::command::unprotect_branch::UnprotectBranchCommand;
+use gitlab_client::command::update_protected_branch::UpdateProtectedBranchCommand;
+use gitlab_client::common::branch::Branch;
+use gitlab_client::common::commit::{CommitAction, CommitContent};
+use gitlab_client::common::project::ProjectRef;
+use gitlab_client::common::protected_access::{ProtectedAccessChange, ProtectedAccessRule};
+use gitlab_client::common::user::UserId;
+use gitlab_client::common::{AccessLevel, Page};
+use gitlab_client::query::get_branch::GetBranchQuery;
+use gitlab_client::query::get_branch_list::GetBranchListQuery;
+use gitlab_client::{BranchClient, CommitClient};
+use http::StatusCode;
+
+#[test]
+fn manage_branches() {
+  let Fixture { mut client, project } = Fixture::new();
+  let commit = |branch: &str, file_path: &str| CreateCommitCommand {
+    commit_message: format_compact!("Add {file_path}"),
+    ..create_commit(
+      project,
+      branch,
+      vec![CommitAction::Create {
+        file_path: CompactString::new(file_path),
+        content: CommitContent::Text(CompactString::new("\n")),
+        execute_filemode: None,
+      }],
+    )
+  };
+  let create_branch = |branch: &str, r#ref: &str| CreateBranchCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    branch: CompactString::new(branch),
+    r#ref: CompactString::new(r#ref),
+  };
+  futures::executor::block_on(client.create_commit(&commit("main", "README.md"))).unwrap();
+  futures::executor::block_on(client.create_branch(&create_branch("feature", "main"))).unwrap();
+  futures::executor::block_on(client.create_commit(&commit("feature", "feature.txt"))).unwrap();
+  let merged = futures::executor::block_on(client.create_branch(&create_branch("fix", "main"))).unwrap();
+  assert!(merged.merged && !merged.default);
+  let actual = futures::executor::block_on(client.create_branch(&create_branch("fix", "main"))).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::BAD_REQUEST));
+
+  let protect = ProtectBranchCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    name: CompactString::new("release/*"),
+    push_access_level: Some(AccessLevel::Developer),
+    merge_access_level: None,
+    unprotect_access_level: None,
+    allow_force_push: None,
+    allowed_to_push: vec![ProtectedAccessRule::User(UserId::new(7))],
+    allowed_to_merge: Vec::new(),
+    allowed_to_unprotect: Vec::new(),
+    code_owner_approval_required: None,
+  };
+  let protection = futures::executor::block_on(client.protect_branch(&protect)).unwrap();
+  assert_eq!(protection.push_access_levels.len(), 2);
+  assert_eq!(
+    protection.merge_access_levels[0].access_level,
+    Some(AccessLevel::Maintainer)
+  );
+  let actual = futures::executor::block_on(client.protect_branch(&protect)).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::CONFLICT));
+
+  futures::executor::block_on(client.create_branch(&create_branch("release/1.0", "main"))).unwrap();
+  let branch = futures::executor::block_on(client.get_branch(&GetBranchQuery {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    branch: CompactString::new("release/1.0"),
+  }))
+  .unwrap();
+  assert!(branch.protected && branch.developers_can_push && !branch.developers_can_merge);
+
+  let list = GetBranchListQuery {
+    search: Some(CompactString::new("^f")),
+    ..GetBranchListQuery::new(ProjectRef::Id(project)).set_context(context())
+  };
+  let names = |page: Page<Branch>| Vec::from_iter(page.items.into_iter().map(|branch| branch.name));
+  let actual = futures::executor::block_on(client.get_branch_list(&list)).unwrap();
+  assert_eq!(names(actual), vec!["feature", "fix"]);
+
+  futures::executor::block_on(client.delete_merged_branches(&DeleteMergedBranchesCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+  }))
+  .unwrap();
+  let list = GetBranchListQuery::new(ProjectRef::Id(project)).set_context(context());
+  let actual = futures::executor::block_on(client.get_branch_list(&list)).unwrap();
+  assert_eq!(names(actual), vec!["feature", "main", "release/1.0"]);
+
+  let update = UpdateProtectedBranchCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    name: CompactString::new("release/*"),
+    allow_force_push: Some(true),
+    allowed_to_push: vec![ProtectedAccessChange::Remove(protection.push_access_levels[0].id)],
+    allowed_to_merge: vec![ProtectedAccessChange::Add(ProtectedAccessRule::AccessLevel(
+      AccessLevel::Developer,
+    ))],
+    allowed_to_unprotect: Vec::new(),
+    code_owner_approval_required: None,
+  };
+  let protection = futures::executor::block_on(client.update_protected_branch(&update)).unwrap();
+  assert!(protection.allow_force_push);
+  assert_eq!(protection.push_access_levels.len(), 1);
+  assert_eq!(protection.merge_access_levels.len(), 2);
+
+  futures::executor::block_on(client.unprotect_branch(&UnprotectBranchCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    name: CompactString::new("release/*"),
+  }))
+  .unwrap();
+  let delete = |branch: &str| DeleteBranchCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    branch: CompactString::new(branch),
+  };
+  futures::executor::block_on(client.delete_branch(&delete("release/1.0"))).unwrap();
+  let actual = futures::executor::block_on(client.delete_branch(&delete("main"))).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::BAD_REQUEST));
+}
@@ -0,0 +1,212 @@
+use crate::{context, create_commit, Fixture};
+use bytes::Bytes;
+use compact_str::{format_compact, CompactString};
+use gitlab_client::client::http::ResponseError;
+use gitlab_client::command::cherry_pick_commit::CherryPickCommitCommand;
+use gitlab_client::command::create_commit::CreateCommitCommand;
+use gitlab_client::command::revert_commit::RevertCommitCommand;
+use gitlab_client::common::commit::{CommitAction, CommitApplyFailure, CommitContent, CommitStats};
+use gitlab_client::common::project::ProjectRef;
+use gitlab_client::query::get_commit_diff::GetCommitDiffQuery;
+use gitlab_client::query::get_commit_list::GetCommitListQuery;
+use gitlab_client::query::get_commit_ref_list::GetCommitRefListQuery;
+use gitlab_client::CommitClient;
+use http::StatusCode;
+
+#[test]
+fn create_atomic_commit() {
+  let Fixture { mut client, project } = Fixture::new();
+  let command = CreateCommitCommand {
+    commit_message: CompactString::new("Initial commit"),
+    ..create_commit(
+      project,
+      "main",
+      vec![
+        CommitAction::Create {
+          file_path: CompactString::new("README.md"),
+          content: CommitContent::Text(CompactString::new("# Project\n")),
+          execute_filemode: None,
+        },
+        CommitAction::Create {
+          file_path: CompactString::new("build.sh"),
+          content: CommitContent::Base64(b"make\n".to_vec()),
+          execute_filemode: None,
+        },
+      ],
+    )
+  };
+  let initial = futures::executor::block_on(client.create_commit(&command)).unwrap();
+  assert!(initial.parent_ids.is_empty());
+  assert_eq!(initial.title, "Initial commit");
+  assert_eq!(
+    initial.stats,
+    Some(CommitStats {
+      additions: 2,
+      deletions: 0,
+      total: 2
+    })
+  );
+
+  let command = CreateCommitCommand {
+    commit_message: CompactString::new("Rename and remove"),
+    actions: vec![
+      CommitAction::Move {
+        file_path: CompactString::new("scripts/build.sh"),
+        previous_path: CompactString::new("build.sh"),
+        content: None,
+        last_commit_id: None,
+      },
+      CommitAction::Chmod {
+        file_path: CompactString::new("scripts/build.sh"),
+        execute_filemode: true,
+      },
+      CommitAction::Delete {
+        file_path: CompactString::new("missing.txt"),
+        last_commit_id: None,
+      },
+    ],
+    ..command
+  };
+  let actual = futures::executor::block_on(client.create_commit(&command)).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::BAD_REQUEST));
+  assert_eq!(
+    client.repository_file(project, "main", "build.sh"),
+    Some(Bytes::from_static(b"make\n"))
+  );
+
+  let mut command = command;
+  command.actions.pop();
+  let commit = futures::executor::block_on(client.create_commit(&command)).unwrap();
+  assert_eq!(commit.parent_ids, vec![initial.id.clone()]);
+  assert_eq!(client.repository_file(project, "main", "build.sh"), None);
+  assert_eq!(
+    client.repository_file(project, "main", "scripts/build.sh"),
+    Some(Bytes::from_static(b"make\n"))
+  );
+
+  let command = CreateCommitCommand {
+    branch: CompactString::new("main"),
+    commit_message: CompactString::new("Reset"),
+    start_sha: Some(CompactString::new(&initial.id)),
+    actions: Vec::new(),
+    force: Some(true),
+    ..command
+  };
+  let commit = futures::executor::block_on(client.create_commit(&command)).unwrap();
+  assert_eq!(commit.parent_ids, vec![initial.id]);
+  assert!(client.repository_file(project, "main", "build.sh").is_some());
+}
+
+#[test]
+fn cherry_pick_and_revert_commit() {
+  let Fixture { mut client, project } = Fixture::new();
+  let commit = |branch: &str, start_sha: Option<&str>, file_path: &str, content: &str| CreateCommitCommand {
+    commit_message: format_compact!("Write {file_path}"),
+    start_sha: start_sha.map(CompactString::new),
+    ..create_commit(
+      project,
+      branch,
+      vec![CommitAction::Create {
+        file_path: CompactString::new(file_path),
+        content: CommitContent::Text(CompactString::new(content)),
+        execute_filemode: None,
+      }],
+    )
+  };
+  let initial = futures::executor::block_on(client.create_commit(&commit("main", None, "VERSION", "1.0.0\n"))).unwrap();
+  let mut fix = commit("main", None, "VERSION", "1.0.1\n");
+  fix.commit_message = CompactString::new("Fix");
+  fix.actions = vec![CommitAction::Update {
+    file_path: CompactString::new("VERSION"),
+    content: CommitContent::Text(CompactString::new("1.0.1\n")),
+    last_commit_id: None,
+    execute_filemode: None,
+  }];
+  let fix = futures::executor::block_on(client.create_commit(&fix)).unwrap();
+  futures::executor::block_on(client.create_commit(&commit("stable", Some(&initial.id), "STABLE", "yes\n"))).unwrap();
+
+  let history = futures::executor::block_on(client.get_commit_list(&GetCommitListQuery {
+    path: Some(CompactString::new("VERSION")),
+    with_stats: Some(true),
+    ..GetCommitListQuery::new(ProjectRef::Id(project)).set_context(context())
+  }))
+  .unwrap();
+  let ids = Vec::from_iter(history.items.iter().map(|c| c.id.as_str()));
+  assert_eq!(ids, vec![fix.id.as_str(), initial.id.as_str()]);
+  assert_eq!(history.items[0].stats.map(|stats| stats.total), Some(2));
+
+  let diff = futures::executor::block_on(client.get_commit_diff(&GetCommitDiffQuery {
+    unidiff: Some(true),
+    ..GetCommitDiffQuery::new(ProjectRef::Id(project), CompactString::new(&fix.short_id)).set_context(context())
+  }))
+  .unwrap();
+  assert_eq!(diff.items.len(), 1);
+  assert_eq!(
+    diff.items[0].diff,
+    "--- a/VERSION\n+++ b/VERSION\n@@ -1,1 +1,1 @@\n-1.0.0\n+1.0.1\n"
+  );
+
+  let refs = futures::executor::block_on(client.get_commit_ref_list(
+    &GetCommitRefListQuery::new(ProjectRef::Id(project), CompactString::new(&initial.id)).set_context(context()),
+  ))
+  .unwrap();
+  let names = Vec::from_iter(refs.items.iter().map(|r| r.name.as_str()));
+  assert_eq!(names, vec!["main", "stable"]);
+
+  let cherry_pick = CherryPickCommitCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    sha: CompactString::new(&fix.id),
+    branch: CompactString::new("stable"),
+    message: None,
+  };
+  let picked = futures::executor::block_on(client.cherry_pick_commit(&cherry_pick)).unwrap();
+  assert!(picked
+    .message
+    .ends_with(&format!("(cherry picked from commit {})", fix.id)));
+  assert_eq!(
+    client.repository_file(project, "stable", "VERSION"),
+    Some(Bytes::from_static(b"1.0.1\n"))
+  );
+  let actual = futures::executor::block_on(client.cherry_pick_commit(&cherry_pick)).unwrap_err();
+  assert_eq!(
+    actual.response().and_then(ResponseError::commit_apply_failure),
+    Some(CommitApplyFailure::Empty)
+  );
+
+  futures::executor::block_on(client.create_commit(&CreateCommitCommand {
+    actions: vec![CommitAction::Update {
+      file_path: CompactString::new("VERSION"),
+      content: CommitContent::Text(CompactString::new("2.0.0\n")),
+      last_commit_id: None,
+      execute_filemode: None,
+    }],
+    ..commit("stable", None, "VERSION", "")
+  }))
+  .unwrap();
+  let revert = RevertCommitCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    sha: CompactString::new(&fix.id),
+    branch: CompactString::new("stable"),
+  };
+  let actual = futures::executor::block_on(client.revert_commit(&revert)).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::BAD_REQUEST));
+  assert_eq!(
+    actual.response().and_then(ResponseError::commit_apply_failure),
+    Some(CommitApplyFailure::Conflict)
+  );
+
+  let reverted = futures::executor::block_on(client.revert_commit(&RevertCommitCommand {
+    branch: CompactString::new("main"),
+    ..revert
+  }))
+  .unwrap();
+  assert_eq!(reverted.title, "Revert \"Fix\"");
+  assert_eq!(
+    client.repository_file(project, "main", "VERSION"),
+    Some(Bytes::from_static(b"1.0.0\n"))
+  );
+}
@@ -0,0 +1,83 @@
+//! Test suite of the in-memory fake GitLab, grouped by API area.
+
+mod approvals;
+mod branches;
+mod commits;
+mod merge_requests;
+mod milestones;
+mod notes;
+mod packages;
+mod releases;
+mod repository;
+mod tags;
+
+use compact_str::CompactString;
+use gitlab_client::client::fake::FakeGitlabClient;
+use gitlab_client::command::create_commit::CreateCommitCommand;
+use gitlab_client::command::create_release::CreateReleaseCommand;
+use gitlab_client::common::commit::CommitAction;
+use gitlab_client::common::project::{ProjectId, ProjectRef};
+use gitlab_client::common::release::{InputReleaseAssets, InputReleaseLink, ReleaseLinkType};
+use gitlab_client::context::{Context, GitlabUrl};
+
+type Cx = Context<GitlabUrl, ()>;
+
+fn context() -> Cx {
+  Context::new().set_gitlab_url(GitlabUrl(url::Url::parse("https://gitlab.example.com/").unwrap()))
+}
+
+/// Fake GitLab holding a single `group/project` project
+struct Fixture {
+  client: FakeGitlabClient,
+  project: ProjectId,
+}
+
+impl Fixture {
+  fn new() -> Self {
+    let client = FakeGitlabClient::new();
+    let project = client.create_project("group/project");
+    Self { client, project }
+  }
+}
+
+/// Commit command applying `actions` to the head of `branch`
+fn create_commit(project: ProjectId, branch: &str, actions: Vec<CommitAction>) -> CreateCommitCommand<Cx> {
+  CreateCommitCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    branch: CompactString::new(branch),
+    commit_message: CompactString::new("Update"),
+    start_branch: None,
+    start_sha: None,
+    actions,
+    author_email: None,
+    author_name: None,
+    stats: None,
+    force: None,
+  }
+}
+
+/// Release command with a single `linux` package link
+fn create_release(project: ProjectId, tag_name: &str) -> CreateReleaseCommand<Cx> {
+  CreateReleaseCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    tag_name: CompactString::new(tag_name),
+    name: None,
+    tag_message: None,
+    description: None,
+    r#ref: None,
+    milestones: Vec::new(),
+    assets: InputReleaseAssets {
+      links: vec![InputReleaseLink {
+        name: CompactString::new("linux"),
+        url: CompactString::new("https://example.com/linux"),
+        direct_asset_path: Some(CompactString::new("/bin/linux")),
+        link_type: ReleaseLinkType::Package,
+      }],
+    },
+    released_at: None,
+  }
+}
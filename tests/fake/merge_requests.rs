@@ -0,0 +1,284 @@
+use crate::{context, create_commit, Fixture};
+use bytes::Bytes;
+use compact_str::{format_compact, CompactString};
+use gitlab_client::command::accept_merge_request::AcceptMergeRequestCommand;
+use gitlab_client::command::create_commit::CreateCommitCommand;
+use gitlab_client::command::create_merge_request::CreateMergeRequestCommand;
+use gitlab_client::command::rebase_merge_request::RebaseMergeRequestCommand;
+use gitlab_client::command::update_merge_request::UpdateMergeRequestCommand;
+use gitlab_client::common::commit::{CommitAction, CommitContent};
+use gitlab_client::common::merge_request::{
+  MergeFailure, MergeRequest, MergeRequestDiffVersionId, MergeRequestScope, MergeRequestState,
+};
+use gitlab_client::common::project::ProjectRef;
+use gitlab_client::common::user::UserId;
+use gitlab_client::query::get_merge_request::GetMergeRequestQuery;
+use gitlab_client::query::get_merge_request_changes::GetMergeRequestChangesQuery;
+use gitlab_client::query::get_merge_request_commit_list::GetMergeRequestCommitListQuery;
+use gitlab_client::query::get_merge_request_diff_list::GetMergeRequestDiffListQuery;
+use gitlab_client::query::get_merge_request_diff_version::GetMergeRequestDiffVersionQuery;
+use gitlab_client::query::get_merge_request_diff_version_list::GetMergeRequestDiffVersionListQuery;
+use gitlab_client::query::get_merge_request_list::GetMergeRequestListQuery;
+use gitlab_client::query::get_project_merge_request_list::GetProjectMergeRequestListQuery;
+use gitlab_client::{CommitClient, MergeRequestClient};
+use http::StatusCode;
+
+#[test]
+fn manage_merge_requests() {
+  let Fixture { mut client, project } = Fixture::new();
+  let commit = |branch: &str, start_branch: Option<&str>, file_path: &str, content: &str| CreateCommitCommand {
+    commit_message: format_compact!("Update {file_path}"),
+    start_branch: start_branch.map(CompactString::new),
+    ..create_commit(
+      project,
+      branch,
+      vec![CommitAction::Create {
+        file_path: CompactString::new(file_path),
+        content: CommitContent::Text(CompactString::new(content)),
+        execute_filemode: None,
+      }],
+    )
+  };
+  let update = |branch: &str, file_path: &str, content: &str| CreateCommitCommand {
+    actions: vec![CommitAction::Update {
+      file_path: CompactString::new(file_path),
+      content: CommitContent::Text(CompactString::new(content)),
+      execute_filemode: None,
+      last_commit_id: None,
+    }],
+    ..commit(branch, None, file_path, content)
+  };
+  futures::executor::block_on(client.create_commit(&commit("main", None, "README.md", "v1\n"))).unwrap();
+  futures::executor::block_on(client.create_commit(&commit("feature", Some("main"), "src/lib.rs", "\n"))).unwrap();
+  futures::executor::block_on(client.create_commit(&update("main", "README.md", "v2\n"))).unwrap();
+  futures::executor::block_on(client.create_commit(&commit("fix", Some("main"), "fix.txt", "\n"))).unwrap();
+  futures::executor::block_on(client.create_commit(&update("fix", "README.md", "v3\n"))).unwrap();
+  futures::executor::block_on(client.create_commit(&update("main", "README.md", "v4\n"))).unwrap();
+
+  let create = |source_branch: &str, title: &str| CreateMergeRequestCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    source_branch: CompactString::new(source_branch),
+    target_branch: CompactString::new("main"),
+    title: CompactString::new(title),
+    description: None,
+    assignee_ids: Vec::new(),
+    reviewer_ids: vec![UserId::new(2)],
+    labels: vec![CompactString::new("backend")],
+    milestone_id: None,
+    remove_source_branch: Some(true),
+    squash: None,
+  };
+  let feature = futures::executor::block_on(client.create_merge_request(&create("feature", "Draft: Add lib"))).unwrap();
+  assert!(feature.draft);
+  assert_eq!(feature.detailed_merge_status, "draft_status");
+  let fix = futures::executor::block_on(client.create_merge_request(&create("fix", "Fix README"))).unwrap();
+  assert!(fix.has_conflicts);
+  let actual = futures::executor::block_on(client.create_merge_request(&create("fix", "Fix README"))).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::CONFLICT));
+
+  let list = GetProjectMergeRequestListQuery {
+    draft: Some(false),
+    reviewer_username: Some(CompactString::new("user2")),
+    ..GetProjectMergeRequestListQuery::new(ProjectRef::Id(project)).set_context(context())
+  };
+  let actual = futures::executor::block_on(client.get_project_merge_request_list(&list)).unwrap();
+  assert_eq!(Vec::from_iter(actual.items.into_iter().map(|mr| mr.iid)), vec![fix.iid]);
+
+  let accept = |merge_request: &MergeRequest, sha: Option<&str>| AcceptMergeRequestCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    merge_request_iid: merge_request.iid,
+    sha: sha.map(CompactString::new),
+    squash: None,
+    should_remove_source_branch: None,
+    merge_commit_message: None,
+    squash_commit_message: None,
+    auto_merge: None,
+  };
+  let mut failure = {
+    let mut client = client.clone();
+    move |merge_request: &MergeRequest, sha: Option<&str>| {
+      let actual = futures::executor::block_on(client.accept_merge_request(&accept(merge_request, sha))).unwrap_err();
+      actual.merge_failure()
+    }
+  };
+  assert_eq!(failure(&feature, None), Some(MergeFailure::NotMergeable));
+  assert_eq!(failure(&fix, None), Some(MergeFailure::Conflict));
+  let rebase = RebaseMergeRequestCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    merge_request_iid: fix.iid,
+    skip_ci: None,
+  };
+  futures::executor::block_on(client.rebase_merge_request(&rebase)).unwrap();
+  let get = GetMergeRequestQuery {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    merge_request_iid: fix.iid,
+    include_rebase_in_progress: None,
+  };
+  let actual = futures::executor::block_on(client.get_merge_request(&get)).unwrap();
+  assert!(actual.merge_error.is_some());
+
+  let feature = futures::executor::block_on(client.update_merge_request(&UpdateMergeRequestCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    merge_request_iid: feature.iid,
+    title: Some(CompactString::new("Add lib")),
+    description: None,
+    target_branch: None,
+    assignee_ids: Some(vec![UserId::new(1)]),
+    reviewer_ids: None,
+    labels: None,
+    add_labels: vec![CompactString::new("api")],
+    remove_labels: Vec::new(),
+    milestone_id: None,
+    state_event: None,
+    remove_source_branch: None,
+    squash: None,
+    discussion_locked: None,
+  }))
+  .unwrap();
+  assert_eq!(feature.labels, vec!["api", "backend"]);
+  assert_eq!(feature.detailed_merge_status, "mergeable");
+  let rebase = RebaseMergeRequestCommand {
+    merge_request_iid: feature.iid,
+    ..rebase
+  };
+  futures::executor::block_on(client.rebase_merge_request(&rebase)).unwrap();
+  assert_eq!(
+    failure(&feature, feature.sha.as_deref()),
+    Some(MergeFailure::ShaMismatch)
+  );
+  let feature = futures::executor::block_on(client.get_merge_request(&GetMergeRequestQuery {
+    merge_request_iid: feature.iid,
+    ..get
+  }))
+  .unwrap();
+  let merged =
+    futures::executor::block_on(client.accept_merge_request(&accept(&feature, feature.sha.as_deref()))).unwrap();
+  assert_eq!(merged.state, MergeRequestState::Merged);
+  let main = client.repository_file(project, "main", "src/lib.rs");
+  assert_eq!(main, Some(Bytes::from_static(b"\n")));
+  assert_eq!(client.repository_file(project, "feature", "src/lib.rs"), None);
+
+  let list = GetMergeRequestListQuery {
+    scope: Some(MergeRequestScope::AssignedToMe),
+    state: Some(MergeRequestState::Merged),
+    ..GetMergeRequestListQuery::new().set_context(context())
+  };
+  let actual = futures::executor::block_on(client.get_merge_request_list(&list)).unwrap();
+  assert_eq!(
+    Vec::from_iter(actual.items.into_iter().map(|mr| mr.iid)),
+    vec![merged.iid]
+  );
+}
+
+#[test]
+fn read_merge_request_changes() {
+  let Fixture { mut client, project } = Fixture::new();
+  let commit = |branch: &str, start_branch: Option<&str>, action: CommitAction| CreateCommitCommand {
+    start_branch: start_branch.map(CompactString::new),
+    ..create_commit(project, branch, vec![action])
+  };
+  let create = |file_path: &str, content: &str| CommitAction::Create {
+    file_path: CompactString::new(file_path),
+    content: CommitContent::Text(CompactString::new(content)),
+    execute_filemode: None,
+  };
+  let update = |file_path: &str, content: &str| CommitAction::Update {
+    file_path: CompactString::new(file_path),
+    content: CommitContent::Text(CompactString::new(content)),
+    execute_filemode: None,
+    last_commit_id: None,
+  };
+  futures::executor::block_on(client.create_commit(&commit("main", None, create("README.md", "v1\n")))).unwrap();
+  let first =
+    futures::executor::block_on(client.create_commit(&commit("feature", Some("main"), create("src/lib.rs", "\n"))))
+      .unwrap();
+  let merge_request = futures::executor::block_on(client.create_merge_request(&CreateMergeRequestCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    source_branch: CompactString::new("feature"),
+    target_branch: CompactString::new("main"),
+    title: CompactString::new("Add lib"),
+    description: None,
+    assignee_ids: Vec::new(),
+    reviewer_ids: Vec::new(),
+    labels: Vec::new(),
+    milestone_id: None,
+    remove_source_branch: None,
+    squash: None,
+  }))
+  .unwrap();
+  let second =
+    futures::executor::block_on(client.create_commit(&commit("feature", None, update("src/lib.rs", "fn f() {}\n"))))
+      .unwrap();
+  futures::executor::block_on(client.create_commit(&commit("main", None, update("README.md", "v2\n")))).unwrap();
+
+  let query = GetMergeRequestCommitListQuery::new(ProjectRef::Id(project), merge_request.iid).set_context(context());
+  let actual = futures::executor::block_on(client.get_merge_request_commit_list(&query)).unwrap();
+  assert_eq!(
+    Vec::from_iter(actual.items.into_iter().map(|commit| commit.id)),
+    vec![second.id.clone(), first.id.clone()]
+  );
+
+  let query = GetMergeRequestDiffListQuery::new(ProjectRef::Id(project), merge_request.iid).set_context(context());
+  let actual = futures::executor::block_on(client.get_merge_request_diff_list(&query)).unwrap();
+  assert_eq!(actual.items.len(), 1);
+  assert!(actual.items[0].new_file);
+  assert_eq!(actual.items[0].diff, "@@ -0,0 +1,1 @@\n+fn f() {}\n");
+
+  let query = GetMergeRequestChangesQuery {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    merge_request_iid: merge_request.iid,
+    access_raw_diffs: None,
+    unidiff: None,
+  };
+  let actual = futures::executor::block_on(client.get_merge_request_changes(&query)).unwrap();
+  assert_eq!(actual.merge_request.sha.as_deref(), Some(second.id.as_str()));
+  assert_eq!(
+    Vec::from_iter(actual.changes.iter().map(|diff| diff.new_path.as_str())),
+    vec!["src/lib.rs"]
+  );
+
+  let query =
+    GetMergeRequestDiffVersionListQuery::new(ProjectRef::Id(project), merge_request.iid).set_context(context());
+  let versions = futures::executor::block_on(client.get_merge_request_diff_version_list(&query)).unwrap();
+  assert_eq!(
+    Vec::from_iter(versions.items.iter().map(|version| version.head_commit_sha.as_str())),
+    vec![second.id.as_str(), first.id.as_str()]
+  );
+  assert_eq!(versions.items[0].base_commit_sha, versions.items[1].start_commit_sha);
+  assert_ne!(versions.items[0].start_commit_sha, versions.items[1].start_commit_sha);
+
+  let query = GetMergeRequestDiffVersionQuery {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    merge_request_iid: merge_request.iid,
+    version_id: versions.items[1].id,
+    unidiff: Some(true),
+  };
+  let actual = futures::executor::block_on(client.get_merge_request_diff_version(&query)).unwrap();
+  assert_eq!(actual.commits.map(|commits| commits.len()), Some(1));
+  assert_eq!(
+    actual.diffs.map(|diffs| diffs[0].diff.clone()),
+    Some(String::from("--- /dev/null\n+++ b/src/lib.rs\n@@ -0,0 +1,1 @@\n+\n"))
+  );
+  let query = GetMergeRequestDiffVersionQuery {
+    version_id: MergeRequestDiffVersionId::new(0),
+    ..query
+  };
+  let actual = futures::executor::block_on(client.get_merge_request_diff_version(&query)).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::NOT_FOUND));
+}
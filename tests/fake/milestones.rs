@@ -0,0 +1,102 @@
+use crate::{context, create_release, Fixture};
+use compact_str::CompactString;
+use gitlab_client::client::fake::FakeGitlabClient;
+use gitlab_client::client::http::HttpGitlabClientError;
+use gitlab_client::command::create_group_milestone::CreateGroupMilestoneCommand;
+use gitlab_client::command::create_project_milestone::CreateProjectMilestoneCommand;
+use gitlab_client::command::delete_group_milestone::DeleteGroupMilestoneCommand;
+use gitlab_client::command::update_project_milestone::UpdateProjectMilestoneCommand;
+use gitlab_client::common::milestone::{MilestoneIid, MilestoneState, MilestoneStateEvent};
+use gitlab_client::common::project::ProjectRef;
+use gitlab_client::query::get_group_milestone::GetGroupMilestoneQuery;
+use gitlab_client::query::get_project_milestone_list::GetProjectMilestoneListQuery;
+use gitlab_client::{MilestoneClient, ReleaseClient};
+use http::StatusCode;
+
+#[test]
+fn assign_release_milestones() {
+  let Fixture { mut client, project } = Fixture::new();
+  let command = CreateProjectMilestoneCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    title: CompactString::new("v1.0"),
+    description: Some(CompactString::new("First stable version")),
+    due_date: None,
+    start_date: None,
+  };
+  let milestone = futures::executor::block_on(client.create_project_milestone(&command)).unwrap();
+  assert_eq!(milestone.iid, MilestoneIid::new(1));
+  assert_eq!(
+    milestone.web_url,
+    "https://gitlab.example.com/group/project/-/milestones/1"
+  );
+  let actual = futures::executor::block_on(client.create_project_milestone(&command)).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::BAD_REQUEST));
+
+  let mut command = create_release(project, "v1.0.0");
+  command.milestones = vec![CompactString::new("v2.0")];
+  let actual = futures::executor::block_on(client.create_release(&command)).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::BAD_REQUEST));
+  command.milestones = vec![CompactString::new("v1.0")];
+  let release = futures::executor::block_on(client.create_release(&command)).unwrap();
+  assert_eq!(release.milestones, Some(vec![milestone.clone()]));
+
+  let command = UpdateProjectMilestoneCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    milestone_id: milestone.id,
+    title: None,
+    description: None,
+    due_date: None,
+    start_date: None,
+    state_event: Some(MilestoneStateEvent::Close),
+  };
+  futures::executor::block_on(client.update_project_milestone(&command)).unwrap();
+  let mut query = GetProjectMilestoneListQuery::<_>::new(ProjectRef::Id(project)).set_context(context());
+  query.state = Some(MilestoneState::Active);
+  let actual = futures::executor::block_on(client.get_project_milestone_list(&query)).unwrap();
+  assert!(actual.items.is_empty());
+  query.state = Some(MilestoneState::Closed);
+  query.search = Some(CompactString::new("stable"));
+  let actual = futures::executor::block_on(client.get_project_milestone_list(&query)).unwrap();
+  assert_eq!(actual.items.len(), 1);
+}
+
+#[test]
+fn group_milestones() {
+  let mut client = FakeGitlabClient::new();
+  let group = client.create_group("group");
+  let command = CreateGroupMilestoneCommand {
+    context: context(),
+    auth: None,
+    group,
+    title: CompactString::new("Q3"),
+    description: None,
+    due_date: None,
+    start_date: None,
+  };
+  let milestone = futures::executor::block_on(client.create_group_milestone(&command)).unwrap();
+  assert_eq!(milestone.group_id, Some(group));
+  assert_eq!(
+    milestone.web_url,
+    "https://gitlab.example.com/groups/group/-/milestones/1"
+  );
+
+  let command = DeleteGroupMilestoneCommand {
+    context: context(),
+    auth: None,
+    group,
+    milestone_id: milestone.id,
+  };
+  futures::executor::block_on(client.delete_group_milestone(&command)).unwrap();
+  let query = GetGroupMilestoneQuery {
+    context: context(),
+    auth: None,
+    group,
+    milestone_id: milestone.id,
+  };
+  let actual = futures::executor::block_on(client.get_group_milestone(&query)).unwrap_err();
+  assert!(matches!(actual, HttpGitlabClientError::NotFound(_)));
+}
@@ -0,0 +1,195 @@
+use crate::{context, create_commit, Fixture};
+use compact_str::{format_compact, CompactString};
+use gitlab_client::command::create_commit::CreateCommitCommand;
+use gitlab_client::command::create_discussion::CreateDiscussionCommand;
+use gitlab_client::command::create_merge_request::CreateMergeRequestCommand;
+use gitlab_client::command::create_note::CreateNoteCommand;
+use gitlab_client::command::delete_note::DeleteNoteCommand;
+use gitlab_client::command::resolve_discussion::ResolveDiscussionCommand;
+use gitlab_client::command::update_note::UpdateNoteCommand;
+use gitlab_client::common::commit::{CommitAction, CommitContent};
+use gitlab_client::common::note::{NoteLocation, NoteParentRef, NoteType, NoteableRef, Position, PositionType};
+use gitlab_client::common::project::ProjectRef;
+use gitlab_client::common::SortOrder;
+use gitlab_client::query::get_discussion::GetDiscussionQuery;
+use gitlab_client::query::get_discussion_list::GetDiscussionListQuery;
+use gitlab_client::query::get_note_list::GetNoteListQuery;
+use gitlab_client::{CommitClient, MergeRequestClient, NoteClient};
+use http::StatusCode;
+
+#[test]
+fn comment_merge_request() {
+  let Fixture { mut client, project } = Fixture::new();
+  let mut heads = Vec::new();
+  for (branch, start_branch, file_path) in [("main", None, "README.md"), ("feature", Some("main"), "src/lib.rs")] {
+    let commit = futures::executor::block_on(client.create_commit(&CreateCommitCommand {
+      commit_message: format_compact!("Add {file_path}"),
+      start_branch: start_branch.map(CompactString::new),
+      ..create_commit(
+        project,
+        branch,
+        vec![CommitAction::Create {
+          file_path: CompactString::new(file_path),
+          content: CommitContent::Text(CompactString::new("\n")),
+          execute_filemode: None,
+        }],
+      )
+    }))
+    .unwrap();
+    heads.push(CompactString::new(&commit.id));
+  }
+  let merge_request = futures::executor::block_on(client.create_merge_request(&CreateMergeRequestCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    source_branch: CompactString::new("feature"),
+    target_branch: CompactString::new("main"),
+    title: CompactString::new("Add lib"),
+    description: None,
+    assignee_ids: Vec::new(),
+    reviewer_ids: Vec::new(),
+    labels: Vec::new(),
+    milestone_id: None,
+    remove_source_branch: None,
+    squash: None,
+  }))
+  .unwrap();
+  let parent = NoteParentRef::MergeRequest {
+    project: ProjectRef::Id(project),
+    merge_request_iid: merge_request.iid,
+  };
+  let noteable = NoteableRef::from(parent.clone());
+
+  let comment = futures::executor::block_on(client.create_note(&CreateNoteCommand {
+    context: context(),
+    auth: None,
+    location: NoteLocation::Parent(parent.clone()),
+    body: CompactString::new("Thanks!"),
+    internal: None,
+  }))
+  .unwrap();
+  assert_eq!(comment.noteable_iid, Some(merge_request.iid.into_u64()));
+  assert!(!comment.resolvable);
+
+  let position = |head_sha: &str| Position {
+    base_sha: heads[0].clone(),
+    start_sha: heads[0].clone(),
+    head_sha: CompactString::new(head_sha),
+    position_type: PositionType::Text,
+    old_path: CompactString::new("src/lib.rs"),
+    new_path: CompactString::new("src/lib.rs"),
+    old_line: None,
+    new_line: Some(1),
+  };
+  let create_thread = |position| CreateDiscussionCommand {
+    context: context(),
+    auth: None,
+    noteable: noteable.clone(),
+    body: CompactString::new("Empty file?"),
+    position: Some(position),
+  };
+  let actual = futures::executor::block_on(client.create_discussion(&create_thread(position(&heads[0])))).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::BAD_REQUEST));
+  let thread = futures::executor::block_on(client.create_discussion(&create_thread(position(&heads[1])))).unwrap();
+  assert_eq!(thread.notes[0].note_type, Some(NoteType::DiffNote));
+  assert_eq!(thread.notes[0].position.as_ref().unwrap().new_line, Some(1));
+
+  let reply = futures::executor::block_on(client.create_note(&CreateNoteCommand {
+    context: context(),
+    auth: None,
+    location: NoteLocation::Discussion {
+      noteable: noteable.clone(),
+      discussion_id: thread.id.clone(),
+    },
+    body: CompactString::new("Fixed"),
+    internal: None,
+  }))
+  .unwrap();
+  futures::executor::block_on(client.update_note(&UpdateNoteCommand {
+    context: context(),
+    auth: None,
+    location: NoteLocation::Parent(parent.clone()),
+    note_id: reply.id,
+    body: CompactString::new("Fixed, thanks"),
+  }))
+  .unwrap();
+
+  let resolve = |discussion_id: &CompactString| ResolveDiscussionCommand {
+    context: context(),
+    auth: None,
+    noteable: noteable.clone(),
+    discussion_id: discussion_id.clone(),
+    resolved: true,
+  };
+  let actual =
+    futures::executor::block_on(client.resolve_discussion(&resolve(&CompactString::new("0000")))).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::NOT_FOUND));
+  let resolved = futures::executor::block_on(client.resolve_discussion(&resolve(&thread.id))).unwrap();
+  assert!(resolved.notes.iter().all(|note| note.resolved == Some(true)));
+  assert_eq!(resolved.notes[1].body, "Fixed, thanks");
+
+  let discussions = futures::executor::block_on(
+    client.get_discussion_list(&GetDiscussionListQuery::new(noteable.clone()).set_context(context())),
+  )
+  .unwrap();
+  let actual = futures::executor::block_on(client.resolve_discussion(&resolve(&discussions.items[0].id))).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::FORBIDDEN));
+
+  futures::executor::block_on(client.delete_note(&DeleteNoteCommand {
+    context: context(),
+    auth: None,
+    location: NoteLocation::Discussion {
+      noteable: noteable.clone(),
+      discussion_id: thread.id.clone(),
+    },
+    note_id: reply.id,
+  }))
+  .unwrap();
+  let query = GetNoteListQuery {
+    sort: Some(SortOrder::Asc),
+    ..GetNoteListQuery::new(parent).set_context(context())
+  };
+  let notes = futures::executor::block_on(client.get_note_list(&query)).unwrap();
+  assert_eq!(
+    Vec::from_iter(notes.items.iter().map(|note| note.body.as_str())),
+    ["Thanks!", "Empty file?"]
+  );
+
+  let commit = NoteableRef::Commit {
+    project: ProjectRef::Id(project),
+    sha: heads[1].clone(),
+  };
+  let thread = futures::executor::block_on(client.create_discussion(&CreateDiscussionCommand {
+    context: context(),
+    auth: None,
+    noteable: commit.clone(),
+    body: CompactString::new("Nice"),
+    position: None,
+  }))
+  .unwrap();
+  assert_eq!(thread.notes[0].commit_id, Some(heads[1].clone()));
+  assert!(!thread.notes[0].resolvable);
+  let reply = futures::executor::block_on(client.create_note(&CreateNoteCommand {
+    context: context(),
+    auth: None,
+    location: NoteLocation::Discussion {
+      noteable: commit.clone(),
+      discussion_id: thread.id.clone(),
+    },
+    body: CompactString::new("Thanks"),
+    internal: None,
+  }))
+  .unwrap();
+  assert_eq!(reply.commit_id, Some(heads[1].clone()));
+  let query = GetDiscussionQuery {
+    context: context(),
+    auth: None,
+    noteable: commit,
+    discussion_id: thread.id.clone(),
+  };
+  let thread = futures::executor::block_on(client.get_discussion(&query)).unwrap();
+  assert_eq!(
+    Vec::from_iter(thread.notes.iter().map(|note| note.body.as_str())),
+    ["Nice", "Thanks"]
+  );
+}
@@ -0,0 +1,64 @@
+use crate::{context, Fixture};
+use bytes::Bytes;
+use compact_str::CompactString;
+use gitlab_client::command::publish_package_file::PublishPackageFileCommand;
+use gitlab_client::common::package::PackageId;
+use gitlab_client::common::project::{ProjectRef, ProjectSlug};
+use gitlab_client::query::get_package_file::GetPackageFileQuery;
+use gitlab_client::query::get_package_file_list::GetPackageFileListQuery;
+use gitlab_client::query::get_project_package_list::GetProjectPackageListQuery;
+use gitlab_client::{InputPackageStatus, PackageClient};
+use http::StatusCode;
+
+#[test]
+fn publish_then_get_package_file() {
+  let Fixture { mut client, project } = Fixture::new();
+  let command = PublishPackageFileCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Slug(ProjectSlug::new(CompactString::new("group/project"))),
+    package_name: CompactString::new("app"),
+    package_version: CompactString::new("1.0.0"),
+    filename: CompactString::new("app.tar.gz"),
+    status: InputPackageStatus::Default,
+    data: b"archive".to_vec(),
+  };
+  let file = futures::executor::block_on(client.publish_package_file(&command)).unwrap();
+  assert_eq!(file.size, 7);
+  assert_eq!(file.file_name, "app.tar.gz");
+
+  let query = GetPackageFileQuery {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    package_name: CompactString::new("app"),
+    package_version: CompactString::new("1.0.0"),
+    filename: CompactString::new("app.tar.gz"),
+  };
+  let actual = futures::executor::block_on(client.get_package_file(&query)).unwrap();
+  assert_eq!(actual, Bytes::from_static(b"archive"));
+
+  let query = GetPackageFileQuery {
+    filename: CompactString::new("missing.tar.gz"),
+    ..query
+  };
+  let actual = futures::executor::block_on(client.get_package_file(&query)).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::NOT_FOUND));
+
+  let query = GetProjectPackageListQuery::new(ProjectRef::Id(project)).set_context(context());
+  let actual = futures::executor::block_on(client.get_project_package_list(&query)).unwrap();
+  assert_eq!(actual.items.len(), 1);
+  assert_eq!(actual.items[0].id, PackageId::new(file.package_id));
+  assert_eq!(actual.items[0].name, "app");
+  assert_eq!(actual.items[0].version, "1.0.0");
+
+  let query = GetPackageFileListQuery::new(ProjectRef::Id(project), actual.items[0].id).set_context(context());
+  let actual = futures::executor::block_on(client.get_package_file_list(&query)).unwrap();
+  assert_eq!(actual.items.len(), 1);
+  assert_eq!(actual.items[0].file_name, "app.tar.gz");
+  assert_eq!(actual.items[0].file_sha256, file.file_sha256);
+  assert_eq!(
+    file.file_sha256.as_deref(),
+    Some("0eb3e36bfb24dcd9bb1d1bece1531216b59539a8fde17ee80224af0653c92aa3")
+  );
+}
@@ -0,0 +1,93 @@
+use crate::{context, create_release, Fixture};
+use compact_str::CompactString;
+use gitlab_client::client::http::HttpGitlabClientError;
+use gitlab_client::command::delete_release_link::DeleteReleaseLinkCommand;
+use gitlab_client::command::update_release_link::UpdateReleaseLinkCommand;
+use gitlab_client::common::project::ProjectRef;
+use gitlab_client::query::get_release_link::GetReleaseLinkQuery;
+use gitlab_client::query::get_release_link_list::GetReleaseLinkListQuery;
+use gitlab_client::ReleaseClient;
+use http::StatusCode;
+
+#[test]
+fn create_duplicate_release() {
+  let Fixture { mut client, project } = Fixture::new();
+  let command = create_release(project, "v1.0.0");
+  let release = futures::executor::block_on(client.create_release(&command)).unwrap();
+  assert_eq!(release.tag_name, "v1.0.0");
+  assert_eq!(
+    release.assets.links[0].direct_asset_url,
+    "https://gitlab.example.com/group/project/-/releases/v1.0.0/downloads/bin/linux"
+  );
+
+  let actual = futures::executor::block_on(client.create_release(&command)).unwrap_err();
+  match actual {
+    HttpGitlabClientError::Conflict(res) => {
+      assert_eq!(res.method, http::Method::POST);
+      assert_eq!(res.status, StatusCode::CONFLICT);
+      assert_eq!(
+        res.uri.path(),
+        format!("/api/v4/projects/{}/releases", project.into_u64())
+      );
+    }
+    e => panic!("unexpected error: {e:?}"),
+  }
+  assert_eq!(client.releases(project).len(), 1);
+}
+
+#[test]
+fn update_then_delete_release_link() {
+  let Fixture { mut client, project } = Fixture::new();
+  let release = futures::executor::block_on(client.create_release(&create_release(project, "v1.0.0"))).unwrap();
+  let link_id = release.assets.links[0].id;
+
+  let command = UpdateReleaseLinkCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    tag_name: CompactString::new("v1.0.0"),
+    link_id,
+    name: None,
+    url: Some(CompactString::new("https://example.com/linux-v2")),
+    direct_asset_path: Some(CompactString::new("/bin/linux-v2")),
+    link_type: None,
+  };
+  let link = futures::executor::block_on(client.update_release_link(&command)).unwrap();
+  assert_eq!(link.name, "linux");
+  assert_eq!(link.url, "https://example.com/linux-v2");
+  assert_eq!(
+    link.direct_asset_url,
+    "https://gitlab.example.com/group/project/-/releases/v1.0.0/downloads/bin/linux-v2"
+  );
+
+  let query = GetReleaseLinkQuery {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    tag_name: CompactString::new("v1.0.0"),
+    link_id,
+  };
+  let actual = futures::executor::block_on(client.get_release_link(&query)).unwrap();
+  assert_eq!(actual, link);
+
+  let command = DeleteReleaseLinkCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    tag_name: CompactString::new("v1.0.0"),
+    link_id,
+  };
+  let actual = futures::executor::block_on(client.delete_release_link(&command)).unwrap();
+  assert_eq!(actual, link);
+  let actual = futures::executor::block_on(client.delete_release_link(&command)).unwrap_err();
+  assert!(matches!(actual, HttpGitlabClientError::NotFound(_)));
+
+  let query = GetReleaseLinkListQuery {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    tag_name: CompactString::new("v1.0.0"),
+  };
+  let actual = futures::executor::block_on(client.get_release_link_list(&query)).unwrap();
+  assert!(actual.items.is_empty());
+}
@@ -0,0 +1,119 @@
+use crate::{context, Fixture};
+use bytes::Bytes;
+use compact_str::CompactString;
+use gitlab_client::command::create_repository_file::CreateRepositoryFileCommand;
+use gitlab_client::command::delete_repository_file::DeleteRepositoryFileCommand;
+use gitlab_client::command::update_repository_file::UpdateRepositoryFileCommand;
+use gitlab_client::common::project::ProjectRef;
+use gitlab_client::common::tree::{TreeRecord, TreeRecordType};
+use gitlab_client::query::get_repository_file::GetRepositoryFileQuery;
+use gitlab_client::query::get_tree_record_list::GetTreeRecordListQuery;
+use gitlab_client::RepositoryClient;
+use http::StatusCode;
+
+#[test]
+fn list_tree_records() {
+  let Fixture { mut client, project } = Fixture::new();
+  for (path, r#type) in [
+    ("README.md", TreeRecordType::Blob),
+    ("src", TreeRecordType::Tree),
+    ("src/main.rs", TreeRecordType::Blob),
+  ] {
+    client.insert_tree_record(
+      project,
+      TreeRecord {
+        id: CompactString::new("0000000000000000000000000000000000000000"),
+        name: CompactString::new(path.rsplit('/').next().unwrap()),
+        r#type,
+        path: CompactString::new(path),
+        mode: CompactString::new("100644"),
+      },
+    );
+  }
+  let mut query = GetTreeRecordListQuery::<_>::new(ProjectRef::Id(project)).set_context(context());
+  let actual = futures::executor::block_on(client.get_tree_record_list(&query)).unwrap();
+  assert_eq!(
+    actual.items.iter().map(|r| r.path.as_str()).collect::<Vec<_>>(),
+    vec!["README.md", "src"]
+  );
+
+  query.recursive = Some(true);
+  let actual = futures::executor::block_on(client.get_tree_record_list(&query)).unwrap();
+  assert_eq!(actual.items.len(), 3);
+}
+
+#[test]
+fn bump_repository_file() {
+  let Fixture { mut client, project } = Fixture::new();
+  let command = CreateRepositoryFileCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    file_path: CompactString::new("config/VERSION"),
+    branch: CompactString::new("main"),
+    start_branch: None,
+    commit_message: CompactString::new("Add version"),
+    author_email: None,
+    author_name: None,
+    content: b"1.0.0\n".to_vec(),
+    execute_filemode: None,
+  };
+  futures::executor::block_on(client.create_repository_file(&command)).unwrap();
+  let actual = futures::executor::block_on(client.create_repository_file(&command)).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::BAD_REQUEST));
+
+  let query = GetRepositoryFileQuery {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    file_path: CompactString::new("config/VERSION"),
+    r#ref: CompactString::new("main"),
+  };
+  let file = futures::executor::block_on(client.get_repository_file(&query)).unwrap();
+  assert_eq!(file.file_name, "VERSION");
+  assert_eq!(file.content, Bytes::from_static(b"1.0.0\n"));
+
+  let mut command = UpdateRepositoryFileCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    file_path: CompactString::new("config/VERSION"),
+    branch: CompactString::new("bump"),
+    start_branch: Some(CompactString::new("main")),
+    commit_message: CompactString::new("Bump version"),
+    author_email: None,
+    author_name: None,
+    content: b"1.1.0\n".to_vec(),
+    execute_filemode: None,
+    last_commit_id: Some(file.last_commit_id.clone()),
+  };
+  futures::executor::block_on(client.update_repository_file(&command)).unwrap();
+  assert_eq!(
+    client.repository_file(project, "bump", "config/VERSION"),
+    Some(Bytes::from_static(b"1.1.0\n"))
+  );
+  assert_eq!(
+    client.repository_file(project, "main", "config/VERSION"),
+    Some(Bytes::from_static(b"1.0.0\n"))
+  );
+
+  command.content = b"1.2.0\n".to_vec();
+  let actual = futures::executor::block_on(client.update_repository_file(&command)).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::BAD_REQUEST));
+
+  let command = DeleteRepositoryFileCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    file_path: CompactString::new("config/VERSION"),
+    branch: CompactString::new("main"),
+    start_branch: None,
+    commit_message: CompactString::new("Remove version"),
+    author_email: None,
+    author_name: None,
+    last_commit_id: None,
+  };
+  futures::executor::block_on(client.delete_repository_file(&command)).unwrap();
+  let actual = futures::executor::block_on(client.get_repository_file(&query)).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::NOT_FOUND));
+}
@@ -0,0 +1,104 @@
+use crate::{context, create_commit, Fixture};
+use compact_str::CompactString;
+use gitlab_client::command::create_tag::CreateTagCommand;
+use gitlab_client::command::delete_tag::DeleteTagCommand;
+use gitlab_client::command::protect_tag::ProtectTagCommand;
+use gitlab_client::common::commit::{CommitAction, CommitContent, CommitRefType};
+use gitlab_client::common::project::ProjectRef;
+use gitlab_client::common::tag::TagOrder;
+use gitlab_client::common::AccessLevel;
+use gitlab_client::query::get_commit_ref_list::GetCommitRefListQuery;
+use gitlab_client::query::get_protected_tag::GetProtectedTagQuery;
+use gitlab_client::query::get_tag_list::GetTagListQuery;
+use gitlab_client::query::get_tag_signature::GetTagSignatureQuery;
+use gitlab_client::{CommitClient, TagClient};
+use http::StatusCode;
+
+#[test]
+fn manage_tags() {
+  let Fixture { mut client, project } = Fixture::new();
+  let command = create_commit(
+    project,
+    "main",
+    vec![CommitAction::Create {
+      file_path: CompactString::new("README.md"),
+      content: CommitContent::Text(CompactString::new("\n")),
+      execute_filemode: None,
+    }],
+  );
+  futures::executor::block_on(client.create_commit(&command)).unwrap();
+  let create_tag = |tag_name: &str, message: Option<&str>| CreateTagCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    tag_name: CompactString::new(tag_name),
+    r#ref: CompactString::new("main"),
+    message: message.map(CompactString::new),
+  };
+  let lightweight = futures::executor::block_on(client.create_tag(&create_tag("v1.9.0", None))).unwrap();
+  assert_eq!(lightweight.target, lightweight.commit.id);
+  assert_eq!(lightweight.created_at, None);
+  futures::executor::block_on(client.protect_tag(&ProtectTagCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    name: CompactString::new("v*"),
+    create_access_level: None,
+    allowed_to_create: Vec::new(),
+  }))
+  .unwrap();
+  let annotated =
+    futures::executor::block_on(client.create_tag(&create_tag("v1.10.0", Some("Version 1.10.0")))).unwrap();
+  assert_ne!(annotated.target, annotated.commit.id);
+  assert!(annotated.protected);
+  let actual = futures::executor::block_on(client.create_tag(&create_tag("v1.10.0", None))).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::BAD_REQUEST));
+
+  let list = GetTagListQuery {
+    order_by: Some(TagOrder::Version),
+    ..GetTagListQuery::new(ProjectRef::Id(project)).set_context(context())
+  };
+  let actual = futures::executor::block_on(client.get_tag_list(&list)).unwrap();
+  assert_eq!(
+    Vec::from_iter(actual.items.into_iter().map(|tag| tag.name)),
+    vec!["v1.10.0", "v1.9.0"]
+  );
+
+  let refs = futures::executor::block_on(client.get_commit_ref_list(&GetCommitRefListQuery {
+    r#type: Some(CommitRefType::Tag),
+    ..GetCommitRefListQuery::new(ProjectRef::Id(project), CompactString::new("v1.9.0")).set_context(context())
+  }))
+  .unwrap();
+  assert_eq!(refs.items.len(), 2);
+
+  let signature = futures::executor::block_on(client.get_tag_signature(&GetTagSignatureQuery {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    tag_name: CompactString::new("v1.10.0"),
+  }))
+  .unwrap_err();
+  assert_eq!(signature.status(), Some(StatusCode::NOT_FOUND));
+
+  let protection = futures::executor::block_on(client.get_protected_tag(&GetProtectedTagQuery {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    name: CompactString::new("v*"),
+  }))
+  .unwrap();
+  assert_eq!(
+    protection.create_access_levels[0].access_level,
+    Some(AccessLevel::Maintainer)
+  );
+
+  let delete = DeleteTagCommand {
+    context: context(),
+    auth: None,
+    project: ProjectRef::Id(project),
+    tag_name: CompactString::new("v1.9.0"),
+  };
+  futures::executor::block_on(client.delete_tag(&delete)).unwrap();
+  let actual = futures::executor::block_on(client.delete_tag(&delete)).unwrap_err();
+  assert_eq!(actual.status(), Some(StatusCode::NOT_FOUND));
+}